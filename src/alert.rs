@@ -0,0 +1,221 @@
+use crunner::CrunnerError;
+use serde_json::json;
+use std::cmp::Ordering;
+use std::str::FromStr;
+use web3::types::U256;
+
+/// Comparison operator of alert condition
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompareOp {
+    Gt,
+    Ge,
+    Lt,
+    Le,
+    Eq,
+    Ne,
+}
+
+impl CompareOp {
+    /// Get symbol of the operator as written in condition.
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            CompareOp::Gt => ">",
+            CompareOp::Ge => ">=",
+            CompareOp::Lt => "<",
+            CompareOp::Le => "<=",
+            CompareOp::Eq => "==",
+            CompareOp::Ne => "!=",
+        }
+    }
+}
+
+/// Condition on returned value of watched call i.e. `value > 1000000`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AlertCondition {
+    /// Comparison operator
+    pub op: CompareOp,
+
+    /// Value to compare against
+    pub threshold: String,
+}
+
+impl FromStr for AlertCondition {
+    type Err = CrunnerError;
+
+    /// Parse condition in form of `value <op> <threshold>` where op is one of
+    /// `>`, `>=`, `<`, `<=`, `==`, or `!=`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || CrunnerError::InvalidArgument(format!("Error, condition must be in form of 'value <op> <threshold>' where op is one of >, >=, <, <=, ==, or !=; condition={}", s));
+
+        let rest = match s.trim().strip_prefix("value") {
+            Some(res) => res.trim_start(),
+            None => return Err(invalid()),
+        };
+        // two-character operators first so that `>=` isn't taken as `>`
+        let ops = [CompareOp::Ge, CompareOp::Le, CompareOp::Eq, CompareOp::Ne, CompareOp::Gt, CompareOp::Lt];
+        let (op, threshold) = match ops.iter().find_map(|op| rest.strip_prefix(op.symbol()).map(|t| (*op, t.trim()))) {
+            Some(res) => res,
+            None => return Err(invalid()),
+        };
+        if threshold.is_empty() {
+            return Err(invalid());
+        }
+        // only equality applies to non-numeric value i.e. bool, or address
+        if !matches!(op, CompareOp::Eq | CompareOp::Ne) && parse_integer(threshold).is_none() && threshold.parse::<f64>().is_err() {
+            return Err(CrunnerError::InvalidArgument(format!("Error, threshold of '{}' must be a number; condition={}", op.symbol(), s)));
+        }
+
+        Ok(AlertCondition {
+            op,
+            threshold: threshold.to_owned(),
+        })
+    }
+}
+
+impl std::fmt::Display for AlertCondition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "value {} {}", self.op.symbol(), self.threshold)
+    }
+}
+
+impl AlertCondition {
+    /// Evaluate the condition against the value. Integers are compared exactly, other
+    /// numbers as floating-point, and anything else as string which supports only
+    /// `==`, and `!=`.
+    ///
+    /// # Arguments
+    /// * `value` - returned value of watched call as printed
+    pub fn is_met(&self, value: &str) -> Result<bool, CrunnerError> {
+        let ordering = match (parse_integer(value), parse_integer(&self.threshold)) {
+            (Some(lhs), Some(rhs)) => Some(compare_integers(lhs, rhs)),
+            _ => match (value.parse::<f64>(), self.threshold.parse::<f64>()) {
+                (Ok(lhs), Ok(rhs)) => lhs.partial_cmp(&rhs),
+                _ => None,
+            },
+        };
+
+        match (ordering, self.op) {
+            (Some(ordering), CompareOp::Gt) => Ok(ordering == Ordering::Greater),
+            (Some(ordering), CompareOp::Ge) => Ok(ordering != Ordering::Less),
+            (Some(ordering), CompareOp::Lt) => Ok(ordering == Ordering::Less),
+            (Some(ordering), CompareOp::Le) => Ok(ordering != Ordering::Greater),
+            (Some(ordering), CompareOp::Eq) => Ok(ordering == Ordering::Equal),
+            (Some(ordering), CompareOp::Ne) => Ok(ordering != Ordering::Equal),
+            (None, CompareOp::Eq) => Ok(value.eq_ignore_ascii_case(&self.threshold)),
+            (None, CompareOp::Ne) => Ok(!value.eq_ignore_ascii_case(&self.threshold)),
+            (None, op) => Err(CrunnerError::InvalidArgument(format!("Error, cannot compare non-numeric value with '{}'; value={}", op.symbol(), value))),
+        }
+    }
+}
+
+/// Parse integer as its sign (`true` if negative), and magnitude.
+fn parse_integer(s: &str) -> Option<(bool, U256)> {
+    let (negative, digits) = match s.strip_prefix('-') {
+        Some(res) => (true, res),
+        None => (false, s),
+    };
+    if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    U256::from_dec_str(digits).ok().map(|magnitude| (negative && !magnitude.is_zero(), magnitude))
+}
+
+/// Compare integers as parsed by `parse_integer`.
+fn compare_integers(lhs: (bool, U256), rhs: (bool, U256)) -> Ordering {
+    match (lhs.0, rhs.0) {
+        (false, false) => lhs.1.cmp(&rhs.1),
+        (true, true) => rhs.1.cmp(&lhs.1),
+        (true, false) => Ordering::Less,
+        (false, true) => Ordering::Greater,
+    }
+}
+
+/// Watched call whose value meets condition of alert
+pub struct AlertEvent<'a> {
+    /// Time the value is read at in RFC 3339 format
+    pub timestamp: &'a str,
+
+    /// Chain of the contract
+    pub chain: &'a str,
+
+    /// Contract address
+    pub address: &'a str,
+
+    /// Function name
+    pub function: &'a str,
+
+    /// Returned value as printed
+    pub value: &'a str,
+
+    /// Condition which is met
+    pub condition: &'a AlertCondition,
+}
+
+impl AlertEvent<'_> {
+    /// Get human readable message of the alert.
+    pub fn message(&self) -> String {
+        format!("crunner alert: {} of {} on {} is {} ({})", self.function, self.address, self.chain, self.value, self.condition)
+    }
+}
+
+/// Execute shell command on alert. Details of the alert are passed via environment
+/// variables i.e. CRUNNER_VALUE, CRUNNER_FUNCTION, CRUNNER_ADDRESS, CRUNNER_CHAIN,
+/// CRUNNER_CONDITION, and CRUNNER_TIMESTAMP.
+///
+/// # Arguments
+/// * `cmd` - shell command
+/// * `event` - alert event
+pub async fn run_alert_cmd(cmd: &str, event: &AlertEvent<'_>) -> Result<(), CrunnerError> {
+    let mut command = if cfg!(windows) {
+        let mut command = tokio::process::Command::new("cmd");
+        command.arg("/C").arg(cmd);
+        command
+    }
+    else {
+        let mut command = tokio::process::Command::new("sh");
+        command.arg("-c").arg(cmd);
+        command
+    };
+    command.env("CRUNNER_VALUE", event.value)
+        .env("CRUNNER_FUNCTION", event.function)
+        .env("CRUNNER_ADDRESS", event.address)
+        .env("CRUNNER_CHAIN", event.chain)
+        .env("CRUNNER_CONDITION", event.condition.to_string())
+        .env("CRUNNER_TIMESTAMP", event.timestamp);
+
+    let status = match command.status().await {
+        Ok(res) => res,
+        Err(e) => return Err(CrunnerError::InvalidArgument(format!("Error executing alert command '{}'; err={}", cmd, e))),
+    };
+    if !status.success() {
+        return Err(CrunnerError::InvalidArgument(format!("Error, alert command '{}' exited with {}", cmd, status)));
+    }
+    Ok(())
+}
+
+/// POST details of the alert as JSON to webhook. Its `text` field holds human readable
+/// message so that it can be posted to Slack-compatible incoming webhook as is.
+///
+/// # Arguments
+/// * `url` - webhook url
+/// * `event` - alert event
+pub async fn post_alert_webhook(url: &str, event: &AlertEvent<'_>) -> Result<(), CrunnerError> {
+    let body = json!({
+        "text": event.message(),
+        "timestamp": event.timestamp,
+        "chain": event.chain,
+        "address": event.address,
+        "function": event.function,
+        "value": event.value,
+        "condition": event.condition.to_string(),
+    });
+
+    let resp = match reqwest::Client::new().post(url).json(&body).send().await {
+        Ok(res) => res,
+        Err(e) => return Err(CrunnerError::RpcError(format!("Error posting alert to webhook {}; err={}", url, e))),
+    };
+    if !resp.status().is_success() {
+        return Err(CrunnerError::RpcError(format!("Error, webhook {} responded with {}", url, resp.status())));
+    }
+    Ok(())
+}
@@ -0,0 +1,135 @@
+use crunner::CrunnerError;
+use serde::Deserialize;
+use std::path::Path;
+
+/// Operation to perform for an entry of batch file
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum BatchMode {
+    /// Make a call to getter method
+    Call,
+
+    /// Dry run of setter method to estimate gas used
+    Estimate,
+
+    /// Make a call to setter method; this sends a transaction
+    Send,
+}
+
+impl BatchMode {
+    /// Get name of the mode as written in batch file.
+    pub fn name(&self) -> &'static str {
+        match self {
+            BatchMode::Call => "call",
+            BatchMode::Estimate => "estimate",
+            BatchMode::Send => "send",
+        }
+    }
+}
+
+/// Call specification as an entry of batch file
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BatchEntry {
+    /// Chain to work with; --chain if not set
+    pub chain: Option<String>,
+
+    /// Target contract address (or ENS name)
+    pub address: String,
+
+    /// Function name, or signature i.e. `transfer(address,uint256)`
+    #[serde(rename = "fn")]
+    pub function: String,
+
+    /// Parameters to be supplied to the function
+    #[serde(default)]
+    pub params: Vec<String>,
+
+    /// Operation to perform; call if not set
+    pub mode: Option<BatchMode>,
+
+    /// ABI filepath describing the function; built-in ABI if not set
+    pub abi: Option<String>,
+
+    /// From address (or ENS name); required for estimate
+    pub from: Option<String>,
+
+    /// Amount of native currency to send along; in Wei, or with unit suffix
+    pub value: Option<String>,
+
+    /// Gas limit of sent transaction; estimated by the node if not set
+    pub gas_limit: Option<u64>,
+
+    /// Number of block confirmations of sent transaction to wait for
+    pub block_confirmations: Option<u64>,
+}
+
+/// Layout of batch file in TOML; entries are `[[call]]` tables
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct BatchFile {
+    #[serde(default)]
+    call: Vec<BatchEntry>,
+}
+
+/// Entry of batch file in CSV whose params are separated by spaces in a single column
+#[derive(Deserialize)]
+struct CsvBatchEntry {
+    chain: Option<String>,
+    address: String,
+    #[serde(rename = "fn")]
+    function: String,
+    params: Option<String>,
+    mode: Option<BatchMode>,
+    abi: Option<String>,
+    from: Option<String>,
+    value: Option<String>,
+    gas_limit: Option<u64>,
+    block_confirmations: Option<u64>,
+}
+
+impl From<CsvBatchEntry> for BatchEntry {
+    fn from(entry: CsvBatchEntry) -> Self {
+        Self {
+            chain: entry.chain,
+            address: entry.address,
+            function: entry.function,
+            params: entry.params.map(|p| p.split_whitespace().map(|v| v.to_owned()).collect()).unwrap_or_default(),
+            mode: entry.mode,
+            abi: entry.abi,
+            from: entry.from,
+            value: entry.value,
+            gas_limit: entry.gas_limit,
+            block_confirmations: entry.block_confirmations,
+        }
+    }
+}
+
+/// Load entries of batch file; its format is detected from its extension.
+///
+/// # Arguments
+/// * `filepath` - filepath of batch file in TOML (`[[call]]` tables), JSON (array of
+///   entries), or CSV (header row naming the fields)
+pub fn load_batch_file(filepath: &str) -> Result<Vec<BatchEntry>, CrunnerError> {
+    let extension = Path::new(filepath).extension().and_then(|e| e.to_str()).unwrap_or_default().to_lowercase();
+    let content = match std::fs::read_to_string(filepath) {
+        Ok(res) => res,
+        Err(e) => return Err(CrunnerError::InvalidArgument(format!("Error reading batch file {}; err={}", filepath, e))),
+    };
+
+    let entries: Result<Vec<BatchEntry>, String> = match extension.as_str() {
+        "toml" => toml::from_str::<BatchFile>(&content).map(|f| f.call).map_err(|e| e.to_string()),
+        "json" => serde_json::from_str::<Vec<BatchEntry>>(&content).map_err(|e| e.to_string()),
+        "csv" => csv::Reader::from_reader(content.as_bytes())
+            .deserialize::<CsvBatchEntry>()
+            .map(|entry| entry.map(BatchEntry::from))
+            .collect::<Result<Vec<BatchEntry>, csv::Error>>()
+            .map_err(|e| e.to_string()),
+        _ => return Err(CrunnerError::InvalidArgument(format!("Error, unknown format of batch file {}; expected .toml, .json, or .csv", filepath))),
+    };
+    match entries {
+        Ok(res) if res.is_empty() => Err(CrunnerError::InvalidArgument(format!("Error, batch file {} has no entries", filepath))),
+        Ok(res) => Ok(res),
+        Err(e) => Err(CrunnerError::InvalidArgument(format!("Error parsing batch file {}; err={}", filepath, e))),
+    }
+}
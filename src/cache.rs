@@ -0,0 +1,130 @@
+use crate::error::CrunnerError;
+use crate::json_store;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+use web3::types::Bytes;
+
+/// Name of the response cache as mentioned in error messages
+static STORE_NAME: &str = "response cache";
+
+/// Default time to live of cached responses; a week
+static DEFAULT_TTL_SECS: u64 = 7 * 24 * 60 * 60;
+
+/// Selectors of getters whose returned data never changes once deployed i.e.
+/// `name()`, `symbol()`, and `decimals()`
+static IMMUTABLE_SELECTORS: [[u8; 4]; 3] = [
+    [0x06, 0xfd, 0xde, 0x03],
+    [0x95, 0xd8, 0x9b, 0x41],
+    [0x31, 0x3c, 0xe5, 0x67],
+];
+
+/// Returned data of a call as stored on disk
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CachedResponse {
+    /// Returned data
+    data: Bytes,
+
+    /// Unix timestamp in seconds of when it's cached
+    cached_at: u64,
+}
+
+/// Cache of returned data of immutable getters on disk, keyed by chain, contract
+/// address, and calldata (selector, and arguments), so repeated runs don't query
+/// the same values again. Failure to read, or write the cache never fails the call;
+/// it's treated as a cache miss.
+pub struct ResponseCache {
+    store: PathBuf,
+    ttl: Duration,
+    lock: Mutex<()>,
+}
+
+impl ResponseCache {
+    /// Create a response cache stored in the specified JSON file.
+    ///
+    /// # Arguments
+    /// * `filepath` - filepath of the cache
+    pub fn new(filepath: &Path) -> Self {
+        Self {
+            store: filepath.to_path_buf(),
+            ttl: Duration::from_secs(DEFAULT_TTL_SECS),
+            lock: Mutex::new(()),
+        }
+    }
+
+    /// Expire cached responses after the specified time instead of a week.
+    ///
+    /// # Arguments
+    /// * `ttl` - time to live of cached responses
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Get the default filepath of the cache which is `~/.cache/crunner/responses.json`
+    /// on Linux, or platform's equivalent.
+    pub fn default_filepath() -> Option<PathBuf> {
+        dirs::cache_dir().map(|dir| dir.join("crunner").join("responses.json"))
+    }
+
+    /// Check whether returned data of the calldata can be cached i.e. it calls one of
+    /// immutable getters.
+    ///
+    /// # Arguments
+    /// * `calldata` - calldata of the call
+    pub fn is_cacheable(calldata: &[u8]) -> bool {
+        calldata.len() >= 4 && IMMUTABLE_SELECTORS.iter().any(|selector| calldata[..4] == selector[..])
+    }
+
+    /// Build cache key of the call.
+    ///
+    /// # Arguments
+    /// * `chain` - name of the chain
+    /// * `address` - contract address
+    /// * `calldata` - calldata of the call
+    pub fn key(chain: &str, address: &str, calldata: &[u8]) -> String {
+        format!("{}:{}:0x{}", chain, address.to_lowercase(), hex::encode(calldata))
+    }
+
+    /// Get cached returned data of the call if not yet expired.
+    ///
+    /// # Arguments
+    /// * `key` - cache key as built by `key`
+    pub fn get(&self, key: &str) -> Option<Vec<u8>> {
+        let _guard = self.lock.lock().ok()?;
+        let entries: BTreeMap<String, CachedResponse> = match json_store::read(&self.store, STORE_NAME) {
+            Ok(res) => res,
+            Err(e) => {
+                tracing::debug!(error = %e, "ignored unreadable response cache");
+                return None;
+            },
+        };
+        match entries.get(key) {
+            Some(entry) if json_store::unix_now().saturating_sub(entry.cached_at) < self.ttl.as_secs() => {
+                tracing::debug!(key, "response cache hit");
+                Some(entry.data.0.clone())
+            },
+            _ => None,
+        }
+    }
+
+    /// Cache returned data of the call; expired entries are pruned along the way.
+    ///
+    /// # Arguments
+    /// * `key` - cache key as built by `key`
+    /// * `data` - returned data of the call
+    pub fn insert(&self, key: &str, data: &[u8]) -> Result<(), CrunnerError> {
+        let _guard = match self.lock.lock() {
+            Ok(res) => res,
+            Err(_) => return Err(CrunnerError::ConfigError("Error, response cache is poisoned".to_owned())),
+        };
+        // start over if the cache is corrupted rather than failing every run
+        let mut entries: BTreeMap<String, CachedResponse> = json_store::read(&self.store, STORE_NAME).unwrap_or_default();
+        let now = json_store::unix_now();
+        entries.retain(|_, entry| now.saturating_sub(entry.cached_at) < self.ttl.as_secs());
+        entries.insert(key.to_owned(), CachedResponse { data: Bytes(data.to_vec()), cached_at: now });
+        json_store::write(&self.store, STORE_NAME, &entries)
+    }
+}
@@ -0,0 +1,181 @@
+use crate::error::CrunnerError;
+use crate::types::ChainType;
+use crate::util::validate_address_format;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// User-defined chain as configured in chains.toml
+#[derive(Debug, Clone, Deserialize)]
+pub struct ChainConfig {
+    /// Name of the chain to be supplied to `--chain`
+    pub name: String,
+
+    /// Chain id
+    pub chain_id: u64,
+
+    /// RPC endpoints; the first one is used by default
+    pub rpc: Vec<String>,
+
+    /// Symbol of native currency i.e. `ETH`
+    pub symbol: String,
+
+    /// (optional) Etherscan-compatible block explorer API endpoint
+    #[serde(default)]
+    pub explorer_api: Option<String>,
+
+    /// (optional) HTTP headers sent along with each RPC request i.e. `Authorization`
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+
+    /// (optional) Number of blocks to query event logs for per request
+    #[serde(default)]
+    pub logs_chunk_size: Option<u64>,
+
+    /// (optional) Chainlink price feed of native currency / USD used by `--usd`
+    #[serde(default)]
+    pub price_feed: Option<String>,
+}
+
+/// Layout of chains.toml
+#[derive(Default, Deserialize)]
+struct ChainsFile {
+    /// User-defined chains
+    #[serde(default)]
+    chain: Vec<ChainConfig>,
+
+    /// HTTP headers of RPC requests for built-in chains keyed by chain name
+    #[serde(default)]
+    headers: BTreeMap<String, BTreeMap<String, String>>,
+
+    /// Address book; addresses keyed by their aliases i.e. `treasury`, or `usdc.bsc`
+    #[serde(default)]
+    addresses: BTreeMap<String, String>,
+}
+
+/// Registry of user-defined chains; loaded at most once
+static CHAIN_REGISTRY: OnceLock<ChainsFile> = OnceLock::new();
+
+/// Get the default filepath of chains.toml which is `~/.config/crunner/chains.toml`.
+pub fn default_chains_filepath() -> Option<PathBuf> {
+    dirs::home_dir().map(|home| home.join(".config").join("crunner").join("chains.toml"))
+}
+
+/// Load user-defined chains into the registry. It's fine if the file doesn't exist.
+/// Subsequent calls after the registry has been loaded have no effect.
+///
+/// # Arguments
+/// * `filepath` - filepath of chains.toml; the default filepath if `None`
+pub fn load_chain_registry(filepath: Option<&Path>) -> Result<(), CrunnerError> {
+    if CHAIN_REGISTRY.get().is_some() {
+        return Ok(());
+    }
+
+    let filepath = match filepath {
+        Some(filepath) => filepath.to_path_buf(),
+        None => match default_chains_filepath() {
+            Some(res) => res,
+            None => {
+                let _ = CHAIN_REGISTRY.set(ChainsFile::default());
+                return Ok(());
+            }
+        },
+    };
+
+    let chains_file = if filepath.exists() {
+        parse_chains_file(&filepath)?
+    }
+    else {
+        ChainsFile::default()
+    };
+
+    let _ = CHAIN_REGISTRY.set(chains_file);
+    Ok(())
+}
+
+/// Parse and validate chains.toml.
+fn parse_chains_file(filepath: &Path) -> Result<ChainsFile, CrunnerError> {
+    let content = match std::fs::read_to_string(filepath) {
+        Ok(res) => res,
+        Err(e) => return Err(CrunnerError::ConfigError(format!("Error reading chains file {}; err={}", filepath.display(), e))),
+    };
+    let chains_file: ChainsFile = match toml::from_str(&content) {
+        Ok(res) => res,
+        Err(e) => return Err(CrunnerError::ConfigError(format!("Error parsing chains file {}; err={}", filepath.display(), e))),
+    };
+
+    for chain in chains_file.chain.iter() {
+        if chain.rpc.is_empty() {
+            return Err(CrunnerError::ConfigError(format!("Error, chain '{}' in chains file {} requires at least one rpc endpoint", chain.name, filepath.display())));
+        }
+    }
+    // aliases map to literal addresses only, so they never refer to each other
+    for (alias, address) in chains_file.addresses.iter() {
+        if !validate_address_format(address) {
+            return Err(CrunnerError::ConfigError(format!("Error, address of alias '{}' in chains file {} is not in the correct format; addr={}", alias, filepath.display(), address)));
+        }
+    }
+
+    Ok(chains_file)
+}
+
+/// Get user-defined chains in the registry; empty if not loaded.
+pub fn chain_registry() -> &'static [ChainConfig] {
+    match CHAIN_REGISTRY.get() {
+        Some(chains_file) => chains_file.chain.as_slice(),
+        None => &[],
+    }
+}
+
+/// Get configured HTTP headers of RPC requests for the chain; either from
+/// user-defined chain, or `[headers.<name>]` table for built-in chain.
+///
+/// # Arguments
+/// * `name` - name of the chain
+pub fn configured_rpc_headers(name: &str) -> Vec<(String, String)> {
+    let chains_file = match CHAIN_REGISTRY.get() {
+        Some(res) => res,
+        None => return Vec::new(),
+    };
+
+    let headers = match chains_file.chain.iter().find(|c| c.name.eq_ignore_ascii_case(name)) {
+        Some(config) => Some(&config.headers),
+        None => chains_file.headers.iter()
+            .find(|(chain_name, _)| chain_name.eq_ignore_ascii_case(name))
+            .map(|(_, headers)| headers),
+    };
+    match headers {
+        Some(headers) => headers.iter().map(|(k, v)| (k.to_owned(), v.to_owned())).collect(),
+        None => Vec::new(),
+    }
+}
+
+/// Look up address of the alias in the address book (case-insensitive).
+///
+/// # Arguments
+/// * `alias` - alias of the address i.e. `treasury`
+pub fn address_alias(alias: &str) -> Option<&'static str> {
+    let chains_file = CHAIN_REGISTRY.get()?;
+    chains_file.addresses.iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(alias))
+        .map(|(_, address)| address.as_str())
+}
+
+/// Find user-defined chain by name (case-insensitive).
+///
+/// # Arguments
+/// * `name` - name of the chain
+pub fn find_chain(name: &str) -> Option<ChainType> {
+    chain_registry().iter()
+        .position(|c| c.name.eq_ignore_ascii_case(name))
+        .map(ChainType::Custom)
+}
+
+/// Get configuration of user-defined chain.
+///
+/// # Arguments
+/// * `index` - index of the chain in the registry as held by `ChainType::Custom`
+pub fn chain_config(index: usize) -> Option<&'static ChainConfig> {
+    chain_registry().get(index)
+}
@@ -0,0 +1,1456 @@
+use crate::alert::AlertCondition;
+use crate::output::{LogFormat, OutputFormat};
+use clap::{ArgEnum, Args, Parser, Subcommand};
+use clap_complete::Shell;
+use crunner::error::CrunnerError;
+use crunner::metadata::DEFAULT_IPFS_GATEWAY;
+use crunner::types::TokenStandard;
+use crunner::wallet::DEFAULT_HD_PATH;
+use std::time::Duration;
+
+/// Flags of the flat command line before it was split into subcommands, along with
+/// the subcommand replacing each; checked in this order as they used to be combined
+static LEGACY_FLAGS: [(&str, &str); 3] = [
+    ("--dry-run-estimate-gas", "estimate-gas -c <chain> -a <address> -f <fn-name> --from <address>"),
+    ("--ensure-setter", "send -c <chain> -a <address> -f <fn-name>"),
+    ("--rpc-eth", "balance -c <chain> -a <address>"),
+];
+
+/// Check command line arguments for flags of the flat command line which were replaced
+/// by subcommands, so that existing scripts fail pointing to the replacement rather than
+/// with a bare parse error.
+///
+/// # Arguments
+/// * `args` - command line arguments without the program name
+pub fn check_legacy_flags(args: &[String]) -> Result<(), CrunnerError> {
+    // anything after `--` is a value
+    let args = args.split(|arg| arg == "--").next().unwrap_or_default();
+    for (flag, replacement) in LEGACY_FLAGS.iter() {
+        if args.iter().any(|arg| arg == flag) {
+            return Err(CrunnerError::InvalidArgument(format!("Error, {} is no longer supported; use `crunner {}` instead", flag, replacement)));
+        }
+    }
+    Ok(())
+}
+
+/// Commandline's arguments type
+#[derive(Debug, Parser)]
+#[clap(author="Wasin Thonkaew (wasin@wasin.io)")]
+#[clap(name="crunner")]
+#[clap(about="Runner/Executor of target smart contract on EVM-based chain at command line")]
+pub struct CommandlineArgs {
+    /// Which chain to work with; one of bsc, ethereum, polygon, arbitrum, optimism,
+    /// base, avalanche, fantom, gnosis, bsc-testnet, sepolia, holesky, polygon-amoy,
+    /// local, or name of user-defined chain in ~/.config/crunner/chains.toml.
+    /// Repeat it to make the same `call` on several chains at once.
+    #[clap(long="chain", short='c', global=true, multiple_occurrences=true, takes_value=true)]
+    pub chains: Vec<String>,
+
+    /// Make the same `call` on all built-in mainnets at once i.e. bsc, ethereum,
+    /// polygon, arbitrum, optimism, base, avalanche, fantom, and gnosis; --chain
+    /// adds more chains on top i.e. user-defined ones
+    #[clap(long="all-chains", global=true, takes_value=false)]
+    pub all_chains: bool,
+
+    /// RPC endpoint to connect to, overriding the chain's default one.
+    /// Path to IPC socket i.e. /path/to/geth.ipc is also accepted for local nodes.
+    /// Repeat it to fail over to the next endpoint whenever the current one fails.
+    /// It can also be set per chain via environment variable i.e. CRUNNER_RPC_BSC.
+    #[clap(long="rpc-url", global=true, multiple_occurrences=true, takes_value=true)]
+    pub rpc_urls: Vec<String>,
+
+    /// HTTP header sent along with each RPC request in format "Name: value"
+    /// i.e. "Authorization: Bearer <token>". Repeat it for multiple headers.
+    #[clap(long="rpc-header", global=true, multiple_occurrences=true, takes_value=true)]
+    pub rpc_headers: Vec<String>,
+
+    /// Maximum number of retries of RPC request failed with transient errors
+    /// i.e. rate limited (429), server errors (5xx), or timeouts. Transaction
+    /// broadcast is never retried.
+    #[clap(long="retries", global=true, multiple_values=false, takes_value=true, default_value="3")]
+    pub retries: u32,
+
+    /// Delay in milliseconds before the first retry; it's doubled for each subsequent retry
+    #[clap(long="retry-base-delay-ms", global=true, multiple_values=false, takes_value=true, default_value="500")]
+    pub retry_base_delay_ms: u64,
+
+    /// Maximum random delay in milliseconds added on top of each retry delay
+    #[clap(long="retry-jitter-ms", global=true, multiple_values=false, takes_value=true, default_value="250")]
+    pub retry_jitter_ms: u64,
+
+    /// Timeout in seconds of each RPC request; timed out request fails over, and is retried
+    #[clap(long="timeout-secs", global=true, multiple_values=false, takes_value=true, default_value="30")]
+    pub timeout_secs: u64,
+
+    /// Maximum number of getter calls sent in a single JSON-RPC batch request i.e. with
+    /// --addresses-file, or call entries of batch file; 0 sends each call on its own
+    #[clap(long="rpc-batch-size", global=true, multiple_values=false, takes_value=true, default_value="100")]
+    pub rpc_batch_size: usize,
+
+    /// Don't read, nor write the response cache of immutable getters i.e. name, symbol,
+    /// and decimals
+    #[clap(long="no-cache", global=true, multiple_values=false, takes_value=false)]
+    pub no_cache: bool,
+
+    /// Time to live in seconds of cached responses of immutable getters
+    #[clap(long="cache-ttl-secs", global=true, multiple_values=false, takes_value=true, default_value="604800")]
+    pub cache_ttl_secs: u64,
+
+    /// Don't record getter calls, and setter transactions in local history
+    #[clap(long="no-history", global=true, multiple_values=false, takes_value=false)]
+    pub no_history: bool,
+
+    /// Look up signature of unknown custom error that a reverted call, estimate-gas, or send
+    /// failed with from public signature databases, and decode its arguments against it
+    #[clap(long="lookup-errors", global=true, multiple_values=false, takes_value=false)]
+    pub lookup_errors: bool,
+
+    /// Timeout in seconds of waiting for block confirmations of a sent transaction;
+    /// its hash is reported as still pending after that
+    #[clap(long="confirmation-timeout-secs", visible_alias="confirm-timeout-secs", global=true, multiple_values=false, takes_value=true, default_value="600")]
+    pub confirmation_timeout_secs: u64,
+
+    /// Skip verifying that chain id of RPC endpoint matches the selected chain
+    #[clap(long="skip-chain-check", global=true, multiple_values=false, takes_value=false)]
+    pub skip_chain_check: bool,
+
+    /// Output format of results on stdout, and errors on stderr
+    #[clap(long="output", short='o', global=true, arg_enum, default_value="text")]
+    pub output: OutputFormat,
+
+    /// Print only the raw value in text output i.e. returned value of getter method,
+    /// transaction hash of setter method, gas used, or balance in Wei.
+    /// Handy to capture the result in shell i.e. var=$(crunner ...)
+    #[clap(long="quiet", short='q', global=true, multiple_values=false, takes_value=false)]
+    pub quiet: bool,
+
+    /// Log JSON-RPC requests including encoded calldata on stderr; repeat it
+    /// i.e. -vv to log raw responses as well. RUST_LOG takes precedence if set.
+    #[clap(long="verbose", short='v', global=true, parse(from_occurrences))]
+    pub verbose: u8,
+
+    /// Format of logs printed on stderr
+    #[clap(long="log-format", global=true, arg_enum, default_value="pretty")]
+    pub log_format: LogFormat,
+
+    /// Skip EIP-55 checksum validation of input addresses
+    #[clap(long="no-checksum", global=true, multiple_values=false, takes_value=false)]
+    pub no_checksum: bool,
+
+    /// Address of ENS-compatible registry on the selected chain used to resolve
+    /// ENS names. By default, ENS names are resolved via ENS registry on Ethereum.
+    #[clap(long="ens-registry", global=true, multiple_values=false, takes_value=true)]
+    pub ens_registry: Option<String>,
+
+    /// Strategy to determine gas price, or fees of sent transaction, and estimated
+    /// gas cost; one of node, fixed:<price> (in gwei unless suffixed with unit),
+    /// fee-history[:<percentile>], or oracle[:safe|propose|fast] which queries gas
+    /// oracle of the chain's block explorer. By default, fee-history:50 on chains
+    /// sending EIP-1559 transaction by default, otherwise node.
+    #[clap(long="gas-strategy", global=true, multiple_values=false, takes_value=true)]
+    pub gas_strategy: Option<String>,
+
+    /// Profile of defaults i.e. chain, RPC endpoints, block confirmations, gas strategy,
+    /// and signer in ~/.config/crunner/config.toml; options set at command line take
+    /// precedence. By default, the profile named by `default_profile` in the file if any.
+    #[clap(long="profile", global=true, multiple_values=false, takes_value=true)]
+    pub profile: Option<String>,
+
+    /// Load environment variables i.e. CRUNNER_SETTER_SECRETKEY, or CRUNNER_RPC_<CHAIN>
+    /// from the specified dotenv file on top of .env in the working directory.
+    /// Variables already set in the environment take precedence over both files.
+    #[clap(long="env-file", global=true, multiple_values=false, takes_value=true)]
+    pub env_file: Option<String>,
+
+    /// Operation to perform
+    #[clap(subcommand)]
+    pub command: Command,
+}
+
+/// Subcommands, each represents an operation to perform
+#[derive(Debug, Subcommand)]
+#[allow(clippy::large_enum_variant)]
+pub enum Command {
+    /// Make a call to getter method of target smart contract
+    #[clap(visible_alias="get")]
+    Call(CallArgs),
+
+    /// Make a call to setter method of target smart contract; this sends a transaction
+    #[clap(visible_alias="set")]
+    Send(SendArgs),
+
+    /// Make read-only call with calldata already at hand i.e. from `send --encode-only`,
+    /// block explorer, or failed transaction, and print its returned data
+    RawCall(RawCallArgs),
+
+    /// Dry run to estimate gas used for setter method of target smart contract
+    #[clap(visible_alias="estimate")]
+    EstimateGas(EstimateGasArgs),
+
+    /// Trace calling method of target smart contract via debug_traceCall, and print
+    /// its internal call tree; requires node with debug namespace enabled
+    TraceCall(TraceCallArgs),
+
+    /// Aggregate multiple calls to getter methods of target smart contracts into a
+    /// single call via Multicall3
+    Multicall(MulticallArgs),
+
+    /// Query balance of the target address via RPC-ETH
+    Balance(BalanceArgs),
+
+    /// Query event logs emitted by the target contract address
+    Events(EventsArgs),
+
+    /// Print name, symbol, decimals, and total supply of ERC-20 token at once
+    TokenInfo(TokenInfoArgs),
+
+    /// Query, or transfer ERC-20 token with its ABI built in, and amounts in units of
+    /// the token i.e. 100.25 scaled by its decimals
+    Erc20(Erc20Args),
+
+    /// Sign EIP-2612 permit allowing the spender to spend ERC-20 token on behalf of the
+    /// signer without a transaction, and print its signature; or submit it on-chain
+    Permit(PermitArgs),
+
+    /// Sign EIP-712 typed data i.e. off-chain order, or governance vote with the secret
+    /// key, and print the signature
+    SignTyped(SignTypedArgs),
+
+    /// Sign message with the secret key as `personal_sign` does (EIP-191), and print
+    /// the signature
+    SignMessage(SignMessageArgs),
+
+    /// Recover the signer of signature over EIP-191 message, or raw hash, and print it
+    VerifySig(VerifySigArgs),
+
+    /// Print Keccak-256 hash of text, or bytes in hex
+    Keccak(KeccakArgs),
+
+    /// Print 4-byte selector of function signature, or list selectors of all functions
+    /// in ABI
+    Selector(SelectorArgs),
+
+    /// Print topic0 of event signature, or list topics of all events in ABI
+    Topic(TopicArgs),
+
+    /// ABI-decode raw returned data i.e. of `eth_call` against the list of types, and
+    /// print the values
+    DecodeOutput(DecodeOutputArgs),
+
+    /// Decode calldata of function call against ABI, or function signature, and print
+    /// its named arguments
+    DecodeCalldata(DecodeCalldataArgs),
+
+    /// Decode signed raw transaction (legacy, EIP-2930, or EIP-1559) into its fields, and
+    /// recover its sender
+    DecodeTx(DecodeTxArgs),
+
+    /// Query, or transfer ERC-721 (NFT) token with its ABI built in, including fetching
+    /// of its metadata
+    Erc721(Erc721Args),
+
+    /// Query, or transfer ERC-1155 multi-token with its ABI built in, several token ids
+    /// at once
+    Erc1155(Erc1155Args),
+
+    /// Manage signing keys stored in OS secret store (keychain, libsecret, or wincred)
+    Key(KeyArgs),
+
+    /// Speed up, or cancel a pending transaction
+    Tx(TxArgs),
+
+    /// Execute calls specified in a file (TOML, JSON, or CSV), and print the result
+    /// of each; each entry may work with its own chain
+    Batch(BatchArgs),
+
+    /// Run HTTP server exposing configured getters as GET endpoints, and setters as
+    /// POST endpoints guarded by bearer token
+    Serve(ServeArgs),
+
+    /// List past getter calls, and setter transactions recorded in local history,
+    /// the latest first; filtered by --chain if set
+    History(HistoryArgs),
+
+    /// Run Rhai script of multi-step workflow i.e. read allowance, approve if too low,
+    /// then swap; it calls get(), send(), estimate(), and balance() functions
+    Script(ScriptArgs),
+
+    /// Print shell completion script to stdout i.e. `crunner completions bash > /etc/bash_completion.d/crunner`
+    Completions(CompletionsArgs),
+}
+
+/// Arguments common to all operations interacting with a smart contract's method
+#[derive(Debug, Args)]
+// so that negative number i.e. `-p -5` is taken as parameter rather than flag
+#[clap(allow_negative_numbers=true)]
+pub struct ContractArgs {
+    /// Target contract address (or ENS name) to interact with; required unless
+    /// `call` is made against contracts listed in --addresses-file
+    #[clap(long="address", short='a', multiple_values=false)]
+    pub contract_address: Option<String>,
+
+    /// Function name of target smart contract to make a call to
+    #[clap(long="fn-name", short='f', required_unless_present="fn-sig", conflicts_with="fn-sig", multiple_values=false)]
+    pub fn_name: Option<String>,
+
+    /// Function signature i.e. `transfer(address,uint256)` to select among
+    /// overloaded functions of the same name; use instead of --fn-name
+    #[clap(long="fn-sig", multiple_values=false, takes_value=true)]
+    pub fn_sig: Option<String>,
+
+    /// Multiple parameters to be supplied to the function.
+    /// Type is guessed from the shape of each parameter unless it is annotated
+    /// with a type prefix i.e. `addr:0x...`, `u256:1000`, `str:foo`, or `bytes32:0x...`.
+    /// Amounts take unit suffix i.e. `1.5ether`, or `2500gwei`, or scientific notation
+    /// i.e. `10e18`, and are converted into Wei.
+    /// Token amount i.e. `amount:100.25` is scaled by decimals of the token called, or
+    /// the one following `@` i.e. `amount:100.25@0x...`.
+    /// Array is supported via array type prefix i.e. `addr[]:0xAAA,0xBBB`, and
+    /// tuple via `tuple:(0xAAA,0xBBB,3000)` whose component types are taken from ABI.
+    /// Template variables `{self}`, `{sender}`, `{block.timestamp+3600}`, `{block.number}`,
+    /// and `{env:NAME}` are substituted at runtime.
+    #[clap(long="params", short='p', multiple_values=true, takes_value=true)]
+    pub params: Vec<String>,
+
+    /// ABI filepath to describe function signatures of smart contract.
+    /// Its entries are merged with the built-in ABI which covers name, decimals,
+    /// allowance, approve, and Transfer, and Approval events.
+    #[clap(long="abi-filepath", multiple_values=false, takes_value=true)]
+    pub abi_filepath: Option<String>,
+
+    /// Fetch the verified ABI of --address from block explorer of the chain
+    /// (i.e. BscScan, Etherscan, or Polygonscan) instead of reading from ABI file.
+    /// Set CRUNNER_EXPLORER_API_KEY environment variable to use an API key.
+    #[clap(long="abi-from-explorer", multiple_values=false, takes_value=false, conflicts_with="abi-filepath")]
+    pub abi_from_explorer: bool,
+
+    /// Built-in ABI of token standard to use instead; set by token subcommands i.e. `erc20`
+    #[clap(skip)]
+    pub standard: Option<TokenStandard>,
+
+    /// Skip validating that --address is a contract which queries the node; set by
+    /// `send --sign-only`
+    #[clap(skip)]
+    pub skip_code_check: bool,
+}
+
+impl ContractArgs {
+    /// Get function name either from --fn-name, or --fn-sig.
+    pub fn fn_name(&self) -> String {
+        match (&self.fn_name, &self.fn_sig) {
+            (Some(fn_name), _) => fn_name.to_owned(),
+            (None, Some(fn_sig)) => fn_sig.split('(').next().unwrap_or_default().trim().to_owned(),
+            // either one is required by clap
+            (None, None) => String::new(),
+        }
+    }
+}
+
+/// Arguments for `call` subcommand
+#[derive(Debug, Args)]
+pub struct CallArgs {
+    #[clap(flatten)]
+    pub contract: ContractArgs,
+
+    /// Function's returning type; one of String, U256, I256, bool, Address, u8, u64, or bytes.
+    /// Append `[]` for array of such type i.e. `Address[]`.
+    /// For multi-value returns or tuple, supply composite return signature
+    /// i.e. `(uint112,uint112,uint32)`.
+    /// If not set, returning type is inferred from function's outputs declared in ABI.
+    #[clap(long="fn-ret-type", short='r', multiple_values=false, takes_value=true, validator=validate_fn_ret_type)]
+    pub fn_ret_type: Option<String>,
+
+    /// Block to read contract's state at; either block number, block hash, or one of
+    /// tags latest, earliest, pending, safe, or finalized. Latest block if not set.
+    /// Reading far past state requires archive node.
+    #[clap(long="block", short='b', multiple_values=false, takes_value=true)]
+    pub block: Option<String>,
+
+    /// State overrides applied to the call as inline JSON, or filepath of JSON file;
+    /// keyed by account address with balance, nonce, code, state, or stateDiff
+    /// i.e. '{"0x...": {"stateDiff": {"0x<slot>": "0x<value>"}}}'
+    #[clap(long="state-override", multiple_values=false, takes_value=true)]
+    pub state_override: Option<String>,
+
+    /// Print returned token amount scaled by decimals() of the contract called along with
+    /// the raw value i.e. `100250000 100.25` for USDC; the function must return a single
+    /// unsigned integer i.e. balanceOf, allowance, or totalSupply
+    #[clap(long="human-amount", takes_value=false, conflicts_with_all=&["addresses-file", "params-stdin", "watch"])]
+    pub human_amount: bool,
+
+    /// Filepath of contract addresses (or ENS names), one per line, to make the same
+    /// call against each of them instead of --address; empty lines, and lines starting
+    /// with `#` are skipped. One line of result is printed per contract.
+    #[clap(long="addresses-file", multiple_values=false, takes_value=true, conflicts_with="contract-address")]
+    pub addresses_file: Option<String>,
+
+    /// Read parameters from stdin instead, one full set per line separated by spaces,
+    /// and make the call once per line; they're appended to --params (if any). Result
+    /// of each line is printed as soon as it's ready. Empty lines, and lines starting
+    /// with `#` are skipped.
+    #[clap(long="params-stdin", takes_value=false, conflicts_with_all=&["addresses-file", "watch"])]
+    pub params_stdin: bool,
+
+    /// Number of contracts listed in --addresses-file to prepare i.e. resolve ENS name,
+    /// or fetch ABI concurrently before calls are sent in batch requests, or number of
+    /// calls of --params-stdin in flight at once. Results are printed in the same order
+    /// as listed regardless.
+    #[clap(long="concurrency", multiple_values=false, takes_value=true, default_value="8")]
+    pub concurrency: usize,
+
+    /// Re-run the call at the interval i.e. 5s, 500ms, or 1m, and print each returned
+    /// value with its timestamp until interrupted
+    #[clap(long="watch", multiple_values=false, takes_value=true, parse(try_from_str=humantime::parse_duration), conflicts_with_all=&["block", "addresses-file"])]
+    pub watch: Option<Duration>,
+
+    /// Print returned value in --watch mode only when it changes from the previous one
+    #[clap(long="changes-only", takes_value=false, requires="watch")]
+    pub changes_only: bool,
+
+    /// Condition on returned value in --watch mode i.e. "value > 1000000" which
+    /// triggers --alert-cmd, and --alert-webhook whenever the value crosses into it;
+    /// op is one of >, >=, <, <=, ==, or !=
+    #[clap(long="alert-if", multiple_values=false, takes_value=true, parse(try_from_str), requires="watch")]
+    pub alert_if: Option<AlertCondition>,
+
+    /// Shell command executed on alert; the value is passed via CRUNNER_VALUE
+    /// environment variable along with CRUNNER_FUNCTION, CRUNNER_ADDRESS,
+    /// CRUNNER_CHAIN, CRUNNER_CONDITION, and CRUNNER_TIMESTAMP
+    #[clap(long="alert-cmd", multiple_values=false, takes_value=true, requires="alert-if")]
+    pub alert_cmd: Option<String>,
+
+    /// Webhook url to POST details of alert to as JSON; its `text` field makes it
+    /// compatible with Slack incoming webhook
+    #[clap(long="alert-webhook", multiple_values=false, takes_value=true, requires="alert-if")]
+    pub alert_webhook: Option<String>,
+}
+
+/// Arguments for `send` subcommand
+#[derive(Debug, Args)]
+pub struct SendArgs {
+    #[clap(flatten)]
+    pub contract: ContractArgs,
+
+    /// Only print ABI-encoded calldata of the call without signing, or sending the
+    /// transaction i.e. to submit it via multisig UI, or raw `eth_call`
+    #[clap(long="encode-only", takes_value=false)]
+    pub encode_only: bool,
+
+    /// Only print the unsigned transaction i.e. to, data, value, gas, nonce, fees, and
+    /// chain id as JSON without signing, or sending it; to review it, or to sign it
+    /// elsewhere. It's from --from, or the address of the signing key.
+    #[clap(long="build-only", takes_value=false, conflicts_with="encode-only")]
+    pub build_only: bool,
+
+    /// Only sign the transaction with the local key, or external signer, and print it
+    /// RLP-encoded as raw transaction in hex without broadcasting it. Nothing is queried
+    /// from the node if --nonce, --gas-limit, and fees are set i.e. on air-gapped machine.
+    #[clap(long="sign-only", takes_value=false, conflicts_with_all=&["encode-only", "build-only"])]
+    pub sign_only: bool,
+
+    #[clap(flatten)]
+    pub tx: TransactionArgs,
+}
+
+/// Arguments for `raw-call` subcommand
+#[derive(Debug, Args)]
+pub struct RawCallArgs {
+    /// Calldata in hex with 0x prefix i.e. 4-byte selector followed by encoded arguments
+    #[clap(long="data", short='d', required=true, multiple_values=false, takes_value=true)]
+    pub data: String,
+
+    /// Address (or ENS name) to make the call to; without it, the data is executed as
+    /// contract creation code
+    #[clap(long="to", multiple_values=false, takes_value=true)]
+    pub to: Option<String>,
+
+    /// From address (or ENS name) to make the call from
+    #[clap(long="from", multiple_values=false, takes_value=true)]
+    pub from: Option<String>,
+
+    /// Amount of native currency to send along; in Wei, or with unit suffix i.e.
+    /// 0.1eth, or 5gwei
+    #[clap(long="value", multiple_values=false, takes_value=true)]
+    pub value: Option<String>,
+
+    /// Block to read state at; either block number, block hash, or tag. Latest block
+    /// if not set.
+    #[clap(long="block", short='b', multiple_values=false, takes_value=true)]
+    pub block: Option<String>,
+
+    /// State overrides applied to the call as inline JSON, or filepath of JSON file;
+    /// same as of `call`
+    #[clap(long="state-override", multiple_values=false, takes_value=true)]
+    pub state_override: Option<String>,
+
+    /// Types of returned values separated by comma i.e. `uint256,address` to decode
+    /// returned data against as `decode-output` does; printed in hex otherwise
+    #[clap(long="types", short='t', multiple_values=false, takes_value=true)]
+    pub types: Option<String>,
+
+    /// ABI filepath to decode custom error of revert by; its entries are merged with
+    /// the built-in ABI
+    #[clap(long="abi-filepath", multiple_values=false, takes_value=true)]
+    pub abi_filepath: Option<String>,
+}
+
+/// Options of transaction sent to setter method, and how it's signed
+#[derive(Debug, Args)]
+pub struct TransactionArgs {
+    /// Number of block confirmations when making call to setter methods.
+    /// Default is 20, or 0 on local chain as it usually mines a block only on a new transaction.
+    #[clap(long="block-confirmations", multiple_values=false, takes_value=true)]
+    pub block_confirmations: Option<u64>,
+
+    /// Amount of native currency to send along to payable function; in Wei, or
+    /// with unit suffix i.e. 0.1eth, or 5gwei
+    #[clap(long="value", multiple_values=false, takes_value=true)]
+    pub value: Option<String>,
+
+    /// Gas limit of the transaction instead of relying on the node's estimate which
+    /// can be too low when gas used depends on state changing before inclusion
+    #[clap(long="gas-limit", multiple_values=false, takes_value=true)]
+    pub gas_limit: Option<u64>,
+
+    /// Legacy gas price of the transaction instead of the node's suggestion; in Gwei,
+    /// or with unit suffix i.e. 3000000000wei
+    #[clap(long="gas-price", multiple_values=false, takes_value=true, conflicts_with_all=&["max-fee", "max-priority-fee"])]
+    pub gas_price: Option<String>,
+
+    /// Max fee per gas of EIP-1559 transaction; in Gwei, or with unit suffix. Setting
+    /// either this or `--max-priority-fee` sends EIP-1559 transaction; the other one
+    /// is suggested from fee history if not set. It is the default on Ethereum, and
+    /// Polygon including their testnets.
+    #[clap(long="max-fee", multiple_values=false, takes_value=true)]
+    pub max_fee: Option<String>,
+
+    /// Max priority fee (tip) per gas of EIP-1559 transaction; in Gwei, or with unit suffix
+    #[clap(long="max-priority-fee", multiple_values=false, takes_value=true)]
+    pub max_priority_fee: Option<String>,
+
+    /// Nonce of the transaction instead of the sender's pending transaction count i.e.
+    /// to replace a stuck transaction, or to send several dependent transactions
+    #[clap(long="nonce", multiple_values=false, takes_value=true)]
+    pub nonce: Option<u64>,
+
+    /// Track allocated nonces on disk at ~/.config/crunner/nonces.json so that
+    /// transactions sent quickly across runs get sequential nonces before the
+    /// previous ones are seen by the node as pending
+    #[clap(long="track-nonce", conflicts_with="nonce")]
+    pub track_nonce: bool,
+
+    /// Send from an account unlocked on the node instead of signing with
+    /// CRUNNER_SETTER_SECRETKEY. On local chain, the first account of the node
+    /// is used if CRUNNER_SETTER_SECRETKEY is not set. Required by `--signer node`.
+    #[clap(long="from", multiple_values=false, takes_value=true, required_if_eq("signer", "node"), conflicts_with_all=&["keystore", "private-key-file", "from-profile", "mnemonic-env", "mnemonic-file"])]
+    pub from: Option<String>,
+
+    #[clap(flatten)]
+    pub key: SigningKeyArgs,
+
+    /// Sign with external signer instead of a local key; the key never leaves it
+    #[clap(long="signer", arg_enum, multiple_values=false, takes_value=true, conflicts_with_all=&["keystore", "private-key-file", "from-profile", "mnemonic-env", "mnemonic-file"])]
+    pub signer: Option<SignerType>,
+
+    /// Key id, ARN, or alias of AWS KMS key to sign with; required by `--signer kms`
+    #[clap(long="kms-key-id", multiple_values=false, takes_value=true, required_if_eq("signer", "kms"))]
+    pub kms_key_id: Option<String>,
+}
+
+/// Arguments selecting local secret key to sign transactions with instead of
+/// CRUNNER_SETTER_SECRETKEY
+#[derive(Debug, Args)]
+pub struct SigningKeyArgs {
+    /// Sign with the secret key decrypted from keystore JSON file (web3 secret
+    /// storage) i.e. `UTC--...` instead of CRUNNER_SETTER_SECRETKEY. Its password
+    /// is read from CRUNNER_KEYSTORE_PASSWORD, or prompted interactively.
+    #[clap(long="keystore", multiple_values=false, takes_value=true, conflicts_with_all=&["mnemonic-env", "mnemonic-file"])]
+    pub keystore: Option<String>,
+
+    /// Sign with the secret key in hex string read from the specified file instead
+    /// of CRUNNER_SETTER_SECRETKEY; both cannot be set at the same time. The file
+    /// must not be world-readable.
+    #[clap(long="private-key-file", multiple_values=false, takes_value=true, conflicts_with_all=&["keystore", "mnemonic-env", "mnemonic-file"])]
+    pub private_key_file: Option<String>,
+
+    /// Sign with the secret key stored in OS secret store under the specified
+    /// profile via `crunner key import`
+    #[clap(long="from-profile", multiple_values=false, takes_value=true, conflicts_with_all=&["keystore", "private-key-file", "mnemonic-env", "mnemonic-file"])]
+    pub from_profile: Option<String>,
+
+    /// Sign with the key derived from BIP-39 mnemonic phrase held in the specified
+    /// environment variable i.e. `MY_MNEMONIC`. BIP-39 passphrase (if any) is read
+    /// from CRUNNER_MNEMONIC_PASSPHRASE.
+    #[clap(long="mnemonic-env", multiple_values=false, takes_value=true, conflicts_with="mnemonic-file")]
+    pub mnemonic_env: Option<String>,
+
+    /// Sign with the key derived from BIP-39 mnemonic phrase read from the specified file
+    #[clap(long="mnemonic-file", multiple_values=false, takes_value=true)]
+    pub mnemonic_file: Option<String>,
+
+    /// BIP-44 derivation path of the key derived from mnemonic phrase, or of the
+    /// account on hardware wallet
+    #[clap(long="hd-path", multiple_values=false, takes_value=true, default_value=DEFAULT_HD_PATH)]
+    pub hd_path: String,
+}
+
+/// External signer of setter transactions
+#[derive(Debug, Clone, Copy, PartialEq, ArgEnum)]
+pub enum SignerType {
+    /// Ledger hardware wallet over USB with Ethereum app opened; requires `ledger` feature
+    Ledger,
+
+    /// Trezor hardware wallet over USB; requires `trezor` feature
+    Trezor,
+
+    /// secp256k1 key held in AWS KMS; requires `kms` feature
+    Kms,
+
+    /// Account managed by the node i.e. unlocked, or via clef; the node signs the
+    /// transaction submitted via `eth_sendTransaction` with `--from`
+    Node,
+}
+
+/// Arguments for `estimate-gas` subcommand
+#[derive(Debug, Args)]
+pub struct EstimateGasArgs {
+    #[clap(flatten)]
+    pub contract: ContractArgs,
+
+    /// From address (or ENS name) used only for dry-run for estimating gas
+    #[clap(long="from", alias="estimate-gas-from-addr", required=true, multiple_values=false, takes_value=true)]
+    pub from: String,
+
+    /// Amount of native currency to send along to payable function; in Wei, or
+    /// with unit suffix i.e. 0.1eth, or 5gwei
+    #[clap(long="value", multiple_values=false, takes_value=true)]
+    pub value: Option<String>,
+
+    /// Print estimated fees in USD as well; the price of native currency is read
+    /// from the known Chainlink price feed of the chain, or `price_feed` of user-defined chain
+    #[clap(long="usd", multiple_values=false, takes_value=false)]
+    pub usd: bool,
+
+    /// Address of Chainlink price feed of native currency / USD to read the price from
+    #[clap(long="price-feed", requires="usd", conflicts_with="price-api", multiple_values=false, takes_value=true)]
+    pub price_feed: Option<String>,
+
+    /// HTTP price API to read the price of native currency in USD from; `{symbol}` in
+    /// the url is replaced with symbol of native currency i.e. ETH. Its JSON response is
+    /// either a number, or an object with usd, price, or amount field at its top level,
+    /// or nested one level deep
+    #[clap(long="price-api", requires="usd", multiple_values=false, takes_value=true)]
+    pub price_api: Option<String>,
+}
+
+/// Arguments for `trace-call` subcommand
+#[derive(Debug, Args)]
+pub struct TraceCallArgs {
+    #[clap(flatten)]
+    pub contract: ContractArgs,
+
+    /// From address (or ENS name) to make the call from
+    #[clap(long="from", multiple_values=false, takes_value=true)]
+    pub from: Option<String>,
+
+    /// Amount of native currency to send along to payable function; in Wei, or
+    /// with unit suffix i.e. 0.1eth, or 5gwei
+    #[clap(long="value", multiple_values=false, takes_value=true)]
+    pub value: Option<String>,
+
+    /// Block to trace the call at; either block number, block hash, or tag.
+    /// Latest block if not set.
+    #[clap(long="block", short='b', multiple_values=false, takes_value=true)]
+    pub block: Option<String>,
+}
+
+/// Arguments for `multicall` subcommand
+#[derive(Debug, Args)]
+pub struct MulticallArgs {
+    /// Call to getter method to aggregate; repeat for each call. It's contract address
+    /// (or ENS name), function name or signature, then its parameters separated by
+    /// spaces i.e. '0x... balanceOf 0x...', or '0x... "transfer(address,uint256)" ...'
+    #[clap(long="call", required=true, multiple_occurrences=true, multiple_values=false, takes_value=true)]
+    pub calls: Vec<String>,
+
+    /// ABI filepath to describe function signatures of all target smart contracts.
+    /// Its entries are merged with the built-in ABI which covers name, decimals,
+    /// allowance, approve, and Transfer, and Approval events.
+    #[clap(long="abi-filepath", multiple_values=false, takes_value=true)]
+    pub abi_filepath: Option<String>,
+
+    /// Fetch the verified ABI of each target contract from block explorer of the chain
+    /// instead of reading from ABI file
+    #[clap(long="abi-from-explorer", multiple_values=false, takes_value=false, conflicts_with="abi-filepath")]
+    pub abi_from_explorer: bool,
+
+    /// Block to read contracts' state at; either block number, block hash, or tag.
+    /// Latest block if not set.
+    #[clap(long="block", short='b', multiple_values=false, takes_value=true)]
+    pub block: Option<String>,
+
+    /// Fail as a whole if any of the calls reverts; by default, the other calls still
+    /// return their results
+    #[clap(long="require-success", multiple_values=false, takes_value=false)]
+    pub require_success: bool,
+
+    /// Address of Multicall3 contract; by default, its canonical address
+    /// 0xcA11bde05977b3631167028862bE2a173976CA11 deployed on most EVM chains
+    #[clap(long="multicall-address", multiple_values=false, takes_value=true)]
+    pub multicall_address: Option<String>,
+}
+
+/// Arguments for `balance` subcommand
+#[derive(Debug, Args)]
+pub struct BalanceArgs {
+    /// Target address (or ENS name) to query balance of
+    #[clap(long="address", short='a', required=true, multiple_values=false)]
+    pub address: String,
+}
+
+/// Arguments for `token-info` subcommand
+#[derive(Debug, Args)]
+pub struct TokenInfoArgs {
+    /// Token address (or ENS name, or alias)
+    #[clap(long="address", short='a', required=true, multiple_values=false)]
+    pub address: String,
+}
+
+/// Arguments for `erc20` subcommand
+#[derive(Debug, Args)]
+pub struct Erc20Args {
+    #[clap(subcommand)]
+    pub command: Erc20Command,
+}
+
+/// Operations on ERC-20 token
+#[derive(Debug, Subcommand)]
+#[allow(clippy::large_enum_variant)]
+pub enum Erc20Command {
+    /// Print token balance of the owner scaled by decimals of the token along with
+    /// the raw amount
+    BalanceOf(Erc20BalanceOfArgs),
+
+    /// Print amount of token the spender is allowed to spend on behalf of the owner
+    Allowance(Erc20AllowanceArgs),
+
+    /// Allow the spender to spend the amount of token on behalf of the sender
+    Approve(Erc20ApproveArgs),
+
+    /// Transfer the amount of token from the sender to the recipient
+    Transfer(Erc20TransferArgs),
+}
+
+impl Erc20Command {
+    /// Get transaction options if the operation sends a transaction.
+    pub fn tx(&self) -> Option<&TransactionArgs> {
+        match self {
+            Erc20Command::Approve(args) => Some(&args.tx),
+            Erc20Command::Transfer(args) => Some(&args.tx),
+            Erc20Command::BalanceOf(_) | Erc20Command::Allowance(_) => None,
+        }
+    }
+
+    /// Get mutable transaction options if the operation sends a transaction.
+    pub fn tx_mut(&mut self) -> Option<&mut TransactionArgs> {
+        match self {
+            Erc20Command::Approve(args) => Some(&mut args.tx),
+            Erc20Command::Transfer(args) => Some(&mut args.tx),
+            Erc20Command::BalanceOf(_) | Erc20Command::Allowance(_) => None,
+        }
+    }
+}
+
+/// Arguments for `erc20 balance-of` subcommand
+#[derive(Debug, Args)]
+pub struct Erc20BalanceOfArgs {
+    /// Token address (or ENS name, or alias)
+    #[clap(required=true)]
+    pub token: String,
+
+    /// Owner address (or ENS name, or alias) to query balance of
+    #[clap(required=true)]
+    pub owner: String,
+}
+
+/// Arguments for `erc20 allowance` subcommand
+#[derive(Debug, Args)]
+pub struct Erc20AllowanceArgs {
+    /// Token address (or ENS name, or alias)
+    #[clap(required=true)]
+    pub token: String,
+
+    /// Owner address (or ENS name, or alias) of the token
+    #[clap(required=true)]
+    pub owner: String,
+
+    /// Spender address (or ENS name, or alias) i.e. DEX router
+    #[clap(required=true)]
+    pub spender: String,
+}
+
+/// Arguments for `erc20 approve` subcommand
+#[derive(Debug, Args)]
+pub struct Erc20ApproveArgs {
+    /// Token address (or ENS name, or alias)
+    #[clap(required=true)]
+    pub token: String,
+
+    /// Spender address (or ENS name, or alias) i.e. DEX router
+    #[clap(required=true)]
+    pub spender: String,
+
+    /// Amount in units of the token i.e. 100.25, or `max` for unlimited allowance
+    #[clap(required=true)]
+    pub amount: String,
+
+    /// Take the amount as raw integer in the token's smallest unit instead
+    #[clap(long="raw", takes_value=false)]
+    pub raw: bool,
+
+    #[clap(flatten)]
+    pub tx: TransactionArgs,
+}
+
+/// Arguments for `erc20 transfer` subcommand
+#[derive(Debug, Args)]
+pub struct Erc20TransferArgs {
+    /// Token address (or ENS name, or alias)
+    #[clap(required=true)]
+    pub token: String,
+
+    /// Recipient address (or ENS name, or alias)
+    #[clap(required=true)]
+    pub to: String,
+
+    /// Amount in units of the token i.e. 100.25
+    #[clap(required=true)]
+    pub amount: String,
+
+    /// Take the amount as raw integer in the token's smallest unit instead
+    #[clap(long="raw", takes_value=false)]
+    pub raw: bool,
+
+    #[clap(flatten)]
+    pub tx: TransactionArgs,
+}
+
+/// Arguments for `permit` subcommand
+#[derive(Debug, Args)]
+pub struct PermitArgs {
+    /// Token address (or ENS name, or alias)
+    #[clap(required=true)]
+    pub token: String,
+
+    /// Spender address (or ENS name, or alias) i.e. DEX router
+    #[clap(required=true)]
+    pub spender: String,
+
+    /// Amount in units of the token i.e. 100.25, or `max` for unlimited allowance
+    #[clap(required=true)]
+    pub amount: String,
+
+    /// Take the amount as raw integer in the token's smallest unit instead
+    #[clap(long="raw", takes_value=false)]
+    pub raw: bool,
+
+    /// Deadline of the permit; either unix timestamp in seconds, or duration from now
+    /// i.e. `30m`
+    #[clap(long="deadline", default_value="1h")]
+    pub deadline: String,
+
+    /// Version of the token's EIP-712 domain; queried via `version()` if the token has
+    /// it, otherwise `1`
+    #[clap(long="domain-version", multiple_values=false)]
+    pub domain_version: Option<String>,
+
+    /// Submit the permit on-chain by calling `permit` with its signature
+    #[clap(long="send", takes_value=false)]
+    pub send: bool,
+
+    #[clap(flatten)]
+    pub tx: TransactionArgs,
+}
+
+/// Arguments for `sign-typed` subcommand
+#[derive(Debug, Args)]
+pub struct SignTypedArgs {
+    /// Path to JSON file of EIP-712 typed data with `types`, `primaryType`, `domain`,
+    /// and `message` as accepted by `eth_signTypedData_v4`; `-` to read from stdin
+    #[clap(long="file", short='f', required=true, multiple_values=false)]
+    pub file: String,
+
+    #[clap(flatten)]
+    pub key: SigningKeyArgs,
+}
+
+/// Arguments for `sign-message` subcommand
+#[derive(Debug, Args)]
+pub struct SignMessageArgs {
+    /// Message to sign; either text, or bytes in hex with 0x prefix
+    #[clap(required=true)]
+    pub message: String,
+
+    /// Sign the message as text even if it's in hex with 0x prefix
+    #[clap(long="text", takes_value=false)]
+    pub text: bool,
+
+    #[clap(flatten)]
+    pub key: SigningKeyArgs,
+}
+
+/// Arguments for `verify-sig` subcommand
+#[derive(Debug, Args)]
+pub struct VerifySigArgs {
+    /// Message signed as `personal_sign` does (EIP-191); either text, or bytes in hex
+    /// with 0x prefix
+    #[clap(long="message", short='m', required_unless_present="hash", conflicts_with="hash", multiple_values=false)]
+    pub message: Option<String>,
+
+    /// Take the message as text even if it's in hex with 0x prefix
+    #[clap(long="text", takes_value=false, requires="message")]
+    pub text: bool,
+
+    /// 32-byte hash in hex signed as it is i.e. EIP-712 digest
+    #[clap(long="hash", multiple_values=false)]
+    pub hash: Option<String>,
+
+    /// 65-byte signature in hex of `r`, `s`, and `v`
+    #[clap(long="signature", short='s', required=true, multiple_values=false)]
+    pub signature: String,
+
+    /// Address expected to be the signer; exit with error if the signature is signed
+    /// by other address
+    #[clap(long="expected", multiple_values=false)]
+    pub expected: Option<String>,
+}
+
+/// Arguments for `keccak` subcommand
+#[derive(Debug, Args)]
+pub struct KeccakArgs {
+    /// Input to hash; either text, or bytes in hex with 0x prefix
+    #[clap(required=true)]
+    pub input: String,
+
+    /// Hash the input as text even if it's in hex with 0x prefix
+    #[clap(long="text", takes_value=false)]
+    pub text: bool,
+}
+
+/// Arguments for `selector` subcommand
+#[derive(Debug, Args)]
+pub struct SelectorArgs {
+    /// Function signature i.e. `transfer(address,uint256)`; parameter names are allowed
+    #[clap(required_unless_present="abi-filepath")]
+    pub signature: Option<String>,
+
+    /// List selectors of all functions in the ABI file merged with the built-in ABI instead
+    #[clap(long="abi-filepath", multiple_values=false, takes_value=true, conflicts_with="signature")]
+    pub abi_filepath: Option<String>,
+}
+
+/// Arguments for `topic` subcommand
+#[derive(Debug, Args)]
+pub struct TopicArgs {
+    /// Event signature i.e. `Transfer(address,address,uint256)`; parameter names, and
+    /// `indexed` are allowed
+    #[clap(required_unless_present="abi-filepath")]
+    pub signature: Option<String>,
+
+    /// List topics of all events in the ABI file merged with the built-in ABI instead
+    #[clap(long="abi-filepath", multiple_values=false, takes_value=true, conflicts_with="signature")]
+    pub abi_filepath: Option<String>,
+}
+
+/// Arguments for `decode-output` subcommand
+#[derive(Debug, Args)]
+pub struct DecodeOutputArgs {
+    /// Types of returned values separated by comma i.e. `uint256,address,bool`, or
+    /// `(uint112,uint112,uint32)`; tuple, and array types are supported
+    #[clap(long="types", short='t', required=true, multiple_values=false, takes_value=true)]
+    pub types: String,
+
+    /// Returned data in hex with 0x prefix
+    #[clap(required=true)]
+    pub data: String,
+}
+
+/// Arguments for `decode-calldata` subcommand
+#[derive(Debug, Args)]
+pub struct DecodeCalldataArgs {
+    /// Calldata in hex with 0x prefix starting with 4-byte selector
+    #[clap(required=true)]
+    pub calldata: String,
+
+    /// Function signature i.e. `transfer(address to, uint256 amount)` to decode against
+    /// instead of ABI; parameter names are printed along with the values
+    #[clap(long="fn-sig", multiple_values=false, takes_value=true, conflicts_with="abi-filepath")]
+    pub fn_sig: Option<String>,
+
+    /// ABI filepath to find the function of the selector in; its entries are merged
+    /// with the built-in ABI
+    #[clap(long="abi-filepath", multiple_values=false, takes_value=true)]
+    pub abi_filepath: Option<String>,
+
+    /// Look up candidate signatures of selector not found in ABI from public signature
+    /// databases (openchain.xyz, then 4byte.directory), and decode against the first one
+    /// that fits; found ones are cached at ~/.cache/crunner/signatures.json
+    #[clap(long="lookup", takes_value=false, conflicts_with="fn-sig")]
+    pub lookup: bool,
+}
+
+/// Arguments for `decode-tx` subcommand
+#[derive(Debug, Args)]
+pub struct DecodeTxArgs {
+    /// Signed raw transaction in hex with 0x prefix i.e. as printed by `send --sign-only`
+    #[clap(required=true)]
+    pub raw_tx: String,
+}
+
+/// Arguments for `erc721` subcommand
+#[derive(Debug, Args)]
+pub struct Erc721Args {
+    #[clap(subcommand)]
+    pub command: Erc721Command,
+}
+
+/// Operations on ERC-721 token
+#[derive(Debug, Subcommand)]
+#[allow(clippy::large_enum_variant)]
+pub enum Erc721Command {
+    /// Print owner of the token id
+    OwnerOf(Erc721OwnerOfArgs),
+
+    /// Print metadata URI of the token id, or its metadata JSON with --resolve
+    TokenUri(Erc721TokenUriArgs),
+
+    /// Transfer the token id from its owner to the recipient with `safeTransferFrom`
+    SafeTransfer(Erc721SafeTransferArgs),
+
+    /// Approve the address to transfer the token id on behalf of the sender
+    Approve(Erc721ApproveArgs),
+}
+
+impl Erc721Command {
+    /// Get transaction options if the operation sends a transaction.
+    pub fn tx(&self) -> Option<&TransactionArgs> {
+        match self {
+            Erc721Command::SafeTransfer(args) => Some(&args.tx),
+            Erc721Command::Approve(args) => Some(&args.tx),
+            Erc721Command::OwnerOf(_) | Erc721Command::TokenUri(_) => None,
+        }
+    }
+
+    /// Get mutable transaction options if the operation sends a transaction.
+    pub fn tx_mut(&mut self) -> Option<&mut TransactionArgs> {
+        match self {
+            Erc721Command::SafeTransfer(args) => Some(&mut args.tx),
+            Erc721Command::Approve(args) => Some(&mut args.tx),
+            Erc721Command::OwnerOf(_) | Erc721Command::TokenUri(_) => None,
+        }
+    }
+}
+
+/// Arguments for `erc721 owner-of` subcommand
+#[derive(Debug, Args)]
+pub struct Erc721OwnerOfArgs {
+    /// Token contract address (or ENS name, or alias)
+    #[clap(required=true)]
+    pub token: String,
+
+    /// Token id in decimal, or hex with 0x prefix
+    #[clap(required=true)]
+    pub token_id: String,
+}
+
+/// Arguments for `erc721 token-uri` subcommand
+#[derive(Debug, Args)]
+pub struct Erc721TokenUriArgs {
+    /// Token contract address (or ENS name, or alias)
+    #[clap(required=true)]
+    pub token: String,
+
+    /// Token id in decimal, or hex with 0x prefix
+    #[clap(required=true)]
+    pub token_id: String,
+
+    /// Fetch the metadata JSON the URI points to, and print it instead; supports
+    /// http(s)://, ipfs://, and data: URIs
+    #[clap(long="resolve", takes_value=false)]
+    pub resolve: bool,
+
+    /// IPFS gateway to fetch ipfs:// URI through
+    #[clap(long="ipfs-gateway", default_value=DEFAULT_IPFS_GATEWAY)]
+    pub ipfs_gateway: String,
+}
+
+/// Arguments for `erc721 safe-transfer` subcommand
+#[derive(Debug, Args)]
+pub struct Erc721SafeTransferArgs {
+    /// Token contract address (or ENS name, or alias)
+    #[clap(required=true)]
+    pub token: String,
+
+    /// Recipient address (or ENS name, or alias)
+    #[clap(required=true)]
+    pub to: String,
+
+    /// Token id in decimal, or hex with 0x prefix
+    #[clap(required=true)]
+    pub token_id: String,
+
+    /// Current owner of the token (or ENS name, or alias) to transfer from when the
+    /// sender is an approved operator; the sender by default
+    #[clap(long="owner", multiple_values=false)]
+    pub owner: Option<String>,
+
+    #[clap(flatten)]
+    pub tx: TransactionArgs,
+}
+
+/// Arguments for `erc721 approve` subcommand
+#[derive(Debug, Args)]
+pub struct Erc721ApproveArgs {
+    /// Token contract address (or ENS name, or alias)
+    #[clap(required=true)]
+    pub token: String,
+
+    /// Address (or ENS name, or alias) to approve; the zero address clears the approval
+    #[clap(required=true)]
+    pub to: String,
+
+    /// Token id in decimal, or hex with 0x prefix
+    #[clap(required=true)]
+    pub token_id: String,
+
+    #[clap(flatten)]
+    pub tx: TransactionArgs,
+}
+
+/// Arguments for `erc1155` subcommand
+#[derive(Debug, Args)]
+pub struct Erc1155Args {
+    #[clap(subcommand)]
+    pub command: Erc1155Command,
+}
+
+/// Operations on ERC-1155 token
+#[derive(Debug, Subcommand)]
+#[allow(clippy::large_enum_variant)]
+pub enum Erc1155Command {
+    /// Print balance of the token id held by the owner
+    BalanceOf(Erc1155BalanceOfArgs),
+
+    /// Print balances of several token ids, and owners in one call, one line each
+    BalanceOfBatch(Erc1155BalanceOfBatchArgs),
+
+    /// Transfer amounts of token ids from the owner to the recipient; several token ids
+    /// are transferred with `safeBatchTransferFrom`
+    SafeTransferFrom(Erc1155SafeTransferFromArgs),
+}
+
+impl Erc1155Command {
+    /// Get transaction options if the operation sends a transaction.
+    pub fn tx(&self) -> Option<&TransactionArgs> {
+        match self {
+            Erc1155Command::SafeTransferFrom(args) => Some(&args.tx),
+            Erc1155Command::BalanceOf(_) | Erc1155Command::BalanceOfBatch(_) => None,
+        }
+    }
+
+    /// Get mutable transaction options if the operation sends a transaction.
+    pub fn tx_mut(&mut self) -> Option<&mut TransactionArgs> {
+        match self {
+            Erc1155Command::SafeTransferFrom(args) => Some(&mut args.tx),
+            Erc1155Command::BalanceOf(_) | Erc1155Command::BalanceOfBatch(_) => None,
+        }
+    }
+}
+
+/// Arguments for `erc1155 balance-of` subcommand
+#[derive(Debug, Args)]
+pub struct Erc1155BalanceOfArgs {
+    /// Token contract address (or ENS name, or alias)
+    #[clap(required=true)]
+    pub token: String,
+
+    /// Owner address (or ENS name, or alias) to query balance of
+    #[clap(required=true)]
+    pub owner: String,
+
+    /// Token id in decimal, or hex with 0x prefix
+    #[clap(required=true)]
+    pub token_id: String,
+}
+
+/// Arguments for `erc1155 balance-of-batch` subcommand
+#[derive(Debug, Args)]
+pub struct Erc1155BalanceOfBatchArgs {
+    /// Token contract address (or ENS name, or alias)
+    #[clap(required=true)]
+    pub token: String,
+
+    /// Owner addresses (or ENS names, or aliases); either one per token id, or a single
+    /// owner for all token ids
+    #[clap(long="owners", required=true, multiple_values=true, use_value_delimiter=true)]
+    pub owners: Vec<String>,
+
+    /// Token ids in decimal, or hex with 0x prefix
+    #[clap(long="ids", required=true, multiple_values=true, use_value_delimiter=true)]
+    pub ids: Vec<String>,
+}
+
+/// Arguments for `erc1155 safe-transfer-from` subcommand
+#[derive(Debug, Args)]
+pub struct Erc1155SafeTransferFromArgs {
+    /// Token contract address (or ENS name, or alias)
+    #[clap(required=true)]
+    pub token: String,
+
+    /// Recipient address (or ENS name, or alias)
+    #[clap(required=true)]
+    pub to: String,
+
+    /// Token ids in decimal, or hex with 0x prefix
+    #[clap(long="ids", required=true, multiple_values=true, use_value_delimiter=true)]
+    pub ids: Vec<String>,
+
+    /// Amount of each token id in the same order as --ids
+    #[clap(long="amounts", required=true, multiple_values=true, use_value_delimiter=true)]
+    pub amounts: Vec<String>,
+
+    /// Data in hex with 0x prefix passed along to the recipient's `onERC1155Received`
+    #[clap(long="data", default_value="0x")]
+    pub data: String,
+
+    /// Current owner of the tokens (or ENS name, or alias) to transfer from when the
+    /// sender is an approved operator; the sender by default
+    #[clap(long="owner", multiple_values=false)]
+    pub owner: Option<String>,
+
+    #[clap(flatten)]
+    pub tx: TransactionArgs,
+}
+
+/// Arguments for `events` subcommand
+#[derive(Debug, Args)]
+pub struct EventsArgs {
+    /// Target contract address (or ENS name) whose emitted event logs to query
+    #[clap(long="address", short='a', required=true, multiple_values=false)]
+    pub contract_address: String,
+
+    /// Block number to start querying from; latest block if not set
+    #[clap(long="from-block", multiple_values=false, takes_value=true)]
+    pub from_block: Option<u64>,
+
+    /// Block number to query up to (inclusive); latest block if not set
+    #[clap(long="to-block", multiple_values=false, takes_value=true)]
+    pub to_block: Option<u64>,
+
+    /// Only query logs of the event, and decode them by its signature i.e.
+    /// "Transfer(address,address,uint256)". Annotate indexed parameters, and names
+    /// as in Solidity i.e. "Transfer(address indexed from, address indexed to, uint256 value)"
+    /// if the event is not declared in ABI.
+    #[clap(long="event", multiple_values=false, takes_value=true)]
+    pub event: Option<String>,
+
+    /// ABI filepath to decode logs against; its entries are merged with the built-in
+    /// ABI which covers Transfer, and Approval events.
+    #[clap(long="abi-filepath", multiple_values=false, takes_value=true)]
+    pub abi_filepath: Option<String>,
+
+    /// Keep streaming logs as they arrive after ones from `--from-block` (if set).
+    /// It requires WebSocket RPC endpoint i.e. `--rpc-url wss://...`, and resubscribes
+    /// automatically on disconnect.
+    #[clap(long="follow", conflicts_with="to-block")]
+    pub follow: bool,
+
+    /// Number of blocks to query per request; wide block range is split into chunks
+    /// of it. Default depends on the chain i.e. 5000 for BSC, 2000 for Ethereum.
+    #[clap(long="chunk-size", multiple_values=false, takes_value=true)]
+    pub chunk_size: Option<u64>,
+}
+
+/// Arguments for `key` subcommand
+#[derive(Debug, Args)]
+pub struct KeyArgs {
+    #[clap(subcommand)]
+    pub command: KeyCommand,
+}
+
+/// Operations on signing keys stored in OS secret store
+#[derive(Debug, Subcommand)]
+pub enum KeyCommand {
+    /// Store signing key under the named profile; the key is prompted interactively,
+    /// or read from stdin if piped
+    Import(KeyProfileArgs),
+
+    /// Remove signing key of the named profile
+    Remove(KeyProfileArgs),
+}
+
+/// Arguments identifying a key profile
+#[derive(Debug, Args)]
+pub struct KeyProfileArgs {
+    /// Name of the profile i.e. `deployer`
+    #[clap(required=true)]
+    pub profile: String,
+}
+
+/// Arguments for `batch` subcommand
+#[derive(Debug, Args)]
+pub struct BatchArgs {
+    /// Filepath of call specifications; its format is detected from its extension
+    /// i.e. .toml, .json, or .csv. Each entry specifies chain (default to --chain),
+    /// address, fn, params, and mode which is one of call (default), estimate, or send.
+    #[clap(long="file", required=true, multiple_values=false, takes_value=true)]
+    pub file: String,
+
+    /// Number of entries to execute concurrently; entries are executed sequentially by
+    /// default. Results are printed in the same order as entries regardless.
+    #[clap(long="concurrency", multiple_values=false, takes_value=true, default_value="1")]
+    pub concurrency: usize,
+}
+
+/// Arguments for `serve` subcommand
+#[derive(Debug, Args)]
+pub struct ServeArgs {
+    /// Filepath of TOML config with `[[get]]`, and `[[post]]` tables each mapping path
+    /// of endpoint to address, fn, and optionally fixed leading params, and abi
+    #[clap(long="config", required=true, multiple_values=false, takes_value=true)]
+    pub config: String,
+
+    /// Address to listen on
+    #[clap(long="listen", multiple_values=false, takes_value=true, default_value="127.0.0.1:8080")]
+    pub listen: String,
+}
+
+/// Arguments for `history` subcommand
+#[derive(Debug, Args)]
+pub struct HistoryArgs {
+    /// Kind of operations to list
+    #[clap(long="kind", multiple_values=false, takes_value=true, possible_values=&["call", "send"])]
+    pub kind: Option<String>,
+
+    /// Contract address of operations to list
+    #[clap(long="address", short='a', multiple_values=false, takes_value=true)]
+    pub address: Option<String>,
+
+    /// Function name of operations to list
+    #[clap(long="fn-name", short='f', multiple_values=false, takes_value=true)]
+    pub fn_name: Option<String>,
+
+    /// Outcome of operations to list
+    #[clap(long="status", multiple_values=false, takes_value=true, possible_values=&["success", "reverted", "pending", "failed"])]
+    pub status: Option<String>,
+
+    /// List only operations made within the duration i.e. 1h, or 7d, or since the time
+    /// in RFC 3339 format i.e. 2024-01-01T00:00:00Z
+    #[clap(long="since", multiple_values=false, takes_value=true)]
+    pub since: Option<String>,
+
+    /// Maximum number of the latest operations to list; 0 lists all of them
+    #[clap(long="limit", multiple_values=false, takes_value=true, default_value="50")]
+    pub limit: usize,
+}
+
+/// Arguments for `script` subcommand
+#[derive(Debug, Args)]
+pub struct ScriptArgs {
+    /// Filepath of Rhai script i.e. flow.rhai
+    #[clap(required=true)]
+    pub script: String,
+
+    /// ABI filepath describing functions of contracts the script interacts with.
+    /// Its entries are merged with the built-in ABI.
+    #[clap(long="abi-filepath", multiple_values=false, takes_value=true)]
+    pub abi_filepath: Option<String>,
+
+    /// Number of block confirmations to wait for each transaction sent by the script.
+    /// Default is 20, or 0 on local chain as it usually mines a block only on a new transaction.
+    #[clap(long="block-confirmations", multiple_values=false, takes_value=true)]
+    pub block_confirmations: Option<u64>,
+
+    /// Key to sign transactions with; CRUNNER_SETTER_SECRETKEY is used if none is specified
+    #[clap(flatten)]
+    pub key: SigningKeyArgs,
+}
+
+/// Arguments for `completions` subcommand
+#[derive(Debug, Args)]
+pub struct CompletionsArgs {
+    /// Shell to generate completion script for
+    #[clap(arg_enum, required=true)]
+    pub shell: Shell,
+}
+
+/// Arguments for `tx` subcommand
+#[derive(Debug, Args)]
+pub struct TxArgs {
+    #[clap(subcommand)]
+    pub command: TxCommand,
+}
+
+/// Operations on a pending transaction
+#[derive(Debug, Subcommand)]
+pub enum TxCommand {
+    /// Rebroadcast the pending transaction with the same nonce, and a higher gas price
+    Speedup(TxReplaceArgs),
+
+    /// Replace the pending transaction with zero-value self-transfer of the same
+    /// nonce, and a higher gas price
+    Cancel(TxReplaceArgs),
+}
+
+/// Arguments for replacing a pending transaction
+#[derive(Debug, Args)]
+pub struct TxReplaceArgs {
+    /// Hash of the pending transaction
+    #[clap(required=true)]
+    pub tx_hash: String,
+
+    /// Percentage to bump gas price, or fees of EIP-1559 transaction by i.e. 20%.
+    /// Nodes usually require at least 10% to accept a replacement.
+    #[clap(long="bump", multiple_values=false, takes_value=true, default_value="10%")]
+    pub bump: String,
+
+    /// Number of block confirmations to wait for the replacement transaction.
+    /// Default is 20, or 0 on local chain as it usually mines a block only on a new transaction.
+    #[clap(long="block-confirmations", multiple_values=false, takes_value=true)]
+    pub block_confirmations: Option<u64>,
+
+    /// Key of the sender of the pending transaction; CRUNNER_SETTER_SECRETKEY is used if none is specified
+    #[clap(flatten)]
+    pub key: SigningKeyArgs,
+}
+
+/// Supported simple return types for `--fn-ret-type`
+static FN_RET_TYPES: &[&str] = &["String", "U256", "I256", "bool", "Address", "u8", "u64", "bytes", "String[]", "U256[]", "I256[]", "bool[]", "Address[]", "u8[]", "u64[]", "bytes[]"];
+
+/// Validate value of `--fn-ret-type`; it is either one of the supported simple
+/// return types, or composite return signature enclosed in parentheses.
+fn validate_fn_ret_type(value: &str) -> Result<(), String> {
+    if FN_RET_TYPES.contains(&value) || (value.starts_with('(') && value.ends_with(')')) {
+        Ok(())
+    }
+    else {
+        Err(format!("must be one of {}, or composite return signature i.e. (uint112,uint112,uint32)", FN_RET_TYPES.join(", ")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn negative_number_is_parsed_as_param() {
+        let cmd_args = CommandlineArgs::try_parse_from(["crunner", "-c", "ethereum", "send", "-a", "0xe2d26507981a4daaaa8040bae1846c14e0fb56bf", "-f", "adjust", "-p", "-5", "7", "--encode-only"]).unwrap();
+        match cmd_args.command {
+            Command::Send(args) => {
+                assert_eq!(args.contract.params, vec!["-5", "7"]);
+                assert!(args.encode_only);
+            },
+            _ => panic!("expected send subcommand"),
+        }
+    }
+}
@@ -0,0 +1,1100 @@
+use crate::cache::ResponseCache;
+use crate::chains::address_alias;
+use crate::ens::*;
+use crate::error::CrunnerError;
+use crate::gas::{GasFees, GasStrategy};
+use crate::history::{History, HistoryKind, HistoryRecord};
+use crate::nonce::NonceManager;
+use crate::price::{default_price_feed, native_usd_price, PriceSource};
+use crate::signer::{ExternalSigner, UnsignedTx};
+use crate::transport::{is_ws_url, CrunnerTransport, RetryPolicy};
+use crate::types::{CallFrame, ChainType, ConfirmationProgress, FeeSuggestion, LogScanProgress, MulticallResult, StateOverride, TemplateVars, TokenInfo, TokenStandard};
+use crate::util::*;
+use crate::wallet::secret_key_from_env;
+use ethabi::{ParamType, Token};
+use secp256k1::SecretKey;
+use std::collections::HashMap;
+use std::future::Future;
+use std::time::{Duration, Instant};
+
+use web3::{
+    Web3,
+    types::{Address, BlockId, BlockNumber, CallRequest, H256, Log, TransactionReceipt, U256},
+    contract::{Contract, Options, tokens::Detokenize},
+    signing::{Key, SecretKeyRef},
+};
+
+/// Callback receiving progress of waiting for block confirmations
+type ConfirmationProgressFn = Box<dyn Fn(&ConfirmationProgress) + Send + Sync>;
+
+/// Callback receiving progress of scanning event logs
+type LogScanProgressFn = Box<dyn Fn(&LogScanProgress) + Send + Sync>;
+
+/// Client to interact with smart contracts on the specified chain.
+///
+/// This is the entry point for embedding crunner's functionality into other
+/// Rust programs without shelling out to the CLI.
+pub struct CrunnerClient {
+    chain: ChainType,
+    web3: Web3<CrunnerTransport>,
+    ens_registry: Option<Address>,
+    block: Option<BlockId>,
+    state_override: Option<StateOverride>,
+    confirmation_timeout: Option<Duration>,
+    confirmation_progress: Option<ConfirmationProgressFn>,
+    logs_chunk_size: Option<u64>,
+    log_scan_progress: Option<LogScanProgressFn>,
+    gas_strategy: Option<GasStrategy>,
+    price_source: Option<PriceSource>,
+    multicall_address: Option<Address>,
+    rpc_batch_size: Option<usize>,
+    response_cache: Option<ResponseCache>,
+    history: Option<History>,
+    nonce_manager: NonceManager,
+}
+
+impl CrunnerClient {
+    /// Create a new client connecting to the built-in RPC endpoint of the chain,
+    /// or the one set via per-chain environment variable i.e. `CRUNNER_RPC_BSC`.
+    ///
+    /// # Arguments
+    /// * `chain` - `ChainType` to work with
+    pub fn new(chain: ChainType) -> Result<Self, CrunnerError> {
+        Ok(Self {
+            chain,
+            web3: create_web3(chain)?,
+            ens_registry: None,
+            block: None,
+            state_override: None,
+            confirmation_timeout: None,
+            confirmation_progress: None,
+            logs_chunk_size: None,
+            log_scan_progress: None,
+            gas_strategy: None,
+            price_source: None,
+            multicall_address: None,
+            rpc_batch_size: None,
+            response_cache: None,
+            history: None,
+            nonce_manager: NonceManager::new(),
+        })
+    }
+
+    /// Connect to the specified RPC endpoint instead of the chain's default one.
+    ///
+    /// # Arguments
+    /// * `rpc_url` - RPC endpoint url, or IPC path i.e. `/path/to/geth.ipc`
+    pub fn with_rpc_url(mut self, rpc_url: &str) -> Result<Self, CrunnerError> {
+        self.web3 = create_web3_from_url(rpc_url)?;
+        Ok(self)
+    }
+
+    /// Connect to the specified RPC endpoints instead of the chain's default one;
+    /// it fails over to the next endpoint whenever the current one fails.
+    ///
+    /// # Arguments
+    /// * `rpc_urls` - RPC endpoint urls, or IPC paths in order of preference
+    pub fn with_rpc_urls(mut self, rpc_urls: &[String]) -> Result<Self, CrunnerError> {
+        self.web3 = create_web3_from_urls(rpc_urls)?;
+        Ok(self)
+    }
+
+    /// Send the specified HTTP headers along with each RPC request i.e.
+    /// `Authorization` header required by RPC provider.
+    ///
+    /// # Arguments
+    /// * `headers` - pairs of header name, and value
+    pub fn with_rpc_headers(mut self, headers: &[(String, String)]) -> Result<Self, CrunnerError> {
+        let transport = self.web3.transport().clone().with_headers(headers)?;
+        self.web3 = Web3::new(transport);
+        Ok(self)
+    }
+
+    /// Use the specified policy to retry RPC requests on transient errors.
+    ///
+    /// # Arguments
+    /// * `retry_policy` - retry policy
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        let transport = self.web3.transport().clone().with_retry_policy(retry_policy);
+        self.web3 = Web3::new(transport);
+        self
+    }
+
+    /// Time out each RPC request after the specified duration.
+    ///
+    /// # Arguments
+    /// * `request_timeout` - timeout of each RPC request
+    pub fn with_request_timeout(mut self, request_timeout: Duration) -> Self {
+        let transport = self.web3.transport().clone().with_request_timeout(request_timeout);
+        self.web3 = Web3::new(transport);
+        self
+    }
+
+    /// Make calls to getter methods read state as of the specified block instead of
+    /// the latest one.
+    ///
+    /// # Arguments
+    /// * `block` - block to read state at
+    pub fn with_block(mut self, block: BlockId) -> Self {
+        self.block = Some(block);
+        self
+    }
+
+    /// Apply state overrides of accounts i.e. balance, code, or storage slots to calls
+    /// to getter methods, which simulates calls against modified state.
+    ///
+    /// # Arguments
+    /// * `state_override` - state overrides keyed by account address
+    pub fn with_state_override(mut self, state_override: StateOverride) -> Self {
+        self.state_override = Some(state_override);
+        self
+    }
+
+    /// Stop waiting for block confirmations of a sent transaction after the specified
+    /// duration; the transaction is reported as still pending with its hash.
+    ///
+    /// # Arguments
+    /// * `confirmation_timeout` - timeout of confirming a transaction
+    pub fn with_confirmation_timeout(mut self, confirmation_timeout: Duration) -> Self {
+        self.confirmation_timeout = Some(confirmation_timeout);
+        self
+    }
+
+    /// Report progress of waiting for block confirmations of a sent transaction
+    /// to the specified callback; it is called about every second.
+    ///
+    /// # Arguments
+    /// * `progress` - callback receiving the progress
+    pub fn with_confirmation_progress<F>(mut self, progress: F) -> Self
+    where
+        F: Fn(&ConfirmationProgress) + Send + Sync + 'static
+    {
+        self.confirmation_progress = Some(Box::new(progress));
+        self
+    }
+
+    /// Query event logs for at most the specified number of blocks per request,
+    /// instead of the default one of the chain.
+    ///
+    /// # Arguments
+    /// * `logs_chunk_size` - number of blocks per request
+    pub fn with_logs_chunk_size(mut self, logs_chunk_size: u64) -> Self {
+        self.logs_chunk_size = Some(logs_chunk_size);
+        self
+    }
+
+    /// Report progress of scanning event logs over a block range to the specified
+    /// callback; it is called after each chunk of blocks is queried.
+    ///
+    /// # Arguments
+    /// * `progress` - callback receiving the progress
+    pub fn with_log_scan_progress<F>(mut self, progress: F) -> Self
+    where
+        F: Fn(&LogScanProgress) + Send + Sync + 'static
+    {
+        self.log_scan_progress = Some(Box::new(progress));
+        self
+    }
+
+    /// Determine gas price, or fees of transactions with the specified strategy,
+    /// instead of the default one of the chain.
+    ///
+    /// # Arguments
+    /// * `gas_strategy` - gas strategy
+    pub fn with_gas_strategy(mut self, gas_strategy: GasStrategy) -> Self {
+        self.gas_strategy = Some(gas_strategy);
+        self
+    }
+
+    /// Query the price of native currency in USD from the specified source, instead
+    /// of the known Chainlink price feed of the chain.
+    ///
+    /// # Arguments
+    /// * `price_source` - source of the price
+    pub fn with_price_source(mut self, price_source: PriceSource) -> Self {
+        self.price_source = Some(price_source);
+        self
+    }
+
+    /// Aggregate calls via Multicall3 deployed at the specified address, instead of
+    /// its canonical address which is the same on most EVM chains.
+    ///
+    /// # Arguments
+    /// * `multicall_address` - address of Multicall3 contract
+    pub fn with_multicall_address(mut self, multicall_address: Address) -> Self {
+        self.multicall_address = Some(multicall_address);
+        self
+    }
+
+    /// Send at most the specified number of calls per JSON-RPC batch request in
+    /// `call_many`, instead of the default of 100. Zero disables batching so that each
+    /// call is sent as its own request i.e. for endpoints not supporting batch requests.
+    ///
+    /// # Arguments
+    /// * `rpc_batch_size` - maximum number of calls per batch request
+    pub fn with_rpc_batch_size(mut self, rpc_batch_size: usize) -> Self {
+        self.rpc_batch_size = Some(rpc_batch_size);
+        self
+    }
+
+    /// Serve returned data of immutable getters i.e. `decimals()` from the specified
+    /// response cache, and store them there after querying. It applies only to calls
+    /// at the latest block without state overrides, and not on local chain.
+    ///
+    /// # Arguments
+    /// * `response_cache` - response cache
+    pub fn with_response_cache(mut self, response_cache: ResponseCache) -> Self {
+        self.response_cache = Some(response_cache);
+        self
+    }
+
+    /// Record every getter call along with its result, and every transaction sent to
+    /// setter method along with its outcome in the specified history.
+    ///
+    /// # Arguments
+    /// * `history` - history to record into
+    pub fn with_history(mut self, history: History) -> Self {
+        self.history = Some(history);
+        self
+    }
+
+    /// Use the specified ENS registry deployed on the client's chain to resolve
+    /// ENS names, instead of the default ENS registry on Ethereum.
+    ///
+    /// # Arguments
+    /// * `registry` - address of ENS-compatible registry
+    pub fn with_ens_registry(mut self, registry: Address) -> Self {
+        self.ens_registry = Some(registry);
+        self
+    }
+
+    /// Allocate nonces of transactions with the specified nonce manager i.e. one
+    /// persisting tracked nonces on disk. By default, nonces are tracked only in memory.
+    ///
+    /// # Arguments
+    /// * `nonce_manager` - nonce manager
+    pub fn with_nonce_manager(mut self, nonce_manager: NonceManager) -> Self {
+        self.nonce_manager = nonce_manager;
+        self
+    }
+
+    /// Get the chain this client works with.
+    pub fn chain(&self) -> ChainType {
+        self.chain
+    }
+
+    /// Get the underlying web3 instance.
+    pub fn web3(&self) -> &Web3<CrunnerTransport> {
+        &self.web3
+    }
+
+    /// Resolve block to read state at from block number, hash, or tag i.e. `finalized`.
+    ///
+    /// # Arguments
+    /// * `block` - block number, hash, or tag
+    pub async fn resolve_block(&self, block: &str) -> Result<BlockId, CrunnerError> {
+        web3_resolve_block(&self.web3, block).await
+    }
+
+    /// Resolve ENS name, or alias in the address book into checksummed address;
+    /// other address string is returned as it is.
+    ///
+    /// # Arguments
+    /// * `name_or_address` - ENS name i.e. `vitalik.eth`, alias, or address
+    pub async fn resolve_address(&self, name_or_address: &str) -> Result<String, CrunnerError> {
+        if address_alias(name_or_address).is_some() {
+            return Ok(to_checksum_address(&get_address_from_str(name_or_address)?));
+        }
+        if !is_ens_name(name_or_address) {
+            return Ok(name_or_address.to_owned());
+        }
+
+        let (web3, registry) = self.ens_web3_and_registry()?;
+        let addr = resolve_ens_name(&web3, registry, name_or_address).await?;
+        Ok(to_checksum_address(&addr))
+    }
+
+    /// Resolve ENS names found in parameter strings into checksummed addresses.
+    ///
+    /// # Arguments
+    /// * `params` - parameter strings
+    pub async fn resolve_params(&self, params: &[String]) -> Result<Vec<String>, CrunnerError> {
+        let (web3, registry) = self.ens_web3_and_registry()?;
+        resolve_ens_params(&web3, registry, params).await
+    }
+
+    /// Substitute template variables i.e. `{self}`, or `{block.timestamp+3600}` in
+    /// parameter strings with their values, then scale human amounts i.e. `amount:12.5`
+    /// by decimals of the token. The block is fetched only if referred to; it's the
+    /// block to read state at if set, otherwise the latest one.
+    ///
+    /// # Arguments
+    /// * `params` - parameter strings
+    /// * `self_address` - address of the contract called (if known)
+    /// * `sender` - address the call, or transaction is made from (if known)
+    pub async fn expand_params(&self, params: &[String], self_address: Option<&str>, sender: Option<&str>) -> Result<Vec<String>, CrunnerError> {
+        let mut vars = TemplateVars {
+            self_address: self_address.map(|a| a.to_owned()),
+            sender: sender.map(|a| a.to_owned()),
+            ..Default::default()
+        };
+        if params_refer_template(params, "block.") {
+            let block_id = self.block.unwrap_or(BlockId::Number(BlockNumber::Latest));
+            let block = match self.web3.eth().block(block_id).await {
+                Ok(Some(res)) => res,
+                Ok(None) => return Err(CrunnerError::RpcError("Error, block of template in parameters is not found".to_owned())),
+                Err(e) => return Err(CrunnerError::RpcError(format!("Error querying block of template in parameters; err={}", e))),
+            };
+            vars.block_number = block.number.map(|n| U256::from(n.as_u64()));
+            vars.block_timestamp = Some(block.timestamp);
+        }
+        let params = expand_param_templates(params, &vars)?;
+        self.scale_amounts(params, self_address).await
+    }
+
+    /// Scale human amounts annotated as `amount:100.25` in parameter strings by decimals
+    /// of the token; either the contract called, or the one following `@` i.e.
+    /// `amount:100.25@0x...` as in calls to DEX router. Decimals of each token are
+    /// queried once.
+    async fn scale_amounts(&self, params: Vec<String>, self_address: Option<&str>) -> Result<Vec<String>, CrunnerError> {
+        let mut decimals_of: HashMap<String, usize> = HashMap::new();
+        let mut scaled_params: Vec<String> = Vec::new();
+
+        for p in params {
+            let amount = match p.strip_prefix("amount:") {
+                Some(res) => res,
+                None => {
+                    scaled_params.push(p);
+                    continue;
+                }
+            };
+            let (number, token) = match (amount.split_once('@'), self_address) {
+                (Some((number, token)), _) => (number, self.resolve_address(token.trim()).await?),
+                (None, Some(self_address)) => (amount, self_address.to_owned()),
+                (None, None) => return Err(CrunnerError::InvalidArgument(format!("Error, parameter '{}' requires --address of a single token, or the token following @", p))),
+            };
+            let decimals = match decimals_of.get(&token) {
+                Some(res) => *res,
+                None => {
+                    let decimals = self.token_decimals(&token).await?;
+                    decimals_of.insert(token, decimals);
+                    decimals
+                }
+            };
+            match parse_units(number, decimals) {
+                Ok(res) => scaled_params.push(res.to_string()),
+                Err(e) => return Err(e.context(format!("Error parsing parameter '{}'", p))),
+            }
+        }
+
+        Ok(scaled_params)
+    }
+
+    /// Query number of decimals of ERC-20 token via its `decimals()`.
+    ///
+    /// # Arguments
+    /// * `token` - address of the token
+    pub async fn token_decimals(&self, token: &str) -> Result<usize, CrunnerError> {
+        let contract = self.contract(token, None)?;
+        let tokens = match web3_query_get_tokens(&self.web3, &contract, "decimals", &[], &[ParamType::Uint(8)], self.block, None).await {
+            Ok(res) => res,
+            Err(e) => return Err(e.context(format!("Error querying decimals of token {}", token))),
+        };
+        match tokens.first() {
+            Some(Token::Uint(decimals)) => match decimals_to_usize(*decimals) {
+                Some(res) => Ok(res),
+                None => Err(CrunnerError::AbiError(format!("Error, token {} returned invalid decimals; decimals={}", token, decimals))),
+            },
+            _ => Err(CrunnerError::AbiError(format!("Error decoding decimals of token {}", token))),
+        }
+    }
+
+    /// Query name, symbol, decimals, and total supply of ERC-20 token at once; via
+    /// Multicall3 if it's deployed on the chain, otherwise in a JSON-RPC batch request.
+    ///
+    /// # Arguments
+    /// * `token` - address of the token
+    pub async fn token_info(&self, token: &str) -> Result<TokenInfo, CrunnerError> {
+        let contract = self.standard_contract(token, TokenStandard::Erc20)?;
+        let calls: Vec<(&Contract<CrunnerTransport>, &str, &[String])> = ["name", "symbol", "decimals", "totalSupply"].iter()
+            .map(|fn_name| (&contract, *fn_name, &[][..]))
+            .collect();
+        let results = match self.multicall(calls.as_slice(), true).await {
+            Ok(res) => res,
+            Err(e) => {
+                tracing::debug!(err = %e, "multicall unavailable; falling back to batch request");
+                self.call_many(calls.as_slice(), None).await
+            },
+        };
+
+        let mut outputs = results.into_iter().map(|res| res.ok().and_then(|outputs| outputs.into_iter().next()).map(|(_, token)| token));
+        let mut next_output = || outputs.next().flatten();
+        let info = TokenInfo {
+            name: next_output().and_then(Token::into_string),
+            symbol: next_output().and_then(Token::into_string),
+            decimals: next_output().and_then(Token::into_uint).and_then(|decimals| {
+                let res = decimals_to_usize(decimals);
+                if res.is_none() {
+                    tracing::warn!(token, %decimals, "ignored invalid decimals returned by token");
+                }
+                res
+            }),
+            total_supply: next_output().and_then(Token::into_uint),
+        };
+        if info.name.is_none() && info.symbol.is_none() && info.decimals.is_none() && info.total_supply.is_none() {
+            return Err(CrunnerError::AbiError(format!("Error, {} implements none of ERC-20 metadata methods", token)));
+        }
+        Ok(info)
+    }
+
+    /// Get web3 instance, and address of ENS registry used to resolve ENS names.
+    /// Without a custom registry, ENS names are resolved on Ethereum.
+    fn ens_web3_and_registry(&self) -> Result<(Web3<CrunnerTransport>, Address), CrunnerError> {
+        match (self.ens_registry, self.chain) {
+            (Some(registry), _) => Ok((self.web3.clone(), registry)),
+            (None, ChainType::Ethereum) => Ok((self.web3.clone(), default_ens_registry())),
+            (None, _) => Ok((create_web3(ChainType::Ethereum)?, default_ens_registry())),
+        }
+    }
+
+    /// Verify that chain id reported by the connected RPC endpoint matches the
+    /// expected chain id of the client's chain. Local chain is not verified.
+    pub async fn verify_chain_id(&self) -> Result<(), CrunnerError> {
+        let expected = match chain_id(self.chain) {
+            Some(res) => res,
+            None => return Ok(()),
+        };
+
+        let actual = match self.web3.eth().chain_id().await {
+            Ok(res) => res,
+            Err(e) => return Err(CrunnerError::RpcError(format!("Error querying chain id; err={}", e))),
+        };
+        if actual != U256::from(expected) {
+            return Err(CrunnerError::ChainMismatch(format!("Error, RPC endpoint is on chain id {} but {} expects chain id {}; use --skip-chain-check to proceed anyway", actual, chain_name(self.chain), expected)));
+        }
+        Ok(())
+    }
+
+    /// Check whether the specified address is an EOA.
+    ///
+    /// # Arguments
+    /// * `address` - address to check; in format `0x...`
+    pub async fn is_eoa(&self, address: &str) -> Result<bool, CrunnerError> {
+        perform_check_is_eoa(&self.web3, address).await
+    }
+
+    /// Create a contract instance from the built-in ABI merged with entries from
+    /// ABI file (if any).
+    ///
+    /// # Arguments
+    /// * `address` - contract address
+    /// * `abi_filepath` - optional ABI json filepath
+    pub fn contract(&self, address: &str, abi_filepath: Option<&str>) -> Result<Contract<CrunnerTransport>, CrunnerError> {
+        create_contract_from_abifile(&self.web3, address, abi_filepath)
+    }
+
+    /// Create a contract instance from the built-in ABI merged with the verified
+    /// ABI fetched from block explorer of the chain.
+    ///
+    /// # Arguments
+    /// * `address` - contract address
+    pub async fn contract_from_explorer(&self, address: &str) -> Result<Contract<CrunnerTransport>, CrunnerError> {
+        let abi = load_abi_from_explorer(self.chain, address).await?;
+        create_contract_with_abi(&self.web3, address, abi)
+    }
+
+    /// Create a contract instance from the built-in ABI of the token standard.
+    ///
+    /// # Arguments
+    /// * `address` - contract address
+    /// * `standard` - token standard i.e. ERC-20
+    pub fn standard_contract(&self, address: &str, standard: TokenStandard) -> Result<Contract<CrunnerTransport>, CrunnerError> {
+        let abi = load_standard_abi(standard)?;
+        create_contract_with_abi(&self.web3, address, abi)
+    }
+
+    /// Select overload of the function to call among overloaded functions of
+    /// the same name; either by `fn_sig`, or by matching types of `params`.
+    /// Return the contract instance which uses such overload when calling by name.
+    ///
+    /// # Arguments
+    /// * `contract` - contract instance
+    /// * `fn_name` - name of the function
+    /// * `fn_sig` - optional function signature i.e. `transfer(address,uint256)`
+    /// * `params` - parameter strings to pass to such function
+    pub fn select_overload(&self, contract: &Contract<CrunnerTransport>, fn_name: &str, fn_sig: Option<&str>, params: &[String]) -> Result<Contract<CrunnerTransport>, CrunnerError> {
+        select_function_overload(&self.web3, contract, fn_name, fn_sig, params)
+    }
+
+    /// Make a call to getter method of the contract.
+    ///
+    /// # Arguments
+    /// * `contract` - contract instance to interact with
+    /// * `fn_name` - name of the function to make a call
+    /// * `params` - parameter strings to pass to such function
+    pub async fn call<R>(&self, contract: &Contract<CrunnerTransport>, fn_name: &str, params: &[String]) -> Result<R, CrunnerError>
+    where
+        R: Detokenize
+    {
+        // web3's contract query can't carry state overrides, nor be cached; go through raw eth_call
+        let is_cached = match encode_function_call(contract, fn_name, params) {
+            Ok(calldata) => self.cache_key(contract, &calldata).is_some(),
+            Err(_) => false,
+        };
+        // returned value is recorded in history as decoded tokens
+        if self.state_override.is_some() || is_cached || self.history.is_some() {
+            let tokens = self.call_inferred(contract, fn_name, params).await?;
+            return match R::from_tokens(tokens.into_iter().map(|(_, token)| token).collect()) {
+                Ok(res) => Ok(res),
+                Err(e) => Err(CrunnerError::AbiError(format!("Error decoding returned data of function '{}'; err={}", fn_name, e))),
+            };
+        }
+        web3_query_get::<R>(contract, fn_name, params, self.block).await
+    }
+
+    /// Make a call to getter method of the contract then decode its returned data
+    /// against `ret_types`, which supports multi-value returns and tuples.
+    ///
+    /// # Arguments
+    /// * `contract` - contract instance to interact with
+    /// * `fn_name` - name of the function to make a call
+    /// * `params` - parameter strings to pass to such function
+    /// * `ret_types` - types to decode the returned data into
+    pub async fn call_tokens(&self, contract: &Contract<CrunnerTransport>, fn_name: &str, params: &[String], ret_types: &[ParamType]) -> Result<Vec<Token>, CrunnerError> {
+        let res = if self.response_cache.is_some() {
+            self.call_cached(contract, fn_name, params, Some(ret_types)).await
+                .map(|outputs| outputs.into_iter().map(|(_, token)| token).collect())
+        }
+        else {
+            web3_query_get_tokens(&self.web3, contract, fn_name, params, ret_types, self.block, self.state_override.as_ref()).await
+        };
+        if self.history.is_some() {
+            let outcome = res.as_ref().map(|tokens| format_returned(tokens.iter())).map_err(|e| e.to_string());
+            self.record_calls(&[(contract, fn_name, params)], vec![outcome]);
+        }
+        res
+    }
+
+    /// Make a call to getter method of the contract then decode its returned data
+    /// against the function's outputs as declared in ABI.
+    ///
+    /// # Arguments
+    /// * `contract` - contract instance to interact with
+    /// * `fn_name` - name of the function to make a call
+    /// * `params` - parameter strings to pass to such function
+    ///
+    /// # Return
+    /// Return name of each output (empty if unnamed) along with its decoded `Token`.
+    pub async fn call_inferred(&self, contract: &Contract<CrunnerTransport>, fn_name: &str, params: &[String]) -> Result<Vec<(String, Token)>, CrunnerError> {
+        let res = if self.response_cache.is_some() {
+            self.call_cached(contract, fn_name, params, None).await
+        }
+        else {
+            web3_query_get_inferred(&self.web3, contract, fn_name, params, self.block, self.state_override.as_ref()).await
+        };
+        if self.history.is_some() {
+            let outcome = res.as_ref().map(|outputs| format_returned(outputs.iter().map(|(_, token)| token))).map_err(|e| e.to_string());
+            self.record_calls(&[(contract, fn_name, params)], vec![outcome]);
+        }
+        res
+    }
+
+    /// Make a read-only call with calldata already at hand i.e. as printed by
+    /// `send --encode-only`, and return its returned data as is. The call is made at the
+    /// block set via `with_block`, and with state overrides set via `with_state_override`
+    /// (if any).
+    ///
+    /// # Arguments
+    /// * `call_req` - call request with `to`, `data`, and optionally `from`, and `value`
+    /// * `abi` - ABI to look up custom errors in if the call reverts (if any)
+    pub async fn call_raw(&self, call_req: CallRequest, abi: Option<&ethabi::Contract>) -> Result<Vec<u8>, CrunnerError> {
+        web3_call_raw(&self.web3, call_req, self.block, self.state_override.as_ref(), abi).await
+    }
+
+    /// Make a call to getter method of the contract served from the response cache if
+    /// it's cached, otherwise query then store its returned data there if applicable.
+    async fn call_cached(&self, contract: &Contract<CrunnerTransport>, fn_name: &str, params: &[String], ret_types: Option<&[ParamType]>) -> Result<Vec<(String, Token)>, CrunnerError> {
+        let calldata = encode_function_call(contract, fn_name, params)?;
+        let cache = match (&self.response_cache, self.cache_key(contract, &calldata)) {
+            (Some(cache), Some(key)) => Some((cache, key)),
+            _ => None,
+        };
+
+        let data = match cache.as_ref().and_then(|(cache, key)| cache.get(key)) {
+            Some(res) => res,
+            None => {
+                let data = web3_query_get_raw(&self.web3, contract, fn_name, calldata, self.block, self.state_override.as_ref()).await?;
+                // only well-formed returned data is worth caching
+                let outputs = decode_outputs(contract, fn_name, &data, ret_types)?;
+                if let Some((cache, key)) = &cache {
+                    if let Err(e) = cache.insert(key, &data) {
+                        tracing::debug!(error = %e, "failed to cache response");
+                    }
+                }
+                return Ok(outputs);
+            },
+        };
+        decode_outputs(contract, fn_name, &data, ret_types)
+    }
+
+    /// Get key of the call in the response cache if its returned data can be cached
+    /// i.e. immutable getter read at the latest block without state overrides.
+    fn cache_key(&self, contract: &Contract<CrunnerTransport>, calldata: &[u8]) -> Option<String> {
+        let applies = self.response_cache.is_some()
+            && self.block.is_none()
+            && self.state_override.is_none()
+            && !matches!(self.chain, ChainType::Local)
+            && ResponseCache::is_cacheable(calldata);
+        if applies {
+            Some(ResponseCache::key(chain_name(self.chain), &format!("{:?}", contract.address()), calldata))
+        }
+        else {
+            None
+        }
+    }
+
+    /// Make a call to setter method of the contract; this sends a transaction signed
+    /// with the secret key set in `CRUNNER_SETTER_SECRETKEY` environment variable.
+    ///
+    /// # Arguments
+    /// * `contract` - contract instance to interact with
+    /// * `fn_name` - name of the function to make a call
+    /// * `params` - parameter strings to pass to such function
+    /// * `options` - transaction options i.e. value in Wei to send along
+    /// * `confirmations` - number of block confirmations to wait for
+    pub async fn send(&self, contract: &Contract<CrunnerTransport>, fn_name: &str, params: &[String], options: Options, confirmations: u64) -> Result<TransactionReceipt, CrunnerError> {
+        let prvk = secret_key_from_env()?;
+        self.send_with_key(contract, fn_name, params, options, &prvk, confirmations).await
+    }
+
+    /// Make a call to setter method of the contract; this sends a transaction
+    /// signed with the specified secret key i.e. decrypted from keystore.
+    ///
+    /// # Arguments
+    /// * `contract` - contract instance to interact with
+    /// * `fn_name` - name of the function to make a call
+    /// * `params` - parameter strings to pass to such function
+    /// * `options` - transaction options i.e. value in Wei to send along
+    /// * `prvk` - secret key to sign the transaction with
+    /// * `confirmations` - number of block confirmations to wait for
+    pub async fn send_with_key(&self, contract: &Contract<CrunnerTransport>, fn_name: &str, params: &[String], options: Options, prvk: &SecretKey, confirmations: u64) -> Result<TransactionReceipt, CrunnerError> {
+        let from = SecretKeyRef::new(prvk).address();
+        let (tx_hash, res) = self.send_with_nonce_of(Some(from), options, confirmations, |options| web3_query_set(&self.web3, contract, fn_name, params, options, prvk)).await;
+        self.record_send(contract, fn_name, params, tx_hash, &res);
+        res
+    }
+
+    /// Make a call to setter method of the contract; this sends a transaction signed
+    /// by external signer i.e. hardware wallet.
+    ///
+    /// # Arguments
+    /// * `contract` - contract instance to interact with
+    /// * `fn_name` - name of the function to make a call
+    /// * `params` - parameter strings to pass to such function
+    /// * `signer` - external signer to sign the transaction
+    /// * `options` - transaction options i.e. value in Wei to send along
+    /// * `confirmations` - number of block confirmations to wait for
+    pub async fn send_with_signer(&self, contract: &Contract<CrunnerTransport>, fn_name: &str, params: &[String], signer: &dyn ExternalSigner, options: Options, confirmations: u64) -> Result<TransactionReceipt, CrunnerError> {
+        let from = signer.address().await?;
+        let (tx_hash, res) = self.send_with_nonce_of(Some(from), options, confirmations, |options| web3_query_set_external(&self.web3, contract, fn_name, params, signer, options)).await;
+        self.record_send(contract, fn_name, params, tx_hash, &res);
+        res
+    }
+
+    /// Build unsigned transaction calling setter method of the contract without signing,
+    /// or sending it. Nonce, gas price, and gas limit not set in `options` are queried
+    /// from the node; chain id is the chain's own unless it's local chain.
+    ///
+    /// # Arguments
+    /// * `contract` - contract instance to interact with
+    /// * `fn_name` - name of the function to make a call
+    /// * `params` - parameter strings to pass to such function
+    /// * `from` - address the transaction is to be sent from
+    /// * `options` - transaction options i.e. value in Wei to send along
+    pub async fn build_tx(&self, contract: &Contract<CrunnerTransport>, fn_name: &str, params: &[String], from: Address, options: Options) -> Result<UnsignedTx, CrunnerError> {
+        web3_build_set_tx(&self.web3, contract, fn_name, params, from, options, chain_id(self.chain)).await
+    }
+
+    /// Make a call to setter method of the contract from an account unlocked on
+    /// the node i.e. on local development chain; the node signs the transaction.
+    ///
+    /// # Arguments
+    /// * `contract` - contract instance to interact with
+    /// * `fn_name` - name of the function to make a call
+    /// * `params` - parameter strings to pass to such function
+    /// * `from` - unlocked account to send from; first account of the node if `None`
+    /// * `options` - transaction options i.e. value in Wei to send along
+    /// * `confirmations` - number of block confirmations to wait for
+    pub async fn send_unlocked(&self, contract: &Contract<CrunnerTransport>, fn_name: &str, params: &[String], from: Option<&str>, options: Options, confirmations: u64) -> Result<TransactionReceipt, CrunnerError> {
+        let from_addr = match from {
+            Some(from) => Some(get_address_from_str(from)?),
+            None => None,
+        };
+        let (tx_hash, res) = self.send_with_nonce_of(from_addr, options, confirmations, |options| web3_query_set_unlocked(&self.web3, contract, fn_name, params, from, options)).await;
+        self.record_send(contract, fn_name, params, tx_hash, &res);
+        res
+    }
+
+    /// Record getter calls along with their decoded results in history (if any).
+    fn record_call_results(&self, calls: &[(&Contract<CrunnerTransport>, &str, &[String])], results: &[MulticallResult]) {
+        if self.history.is_some() {
+            let outcomes = results.iter()
+                .map(|res| res.as_ref().map(|outputs| format_returned(outputs.iter().map(|(_, token)| token))).map_err(|e| e.to_string()))
+                .collect();
+            self.record_calls(calls, outcomes);
+        }
+    }
+
+    /// Record getter calls along with their formatted returned value, or error message
+    /// in history (if any). Failure to record doesn't fail the calls; it's warned about.
+    fn record_calls(&self, calls: &[(&Contract<CrunnerTransport>, &str, &[String])], outcomes: Vec<Result<String, String>>) {
+        let history = match &self.history {
+            Some(res) => res,
+            None => return,
+        };
+        let timestamp = timestamp_rfc3339();
+        let records: Vec<HistoryRecord> = calls.iter().zip(outcomes).map(|((contract, fn_name, params), outcome)| HistoryRecord {
+            id: 0,
+            timestamp: timestamp.clone(),
+            chain: chain_name(self.chain).to_owned(),
+            kind: HistoryKind::Call.name().to_owned(),
+            address: to_checksum_address(&contract.address()),
+            function: fn_name.to_string(),
+            params: params.to_vec(),
+            status: if outcome.is_ok() { "success" } else { "failed" }.to_owned(),
+            result: outcome.as_ref().ok().cloned(),
+            error: outcome.err(),
+            tx_hash: None,
+            gas_used: None,
+        }).collect();
+        if let Err(e) = history.record(&records) {
+            tracing::warn!(error = %e, "failed to record call in history");
+        }
+    }
+
+    /// Record transaction sent to setter method along with its outcome in history (if any).
+    /// Transaction which timed out, or was interrupted while waiting for confirmations
+    /// is recorded as pending along with its hash. Failure to record is warned about.
+    fn record_send(&self, contract: &Contract<CrunnerTransport>, fn_name: &str, params: &[String], tx_hash: Option<H256>, res: &Result<TransactionReceipt, CrunnerError>) {
+        let history = match &self.history {
+            Some(res) => res,
+            None => return,
+        };
+        let (gas_used, status, error) = match res {
+            Ok(receipt) => (
+                receipt.gas_used.map(|gas_used| gas_used.to_string()),
+                if receipt.status.map(|st| st.is_zero()).unwrap_or(false) { "reverted" } else { "success" },
+                None,
+            ),
+            Err(e @ (CrunnerError::Timeout(_) | CrunnerError::Interrupted(_))) => (None, "pending", Some(e.to_string())),
+            Err(e) => (None, "failed", Some(e.to_string())),
+        };
+        let tx_hash = res.as_ref().ok().map(|receipt| receipt.transaction_hash).or(tx_hash).map(|tx_hash| format!("{:?}", tx_hash));
+        let record = HistoryRecord {
+            id: 0,
+            timestamp: timestamp_rfc3339(),
+            chain: chain_name(self.chain).to_owned(),
+            kind: HistoryKind::Send.name().to_owned(),
+            address: to_checksum_address(&contract.address()),
+            function: fn_name.to_owned(),
+            params: params.to_vec(),
+            result: None,
+            tx_hash,
+            gas_used,
+            status: status.to_owned(),
+            error,
+        };
+        if let Err(e) = history.record(&[record]) {
+            tracing::warn!(error = %e, "failed to record transaction in history");
+        }
+    }
+
+    /// Speed up the pending transaction by rebroadcasting it with the same nonce,
+    /// and fees bumped by the specified percentage.
+    ///
+    /// # Arguments
+    /// * `tx_hash` - hash of the pending transaction
+    /// * `bump_percent` - percentage to bump fees by
+    /// * `prvk` - secret key of the sender of the pending transaction
+    /// * `confirmations` - number of block confirmations to wait for
+    pub async fn speed_up_transaction(&self, tx_hash: H256, bump_percent: u64, prvk: &SecretKey, confirmations: u64) -> Result<TransactionReceipt, CrunnerError> {
+        let replacement_tx_hash = web3_replace_transaction(&self.web3, tx_hash, false, bump_percent, prvk).await?;
+        self.wait_for_confirmations(replacement_tx_hash, confirmations).await
+    }
+
+    /// Cancel the pending transaction by replacing it with zero-value self-transfer
+    /// of the same nonce, and fees bumped by the specified percentage.
+    ///
+    /// # Arguments
+    /// * `tx_hash` - hash of the pending transaction
+    /// * `bump_percent` - percentage to bump fees by
+    /// * `prvk` - secret key of the sender of the pending transaction
+    /// * `confirmations` - number of block confirmations to wait for
+    pub async fn cancel_transaction(&self, tx_hash: H256, bump_percent: u64, prvk: &SecretKey, confirmations: u64) -> Result<TransactionReceipt, CrunnerError> {
+        let replacement_tx_hash = web3_replace_transaction(&self.web3, tx_hash, true, bump_percent, prvk).await?;
+        self.wait_for_confirmations(replacement_tx_hash, confirmations).await
+    }
+
+    /// Allocate nonce of the sender (if known) unless it is set in `options`, then
+    /// send a transaction with it, and wait for its confirmations. The nonce is
+    /// released if sending fails. Hash of the transaction is returned along with
+    /// the outcome if it was sent, so a transaction still pending can be tracked.
+    async fn send_with_nonce_of<F, Fut>(&self, from: Option<Address>, mut options: Options, confirmations: u64, send: F) -> (Option<H256>, Result<TransactionReceipt, CrunnerError>)
+    where
+        F: FnOnce(Options) -> Fut,
+        Fut: Future<Output = Result<H256, CrunnerError>>
+    {
+        let allocated = match (from, options.nonce) {
+            (Some(from), None) => {
+                let nonce = match self.nonce_manager.next_nonce(&self.web3, from).await {
+                    Ok(res) => res,
+                    Err(e) => return (None, Err(e)),
+                };
+                options.nonce = Some(nonce);
+                Some((from, nonce))
+            },
+            _ => None,
+        };
+
+        let tx_hash = match send(options).await {
+            Ok(res) => res,
+            Err(e) => {
+                if let Some((from, nonce)) = allocated {
+                    // it's safe even if the transaction was broadcast; it is then counted as pending
+                    if let Err(e) = self.nonce_manager.release(&self.web3, from, nonce).await {
+                        tracing::warn!(error = %e, "failed to release nonce");
+                    }
+                }
+                return (None, Err(e));
+            },
+        };
+        (Some(tx_hash), self.wait_for_confirmations(tx_hash, confirmations).await)
+    }
+
+    /// Wait for block confirmations of the sent transaction while reporting its
+    /// progress. Stop waiting on confirmation timeout (if any), or on Ctrl-C; the
+    /// transaction is still pending then, and its hash is reported in the error.
+    ///
+    /// # Arguments
+    /// * `tx_hash` - hash of the sent transaction
+    /// * `confirmations` - number of block confirmations to wait for
+    pub async fn wait_for_confirmations(&self, tx_hash: H256, confirmations: u64) -> Result<TransactionReceipt, CrunnerError> {
+        let started_at = Instant::now();
+        let report = |current: Option<u64>| {
+            if let Some(progress) = &self.confirmation_progress {
+                progress(&ConfirmationProgress { tx_hash, confirmations: current, target: confirmations, elapsed: started_at.elapsed() });
+            }
+        };
+        let timeout = async {
+            match self.confirmation_timeout {
+                Some(res) => tokio::time::sleep(res).await,
+                None => std::future::pending().await,
+            }
+        };
+
+        tokio::select! {
+            res = web3_wait_for_confirmations(&self.web3, tx_hash, confirmations, report) => res,
+            _ = timeout => Err(CrunnerError::Timeout(format!("Error, transaction {:?} is still pending after {} secs; it may still land later", tx_hash, started_at.elapsed().as_secs()))),
+            _ = tokio::signal::ctrl_c() => Err(CrunnerError::Interrupted(format!("Error, interrupted while waiting for transaction {:?} to be confirmed; it is still pending", tx_hash))),
+        }
+    }
+
+    /// Estimate gas used for calling setter method of the contract.
+    ///
+    /// # Arguments
+    /// * `contract` - contract instance to interact with
+    /// * `fn_name` - name of the function to make a call
+    /// * `params` - parameter strings to pass to such function
+    /// * `from` - address from
+    /// * `options` - transaction options i.e. value in Wei to send along
+    pub async fn estimate_gas(&self, contract: &Contract<CrunnerTransport>, fn_name: &str, params: &[String], from: &str, options: Options) -> Result<U256, CrunnerError> {
+        web3_query_estimate_gas(contract, fn_name, params, from, options).await
+    }
+
+    /// Aggregate calls to getter methods of (possibly different) contracts into a
+    /// single call to Multicall3, then decode returned data of each against the
+    /// function's outputs as declared in ABI. Calls are made at the block set via
+    /// `with_block` (if any).
+    ///
+    /// # Arguments
+    /// * `calls` - contract instance, function name, and parameter strings of each call
+    /// * `allow_failure` - whether other calls still succeed when some of them revert;
+    ///   otherwise the whole aggregated call fails
+    ///
+    /// # Return
+    /// Return the result of each call in the same order.
+    pub async fn multicall(&self, calls: &[(&Contract<CrunnerTransport>, &str, &[String])], allow_failure: bool) -> Result<Vec<MulticallResult>, CrunnerError> {
+        let multicall = match self.multicall_address {
+            Some(res) => res,
+            None => get_address_from_str(MULTICALL3_ADDRESS)?,
+        };
+        let res = web3_multicall(&self.web3, multicall, calls, allow_failure, self.block).await;
+        match &res {
+            Ok(results) => self.record_call_results(calls, results),
+            Err(e) => self.record_calls(calls, calls.iter().map(|_| Err(e.to_string())).collect()),
+        }
+        res
+    }
+
+    /// Make calls to getter methods of (possibly different) contracts sent together
+    /// in JSON-RPC batch requests of at most the size set via `with_rpc_batch_size`,
+    /// then decode returned data of each. Unlike `multicall`, this requires no
+    /// contract deployed on the chain, and honors state overrides set via
+    /// `with_state_override`. Calls are made at the block set via `with_block` (if any).
+    ///
+    /// # Arguments
+    /// * `calls` - contract instance, function name, and parameter strings of each call
+    /// * `ret_types` - types to decode returned data of every call into; function's
+    ///   outputs declared in ABI if `None`
+    ///
+    /// # Return
+    /// Return the result of each call in the same order; calls of a batch request
+    /// failed as a whole all fail with its error.
+    pub async fn call_many(&self, calls: &[(&Contract<CrunnerTransport>, &str, &[String])], ret_types: Option<&[ParamType]>) -> Vec<MulticallResult> {
+        let batch_size = self.rpc_batch_size.unwrap_or(DEFAULT_RPC_BATCH_SIZE);
+        if batch_size == 0 {
+            return futures::future::join_all(calls.iter().map(|(contract, fn_name, params)| async move {
+                match ret_types {
+                    Some(ret_types) => {
+                        let tokens = self.call_tokens(contract, fn_name, params, ret_types).await?;
+                        Ok(tokens.into_iter().map(|token| (String::new(), token)).collect())
+                    },
+                    None => self.call_inferred(contract, fn_name, params).await,
+                }
+            })).await;
+        }
+
+        let batches = futures::future::join_all(calls.chunks(batch_size).map(|batch| async move {
+            match web3_batch_query_get(&self.web3, batch, ret_types, self.block, self.state_override.as_ref()).await {
+                Ok(res) => res,
+                Err(e) => batch.iter().map(|_| Err(CrunnerError::RpcError(e.to_string()))).collect(),
+            }
+        })).await;
+        let results: Vec<MulticallResult> = batches.into_iter().flatten().collect();
+        self.record_call_results(calls, &results);
+        results
+    }
+
+    /// Trace calling method of the contract via `debug_traceCall` without sending a
+    /// transaction; the call is made at the block set via `with_block` (if any).
+    ///
+    /// # Arguments
+    /// * `contract` - contract instance to interact with
+    /// * `fn_name` - name of the function to make a call
+    /// * `params` - parameter strings to pass to such function
+    /// * `from` - address to call from (if any)
+    /// * `options` - transaction options i.e. value in Wei to send along
+    pub async fn trace_call(&self, contract: &Contract<CrunnerTransport>, fn_name: &str, params: &[String], from: Option<&str>, options: Options) -> Result<CallFrame, CrunnerError> {
+        web3_trace_call(&self.web3, contract, fn_name, params, from, options, self.block).await
+    }
+
+    /// Suggest fees of EIP-1559 transaction from fee history of recent blocks.
+    ///
+    /// # Return
+    /// Return tuple of `(max_fee_per_gas, max_priority_fee_per_gas)` in Wei.
+    pub async fn suggest_eip1559_fees(&self) -> Result<(U256, U256), CrunnerError> {
+        web3_suggest_eip1559_fees(&self.web3).await
+    }
+
+    /// Suggest fees of EIP-1559 transaction for slow, standard, and fast tiers from
+    /// fee history of recent blocks.
+    pub async fn suggest_fee_tiers(&self) -> Result<Vec<FeeSuggestion>, CrunnerError> {
+        web3_suggest_fee_tiers(&self.web3).await
+    }
+
+    /// Determine fees of transaction with the gas strategy set via `with_gas_strategy`,
+    /// otherwise the default one of the chain.
+    pub async fn gas_fees(&self) -> Result<GasFees, CrunnerError> {
+        let gas_strategy = self.gas_strategy.clone().unwrap_or_else(|| GasStrategy::default_for(self.chain));
+        gas_strategy.fees(&self.web3, self.chain).await
+    }
+
+    /// Query the price of native currency in USD from the source set via
+    /// `with_price_source`, otherwise the known Chainlink price feed of the chain.
+    pub async fn native_usd_price(&self) -> Result<f64, CrunnerError> {
+        let price_source = match &self.price_source {
+            Some(res) => res.clone(),
+            None => PriceSource::Chainlink(default_price_feed(self.chain)?),
+        };
+        native_usd_price(&self.web3, self.chain, &price_source).await
+    }
+
+    /// Query the current gas price in Wei.
+    pub async fn gas_price(&self) -> Result<U256, CrunnerError> {
+        match self.web3.eth().gas_price().await {
+            Ok(res) => Ok(res),
+            Err(e) => Err(CrunnerError::RpcError(format!("Error in querying gas price; err={}", e))),
+        }
+    }
+
+    /// Query balance in Wei of the specified address.
+    ///
+    /// # Arguments
+    /// * `address` - address to query balance of
+    pub async fn balance(&self, address: &str) -> Result<U256, CrunnerError> {
+        let addr: Address = get_address_from_str(address)?;
+        match self.web3.eth().balance(addr, None).await {
+            Ok(res) => Ok(res),
+            Err(e) => Err(CrunnerError::RpcError(format!("Error querying balance of {}; err={}", address, e))),
+        }
+    }
+
+    /// Query event logs emitted by the contract address within block range. Wide
+    /// range is queried in chunks of blocks; see `with_logs_chunk_size`.
+    ///
+    /// # Arguments
+    /// * `address` - contract address
+    /// * `event_topic` - signature hash of the event to filter logs by (if any)
+    /// * `from_block` - block number to start from; latest block if `None`
+    /// * `to_block` - block number to query up to (inclusive); latest block if `None`
+    pub async fn logs(&self, address: &str, event_topic: Option<H256>, from_block: Option<u64>, to_block: Option<u64>) -> Result<Vec<Log>, CrunnerError> {
+        let chunk_size = self.logs_chunk_size.unwrap_or_else(|| logs_chunk_size(self.chain));
+        web3_query_logs(&self.web3, address, event_topic, from_block, to_block, chunk_size, |progress| {
+            if let Some(on_progress) = &self.log_scan_progress {
+                on_progress(progress);
+            }
+        }).await
+    }
+
+    /// Stream event logs emitted by the contract address as they arrive. It requires
+    /// WebSocket RPC endpoint, and runs until the subscription can't be established.
+    ///
+    /// # Arguments
+    /// * `address` - contract address
+    /// * `event_topic` - signature hash of the event to filter logs by (if any)
+    /// * `on_log` - called for each log
+    pub async fn follow_logs<F: FnMut(&Log)>(&self, address: &str, event_topic: Option<H256>, on_log: F) -> Result<(), CrunnerError> {
+        let rpc_url = self.web3.transport().current_url();
+        if !is_ws_url(rpc_url) {
+            return Err(CrunnerError::InvalidArgument(format!("Error, following logs requires WebSocket RPC endpoint i.e. wss://...; rpc_url={}", rpc_url)));
+        }
+        web3_follow_logs(rpc_url, address, event_topic, on_log).await
+    }
+}
+
+/// Format returned values of getter as printed; tuple of them if there are multiple.
+fn format_returned<'a>(tokens: impl Iterator<Item = &'a Token>) -> String {
+    let mut tokens: Vec<Token> = tokens.cloned().collect();
+    if tokens.len() == 1 {
+        format_token(&tokens.remove(0))
+    }
+    else {
+        format_token(&Token::Tuple(tokens))
+    }
+}
+
+/// Convert decimals returned by token into usize; `None` if it doesn't fit in `uint8`
+/// as declared by ERC-20, as ABI decoding doesn't check the width.
+fn decimals_to_usize(decimals: U256) -> Option<usize> {
+    if decimals > U256::from(u8::MAX) {
+        None
+    }
+    else {
+        Some(decimals.as_usize())
+    }
+}
+
+/// Get current time in RFC 3339 format.
+fn timestamp_rfc3339() -> String {
+    humantime::format_rfc3339_seconds(std::time::SystemTime::now()).to_string()
+}
@@ -0,0 +1,220 @@
+use crate::cli::{CommandlineArgs, Command, SignerType, SigningKeyArgs, TransactionArgs, TxCommand};
+use clap::ArgEnum;
+use crunner::CrunnerError;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Named set of defaults as configured in config.toml
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Profile {
+    /// Chain to work with
+    pub chain: Option<String>,
+
+    /// RPC endpoints of the profile's chain; the first one is used, and the rest are
+    /// failed over to
+    #[serde(default)]
+    pub rpc: Vec<String>,
+
+    /// (setter only) Number of block confirmations to wait for
+    pub block_confirmations: Option<u64>,
+
+    /// Strategy to determine gas price, or fees as accepted by `--gas-strategy`
+    pub gas_strategy: Option<String>,
+
+    /// (setter only) External signer; one of ledger, trezor, kms, or node
+    pub signer: Option<String>,
+
+    /// (setter only) Key id, ARN, or alias of AWS KMS key; required by `signer = "kms"`
+    pub kms_key_id: Option<String>,
+
+    /// (setter only) Account unlocked on the node; required by `signer = "node"`
+    pub from: Option<String>,
+
+    /// (setter only) Keystore JSON file to sign with
+    pub keystore: Option<String>,
+
+    /// (setter only) Profile of the secret key in OS secret store to sign with
+    pub from_profile: Option<String>,
+}
+
+/// Layout of config.toml
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ConfigFile {
+    /// Profile applied when `--profile` is not set
+    default_profile: Option<String>,
+
+    /// Profiles keyed by their names i.e. `prod`, or `testnet`
+    #[serde(default)]
+    profile: BTreeMap<String, Profile>,
+}
+
+/// Get the default filepath of config file which is `~/.config/crunner/config.toml`.
+pub fn default_config_filepath() -> Option<PathBuf> {
+    dirs::home_dir().map(|home| home.join(".config").join("crunner").join("config.toml"))
+}
+
+/// Parse and validate config file.
+fn parse_config_file(filepath: &Path) -> Result<ConfigFile, CrunnerError> {
+    let content = match std::fs::read_to_string(filepath) {
+        Ok(res) => res,
+        Err(e) => return Err(CrunnerError::ConfigError(format!("Error reading config file {}; err={}", filepath.display(), e))),
+    };
+    let config_file: ConfigFile = match toml::from_str(&content) {
+        Ok(res) => res,
+        Err(e) => return Err(CrunnerError::ConfigError(format!("Error parsing config file {}; err={}", filepath.display(), e))),
+    };
+
+    if let Some(name) = &config_file.default_profile {
+        if !config_file.profile.contains_key(name) {
+            return Err(CrunnerError::ConfigError(format!("Error, default profile '{}' is not defined in config file {}", name, filepath.display())));
+        }
+    }
+    for (name, profile) in config_file.profile.iter() {
+        let signer = match &profile.signer {
+            Some(signer) => match SignerType::from_str(signer, true) {
+                Ok(res) => Some(res),
+                Err(_) => return Err(CrunnerError::ConfigError(format!("Error, signer of profile '{}' in config file {} must be one of ledger, trezor, kms, or node; signer={}", name, filepath.display(), signer))),
+            },
+            None => None,
+        };
+        if signer == Some(SignerType::Kms) && profile.kms_key_id.is_none() {
+            return Err(CrunnerError::ConfigError(format!("Error, profile '{}' in config file {} requires kms_key_id for kms signer", name, filepath.display())));
+        }
+        if signer == Some(SignerType::Node) && profile.from.is_none() {
+            return Err(CrunnerError::ConfigError(format!("Error, profile '{}' in config file {} requires from for node signer", name, filepath.display())));
+        }
+        if [signer.is_some(), profile.keystore.is_some(), profile.from_profile.is_some()].iter().filter(|set| **set).count() > 1 {
+            return Err(CrunnerError::ConfigError(format!("Error, profile '{}' in config file {} can set only one of signer, keystore, or from_profile", name, filepath.display())));
+        }
+    }
+
+    Ok(config_file)
+}
+
+/// Load the profile selected via `--profile`, or `default_profile` of config file.
+/// It's fine if the file doesn't exist unless `--profile` is set.
+///
+/// # Arguments
+/// * `name` - name of the profile set via `--profile`
+pub fn load_profile(name: Option<&str>) -> Result<Option<Profile>, CrunnerError> {
+    let filepath = match default_config_filepath() {
+        Some(res) => res,
+        None => match name {
+            Some(_) => return Err(CrunnerError::ConfigError("Error, cannot locate home directory for --profile".to_owned())),
+            None => return Ok(None),
+        },
+    };
+    if !filepath.exists() {
+        return match name {
+            Some(name) => Err(CrunnerError::ConfigError(format!("Error, profile '{}' is set but config file {} doesn't exist", name, filepath.display()))),
+            None => Ok(None),
+        };
+    }
+
+    let mut config_file = parse_config_file(&filepath)?;
+    let name = match name.map(|n| n.to_owned()).or(config_file.default_profile) {
+        Some(res) => res,
+        None => return Ok(None),
+    };
+    match config_file.profile.remove(&name) {
+        Some(profile) => Ok(Some(profile)),
+        None => Err(CrunnerError::ConfigError(format!("Error, profile '{}' is not defined in config file {}", name, filepath.display()))),
+    }
+}
+
+/// Fill options not set at command line with the profile's defaults.
+///
+/// # Arguments
+/// * `cmd_args` - parsed commandline arguments
+/// * `profile` - profile of defaults
+pub fn apply_profile(cmd_args: &mut CommandlineArgs, profile: &Profile) {
+    let same_chain = match (cmd_args.chains.first(), &profile.chain) {
+        (None, _) => !cmd_args.all_chains,
+        (Some(chain), Some(profile_chain)) => cmd_args.chains.len() == 1 && chain.eq_ignore_ascii_case(profile_chain),
+        (Some(_), None) => false,
+    };
+    if cmd_args.chains.is_empty() && !cmd_args.all_chains {
+        if let Some(chain) = &profile.chain {
+            cmd_args.chains.push(chain.to_owned());
+        }
+    }
+    // RPC endpoints belong to the profile's chain only
+    if cmd_args.rpc_urls.is_empty() && same_chain {
+        cmd_args.rpc_urls = profile.rpc.clone();
+    }
+    if cmd_args.gas_strategy.is_none() {
+        cmd_args.gas_strategy = profile.gas_strategy.clone();
+    }
+
+    match &mut cmd_args.command {
+        Command::Send(args) => apply_send_defaults(&mut args.tx, profile),
+        Command::Erc20(args) => {
+            if let Some(tx) = args.command.tx_mut() {
+                apply_send_defaults(tx, profile);
+            }
+        },
+        Command::Permit(args) => apply_send_defaults(&mut args.tx, profile),
+        Command::Erc721(args) => {
+            if let Some(tx) = args.command.tx_mut() {
+                apply_send_defaults(tx, profile);
+            }
+        },
+        Command::Erc1155(args) => {
+            if let Some(tx) = args.command.tx_mut() {
+                apply_send_defaults(tx, profile);
+            }
+        },
+        Command::SignTyped(args) if !is_key_set(&args.key) => {
+            args.key.keystore = profile.keystore.clone();
+            args.key.from_profile = profile.from_profile.clone();
+        },
+        Command::SignMessage(args) if !is_key_set(&args.key) => {
+            args.key.keystore = profile.keystore.clone();
+            args.key.from_profile = profile.from_profile.clone();
+        },
+        Command::Tx(args) => {
+            let (TxCommand::Speedup(args) | TxCommand::Cancel(args)) = &mut args.command;
+            if args.block_confirmations.is_none() {
+                args.block_confirmations = profile.block_confirmations;
+            }
+            if !is_key_set(&args.key) {
+                args.key.keystore = profile.keystore.clone();
+                args.key.from_profile = profile.from_profile.clone();
+            }
+        },
+        Command::Script(args) => {
+            if args.block_confirmations.is_none() {
+                args.block_confirmations = profile.block_confirmations;
+            }
+            if !is_key_set(&args.key) {
+                args.key.keystore = profile.keystore.clone();
+                args.key.from_profile = profile.from_profile.clone();
+            }
+        },
+        _ => {},
+    }
+}
+
+fn apply_send_defaults(args: &mut TransactionArgs, profile: &Profile) {
+    if args.block_confirmations.is_none() {
+        args.block_confirmations = profile.block_confirmations;
+    }
+    // signing options at command line replace the profile's signer as a whole
+    if args.signer.is_some() || args.from.is_some() || is_key_set(&args.key) {
+        return;
+    }
+    args.signer = profile.signer.as_deref().and_then(|signer| SignerType::from_str(signer, true).ok());
+    if args.kms_key_id.is_none() {
+        args.kms_key_id = profile.kms_key_id.clone();
+    }
+    args.from = profile.from.clone();
+    args.key.keystore = profile.keystore.clone();
+    args.key.from_profile = profile.from_profile.clone();
+}
+
+fn is_key_set(key: &SigningKeyArgs) -> bool {
+    key.keystore.is_some() || key.private_key_file.is_some() || key.from_profile.is_some() || key.mnemonic_env.is_some() || key.mnemonic_file.is_some()
+}
@@ -0,0 +1,256 @@
+use crate::error::CrunnerError;
+use crate::util::{parse_hex_bytes, tokenize_param};
+use ethabi::Token;
+use serde::Deserialize;
+use std::collections::{BTreeSet, HashMap};
+use web3::signing::keccak256;
+use web3::types::{Address, H256, U256};
+
+/// Type of EIP-2612 `Permit` struct
+static PERMIT_TYPE: &str = "Permit(address owner,address spender,uint256 value,uint256 nonce,uint256 deadline)";
+
+/// EIP-712 domain; fields which are `None` are left out of the domain type
+#[derive(Debug, Clone, Default)]
+pub struct Eip712Domain {
+    /// Name of the signing domain i.e. name of the token
+    pub name: Option<String>,
+
+    /// Version of the signing domain
+    pub version: Option<String>,
+
+    /// Chain id the signature is valid on
+    pub chain_id: Option<U256>,
+
+    /// Address of the contract verifying the signature
+    pub verifying_contract: Option<Address>,
+
+    /// Salt to disambiguate the domain
+    pub salt: Option<H256>,
+}
+
+impl Eip712Domain {
+    /// Get encoded type of the domain i.e. `EIP712Domain(string name,uint256 chainId)`.
+    pub fn encode_type(&self) -> String {
+        let mut fields: Vec<&str> = Vec::new();
+        if self.name.is_some() {
+            fields.push("string name");
+        }
+        if self.version.is_some() {
+            fields.push("string version");
+        }
+        if self.chain_id.is_some() {
+            fields.push("uint256 chainId");
+        }
+        if self.verifying_contract.is_some() {
+            fields.push("address verifyingContract");
+        }
+        if self.salt.is_some() {
+            fields.push("bytes32 salt");
+        }
+        format!("EIP712Domain({})", fields.join(","))
+    }
+
+    /// Compute domain separator i.e. `hashStruct(domain)`.
+    pub fn separator(&self) -> H256 {
+        let mut tokens = vec![Token::FixedBytes(keccak256(self.encode_type().as_bytes()).to_vec())];
+        if let Some(name) = &self.name {
+            tokens.push(Token::FixedBytes(keccak256(name.as_bytes()).to_vec()));
+        }
+        if let Some(version) = &self.version {
+            tokens.push(Token::FixedBytes(keccak256(version.as_bytes()).to_vec()));
+        }
+        if let Some(chain_id) = self.chain_id {
+            tokens.push(Token::Uint(chain_id));
+        }
+        if let Some(verifying_contract) = self.verifying_contract {
+            tokens.push(Token::Address(verifying_contract));
+        }
+        if let Some(salt) = self.salt {
+            tokens.push(Token::FixedBytes(salt.as_bytes().to_vec()));
+        }
+        H256(keccak256(&ethabi::encode(&tokens)))
+    }
+}
+
+/// Compute digest of typed data to be signed i.e.
+/// `keccak256("\x19\x01" ‖ domainSeparator ‖ hashStruct(message))`.
+///
+/// # Arguments
+/// * `domain_separator` - separator of the signing domain
+/// * `struct_hash` - `hashStruct` of the message
+pub fn typed_data_digest(domain_separator: H256, struct_hash: H256) -> H256 {
+    let payload = [&[0x19, 0x01], domain_separator.as_bytes(), struct_hash.as_bytes()].concat();
+    H256(keccak256(&payload))
+}
+
+/// Compute `hashStruct` of EIP-2612 `Permit` message.
+///
+/// # Arguments
+/// * `owner` - owner of the token signing the permit
+/// * `spender` - spender allowed to spend the token
+/// * `value` - allowance in the token's smallest unit
+/// * `nonce` - current nonce of the owner as of the token's `nonces(owner)`
+/// * `deadline` - unix timestamp in seconds the permit is valid until
+pub fn permit_struct_hash(owner: Address, spender: Address, value: U256, nonce: U256, deadline: U256) -> H256 {
+    let tokens = [
+        Token::FixedBytes(keccak256(PERMIT_TYPE.as_bytes()).to_vec()),
+        Token::Address(owner),
+        Token::Address(spender),
+        Token::Uint(value),
+        Token::Uint(nonce),
+        Token::Uint(deadline),
+    ];
+    H256(keccak256(&ethabi::encode(&tokens)))
+}
+
+/// Field of struct type in EIP-712 typed data
+#[derive(Debug, Clone, Deserialize)]
+pub struct TypedField {
+    /// Name of the field
+    pub name: String,
+
+    /// Type of the field i.e. `address`, `Person`, or `uint256[]`
+    #[serde(rename = "type")]
+    pub kind: String,
+}
+
+/// EIP-712 typed data as accepted by `eth_signTypedData_v4`
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TypedData {
+    /// Struct types by name; `EIP712Domain` is inferred from the domain if not declared
+    pub types: HashMap<String, Vec<TypedField>>,
+
+    /// Name of the struct type of the message
+    pub primary_type: String,
+
+    /// Values of the domain
+    pub domain: serde_json::Value,
+
+    /// Values of the message
+    pub message: serde_json::Value,
+}
+
+impl TypedData {
+    /// Parse typed data from its JSON.
+    ///
+    /// # Arguments
+    /// * `json` - typed data in JSON i.e. content of the file passed to `sign-typed`
+    pub fn from_json(json: &str) -> Result<TypedData, CrunnerError> {
+        let mut typed_data: TypedData = match serde_json::from_str(json) {
+            Ok(res) => res,
+            Err(e) => return Err(CrunnerError::InvalidArgument(format!("Error parsing typed data; err={}", e))),
+        };
+        if !typed_data.types.contains_key("EIP712Domain") {
+            let domain_fields = [("name", "string"), ("version", "string"), ("chainId", "uint256"), ("verifyingContract", "address"), ("salt", "bytes32")];
+            let fields = domain_fields.iter()
+                .filter(|(name, _)| typed_data.domain.get(name).is_some())
+                .map(|(name, kind)| TypedField { name: name.to_string(), kind: kind.to_string() })
+                .collect();
+            typed_data.types.insert("EIP712Domain".to_owned(), fields);
+        }
+        Ok(typed_data)
+    }
+
+    /// Get encoded type of the struct type along with struct types it references
+    /// i.e. `Mail(Person from,Person to,string contents)Person(string name,address wallet)`.
+    ///
+    /// # Arguments
+    /// * `type_name` - name of the struct type
+    pub fn encode_type(&self, type_name: &str) -> Result<String, CrunnerError> {
+        let mut deps = BTreeSet::new();
+        self.collect_deps(type_name, &mut deps)?;
+        deps.remove(type_name);
+
+        let mut encoded = String::new();
+        for name in std::iter::once(type_name).chain(deps.iter().map(|d| d.as_str())) {
+            let fields: Vec<String> = self.fields(name)?.iter().map(|f| format!("{} {}", f.kind, f.name)).collect();
+            encoded.push_str(&format!("{}({})", name, fields.join(",")));
+        }
+        Ok(encoded)
+    }
+
+    /// Compute `hashStruct` of the value of the struct type.
+    ///
+    /// # Arguments
+    /// * `type_name` - name of the struct type
+    /// * `value` - value of the struct as JSON object
+    pub fn hash_struct(&self, type_name: &str, value: &serde_json::Value) -> Result<H256, CrunnerError> {
+        let mut tokens = vec![Token::FixedBytes(keccak256(self.encode_type(type_name)?.as_bytes()).to_vec())];
+        for field in self.fields(type_name)? {
+            let field_value = match value.get(&field.name) {
+                Some(res) => res,
+                None => return Err(CrunnerError::InvalidArgument(format!("Error, field '{}' of '{}' is missing", field.name, type_name))),
+            };
+            tokens.push(self.encode_value(&field.kind, field_value)?);
+        }
+        Ok(H256(keccak256(&ethabi::encode(&tokens))))
+    }
+
+    /// Compute domain separator i.e. `hashStruct(domain)`.
+    pub fn domain_separator(&self) -> Result<H256, CrunnerError> {
+        self.hash_struct("EIP712Domain", &self.domain)
+    }
+
+    /// Compute digest of the typed data to be signed.
+    pub fn digest(&self) -> Result<H256, CrunnerError> {
+        let domain_separator = self.domain_separator()?;
+        // only the domain is signed if it's the primary type
+        if self.primary_type == "EIP712Domain" {
+            let payload = [&[0x19, 0x01], domain_separator.as_bytes()].concat();
+            return Ok(H256(keccak256(&payload)));
+        }
+        Ok(typed_data_digest(domain_separator, self.hash_struct(&self.primary_type, &self.message)?))
+    }
+
+    fn fields(&self, type_name: &str) -> Result<&[TypedField], CrunnerError> {
+        match self.types.get(type_name) {
+            Some(res) => Ok(res.as_slice()),
+            None => Err(CrunnerError::InvalidArgument(format!("Error, type '{}' is not declared in types", type_name))),
+        }
+    }
+
+    fn collect_deps(&self, type_name: &str, deps: &mut BTreeSet<String>) -> Result<(), CrunnerError> {
+        for field in self.fields(type_name)? {
+            let base_type = field.kind.split('[').next().unwrap_or_default();
+            if self.types.contains_key(base_type) && deps.insert(base_type.to_owned()) {
+                self.collect_deps(base_type, deps)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Encode value of the type into a 32-byte word as per `encodeData`; dynamic
+    /// values, structs, and arrays are hashed.
+    fn encode_value(&self, kind: &str, value: &serde_json::Value) -> Result<Token, CrunnerError> {
+        if let Some(item_kind) = kind.strip_suffix(']').and_then(|k| k.rsplit_once('[')).map(|(item_kind, _)| item_kind) {
+            let items = match value.as_array() {
+                Some(res) => res,
+                None => return Err(CrunnerError::InvalidArgument(format!("Error, value of '{}' is not an array; value={}", kind, value))),
+            };
+            let tokens = items.iter().map(|item| self.encode_value(item_kind, item)).collect::<Result<Vec<Token>, CrunnerError>>()?;
+            return Ok(Token::FixedBytes(keccak256(&ethabi::encode(&tokens)).to_vec()));
+        }
+        if self.types.contains_key(kind) {
+            return Ok(Token::FixedBytes(self.hash_struct(kind, value)?.as_bytes().to_vec()));
+        }
+
+        let value_str = match value {
+            serde_json::Value::String(s) => s.clone(),
+            serde_json::Value::Number(n) => n.to_string(),
+            serde_json::Value::Bool(b) => b.to_string(),
+            _ => return Err(CrunnerError::InvalidArgument(format!("Error, value of '{}' is not a string, number, or bool; value={}", kind, value))),
+        };
+        match kind {
+            "string" => Ok(Token::FixedBytes(keccak256(value_str.as_bytes()).to_vec())),
+            "bytes" => Ok(Token::FixedBytes(keccak256(&parse_hex_bytes(&value_str)?).to_vec())),
+            _ => {
+                let param_type = match ethabi::param_type::Reader::read(kind) {
+                    Ok(res) => res,
+                    Err(e) => return Err(CrunnerError::InvalidArgument(format!("Error, unknown type '{}'; err={}", kind, e))),
+                };
+                tokenize_param(&param_type, &value_str)
+            },
+        }
+    }
+}
@@ -0,0 +1,143 @@
+use crate::chains::address_alias;
+use crate::error::CrunnerError;
+use crate::transport::CrunnerTransport;
+use crate::util::{get_address_from_str, to_checksum_address};
+use regex::Regex;
+
+use web3::{
+    Web3,
+    types::{Address, Bytes, CallRequest},
+    signing::keccak256,
+};
+
+/// Address of ENS registry deployed on Ethereum
+pub(crate) static ENS_REGISTRY_ADDRESS: &str = "0x00000000000C2E074eC69A0dFb2997BA6C7d2e1e";
+
+/// Function selector of `resolver(bytes32)` of ENS registry
+static RESOLVER_SELECTOR: [u8; 4] = [0x01, 0x78, 0xb8, 0xbf];
+/// Function selector of `addr(bytes32)` of ENS resolver
+static ADDR_SELECTOR: [u8; 4] = [0x3b, 0x3b, 0x57, 0xde];
+
+/// Check whether the specified string looks like an ENS name i.e. `vitalik.eth`.
+/// Aliases in the address book i.e. `usdc.bsc` take precedence, so they are not.
+///
+/// # Arguments
+/// * `s` - string to check
+pub fn is_ens_name(s: &str) -> bool {
+    if address_alias(s).is_some() {
+        return false;
+    }
+    let regex: Regex = Regex::new(r#"^([a-z0-9-]+\.)+[a-z]+$"#).unwrap();
+    regex.is_match(&s.to_lowercase())
+}
+
+/// Compute namehash of ENS name as specified by EIP-137.
+///
+/// # Arguments
+/// * `name` - ENS name i.e. `vitalik.eth`
+pub fn namehash(name: &str) -> [u8; 32] {
+    let mut node = [0u8; 32];
+    if name.is_empty() {
+        return node;
+    }
+
+    for label in name.to_lowercase().rsplit('.') {
+        let label_hash = keccak256(label.as_bytes());
+        let mut buf = [0u8; 64];
+        buf[..32].copy_from_slice(&node);
+        buf[32..].copy_from_slice(&label_hash);
+        node = keccak256(&buf);
+    }
+    node
+}
+
+/// Resolve ENS name into address via the specified ENS registry.
+///
+/// # Arguments
+/// * `web3` - web3 instance connecting to the chain which such registry is deployed on
+/// * `registry` - address of ENS registry
+/// * `name` - ENS name to resolve i.e. `vitalik.eth`
+pub async fn resolve_ens_name(web3: &Web3<CrunnerTransport>, registry: Address, name: &str) -> Result<Address, CrunnerError> {
+    let node = namehash(name);
+
+    let resolver = call_with_node(web3, registry, RESOLVER_SELECTOR, &node).await
+        .map_err(|e| e.context(format!("Error querying resolver of ENS name '{}'", name)))?;
+    if resolver.is_zero() {
+        return Err(CrunnerError::InvalidAddress(format!("Error ENS name '{}' has no resolver", name)));
+    }
+
+    let addr = call_with_node(web3, resolver, ADDR_SELECTOR, &node).await
+        .map_err(|e| e.context(format!("Error resolving address of ENS name '{}'", name)))?;
+    if addr.is_zero() {
+        return Err(CrunnerError::InvalidAddress(format!("Error ENS name '{}' resolves to no address", name)));
+    }
+
+    Ok(addr)
+}
+
+/// Make a call to function which accepts a single `bytes32` node, and returns an address.
+async fn call_with_node(web3: &Web3<CrunnerTransport>, to: Address, selector: [u8; 4], node: &[u8; 32]) -> Result<Address, CrunnerError> {
+    let mut data = selector.to_vec();
+    data.extend_from_slice(node);
+
+    let call_req = CallRequest {
+        to: Some(to),
+        data: Some(Bytes(data)),
+        ..Default::default()
+    };
+    let ret_bytes = match web3.eth().call(call_req, None).await {
+        Ok(res) => res,
+        Err(e) => return Err(CrunnerError::RpcError(e.to_string())),
+    };
+    if ret_bytes.0.len() < 32 {
+        return Err(CrunnerError::RpcError("returned data is too short".to_owned()));
+    }
+
+    Ok(Address::from_slice(&ret_bytes.0[12..32]))
+}
+
+/// Resolve ENS names found in parameter strings into checksummed addresses.
+/// This includes a bare ENS name ending with `.eth`, an address annotated one
+/// i.e. `addr:vitalik.eth`, and elements of address array i.e. `addr[]:a.eth,b.eth`.
+/// Other parameters are returned as they are.
+///
+/// # Arguments
+/// * `web3` - web3 instance connecting to the chain which such registry is deployed on
+/// * `registry` - address of ENS registry
+/// * `params` - parameter strings
+pub async fn resolve_ens_params(web3: &Web3<CrunnerTransport>, registry: Address, params: &[String]) -> Result<Vec<String>, CrunnerError> {
+    let mut resolved_params: Vec<String> = Vec::new();
+
+    for p in params {
+        let resolved = match p.split_once(':') {
+            Some((type_str @ ("addr" | "address"), value)) if is_ens_name(value) => {
+                format!("{}:{}", type_str, to_checksum_address(&resolve_ens_name(web3, registry, value).await?))
+            },
+            Some((type_str @ ("addr[]" | "address[]"), value)) => {
+                let mut items: Vec<String> = Vec::new();
+                for item in value.trim_start_matches('[').trim_end_matches(']').split(',') {
+                    let item = item.trim();
+                    if is_ens_name(item) {
+                        items.push(to_checksum_address(&resolve_ens_name(web3, registry, item).await?));
+                    }
+                    else {
+                        items.push(item.to_owned());
+                    }
+                }
+                format!("{}:{}", type_str, items.join(","))
+            },
+            None if p.to_lowercase().ends_with(".eth") && is_ens_name(p) => {
+                to_checksum_address(&resolve_ens_name(web3, registry, p).await?)
+            },
+            _ => p.to_owned(),
+        };
+        resolved_params.push(resolved);
+    }
+
+    Ok(resolved_params)
+}
+
+/// Get address of the default ENS registry on Ethereum.
+pub fn default_ens_registry() -> Address {
+    get_address_from_str(ENS_REGISTRY_ADDRESS).unwrap()
+}
@@ -0,0 +1,122 @@
+use std::fmt::Display;
+use thiserror::Error;
+
+/// Error of crunner categorized by its cause. Each category maps to a distinct
+/// process exit code so scripts can branch on failure type.
+#[derive(Debug, Clone, Error)]
+pub enum CrunnerError {
+    /// Invalid input argument or parameter i.e. malformed value of `--params`
+    #[error("{0}")]
+    InvalidArgument(String),
+
+    /// Invalid address i.e. wrong format, bad checksum, or unresolvable ENS name
+    #[error("{0}")]
+    InvalidAddress(String),
+
+    /// Error loading, parsing ABI, or encoding/decoding against it
+    #[error("{0}")]
+    AbiError(String),
+
+    /// Error in configuration i.e. chains.toml, or required environment variable
+    #[error("{0}")]
+    ConfigError(String),
+
+    /// Error communicating with RPC endpoint, or error response from the node
+    #[error("{0}")]
+    RpcError(String),
+
+    /// RPC endpoint is on a different chain than the selected one
+    #[error("{0}")]
+    ChainMismatch(String),
+
+    /// Transaction was mined but reverted
+    #[error("{0}")]
+    TxReverted(String),
+
+    /// Timed out waiting for transaction to be confirmed
+    #[error("{0}")]
+    Timeout(String),
+
+    /// Error from external signer i.e. hardware wallet, or rejected on device
+    #[error("{0}")]
+    SignerError(String),
+
+    /// Interrupted by user i.e. Ctrl-C while waiting for transaction to be confirmed
+    #[error("{0}")]
+    Interrupted(String),
+}
+
+impl CrunnerError {
+    /// Get process exit code of the error category.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            CrunnerError::InvalidArgument(_) => 2,
+            CrunnerError::InvalidAddress(_) => 3,
+            CrunnerError::AbiError(_) => 4,
+            CrunnerError::ConfigError(_) => 5,
+            CrunnerError::RpcError(_) => 6,
+            CrunnerError::ChainMismatch(_) => 7,
+            CrunnerError::TxReverted(_) => 8,
+            CrunnerError::Timeout(_) => 9,
+            CrunnerError::SignerError(_) => 10,
+            CrunnerError::Interrupted(_) => 130,
+        }
+    }
+
+    /// Get name of the error category i.e. `rpc_error`.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            CrunnerError::InvalidArgument(_) => "invalid_argument",
+            CrunnerError::InvalidAddress(_) => "invalid_address",
+            CrunnerError::AbiError(_) => "abi_error",
+            CrunnerError::ConfigError(_) => "config_error",
+            CrunnerError::RpcError(_) => "rpc_error",
+            CrunnerError::ChainMismatch(_) => "chain_mismatch",
+            CrunnerError::TxReverted(_) => "tx_reverted",
+            CrunnerError::Timeout(_) => "timeout",
+            CrunnerError::SignerError(_) => "signer_error",
+            CrunnerError::Interrupted(_) => "interrupted",
+        }
+    }
+
+    /// Create error of the same category with the specified message i.e. to report
+    /// several failures of the same cause at once.
+    ///
+    /// # Arguments
+    /// * `msg` - message of the error
+    pub fn with_message(&self, msg: String) -> Self {
+        match self {
+            CrunnerError::InvalidArgument(_) => CrunnerError::InvalidArgument(msg),
+            CrunnerError::InvalidAddress(_) => CrunnerError::InvalidAddress(msg),
+            CrunnerError::AbiError(_) => CrunnerError::AbiError(msg),
+            CrunnerError::ConfigError(_) => CrunnerError::ConfigError(msg),
+            CrunnerError::RpcError(_) => CrunnerError::RpcError(msg),
+            CrunnerError::ChainMismatch(_) => CrunnerError::ChainMismatch(msg),
+            CrunnerError::TxReverted(_) => CrunnerError::TxReverted(msg),
+            CrunnerError::Timeout(_) => CrunnerError::Timeout(msg),
+            CrunnerError::SignerError(_) => CrunnerError::SignerError(msg),
+            CrunnerError::Interrupted(_) => CrunnerError::Interrupted(msg),
+        }
+    }
+
+    /// Prefix error message with the specified context while keeping its category
+    /// i.e. `Error calling setter method 'foo'; err=<message>`.
+    ///
+    /// # Arguments
+    /// * `context` - context of the error
+    pub fn context<T: Display>(self, context: T) -> Self {
+        let wrap = |msg: String| format!("{}; err={}", context, msg);
+        match self {
+            CrunnerError::InvalidArgument(msg) => CrunnerError::InvalidArgument(wrap(msg)),
+            CrunnerError::InvalidAddress(msg) => CrunnerError::InvalidAddress(wrap(msg)),
+            CrunnerError::AbiError(msg) => CrunnerError::AbiError(wrap(msg)),
+            CrunnerError::ConfigError(msg) => CrunnerError::ConfigError(wrap(msg)),
+            CrunnerError::RpcError(msg) => CrunnerError::RpcError(wrap(msg)),
+            CrunnerError::ChainMismatch(msg) => CrunnerError::ChainMismatch(wrap(msg)),
+            CrunnerError::TxReverted(msg) => CrunnerError::TxReverted(wrap(msg)),
+            CrunnerError::Timeout(msg) => CrunnerError::Timeout(wrap(msg)),
+            CrunnerError::SignerError(msg) => CrunnerError::SignerError(wrap(msg)),
+            CrunnerError::Interrupted(msg) => CrunnerError::Interrupted(wrap(msg)),
+        }
+    }
+}
@@ -0,0 +1,225 @@
+use crate::error::CrunnerError;
+use crate::transport::CrunnerTransport;
+use crate::types::ChainType;
+use crate::util::{explorer_api_endpoint, parse_gas_price, supports_eip1559_by_default, web3_fee_history, EXPLORER_API_KEY_ENV};
+use std::str::FromStr;
+use web3::types::U256;
+use web3::{Transport, Web3};
+
+/// Default percentile of priority fees paid in recent blocks for `fee-history` strategy
+static DEFAULT_FEE_HISTORY_PERCENTILE: f64 = 50.0;
+
+/// Speed of gas price suggested by block explorer's gas oracle
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OracleSpeed {
+    Safe,
+    Propose,
+    Fast,
+}
+
+/// Strategy to determine gas price, or fees of transaction to be sent.
+#[derive(Debug, Clone, PartialEq)]
+pub enum GasStrategy {
+    /// Gas price as suggested by the node via `eth_gasPrice`, and priority fee via
+    /// `eth_maxPriorityFeePerGas`
+    Node,
+
+    /// Fixed gas price in Wei; used as both max fee, and priority fee of EIP-1559
+    /// transaction
+    Fixed(U256),
+
+    /// Priority fee as the median of the percentile of priority fees paid in recent
+    /// blocks from fee history, on top of base fee of the next block
+    FeeHistory(f64),
+
+    /// Gas price as suggested by gas oracle API of Etherscan-compatible block explorer
+    Oracle(OracleSpeed),
+}
+
+/// Fees of transaction as determined by `GasStrategy`; all in Wei.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GasFees {
+    /// Gas price of legacy transaction
+    pub gas_price: U256,
+
+    /// Max fee per gas of EIP-1559 transaction; `None` if the chain doesn't support it
+    pub max_fee: Option<U256>,
+
+    /// Max priority fee per gas of EIP-1559 transaction; `None` if the chain doesn't
+    /// support it
+    pub max_priority_fee: Option<U256>,
+}
+
+impl FromStr for GasStrategy {
+    type Err = CrunnerError;
+
+    /// Parse gas strategy i.e. `node`, `fixed:<price>`, `fee-history[:<percentile>]`,
+    /// or `oracle[:safe|propose|fast]`. Price is in gwei unless suffixed with unit.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, arg) = match s.trim().split_once(':') {
+            Some((name, arg)) => (name, Some(arg.trim())),
+            None => (s.trim(), None),
+        };
+
+        match (name.to_lowercase().as_str(), arg) {
+            ("node", None) => Ok(GasStrategy::Node),
+            ("fixed", Some(price)) => Ok(GasStrategy::Fixed(parse_gas_price(price)?)),
+            ("fee-history", None) => Ok(GasStrategy::FeeHistory(DEFAULT_FEE_HISTORY_PERCENTILE)),
+            ("fee-history", Some(percentile)) => match percentile.parse::<f64>() {
+                Ok(res) if (0.0..=100.0).contains(&res) => Ok(GasStrategy::FeeHistory(res)),
+                _ => Err(CrunnerError::InvalidArgument(format!("Error, percentile must be a number between 0 and 100; value={}", percentile))),
+            },
+            ("oracle", None) => Ok(GasStrategy::Oracle(OracleSpeed::Propose)),
+            ("oracle", Some(speed)) => match speed.to_lowercase().as_str() {
+                "safe" => Ok(GasStrategy::Oracle(OracleSpeed::Safe)),
+                "propose" => Ok(GasStrategy::Oracle(OracleSpeed::Propose)),
+                "fast" => Ok(GasStrategy::Oracle(OracleSpeed::Fast)),
+                _ => Err(CrunnerError::InvalidArgument(format!("Error, oracle speed must be one of safe, propose, or fast; value={}", speed))),
+            },
+            _ => Err(CrunnerError::InvalidArgument(format!("Error, unknown gas strategy '{}'; expected node, fixed:<price>, fee-history[:<percentile>], or oracle[:safe|propose|fast]", s))),
+        }
+    }
+}
+
+impl GasStrategy {
+    /// Default strategy of the chain; `fee-history` on chains sending EIP-1559
+    /// transaction by default, otherwise `node`.
+    ///
+    /// # Arguments
+    /// * `chain` - `ChainType`
+    pub fn default_for(chain: ChainType) -> Self {
+        if supports_eip1559_by_default(chain) {
+            GasStrategy::FeeHistory(DEFAULT_FEE_HISTORY_PERCENTILE)
+        }
+        else {
+            GasStrategy::Node
+        }
+    }
+
+    /// Determine fees of transaction according to the strategy.
+    ///
+    /// # Arguments
+    /// * `web3` - web3 instance
+    /// * `chain` - `ChainType` whose block explorer is used for `oracle` strategy
+    pub async fn fees(&self, web3: &Web3<CrunnerTransport>, chain: ChainType) -> Result<GasFees, CrunnerError> {
+        match self {
+            GasStrategy::Node => node_fees(web3).await,
+            GasStrategy::Fixed(gas_price) => Ok(GasFees {
+                gas_price: *gas_price,
+                max_fee: Some(*gas_price),
+                max_priority_fee: Some(*gas_price),
+            }),
+            GasStrategy::FeeHistory(percentile) => {
+                let (base_fee, priority_fees) = web3_fee_history(web3, &[*percentile]).await?;
+                let priority_fee = priority_fees.first().copied().unwrap_or_default();
+                Ok(GasFees {
+                    gas_price: base_fee + priority_fee,
+                    max_fee: Some(base_fee * 2 + priority_fee),
+                    max_priority_fee: Some(priority_fee),
+                })
+            },
+            GasStrategy::Oracle(speed) => oracle_fees(chain, *speed).await,
+        }
+    }
+}
+
+/// Query fees suggested by the node. Max fee is twice the base fee of the next block
+/// plus suggested priority fee. EIP-1559 fees are omitted if the node doesn't
+/// support querying priority fee, or base fee.
+async fn node_fees(web3: &Web3<CrunnerTransport>) -> Result<GasFees, CrunnerError> {
+    let gas_price = match web3.eth().gas_price().await {
+        Ok(res) => res,
+        Err(e) => return Err(CrunnerError::RpcError(format!("Error in querying gas price; err={}", e))),
+    };
+
+    let priority_fee = match web3.transport().execute("eth_maxPriorityFeePerGas", vec![]).await {
+        Ok(res) => serde_json::from_value::<U256>(res).ok(),
+        Err(e) => {
+            tracing::debug!(error = %e, "no suggested priority fee");
+            None
+        },
+    };
+    let base_fee = match priority_fee {
+        Some(_) => match web3_fee_history(web3, &[]).await {
+            Ok((base_fee, _)) => Some(base_fee),
+            Err(e) => {
+                tracing::debug!(error = %e, "no base fee");
+                None
+            },
+        },
+        None => None,
+    };
+
+    match (base_fee, priority_fee) {
+        (Some(base_fee), Some(priority_fee)) => Ok(GasFees {
+            gas_price,
+            max_fee: Some(base_fee * 2 + priority_fee),
+            max_priority_fee: Some(priority_fee),
+        }),
+        _ => Ok(GasFees {
+            gas_price,
+            max_fee: None,
+            max_priority_fee: None,
+        }),
+    }
+}
+
+/// Query fees suggested by gas oracle API of the chain's block explorer. Priority
+/// fee is the suggested gas price above the suggested base fee, and max fee is
+/// twice such base fee plus priority fee. EIP-1559 fees are omitted if the oracle
+/// doesn't suggest base fee.
+async fn oracle_fees(chain: ChainType, speed: OracleSpeed) -> Result<GasFees, CrunnerError> {
+    let api_endpoint = match explorer_api_endpoint(chain) {
+        Ok(res) => res,
+        Err(e) => return Err(e.context("Error querying gas oracle")),
+    };
+
+    let mut query = vec![("module", "gastracker".to_owned()), ("action", "gasoracle".to_owned())];
+    if let Ok(api_key) = std::env::var(EXPLORER_API_KEY_ENV) {
+        query.push(("apikey", api_key));
+    }
+
+    let resp = match reqwest::Client::new().get(api_endpoint).query(&query).send().await {
+        Ok(res) => res,
+        Err(e) => return Err(CrunnerError::RpcError(format!("Error requesting gas oracle from block explorer; err={}", e))),
+    };
+    let body: serde_json::Value = match resp.json().await {
+        Ok(res) => res,
+        Err(e) => return Err(CrunnerError::RpcError(format!("Error parsing response of gas oracle from block explorer; err={}", e))),
+    };
+
+    // status of "1" indicates success, otherwise `result` holds the error message
+    if body["status"].as_str() != Some("1") {
+        return Err(CrunnerError::RpcError(format!("Error querying gas oracle from block explorer; err={}", body["result"])));
+    }
+
+    let field = match speed {
+        OracleSpeed::Safe => "SafeGasPrice",
+        OracleSpeed::Propose => "ProposeGasPrice",
+        OracleSpeed::Fast => "FastGasPrice",
+    };
+    let gas_price = match body["result"][field].as_str() {
+        Some(res) => parse_gas_price(res)?,
+        None => return Err(CrunnerError::RpcError(format!("Error, gas oracle response has no {}", field))),
+    };
+    let base_fee = match body["result"]["suggestBaseFee"].as_str() {
+        Some(res) => Some(parse_gas_price(res)?),
+        None => None,
+    };
+
+    match base_fee {
+        Some(base_fee) => {
+            let priority_fee = gas_price.saturating_sub(base_fee);
+            Ok(GasFees {
+                gas_price,
+                max_fee: Some(base_fee * 2 + priority_fee),
+                max_priority_fee: Some(priority_fee),
+            })
+        },
+        None => Ok(GasFees {
+            gas_price,
+            max_fee: None,
+            max_priority_fee: None,
+        }),
+    }
+}
@@ -0,0 +1,239 @@
+use crate::error::CrunnerError;
+use rusqlite::{params, Connection};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// Kind of recorded operation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HistoryKind {
+    /// Call to getter method
+    Call,
+
+    /// Transaction sent to setter method
+    Send,
+}
+
+impl HistoryKind {
+    /// Get name of the kind as stored i.e. `call`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            HistoryKind::Call => "call",
+            HistoryKind::Send => "send",
+        }
+    }
+}
+
+/// Getter call, or setter transaction as recorded in history
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HistoryRecord {
+    /// Row id; assigned when recorded
+    pub id: i64,
+
+    /// Time of the operation in RFC 3339 format
+    pub timestamp: String,
+
+    /// Name of the chain
+    pub chain: String,
+
+    /// Kind of the operation; `call`, or `send`
+    pub kind: String,
+
+    /// Contract address
+    pub address: String,
+
+    /// Function name
+    pub function: String,
+
+    /// Parameters of the function
+    pub params: Vec<String>,
+
+    /// Returned value of getter as printed
+    pub result: Option<String>,
+
+    /// Hash of sent transaction
+    pub tx_hash: Option<String>,
+
+    /// Gas used by sent transaction
+    pub gas_used: Option<String>,
+
+    /// Outcome of the operation; `success`, `reverted` (mined but reverted), `pending`
+    /// (sent but not confirmed in time), or `failed`
+    pub status: String,
+
+    /// Error message if it failed
+    pub error: Option<String>,
+}
+
+/// Filter of records listed from history; unset fields match anything.
+#[derive(Debug, Clone, Default)]
+pub struct HistoryFilter {
+    /// Names of the chains
+    pub chains: Vec<String>,
+
+    /// Kind of the operation
+    pub kind: Option<HistoryKind>,
+
+    /// Contract address; matched case-insensitively
+    pub address: Option<String>,
+
+    /// Function name
+    pub function: Option<String>,
+
+    /// Outcome of the operation
+    pub status: Option<String>,
+
+    /// Only records at, or after this time in RFC 3339 format
+    pub since: Option<String>,
+
+    /// Maximum number of the latest records to list
+    pub limit: Option<usize>,
+}
+
+/// Persistent history of getter calls, and setter transactions in SQLite database
+/// serving as an audit trail of operations made.
+pub struct History {
+    conn: Mutex<Connection>,
+}
+
+impl History {
+    /// Open history database at the specified filepath; it's created along with its
+    /// directory if it doesn't exist yet.
+    ///
+    /// # Arguments
+    /// * `filepath` - filepath of SQLite database
+    pub fn open(filepath: &Path) -> Result<Self, CrunnerError> {
+        if let Some(dir) = filepath.parent() {
+            if let Err(e) = std::fs::create_dir_all(dir) {
+                return Err(CrunnerError::ConfigError(format!("Error creating directory of history {}; err={}", filepath.display(), e)));
+            }
+        }
+
+        let conn = match Connection::open(filepath) {
+            Ok(res) => res,
+            Err(e) => return Err(CrunnerError::ConfigError(format!("Error opening history {}; err={}", filepath.display(), e))),
+        };
+        let schema = "CREATE TABLE IF NOT EXISTS history (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                timestamp TEXT NOT NULL,
+                chain TEXT NOT NULL,
+                kind TEXT NOT NULL,
+                address TEXT NOT NULL,
+                function TEXT NOT NULL,
+                params TEXT NOT NULL,
+                result TEXT,
+                tx_hash TEXT,
+                gas_used TEXT,
+                status TEXT NOT NULL,
+                error TEXT
+            );
+            CREATE INDEX IF NOT EXISTS history_timestamp ON history (timestamp);";
+        // concurrent runs may write at the same time; wait for each other's lock
+        if let Err(e) = conn.busy_timeout(std::time::Duration::from_secs(5)).and_then(|_| conn.execute_batch(schema)) {
+            return Err(CrunnerError::ConfigError(format!("Error initializing history {}; err={}", filepath.display(), e)));
+        }
+
+        Ok(Self {
+            conn: Mutex::new(conn),
+        })
+    }
+
+    /// Get the default filepath of history database which is
+    /// `~/.config/crunner/history.sqlite`.
+    pub fn default_filepath() -> Option<PathBuf> {
+        dirs::home_dir().map(|home| home.join(".config").join("crunner").join("history.sqlite"))
+    }
+
+    /// Record operations in a single transaction; `id` of each record is ignored.
+    ///
+    /// # Arguments
+    /// * `records` - records of operations
+    pub fn record(&self, records: &[HistoryRecord]) -> Result<(), CrunnerError> {
+        let mut conn = self.lock()?;
+        let to_error = |e: rusqlite::Error| CrunnerError::ConfigError(format!("Error recording history; err={}", e));
+
+        let tx = conn.transaction().map_err(to_error)?;
+        for record in records.iter() {
+            let params_json = serde_json::Value::from(record.params.clone()).to_string();
+            tx.execute(
+                "INSERT INTO history (timestamp, chain, kind, address, function, params, result, tx_hash, gas_used, status, error)
+                    VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+                params![record.timestamp, record.chain, record.kind, record.address, record.function, params_json, record.result, record.tx_hash, record.gas_used, record.status, record.error],
+            ).map_err(to_error)?;
+        }
+        tx.commit().map_err(to_error)
+    }
+
+    /// List recorded operations matching the filter, the latest first.
+    ///
+    /// # Arguments
+    /// * `filter` - filter of records
+    pub fn list(&self, filter: &HistoryFilter) -> Result<Vec<HistoryRecord>, CrunnerError> {
+        let conn = self.lock()?;
+        let to_error = |e: rusqlite::Error| CrunnerError::ConfigError(format!("Error listing history; err={}", e));
+
+        let mut conditions: Vec<String> = Vec::new();
+        let mut values: Vec<String> = Vec::new();
+        if !filter.chains.is_empty() {
+            let placeholders: Vec<String> = filter.chains.iter().map(|chain| {
+                values.push(chain.to_lowercase());
+                format!("?{}", values.len())
+            }).collect();
+            conditions.push(format!("chain IN ({})", placeholders.join(", ")));
+        }
+        let mut push_condition = |condition: &str, value: String| {
+            values.push(value);
+            conditions.push(format!("{} ?{}", condition, values.len()));
+        };
+        if let Some(kind) = filter.kind {
+            push_condition("kind =", kind.name().to_owned());
+        }
+        if let Some(address) = &filter.address {
+            push_condition("lower(address) =", address.to_lowercase());
+        }
+        if let Some(function) = &filter.function {
+            push_condition("function =", function.to_owned());
+        }
+        if let Some(status) = &filter.status {
+            push_condition("status =", status.to_owned());
+        }
+        if let Some(since) = &filter.since {
+            push_condition("timestamp >=", since.to_owned());
+        }
+
+        let mut sql = "SELECT id, timestamp, chain, kind, address, function, params, result, tx_hash, gas_used, status, error FROM history".to_owned();
+        if !conditions.is_empty() {
+            sql.push_str(&format!(" WHERE {}", conditions.join(" AND ")));
+        }
+        sql.push_str(" ORDER BY id DESC");
+        if let Some(limit) = filter.limit {
+            sql.push_str(&format!(" LIMIT {}", limit));
+        }
+
+        let mut stmt = conn.prepare(&sql).map_err(to_error)?;
+        let rows = stmt.query_map(rusqlite::params_from_iter(values.iter()), |row| {
+            let params_json: String = row.get(6)?;
+            Ok(HistoryRecord {
+                id: row.get(0)?,
+                timestamp: row.get(1)?,
+                chain: row.get(2)?,
+                kind: row.get(3)?,
+                address: row.get(4)?,
+                function: row.get(5)?,
+                params: serde_json::from_str(&params_json).unwrap_or_default(),
+                result: row.get(7)?,
+                tx_hash: row.get(8)?,
+                gas_used: row.get(9)?,
+                status: row.get(10)?,
+                error: row.get(11)?,
+            })
+        }).map_err(to_error)?;
+        rows.collect::<Result<Vec<HistoryRecord>, rusqlite::Error>>().map_err(to_error)
+    }
+
+    fn lock(&self) -> Result<std::sync::MutexGuard<'_, Connection>, CrunnerError> {
+        match self.conn.lock() {
+            Ok(res) => Ok(res),
+            Err(_) => Err(CrunnerError::ConfigError("Error, history is poisoned".to_owned())),
+        }
+    }
+}
@@ -0,0 +1,99 @@
+use crate::error::CrunnerError;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::fs::{File, OpenOptions};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Counter to make temp file of each write unique within the process.
+static TMP_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// Read JSON store from disk i.e. nonce store, or response cache; default (empty)
+/// value if it doesn't exist yet.
+///
+/// # Arguments
+/// * `filepath` - filepath of the store
+/// * `name` - name of the store as mentioned in error messages i.e. `nonce store`
+pub fn read<T: DeserializeOwned + Default>(filepath: &Path, name: &str) -> Result<T, CrunnerError> {
+    if !filepath.exists() {
+        return Ok(T::default());
+    }
+
+    let content = match std::fs::read_to_string(filepath) {
+        Ok(res) => res,
+        Err(e) => return Err(CrunnerError::ConfigError(format!("Error reading {} {}; err={}", name, filepath.display(), e))),
+    };
+    match serde_json::from_str(&content) {
+        Ok(res) => Ok(res),
+        Err(e) => Err(CrunnerError::ConfigError(format!("Error parsing {} {}; err={}", name, filepath.display(), e))),
+    }
+}
+
+/// Write JSON store to disk through temp file renamed over it, so that the store
+/// is never seen half-written by other processes.
+///
+/// # Arguments
+/// * `filepath` - filepath of the store
+/// * `name` - name of the store as mentioned in error messages i.e. `nonce store`
+/// * `value` - content of the store
+pub fn write<T: Serialize>(filepath: &Path, name: &str, value: &T) -> Result<(), CrunnerError> {
+    create_parent_dir(filepath, name)?;
+
+    let content = match serde_json::to_string_pretty(value) {
+        Ok(res) => res,
+        Err(e) => return Err(CrunnerError::ConfigError(format!("Error serializing {}; err={}", name, e))),
+    };
+    // unique across processes by pid, and across concurrent writers within the process by counter
+    let tmp_filepath = sidecar_filepath(filepath, &format!("{}.{}.tmp", std::process::id(), TMP_COUNTER.fetch_add(1, Ordering::Relaxed)));
+    if let Err(e) = std::fs::write(&tmp_filepath, content) {
+        return Err(CrunnerError::ConfigError(format!("Error writing {} {}; err={}", name, tmp_filepath.display(), e)));
+    }
+    match std::fs::rename(&tmp_filepath, filepath) {
+        Ok(_) => Ok(()),
+        Err(e) => Err(CrunnerError::ConfigError(format!("Error writing {} {}; err={}", name, filepath.display(), e))),
+    }
+}
+
+/// Lock JSON store exclusively across processes via its sidecar lock file i.e.
+/// `nonces.json.lock`, so that read-modify-write of the store is atomic. It blocks
+/// until the lock is acquired, and is unlocked when the returned file is dropped.
+///
+/// # Arguments
+/// * `filepath` - filepath of the store
+/// * `name` - name of the store as mentioned in error messages i.e. `nonce store`
+pub fn lock(filepath: &Path, name: &str) -> Result<File, CrunnerError> {
+    create_parent_dir(filepath, name)?;
+
+    let lock_filepath = sidecar_filepath(filepath, "lock");
+    let file = match OpenOptions::new().create(true).truncate(false).write(true).open(&lock_filepath) {
+        Ok(res) => res,
+        Err(e) => return Err(CrunnerError::ConfigError(format!("Error opening lock file of {} {}; err={}", name, lock_filepath.display(), e))),
+    };
+    match file.lock() {
+        Ok(_) => Ok(file),
+        Err(e) => Err(CrunnerError::ConfigError(format!("Error locking {} {}; err={}", name, lock_filepath.display(), e))),
+    }
+}
+
+/// Get current unix timestamp in seconds as stored along with entries of the store.
+pub fn unix_now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or_default()
+}
+
+fn create_parent_dir(filepath: &Path, name: &str) -> Result<(), CrunnerError> {
+    if let Some(dir) = filepath.parent() {
+        if let Err(e) = std::fs::create_dir_all(dir) {
+            return Err(CrunnerError::ConfigError(format!("Error creating directory of {} {}; err={}", name, filepath.display(), e)));
+        }
+    }
+    Ok(())
+}
+
+/// Get filepath next to the store with the extension appended i.e. `nonces.json.lock`.
+fn sidecar_filepath(filepath: &Path, extension: &str) -> PathBuf {
+    let mut sidecar = filepath.as_os_str().to_owned();
+    sidecar.push(".");
+    sidecar.push(extension);
+    PathBuf::from(sidecar)
+}
@@ -0,0 +1,125 @@
+use crate::error::CrunnerError;
+use crate::signer::{ExternalSigner, TxSignature, UnsignedTx};
+use aws_sdk_kms::error::DisplayErrorContext;
+use aws_sdk_kms::primitives::Blob;
+use aws_sdk_kms::types::{MessageType, SigningAlgorithmSpec};
+use aws_sdk_kms::Client;
+use futures::future::BoxFuture;
+use web3::signing::{keccak256, recover};
+use web3::types::{Address, H256, U256};
+
+/// Order of secp256k1 curve
+static SECP256K1_N: &str = "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141";
+
+/// Signer with secp256k1 key (`ECC_SECG_P256K1`) held in AWS KMS; the key never
+/// leaves KMS. Credentials, and region are resolved from the standard AWS
+/// environment variables, or profile.
+pub struct KmsSigner {
+    client: Client,
+    key_id: String,
+}
+
+impl KmsSigner {
+    /// Create signer for the KMS key.
+    ///
+    /// # Arguments
+    /// * `key_id` - key id, ARN, or alias of the KMS key
+    pub async fn new(key_id: &str) -> Result<Self, CrunnerError> {
+        let config = aws_config::load_from_env().await;
+        Ok(Self {
+            client: Client::new(&config),
+            key_id: key_id.to_owned(),
+        })
+    }
+
+    async fn get_address(&self) -> Result<Address, CrunnerError> {
+        let output = match self.client.get_public_key().key_id(&self.key_id).send().await {
+            Ok(res) => res,
+            Err(e) => return Err(CrunnerError::SignerError(format!("Error getting public key of KMS key '{}'; err={}", self.key_id, DisplayErrorContext(e)))),
+        };
+
+        // DER-encoded SubjectPublicKeyInfo which ends with uncompressed public key
+        let spki = output.public_key().map(|b| b.as_ref()).unwrap_or_default();
+        if spki.len() < 65 || spki[spki.len() - 65] != 0x04 {
+            return Err(CrunnerError::SignerError(format!("Error, KMS key '{}' is not a secp256k1 key", self.key_id)));
+        }
+        let hash = keccak256(&spki[spki.len() - 64..]);
+        Ok(Address::from_slice(&hash[12..]))
+    }
+
+    async fn sign(&self, tx: &UnsignedTx) -> Result<TxSignature, CrunnerError> {
+        let sighash = tx.sighash();
+        let output = match self.client.sign()
+            .key_id(&self.key_id)
+            .message(Blob::new(sighash.to_vec()))
+            .message_type(MessageType::Digest)
+            .signing_algorithm(SigningAlgorithmSpec::EcdsaSha256)
+            .send()
+            .await {
+            Ok(res) => res,
+            Err(e) => return Err(CrunnerError::SignerError(format!("Error signing with KMS key '{}'; err={}", self.key_id, DisplayErrorContext(e)))),
+        };
+        let der = output.signature().map(|b| b.as_ref()).unwrap_or_default();
+        let (r, mut s) = parse_der_signature(der)?;
+
+        // KMS doesn't enforce low-s as required by Ethereum; normalize it
+        let n = U256::from_str_radix(SECP256K1_N, 16).unwrap();
+        if s > n / 2 {
+            s = n - s;
+        }
+
+        let mut compact = [0u8; 64];
+        r.to_big_endian(&mut compact[..32]);
+        s.to_big_endian(&mut compact[32..]);
+
+        // KMS doesn't return recovery id; find the one that recovers to the key's address
+        let address = self.get_address().await?;
+        let parity = match (0..2).find(|id| matches!(recover(&sighash, &compact, *id), Ok(addr) if addr == address)) {
+            Some(res) => res as u64,
+            None => return Err(CrunnerError::SignerError(format!("Error recovering signature from KMS key '{}'", self.key_id))),
+        };
+        Ok(TxSignature {
+            v: tx.signature_v(parity),
+            r: H256::from_slice(&compact[..32]),
+            s: H256::from_slice(&compact[32..]),
+        })
+    }
+}
+
+impl ExternalSigner for KmsSigner {
+    fn address(&self) -> BoxFuture<'_, Result<Address, CrunnerError>> {
+        Box::pin(self.get_address())
+    }
+
+    fn sign_transaction<'a>(&'a self, tx: &'a UnsignedTx) -> BoxFuture<'a, Result<TxSignature, CrunnerError>> {
+        Box::pin(self.sign(tx))
+    }
+}
+
+/// Parse DER-encoded ECDSA signature i.e. `SEQUENCE { INTEGER r, INTEGER s }`.
+fn parse_der_signature(der: &[u8]) -> Result<(U256, U256), CrunnerError> {
+    let invalid = || CrunnerError::SignerError("Error invalid DER signature from KMS".to_owned());
+
+    if der.len() < 2 || der[0] != 0x30 {
+        return Err(invalid());
+    }
+    let mut pos = 2;
+    let mut read_integer = || -> Result<U256, CrunnerError> {
+        if der.get(pos) != Some(&0x02) {
+            return Err(invalid());
+        }
+        let len = *der.get(pos + 1).ok_or_else(invalid)? as usize;
+        let bytes = der.get(pos + 2..pos + 2 + len).ok_or_else(invalid)?;
+        pos += 2 + len;
+
+        // strip leading zero of positive integer
+        let start = bytes.iter().position(|b| *b != 0).unwrap_or(bytes.len());
+        if bytes.len() - start > 32 {
+            return Err(invalid());
+        }
+        Ok(U256::from_big_endian(&bytes[start..]))
+    };
+    let r = read_integer()?;
+    let s = read_integer()?;
+    Ok((r, s))
+}
@@ -0,0 +1,137 @@
+use crate::error::CrunnerError;
+use crate::signer::{parse_hd_path, ExternalSigner, TxSignature, UnsignedTx};
+use futures::future::BoxFuture;
+use ledger_apdu::APDUCommand;
+use ledger_transport_hid::{hidapi::HidApi, TransportNativeHID};
+use std::str::FromStr;
+use web3::types::{Address, H256};
+
+/// APDU class of Ethereum app
+static CLA: u8 = 0xe0;
+
+/// APDU instruction to get public key, and address
+static INS_GET_PUBLIC_KEY: u8 = 0x02;
+
+/// APDU instruction to sign transaction
+static INS_SIGN: u8 = 0x04;
+
+/// Maximum length of APDU payload
+static MAX_CHUNK_LEN: usize = 255;
+
+/// Status word of successful APDU response
+static SW_OK: u16 = 0x9000;
+
+/// Status word when user rejects on device
+static SW_REJECTED: u16 = 0x6985;
+
+/// Signer on Ledger hardware wallet over USB with Ethereum app opened.
+/// Transaction details are shown on device for confirmation before signing.
+pub struct LedgerSigner {
+    transport: TransportNativeHID,
+    hd_path: String,
+    path: Vec<u32>,
+}
+
+impl LedgerSigner {
+    /// Connect to the first Ledger device found.
+    ///
+    /// # Arguments
+    /// * `hd_path` - derivation path of the signing account i.e. `m/44'/60'/0'/0/0`
+    pub fn new(hd_path: &str) -> Result<Self, CrunnerError> {
+        let path = parse_hd_path(hd_path)?;
+        let api = match HidApi::new() {
+            Ok(res) => res,
+            Err(e) => return Err(CrunnerError::SignerError(format!("Error initializing USB HID; err={}", e))),
+        };
+        let transport = match TransportNativeHID::new(&api) {
+            Ok(res) => res,
+            Err(e) => return Err(CrunnerError::SignerError(format!("Error connecting to Ledger device; err={}", e))),
+        };
+
+        Ok(Self {
+            transport,
+            hd_path: hd_path.to_owned(),
+            path,
+        })
+    }
+
+    /// Serialize derivation path as expected by Ethereum app.
+    fn path_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![self.path.len() as u8];
+        for index in self.path.iter() {
+            bytes.extend_from_slice(&index.to_be_bytes());
+        }
+        bytes
+    }
+
+    /// Send APDU command to device, and return data of its response.
+    fn exchange(&self, ins: u8, p1: u8, data: Vec<u8>) -> Result<Vec<u8>, CrunnerError> {
+        let command = APDUCommand { cla: CLA, ins, p1, p2: 0x00, data };
+        let answer = match self.transport.exchange(&command) {
+            Ok(res) => res,
+            Err(e) => return Err(CrunnerError::SignerError(format!("Error communicating with Ledger device; err={}", e))),
+        };
+
+        match answer.retcode() {
+            code if code == SW_OK => Ok(answer.data().to_vec()),
+            code if code == SW_REJECTED => Err(CrunnerError::SignerError("Error, transaction was rejected on Ledger device".to_owned())),
+            code => Err(CrunnerError::SignerError(format!("Error response from Ledger device; status=0x{:04x}; make sure Ethereum app is opened", code))),
+        }
+    }
+
+    fn get_address(&self) -> Result<Address, CrunnerError> {
+        // response: pubkey length, pubkey, address length, address in hex string
+        let data = self.exchange(INS_GET_PUBLIC_KEY, 0x00, self.path_bytes())?;
+        let pubkey_len = *data.first().unwrap_or(&0) as usize;
+        let addr_offset = 1 + pubkey_len;
+        let addr_len = *data.get(addr_offset).unwrap_or(&0) as usize;
+        let addr_str = match data.get(addr_offset + 1..addr_offset + 1 + addr_len).map(std::str::from_utf8) {
+            Some(Ok(res)) => res,
+            _ => return Err(CrunnerError::SignerError(format!("Error invalid address response from Ledger device for HD path '{}'", self.hd_path))),
+        };
+        match Address::from_str(addr_str.trim_start_matches("0x")) {
+            Ok(res) => Ok(res),
+            Err(e) => Err(CrunnerError::SignerError(format!("Error parsing address from Ledger device; err={}", e))),
+        }
+    }
+
+    fn sign(&self, tx: &UnsignedTx) -> Result<TxSignature, CrunnerError> {
+        // payload is derivation path followed by RLP-encoded transaction; sent in chunks
+        let mut payload = self.path_bytes();
+        payload.extend_from_slice(&tx.rlp());
+
+        let mut data: Vec<u8> = Vec::new();
+        for (i, chunk) in payload.chunks(MAX_CHUNK_LEN).enumerate() {
+            let p1 = if i == 0 { 0x00 } else { 0x80 };
+            data = self.exchange(INS_SIGN, p1, chunk.to_vec())?;
+        }
+        if data.len() < 65 {
+            return Err(CrunnerError::SignerError("Error signature response from Ledger device is too short".to_owned()));
+        }
+
+        // device returns only the lowest byte of v for legacy transaction; recover its
+        // parity to get the full v. It is y-parity as-is for EIP-1559 transaction.
+        let parity = if tx.eip1559_fees.is_some() {
+            data[0] as u64 % 2
+        }
+        else {
+            let v_base = tx.chain_id * 2 + 35;
+            (data[0] as u64 + 256 - v_base % 256) % 256 % 2
+        };
+        Ok(TxSignature {
+            v: tx.signature_v(parity),
+            r: H256::from_slice(&data[1..33]),
+            s: H256::from_slice(&data[33..65]),
+        })
+    }
+}
+
+impl ExternalSigner for LedgerSigner {
+    fn address(&self) -> BoxFuture<'_, Result<Address, CrunnerError>> {
+        Box::pin(async move { self.get_address() })
+    }
+
+    fn sign_transaction<'a>(&'a self, tx: &'a UnsignedTx) -> BoxFuture<'a, Result<TxSignature, CrunnerError>> {
+        Box::pin(async move { self.sign(tx) })
+    }
+}
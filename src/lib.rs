@@ -0,0 +1,32 @@
+//! Runner/Executor of target smart contract on EVM-based chain.
+//!
+//! Use [`CrunnerClient`] to make calls to smart contract's getter/setter methods,
+//! estimate gas, and query balance or event logs from other Rust programs.
+
+pub mod cache;
+pub mod chains;
+pub mod client;
+pub mod eip712;
+pub mod ens;
+pub mod error;
+pub mod gas;
+pub mod history;
+pub mod json_store;
+pub mod metadata;
+#[cfg(feature = "kms")]
+pub mod kms;
+#[cfg(feature = "ledger")]
+pub mod ledger;
+pub mod nonce;
+pub mod price;
+pub mod signatures;
+pub mod signer;
+pub mod transport;
+#[cfg(feature = "trezor")]
+pub mod trezor;
+pub mod types;
+pub mod util;
+pub mod wallet;
+
+pub use client::CrunnerClient;
+pub use error::CrunnerError;
@@ -1,208 +1,3923 @@
-use clap::Parser;
+use clap::{CommandFactory, Parser};
 
-mod types;
-mod util;
+mod alert;
+mod batch;
+mod cli;
+mod config;
+mod output;
+mod script;
+mod serve;
 
-use types::*;
-use util::*;
+use alert::*;
+use batch::*;
+use cli::*;
+use config::*;
+use output::*;
+use script::*;
+use serve::*;
+use crunner::{CrunnerClient, CrunnerError};
+use crunner::cache::ResponseCache;
+use crunner::chains::{chain_registry, find_chain, load_chain_registry};
+use crunner::gas::GasStrategy;
+use crunner::eip712::{permit_struct_hash, typed_data_digest, Eip712Domain, TypedData};
+use crunner::history::{History, HistoryFilter, HistoryKind, HistoryRecord};
+use crunner::metadata::fetch_token_metadata;
+use crunner::nonce::NonceManager;
+use crunner::price::PriceSource;
+use crunner::signatures::SignatureDb;
+use crunner::signer::{DecodedTx, ExternalSigner, UnsignedTx};
+use crunner::transport::{parse_rpc_header, CrunnerTransport, RetryPolicy};
+use crunner::types::*;
+use crunner::util::{canonical_signature, decode_function_call, decode_log, decode_log_as, encode_function_call, format_call, parse_256_method_arguments, format_event, format_token, load_abi, resolve_event_sig, load_state_override, parse_amount, parse_gas_price, decode_revert_reason, function_signature_of, format_units, unknown_custom_error_data, parse_hex_bytes, parse_units, chain_name, get_address_from_str, params_refer_template, parse_ret_types, supports_eip1559_by_default, set_checksum_validation, to_checksum_address, token_to_json, unit_str};
+use crunner::wallet::{import_key_to_keyring, remove_key_from_keyring, secret_key_from_env, secret_key_from_file, secret_key_from_keyring, secret_key_from_keystore, secret_key_from_mnemonic};
+use futures::StreamExt;
+use tokio::io::AsyncBufReadExt;
+use secp256k1::SecretKey;
+use serde_json::json;
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::io::IsTerminal;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::str::FromStr;
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+use zeroize::Zeroizing;
 
-use web3::contract::Contract;
-use web3::transports::http::Http;
+use web3::contract::{Contract, Options, tokens::Detokenize};
+use ethabi::{ParamType, Token};
+use web3::signing::{hash_message, keccak256, recover, Key, SecretKeyRef, Signature};
+use web3::types::{Address, Bytes, CallRequest, H256, Log, TransactionReceipt, U256};
+
+/// Environment variable holding bearer token required by POST endpoints of `serve` subcommand
+static SERVE_TOKEN_ENV: &str = "CRUNNER_SERVE_TOKEN";
+
+/// Built-in mainnets on which `--all-chains` makes the call
+static MAINNET_CHAINS: [&str; 9] = ["bsc", "ethereum", "polygon", "arbitrum", "optimism", "base", "avalanche", "fantom", "gnosis"];
+
+/// Built-in testnets, and local chain
+static TESTNET_CHAINS: [&str; 5] = ["bsc-testnet", "sepolia", "holesky", "polygon-amoy", "local"];
+
+/// Keystore password prompted interactively; kept for the rest of the run
+static KEYSTORE_PASSWORD: OnceLock<Zeroizing<String>> = OnceLock::new();
 
 #[tokio::main]
 async fn main() {
-    let cmd_args = CommandlineArgs::parse();
+    let raw_args: Vec<String> = std::env::args_os().skip(1).map(|arg| arg.to_string_lossy().into_owned()).collect();
+    if let Err(e) = check_legacy_flags(&raw_args) {
+        exit_with_error(e);
+    }
+    let mut cmd_args = CommandlineArgs::parse();
+    set_output_format(cmd_args.output);
+    set_quiet(cmd_args.quiet);
+    // loaded before logging so that RUST_LOG can be set in env file as well
+    if let Err(e) = load_env_files(cmd_args.env_file.as_deref()) {
+        exit_with_error(e);
+    }
+    init_logging(cmd_args.verbose, cmd_args.log_format);
+    set_checksum_validation(!cmd_args.no_checksum);
+    if cmd_args.lookup_errors {
+        set_error_hook(lookup_custom_error);
+    }
+
+    // key management is local to the machine; no chain is involved
+    if let Command::Key(args) = cmd_args.command {
+        return handle_key(args);
+    }
+    if let Command::VerifySig(args) = &cmd_args.command {
+        return handle_verify_sig(args);
+    }
+    if let Command::Keccak(args) = &cmd_args.command {
+        return handle_keccak(args);
+    }
+    if let Command::Selector(args) = &cmd_args.command {
+        return handle_selector(args);
+    }
+    if let Command::Topic(args) = &cmd_args.command {
+        return handle_topic(args);
+    }
+    if let Command::DecodeOutput(args) = &cmd_args.command {
+        return handle_decode_output(args);
+    }
+    if let Command::DecodeCalldata(args) = &cmd_args.command {
+        return handle_decode_calldata(args).await;
+    }
+    if let Command::DecodeTx(args) = &cmd_args.command {
+        return handle_decode_tx(args);
+    }
+    if let Command::History(args) = &cmd_args.command {
+        return handle_history(&cmd_args, args);
+    }
+    if let Command::Completions(args) = &cmd_args.command {
+        return handle_completions(args);
+    }
+
+    // fill options not set at command line with defaults of the selected profile
+    match load_profile(cmd_args.profile.as_deref()) {
+        Ok(Some(profile)) => apply_profile(&mut cmd_args, &profile),
+        Ok(None) => {},
+        Err(e) => exit_with_error(e),
+    }
+
+    // signing is local as well, but may use the signing key of the profile
+    if let Command::SignTyped(args) = &cmd_args.command {
+        return handle_sign_typed(args);
+    }
+    if let Command::SignMessage(args) = &cmd_args.command {
+        return handle_sign_message(args);
+    }
+
+    // load user-defined chains, and RPC headers
+    if let Err(e) = load_chain_registry(None) {
+        exit_with_error(e);
+    }
+
+    // each entry of batch file may work with its own chain
+    if let Command::Batch(args) = &cmd_args.command {
+        return handle_batch(&cmd_args, args).await;
+    }
+
+    // the same call may be made on several chains at once
+    if cmd_args.all_chains || cmd_args.chains.len() > 1 {
+        return handle_multi_chain_call(&cmd_args).await;
+    }
 
     // validate value of chain flag option
-    let chain_value = cmd_args.chain;
-    let mut chain: Option<ChainType> = None;
+    let chain_value = match cmd_args.chains.first() {
+        Some(chain_value) => chain_value.to_lowercase(),
+        None => exit_with_error(CrunnerError::InvalidArgument("Error, requires --chain to be set".to_owned())),
+    };
+    let chain = parse_chain(&chain_value);
+
+    let mut client = create_client(chain, &cmd_args, true);
+    let mut command = cmd_args.command;
+
+    let tx_args = match &command {
+        Command::Send(args) => Some(&args.tx),
+        Command::Erc20(args) => args.command.tx(),
+        Command::Permit(args) => Some(&args.tx),
+        Command::Erc721(args) => args.command.tx(),
+        Command::Erc1155(args) => args.command.tx(),
+        _ => None,
+    };
+    if tx_args.map(|tx| tx.track_nonce).unwrap_or(false) {
+        match NonceManager::default_store_filepath() {
+            Some(filepath) => client = client.with_nonce_manager(NonceManager::new().with_store(&filepath)),
+            None => exit_with_error(CrunnerError::ConfigError("Error, cannot locate home directory for --track-nonce".to_owned())),
+        }
+    }
+
+    if let Command::EstimateGas(args) = &command {
+        if let Some(price_feed) = &args.price_feed {
+            match get_address_from_str(price_feed) {
+                Ok(addr) => client = client.with_price_source(PriceSource::Chainlink(addr)),
+                Err(e) => exit_with_error(e.context("Error parsing --price-feed")),
+            }
+        }
+        else if let Some(price_api) = &args.price_api {
+            client = client.with_price_source(PriceSource::Http(price_api.to_owned()));
+        }
+    }
+
+    if let Command::Multicall(args) = &command {
+        if let Some(multicall_address) = &args.multicall_address {
+            match get_address_from_str(multicall_address) {
+                Ok(addr) => client = client.with_multicall_address(addr),
+                Err(e) => exit_with_error(e.context("Error parsing --multicall-address")),
+            }
+        }
+    }
+
+    if let Command::Events(args) = &command {
+        if let Some(chunk_size) = args.chunk_size {
+            if chunk_size == 0 {
+                exit_with_error(CrunnerError::InvalidArgument("Error, --chunk-size must be greater than 0".to_owned()));
+            }
+            client = client.with_logs_chunk_size(chunk_size);
+        }
+    }
+
+    // signing only is meant to work offline; the chain id is the one of --chain
+    let is_sign_only = matches!(&command, Command::Send(args) if args.sign_only);
+    if !cmd_args.skip_chain_check && !is_sign_only {
+        if let Err(e) = client.verify_chain_id().await {
+            exit_with_error(e);
+        }
+    }
+
+    let block = match &command {
+        Command::Call(args) => args.block.as_deref(),
+        Command::RawCall(args) => args.block.as_deref(),
+        Command::TraceCall(args) => args.block.as_deref(),
+        Command::Multicall(args) => args.block.as_deref(),
+        _ => None,
+    };
+    if let Some(block) = block {
+        match client.resolve_block(block).await {
+            Ok(res) => client = client.with_block(res),
+            Err(e) => exit_with_error(e.context("Error parsing --block")),
+        }
+    }
+
+    let state_override = match &command {
+        Command::Call(args) => args.state_override.as_deref(),
+        Command::RawCall(args) => args.state_override.as_deref(),
+        _ => None,
+    };
+    if let Some(state_override) = state_override {
+        match load_state_override(state_override) {
+            Ok(res) => client = client.with_state_override(res),
+            Err(e) => exit_with_error(e.context("Error parsing --state-override")),
+        }
+    }
+
+    if let Err(e) = resolve_ens_names(&client, &mut command).await {
+        exit_with_error(e);
+    }
+
+    match command {
+        Command::Call(args) => handle_call(&client, args).await,
+        Command::RawCall(args) => handle_raw_call(&client, args).await,
+        Command::Send(args) => handle_send(&client, args).await,
+        Command::EstimateGas(args) => handle_estimate_gas(&client, args).await,
+        Command::TraceCall(args) => handle_trace_call(&client, args).await,
+        Command::Multicall(args) => handle_multicall(&client, args).await,
+        Command::Balance(args) => handle_balance(&client, args).await,
+        Command::Events(args) => handle_events(&client, args).await,
+        Command::TokenInfo(args) => handle_token_info(&client, args).await,
+        Command::Erc20(args) => handle_erc20(&client, args).await,
+        Command::Permit(args) => handle_permit(&client, args).await,
+        Command::Erc721(args) => handle_erc721(&client, args).await,
+        Command::Erc1155(args) => handle_erc1155(&client, args).await,
+        Command::Key(_) | Command::History(_) | Command::Completions(_) | Command::SignTyped(_) | Command::SignMessage(_) | Command::VerifySig(_) | Command::Keccak(_) | Command::Selector(_) | Command::Topic(_) | Command::DecodeOutput(_) | Command::DecodeCalldata(_) | Command::DecodeTx(_) => unreachable!("local subcommands are handled before connecting to chain"),
+        Command::Batch(_) => unreachable!("batch subcommand connects to the chain of each entry itself"),
+        Command::Tx(args) => handle_tx(&client, args).await,
+        Command::Serve(args) => handle_serve(client, args).await,
+        Command::Script(args) => handle_script(client, args),
+    }
+}
+
+/// Parse value of chain flag option into `ChainType`; either built-in chain, or
+/// user-defined chain in chains.toml, otherwise exit the program.
+fn parse_chain(chain_value: &str) -> ChainType {
+    let chain: Option<ChainType>;
     if chain_value == "bsc" {
         chain= Some(ChainType::BSC);
     }
-    else if chain_value == "ehtereum" {
+    else if chain_value == "ethereum" {
         chain = Some(ChainType::Ethereum);
     }
     else if chain_value == "polygon" {
         chain = Some(ChainType::Polygon);
     }
-    // non-match case will be handled by clap crate
-    
-    let chain_unwrapped_value = chain.unwrap();
-    let web3 = create_web3(chain_unwrapped_value);
+    else if chain_value == "arbitrum" {
+        chain = Some(ChainType::Arbitrum);
+    }
+    else if chain_value == "optimism" {
+        chain = Some(ChainType::Optimism);
+    }
+    else if chain_value == "base" {
+        chain = Some(ChainType::Base);
+    }
+    else if chain_value == "avalanche" {
+        chain = Some(ChainType::Avalanche);
+    }
+    else if chain_value == "fantom" {
+        chain = Some(ChainType::Fantom);
+    }
+    else if chain_value == "gnosis" {
+        chain = Some(ChainType::Gnosis);
+    }
+    else if chain_value == "bsc-testnet" {
+        chain = Some(ChainType::BSCTestnet);
+    }
+    else if chain_value == "sepolia" {
+        chain = Some(ChainType::Sepolia);
+    }
+    else if chain_value == "holesky" {
+        chain = Some(ChainType::Holesky);
+    }
+    else if chain_value == "polygon-amoy" {
+        chain = Some(ChainType::PolygonAmoy);
+    }
+    else if chain_value == "local" {
+        chain = Some(ChainType::Local);
+    }
+    else {
+        // look up user-defined chains
+        chain = find_chain(chain_value);
+    }
 
-    // validate the input contract address
-    let is_eoa_res = perform_check_is_eoa(&web3, &cmd_args.contract_address).await;
-    match is_eoa_res {
-        Ok(is_eoa) => {
-            if is_eoa {
-                eprintln!("Error, input contract address is EOA");
-                std::process::exit(1);
+    match chain {
+        Some(res) => res,
+        None => exit_with_error(CrunnerError::InvalidArgument(format!("Error, unknown chain '{}'; see --help for supported chains, or define it in chains.toml", chain_value))),
+    }
+}
+
+/// Create client of the chain configured by global options, otherwise exit the program.
+///
+/// # Arguments
+/// * `chain` - `ChainType` to work with
+/// * `cmd_args` - commandline's arguments
+/// * `with_rpc_overrides` - whether --rpc-url, and --rpc-header apply to the chain
+fn create_client(chain: ChainType, cmd_args: &CommandlineArgs, with_rpc_overrides: bool) -> CrunnerClient {
+    let mut client = match CrunnerClient::new(chain) {
+        Ok(res) => res,
+        Err(e) => exit_with_error(e),
+    };
+    if with_rpc_overrides && !cmd_args.rpc_urls.is_empty() {
+        client = match client.with_rpc_urls(cmd_args.rpc_urls.as_slice()) {
+            Ok(res) => res,
+            Err(e) => exit_with_error(e),
+        };
+    }
+    if with_rpc_overrides && !cmd_args.rpc_headers.is_empty() {
+        let mut headers: Vec<(String, String)> = Vec::new();
+        for header in cmd_args.rpc_headers.iter() {
+            match parse_rpc_header(header) {
+                Ok(res) => headers.push(res),
+                Err(e) => exit_with_error(e),
             }
-        },
-        Err(e) => {
-            eprintln!("{}", format!("Error validating input contract address; err={}", e));
-            std::process::exit(1);
         }
+        client = match client.with_rpc_headers(&headers) {
+            Ok(res) => res,
+            Err(e) => exit_with_error(e),
+        };
     }
 
-    // create a contract instance
-    let mut contract: Option<Contract<Http>> = None;
+    let retry_policy = RetryPolicy {
+        max_retries: cmd_args.retries,
+        base_delay: Duration::from_millis(cmd_args.retry_base_delay_ms),
+        max_jitter: Duration::from_millis(cmd_args.retry_jitter_ms),
+    };
+    client = client.with_retry_policy(retry_policy)
+        .with_request_timeout(Duration::from_secs(cmd_args.timeout_secs))
+        .with_rpc_batch_size(cmd_args.rpc_batch_size)
+        .with_confirmation_timeout(Duration::from_secs(cmd_args.confirmation_timeout_secs))
+        .with_confirmation_progress(print_confirmation_progress)
+        .with_log_scan_progress(print_log_scan_progress);
 
-    // only create a contract instance if it's not RPC-ETH call
-    if !cmd_args.rpc_eth {
-        contract = match create_contract_from_abifile(&web3, &cmd_args.contract_address, &cmd_args.abi_filepath.unwrap()) {
-            Ok(res) => Some(res),
-            Err(e) => {
-                eprintln!("{}", format!("Error creating a contract instance; err={}", e));
-                std::process::exit(1);
+    if !cmd_args.no_history {
+        if let Some(filepath) = History::default_filepath() {
+            match History::open(&filepath) {
+                Ok(res) => client = client.with_history(res),
+                Err(e) => exit_with_error(e.context("Error opening history; use --no-history to skip recording")),
             }
-        };
+        }
     }
 
-    // for setter (estimate gas - dry run only)
-    if cmd_args.dry_run_estimate_gas {
-        // --dry-run-estimate-gas requires presence of --ensure-setter
-        if !cmd_args.ensure_setter {
-            eprintln!("Error, --dry-run-estimate-gas requires --ensure-setter flag");
-            std::process::exit(1);
+    if !cmd_args.no_cache {
+        if let Some(filepath) = ResponseCache::default_filepath() {
+            client = client.with_response_cache(ResponseCache::new(&filepath).with_ttl(Duration::from_secs(cmd_args.cache_ttl_secs)));
         }
+    }
 
-        // check required parameter
-        if cmd_args.estimate_gas_from_addr.is_none() {
-            eprintln!("Error, requires --estimate-gas-from-addr to be set");
-            std::process::exit(1);
+    if let Some(ens_registry) = &cmd_args.ens_registry {
+        match get_address_from_str(ens_registry) {
+            Ok(addr) => client = client.with_ens_registry(addr),
+            Err(e) => exit_with_error(e.context("Error parsing --ens-registry")),
         }
+    }
 
-        let estimate_gas_from_addr = cmd_args.estimate_gas_from_addr.unwrap();
+    if let Some(gas_strategy) = &cmd_args.gas_strategy {
+        match GasStrategy::from_str(gas_strategy) {
+            Ok(res) => client = client.with_gas_strategy(res),
+            Err(e) => exit_with_error(e.context("Error parsing --gas-strategy")),
+        }
+    }
 
-        let est_gas_used = web3_query_estimate_gas(&contract.unwrap(), &cmd_args.fn_name, cmd_args.params.as_slice(), &estimate_gas_from_addr).await;
-        let f_est_gas_used: f64;
-        let estimated_gas_used: U256;
-        match est_gas_used {
-            Ok(res) => {
-                estimated_gas_used = res;
+    client
+}
 
-                // convert from base U256 to primitive_types's U256 which has floating point
-                // feature
-                f_est_gas_used = match primitive_types::U256::from_dec_str(&estimated_gas_used.to_string()) {
-                    Ok(res) => res.to_f64_lossy(),
-                    Err(e) => {
-                        eprintln!("Error converting from base U256 to floating-point ready U256; err={}", e);
-                        std::process::exit(1);
-                    }
-                };
-            },
-            Err(e) => {
-                eprintln!("{}", format!("Error estimating gas by calling a setter method '{}'; err={}", &cmd_args.fn_name, e));
-                std::process::exit(1);
-            }
-        };
-
-        // print the gas price
-        // so user can mutiply with the unit of gas used from prior
-        match web3.eth().gas_price().await {
-            Ok(gas_price) => {
-                // convert from base U256 to primitive_types's U256 which has floating point
-                // feature
-                let f_gas_price = match primitive_types::U256::from_dec_str(&gas_price.to_string()) {
-                    Ok(res) => res,
-                    Err(e) => {
-                        eprintln!("Error converting from base U256 to floating-point ready U256; err={}", e);
-                        std::process::exit(1);
-                    }
-                };
+/// Resolve ENS names in address arguments, and parameters of the command.
+async fn resolve_ens_names(client: &CrunnerClient, command: &mut Command) -> Result<(), CrunnerError> {
+    match command {
+        Command::Call(args) => resolve_contract_args(client, &mut args.contract, None).await,
+        Command::Send(args) => {
+            // parameters are resolved by handle_send once the sender for `{sender}` is known
+            if let Some(contract_address) = &args.contract.contract_address {
+                args.contract.contract_address = Some(client.resolve_address(contract_address).await?);
+            }
+            if let Some(from) = &args.tx.from {
+                args.tx.from = Some(client.resolve_address(from).await?);
+            }
+            Ok(())
+        },
+        Command::RawCall(args) => {
+            if let Some(to) = &args.to {
+                args.to = Some(client.resolve_address(to).await?);
+            }
+            if let Some(from) = &args.from {
+                args.from = Some(client.resolve_address(from).await?);
+            }
+            Ok(())
+        },
+        Command::EstimateGas(args) => {
+            args.from = client.resolve_address(&args.from).await?;
+            resolve_contract_args(client, &mut args.contract, Some(&args.from)).await
+        },
+        Command::TraceCall(args) => {
+            if let Some(from) = &args.from {
+                args.from = Some(client.resolve_address(from).await?);
+            }
+            resolve_contract_args(client, &mut args.contract, args.from.as_deref()).await
+        },
+        Command::Multicall(args) => {
+            // each call is contract address, function, then parameters separated by spaces
+            for call in args.calls.iter_mut() {
+                let mut parts: Vec<String> = call.split_whitespace().map(|p| p.to_owned()).collect();
+                if let Some(address) = parts.first() {
+                    parts[0] = client.resolve_address(address).await?;
+                }
+                if parts.len() > 2 {
+                    let params = client.resolve_params(&parts[2..]).await?;
+                    parts.truncate(2);
+                    parts.extend(params);
+                }
+                *call = parts.join(" ");
+            }
+            Ok(())
+        },
+        Command::Balance(args) => {
+            args.address = client.resolve_address(&args.address).await?;
+            Ok(())
+        },
+        Command::Events(args) => {
+            args.contract_address = client.resolve_address(&args.contract_address).await?;
+            Ok(())
+        },
+        Command::TokenInfo(args) => {
+            args.address = client.resolve_address(&args.address).await?;
+            Ok(())
+        },
+        Command::Erc20(args) => {
+            let addresses = match &mut args.command {
+                Erc20Command::BalanceOf(args) => vec![&mut args.token, &mut args.owner],
+                Erc20Command::Allowance(args) => vec![&mut args.token, &mut args.owner, &mut args.spender],
+                Erc20Command::Approve(args) => vec![&mut args.token, &mut args.spender],
+                Erc20Command::Transfer(args) => vec![&mut args.token, &mut args.to],
+            };
+            for address in addresses {
+                *address = client.resolve_address(address).await?;
+            }
+            if let Some(tx) = args.command.tx_mut() {
+                if let Some(from) = &tx.from {
+                    tx.from = Some(client.resolve_address(from).await?);
+                }
+            }
+            Ok(())
+        },
+        Command::Permit(args) => {
+            args.token = client.resolve_address(&args.token).await?;
+            args.spender = client.resolve_address(&args.spender).await?;
+            Ok(())
+        },
+        Command::Erc721(args) => {
+            let addresses = match &mut args.command {
+                Erc721Command::OwnerOf(args) => vec![&mut args.token],
+                Erc721Command::TokenUri(args) => vec![&mut args.token],
+                Erc721Command::SafeTransfer(args) => {
+                    let mut addresses = vec![&mut args.token, &mut args.to];
+                    addresses.extend(args.owner.as_mut());
+                    addresses
+                },
+                Erc721Command::Approve(args) => vec![&mut args.token, &mut args.to],
+            };
+            for address in addresses {
+                *address = client.resolve_address(address).await?;
+            }
+            if let Some(tx) = args.command.tx_mut() {
+                if let Some(from) = &tx.from {
+                    tx.from = Some(client.resolve_address(from).await?);
+                }
+            }
+            Ok(())
+        },
+        Command::Erc1155(args) => {
+            let addresses = match &mut args.command {
+                Erc1155Command::BalanceOf(args) => vec![&mut args.token, &mut args.owner],
+                Erc1155Command::BalanceOfBatch(args) => std::iter::once(&mut args.token).chain(args.owners.iter_mut()).collect(),
+                Erc1155Command::SafeTransferFrom(args) => {
+                    let mut addresses = vec![&mut args.token, &mut args.to];
+                    addresses.extend(args.owner.as_mut());
+                    addresses
+                },
+            };
+            for address in addresses {
+                *address = client.resolve_address(address).await?;
+            }
+            if let Some(tx) = args.command.tx_mut() {
+                if let Some(from) = &tx.from {
+                    tx.from = Some(client.resolve_address(from).await?);
+                }
+            }
+            Ok(())
+        },
+        Command::Key(_) | Command::Tx(_) | Command::Batch(_) | Command::Serve(_) | Command::Script(_) | Command::History(_) | Command::Completions(_) | Command::SignTyped(_) | Command::SignMessage(_) | Command::VerifySig(_) | Command::Keccak(_) | Command::Selector(_) | Command::Topic(_) | Command::DecodeOutput(_) | Command::DecodeCalldata(_) | Command::DecodeTx(_) => Ok(()),
+    }
+}
 
-                let gas_price = f_gas_price.to_f64_lossy() / 10_f64.powf(18_f64);
-                println!("{:?} {} {}", estimated_gas_used, gas_price, gas_price * f_est_gas_used);
-            },
-            Err(e) => {
-                eprintln!("Error in querying gas price; err={}", e);
-                std::process::exit(1);
+/// Resolve ENS names in contract address, and parameters; template variables in
+/// parameters are substituted first with `sender` as `{sender}`.
+async fn resolve_contract_args(client: &CrunnerClient, args: &mut ContractArgs, sender: Option<&str>) -> Result<(), CrunnerError> {
+    if let Some(contract_address) = &args.contract_address {
+        args.contract_address = Some(client.resolve_address(contract_address).await?);
+    }
+    args.params = expand_and_resolve_params(client, args.params.as_slice(), args.contract_address.as_deref(), sender).await?;
+    Ok(())
+}
+
+/// Substitute template variables i.e. `{block.timestamp+3600}` in parameters, then
+/// resolve ENS names in them.
+async fn expand_and_resolve_params(client: &CrunnerClient, params: &[String], self_address: Option<&str>, sender: Option<&str>) -> Result<Vec<String>, CrunnerError> {
+    let params = client.expand_params(params, self_address, sender).await?;
+    client.resolve_params(params.as_slice()).await
+}
+
+/// Validate that the input address is a contract address, otherwise exit the program.
+async fn ensure_contract_address(client: &CrunnerClient, address: &str) {
+    let is_eoa_res = client.is_eoa(address).await;
+    match is_eoa_res {
+        Ok(is_eoa) => {
+            if is_eoa {
+                exit_with_error(CrunnerError::InvalidAddress("Error, input contract address is EOA".to_owned()));
             }
-        }
+        },
+        Err(e) => exit_with_error(e.context("Error validating input contract address")),
     }
-    // for setter (rpc-eth)
-    else if cmd_args.ensure_setter && cmd_args.rpc_eth {
-        // nothing for now...
-        println!("Not support right now");
+}
+
+/// Validate the input contract address then create a contract instance from
+/// the ABI source specified in `args` with the overload of the function selected,
+/// otherwise exit the program.
+async fn prepare_contract(client: &CrunnerClient, args: &ContractArgs) -> Contract<CrunnerTransport> {
+    let contract = create_contract(client, args).await;
+
+    // select the overload to call in case of overloaded functions
+    match client.select_overload(&contract, &args.fn_name(), args.fn_sig.as_deref(), args.params.as_slice()) {
+        Ok(res) => res,
+        Err(e) => exit_with_error(e),
     }
-    // for setter
-    else if cmd_args.ensure_setter {
-        let tx_receipt_res = web3_query_set(&contract.unwrap(), &cmd_args.fn_name, &cmd_args.params.as_slice(), cmd_args.block_confirmations).await;
-        match tx_receipt_res {
-            Ok(tx_receipt) => {
-                println!("{:?}", tx_receipt.transaction_hash);
-            },
-            Err(e) => {
-                eprintln!("{}", format!("Error calling setter method '{}'; err={}", &cmd_args.fn_name, e));
-                std::process::exit(1);
+}
+
+/// Validate the input contract address then create a contract instance from
+/// the ABI source specified in `args`, otherwise exit the program.
+async fn create_contract(client: &CrunnerClient, args: &ContractArgs) -> Contract<CrunnerTransport> {
+    let contract_address = match &args.contract_address {
+        Some(res) => res,
+        None => exit_with_error(CrunnerError::InvalidArgument("Error, requires --address to be set".to_owned())),
+    };
+    if !args.skip_code_check {
+        ensure_contract_address(client, contract_address).await;
+    }
+
+    let contract_res = if let Some(standard) = args.standard {
+        client.standard_contract(contract_address, standard)
+    }
+    else if args.abi_from_explorer {
+        client.contract_from_explorer(contract_address).await
+    }
+    else {
+        client.contract(contract_address, args.abi_filepath.as_deref())
+    };
+    match contract_res {
+        Ok(res) => res,
+        Err(e) => exit_with_error(e.context("Error creating a contract instance")),
+    }
+}
+
+/// Handle `call` subcommand; make a call to getter method.
+async fn handle_call(client: &CrunnerClient, args: CallArgs) {
+    if let Some(addresses_file) = &args.addresses_file {
+        return handle_call_addresses(client, &args, addresses_file).await;
+    }
+    if let Some(interval) = args.watch {
+        return handle_call_watch(client, &args, interval).await;
+    }
+    if args.params_stdin {
+        return handle_call_stdin(client, &args).await;
+    }
+    if args.human_amount {
+        return handle_call_human_amount(client, &args).await;
+    }
+
+    let contract = prepare_contract(client, &args.contract).await;
+    let fn_name = &args.contract.fn_name();
+    let params = args.contract.params.as_slice();
+
+    // infer from function's outputs declared in ABI
+    let fn_ret_type = match args.fn_ret_type {
+        Some(res) => res,
+        None => {
+            match client.call_inferred(&contract, fn_name, params).await {
+                Ok(outputs) => print_call_outputs(fn_name, params, outputs),
+                Err(e) => exit_with_error(e.context(format!("Error querying of method '{}'", fn_name))),
             }
+            return;
         }
-    }
-    // for getter (rpc-eth)
-    else if cmd_args.rpc_eth {
-        // query balance of the target address
-        if &cmd_args.fn_name == "balance" {
-            let contract_addr = match get_address_from_str(&cmd_args.contract_address) {
-                Ok(addr) => addr,
-                Err(e) => {
-                    eprintln!("{}", e);
-                    std::process::exit(1);
-                }
-            };
+    };
 
-            let bal_res = web3.eth().balance(contract_addr, None).await;
-            match bal_res {
-                Ok(bal) => {
-                    let fready_bal = match primitive_types::U256::from_dec_str(&bal.to_string()) {
-                        Ok(res) => res,
-                        Err(e) => {
-                            eprintln!("Error converting from base U256 to floating-point ready U256; err={}", e);
-                            std::process::exit(1);
-                        }
-                    };
+    // composite return signature; decode against declared types then print each component.
+    // signed integer is decoded this way too as there is no native type for int256.
+    // in JSON or CSV output, every return type is decoded this way to convert decoded tokens into such format.
+    let composite_sig = match fn_ret_type.as_str() {
+        "I256" => Some("(int256)".to_owned()),
+        "I256[]" => Some("(int256[])".to_owned()),
+        sig if sig.starts_with('(') => Some(sig.to_owned()),
+        ret_type if is_json() || is_csv() => Some(format!("({})", solidity_type_of(ret_type))),
+        _ => None,
+    };
+    if let Some(sig) = composite_sig {
+        let ret_types = match parse_ret_types(&sig) {
+            Ok(res) => res,
+            Err(e) => exit_with_error(e),
+        };
 
-                    println!("{:?} {:?}", bal, fready_bal.to_f64_lossy() / 10_f64.powf(18_f64));
-                },
-                Err(e) => {
-                    eprintln!("Error converting from base U256 to floating-point ready U256; err={}", e);
-                    std::process::exit(1);
+        match client.call_tokens(&contract, fn_name, params, ret_types.as_slice()).await {
+            Ok(tokens) if is_json() => {
+                let result = if tokens.len() == 1 {
+                    token_to_json(&tokens[0])
                 }
-            }
+                else {
+                    serde_json::Value::Array(tokens.iter().map(token_to_json).collect())
+                };
+                print_json(&json!({ "function": fn_name, "result": result }));
+            },
+            Ok(tokens) if is_csv() => print_call_csv_row(fn_name, params, tokens),
+            Ok(tokens) => {
+                for token in tokens {
+                    println!("{}", format_token(&token));
+                }
+            },
+            Err(e) => exit_with_error(e.context(format!("Error querying of method '{}'", fn_name))),
         }
+        return;
+    }
+
+    // make a call to specified function of the target smart contract
+    match fn_ret_type.as_str() {
+        "String" => query_and_print(client, &contract, fn_name, params, |v: String| v).await,
+        "U256" => query_and_print(client, &contract, fn_name, params, |v: U256| v.to_string()).await,
+        "bool" => query_and_print(client, &contract, fn_name, params, |v: bool| v.to_string()).await,
+        "Address" => query_and_print(client, &contract, fn_name, params, |v: Address| to_checksum_address(&v)).await,
+        "u8" => query_and_print(client, &contract, fn_name, params, |v: u8| v.to_string()).await,
+        "u64" => query_and_print(client, &contract, fn_name, params, |v: u64| v.to_string()).await,
+        "bytes" => query_and_print(client, &contract, fn_name, params, |v: Vec<u8>| format!("0x{}", hex::encode(v))).await,
+        "String[]" => query_and_print(client, &contract, fn_name, params, |v: Vec<String>| format_array(v, |e| e)).await,
+        "U256[]" => query_and_print(client, &contract, fn_name, params, |v: Vec<U256>| format_array(v, |e| e.to_string())).await,
+        "bool[]" => query_and_print(client, &contract, fn_name, params, |v: Vec<bool>| format_array(v, |e| e.to_string())).await,
+        "Address[]" => query_and_print(client, &contract, fn_name, params, |v: Vec<Address>| format_array(v, |e| to_checksum_address(&e))).await,
+        // `Vec<u8>` would be detokenized as bytes, so use wider item type for array of u8
+        "u8[]" => query_and_print(client, &contract, fn_name, params, |v: Vec<u16>| format_array(v, |e| e.to_string())).await,
+        "u64[]" => query_and_print(client, &contract, fn_name, params, |v: Vec<u64>| format_array(v, |e| e.to_string())).await,
+        "bytes[]" => query_and_print(client, &contract, fn_name, params, |v: Vec<Vec<u8>>| format_array(v, |e| format!("0x{}", hex::encode(e)))).await,
+        // the less of non-match cases handled by clap crate
+        _ => (),
+    }
+}
+
+/// Print outputs of getter method decoded against function's outputs declared in ABI;
+/// each component is prefixed with its name if there are multiple of them.
+fn print_call_outputs(fn_name: &str, params: &[String], outputs: Vec<(String, Token)>) {
+    if is_json() {
+        print_json(&json!({ "function": fn_name, "result": outputs_json(&outputs) }));
+    }
+    else if is_csv() {
+        let tokens: Vec<Token> = outputs.into_iter().map(|(_, token)| token).collect();
+        print_call_csv_row(fn_name, params, tokens);
     }
-    // for getter
     else {
-        let ret_type_str = match cmd_args.fn_ret_type {
-            Some(type_str) => type_str,
-            None => {
-                eprintln!("Error, require --fn-ret-type for interacting with getter method of smart contract");
-                std::process::exit(1);
+        let with_name = outputs.len() > 1 && !is_quiet();
+        for (name, token) in outputs {
+            if with_name && !name.is_empty() {
+                println!("{}: {}", name, format_token(&token));
             }
-        };
+            else {
+                println!("{}", format_token(&token));
+            }
+        }
+    }
+}
 
-        // make a call to specified function of the target smart contract
-        // FIXME: this should be more concise and shorter code...
-        if ret_type_str == "String" {
-            let res = web3_query_get::<String>(&contract.unwrap(), &cmd_args.fn_name, cmd_args.params.as_slice()).await;
-            match res {
-                Ok(res) => println!("{}", res),
-                Err(e) => {
-                    eprintln!("{}", format!("Error querying of method '{}'; err={}", &cmd_args.fn_name, e));
-                    std::process::exit(1);
-                }
+/// Make a call to getter method returning token amount, and print it along with the
+/// amount scaled by decimals of the contract called.
+async fn handle_call_human_amount(client: &CrunnerClient, args: &CallArgs) {
+    let contract = prepare_contract(client, &args.contract).await;
+    let fn_name = &args.contract.fn_name();
+    let params = args.contract.params.as_slice();
+    let ret_types = parse_fn_ret_types(args.fn_ret_type.as_deref());
+
+    let (value, decimals) = query_token_amount(client, &contract, fn_name, params, ret_types.as_deref()).await;
+    print_token_amount(fn_name, params, value, decimals);
+}
+
+/// Make a call to getter method returning token amount i.e. `balanceOf`, and query
+/// decimals of the contract called, otherwise exit the program.
+async fn query_token_amount(client: &CrunnerClient, contract: &Contract<CrunnerTransport>, fn_name: &str, params: &[String], ret_types: Option<&[ParamType]>) -> (U256, usize) {
+    let outputs = match call_outputs(client, contract, fn_name, params, ret_types).await {
+        Ok(res) => res,
+        Err(e) => exit_with_error(e.context(format!("Error querying of method '{}'", fn_name))),
+    };
+    let value = match outputs.as_slice() {
+        [(_, Token::Uint(value))] => *value,
+        _ => exit_with_error(CrunnerError::InvalidArgument(format!("Error, method '{}' must return a single unsigned integer as token amount", fn_name))),
+    };
+    match client.token_decimals(&to_checksum_address(&contract.address())).await {
+        Ok(decimals) => (value, decimals),
+        Err(e) => exit_with_error(e),
+    }
+}
+
+/// Print token amount as raw value followed by the value scaled by decimals; only
+/// the scaled one in quiet mode.
+fn print_token_amount(fn_name: &str, params: &[String], value: U256, decimals: usize) {
+    let human_value = format_units(value, decimals);
+    if is_json() {
+        print_json(&json!({ "function": fn_name, "result": value.to_string(), "formatted": human_value, "decimals": decimals }));
+    }
+    else if is_csv() {
+        print_csv_row(&["timestamp", "function", "params", "value", "formatted"]);
+        print_csv_row(&[timestamp_now(), fn_name.to_owned(), params.join(" "), value.to_string(), human_value]);
+    }
+    else if is_quiet() {
+        println!("{}", human_value);
+    }
+    else {
+        println!("{} {}", value, human_value);
+    }
+}
+
+/// Make the same call to getter method against each contract listed in addresses file
+/// concurrently, and print one line of result per contract.
+async fn handle_call_addresses(client: &CrunnerClient, args: &CallArgs, addresses_file: &str) {
+    if args.concurrency == 0 {
+        exit_with_error(CrunnerError::InvalidArgument("Error, --concurrency must be greater than 0".to_owned()));
+    }
+    let addresses = match load_addresses_file(addresses_file) {
+        Ok(res) => res,
+        Err(e) => exit_with_error(e),
+    };
+
+    // contracts are prepared concurrently, then all calls go out in batch requests
+    let contracts: Vec<Result<Contract<CrunnerTransport>, CrunnerError>> = futures::stream::iter(addresses.iter())
+        .map(|address| contract_at_address(client, &args.contract, address, args.contract.params.as_slice()))
+        .buffered(args.concurrency)
+        .collect()
+        .await;
+    let fn_name = args.contract.fn_name();
+    let calls: Vec<(&Contract<CrunnerTransport>, &str, &[String])> = contracts.iter()
+        .filter_map(|contract| contract.as_ref().ok())
+        .map(|contract| (contract, fn_name.as_str(), args.contract.params.as_slice()))
+        .collect();
+    let ret_types = parse_fn_ret_types(args.fn_ret_type.as_deref());
+    let mut call_results = client.call_many(calls.as_slice(), ret_types.as_deref()).await.into_iter();
+    let results: Vec<Result<Vec<(String, Token)>, CrunnerError>> = contracts.into_iter().map(|contract| match contract {
+        Ok(_) => call_results.next().unwrap_or_else(|| Err(CrunnerError::RpcError("Error, no result of the call".to_owned()))),
+        Err(e) => Err(e),
+    }).collect();
+
+    print_call_results("address", addresses.as_slice(), &args.contract, results);
+}
+
+/// Make the call once per line of stdin, each line holding a full set of parameters
+/// separated by spaces appended to --params, and print one line of result per input
+/// line as soon as it's ready.
+async fn handle_call_stdin(client: &CrunnerClient, args: &CallArgs) {
+    if args.concurrency == 0 {
+        exit_with_error(CrunnerError::InvalidArgument("Error, --concurrency must be greater than 0".to_owned()));
+    }
+    // overload is selected per line as parameters differ
+    let contract = create_contract(client, &args.contract).await;
+    let fn_name = args.contract.fn_name();
+    let ret_types = parse_fn_ret_types(args.fn_ret_type.as_deref());
+
+    let lines = Box::pin(futures::stream::unfold(tokio::io::BufReader::new(tokio::io::stdin()).lines(), |mut lines| async move {
+        match lines.next_line().await {
+            Ok(Some(line)) => Some((line, lines)),
+            Ok(None) => None,
+            Err(e) => exit_with_error(CrunnerError::InvalidArgument(format!("Error reading parameters from stdin; err={}", e))),
+        }
+    }));
+    let mut results = lines
+        .filter(|line| futures::future::ready(!line.trim().is_empty() && !line.trim().starts_with('#')))
+        .map(|line| {
+            let (contract, fn_name, ret_types) = (&contract, &fn_name, ret_types.as_deref());
+            async move {
+                let mut params = args.contract.params.clone();
+                params.extend(line.split_whitespace().map(|p| p.to_owned()));
+                let res = call_with_params(client, contract, fn_name, args.contract.fn_sig.as_deref(), params.as_slice(), ret_types).await;
+                (params, res)
             }
+        })
+        .buffered(args.concurrency);
+
+    if is_csv() {
+        print_csv_row(&["timestamp", "function", "params", "success", "value"]);
+    }
+    let mut num_calls = 0;
+    let mut failures = Vec::new();
+    while let Some((params, res)) = results.next().await {
+        num_calls += 1;
+        if let Err(e) = &res {
+            failures.push(e.clone());
         }
-        else if ret_type_str == "U256" {
-            let res = web3_query_get::<U256>(&contract.unwrap(), &cmd_args.fn_name, cmd_args.params.as_slice()).await;
+        if is_json() {
+            let mut result_json = json!({ "function": fn_name, "params": params });
             match res {
-                Ok(res) => println!("{:?}", res),
+                Ok(outputs) => {
+                    result_json["success"] = json!(true);
+                    result_json["result"] = outputs_json(&outputs);
+                },
                 Err(e) => {
-                    eprintln!("{}", format!("Error querying of method '{}'; err={}", &cmd_args.fn_name, e));
-                    std::process::exit(1);
-                }
+                    result_json["success"] = json!(false);
+                    result_json["error"] = json!(e.to_string());
+                },
             }
+            print_json(&result_json);
+            continue;
+        }
+
+        let (success, value) = match res {
+            Ok(outputs) => (true, format_tokens(outputs.into_iter().map(|(_, token)| token).collect())),
+            Err(e) => (false, e.to_string()),
+        };
+        if is_csv() {
+            print_csv_row(&[timestamp_now(), fn_name.to_owned(), params.join(" "), success.to_string(), value]);
+        }
+        // keep one line per input line so that the output lines up with the input
+        else if is_quiet() {
+            println!("{}", if success { value } else { String::new() });
+        }
+        else {
+            println!("{}: {}", params.join(" "), value);
+        }
+    }
+
+    if let Some(e) = aggregate_failure(failures.iter(), num_calls, "calls") {
+        exit_with_error(e);
+    }
+}
+
+/// Make a call to getter method with the parameters; substitute template variables,
+/// resolve ENS names, and aliases in them, and select the overload of the function to call first.
+async fn call_with_params(client: &CrunnerClient, contract: &Contract<CrunnerTransport>, fn_name: &str, fn_sig: Option<&str>, params: &[String], ret_types: Option<&[ParamType]>) -> Result<Vec<(String, Token)>, CrunnerError> {
+    let self_address = to_checksum_address(&contract.address());
+    let params = expand_and_resolve_params(client, params, Some(&self_address), None).await?;
+    let contract = client.select_overload(contract, fn_name, fn_sig, params.as_slice())?;
+    call_outputs(client, &contract, fn_name, params.as_slice(), ret_types).await
+}
+
+/// Handle `call` subcommand made on several chains at once i.e. repeated `--chain`,
+/// or `--all-chains`; print one line of result per chain.
+async fn handle_multi_chain_call(cmd_args: &CommandlineArgs) {
+    let args = match &cmd_args.command {
+        Command::Call(args) => args,
+        _ => exit_with_error(CrunnerError::InvalidArgument("Error, multiple chains are supported only by call subcommand".to_owned())),
+    };
+    let address = match (&args.contract.contract_address, &args.addresses_file) {
+        (Some(res), None) => res,
+        (_, Some(_)) => exit_with_error(CrunnerError::InvalidArgument("Error, --addresses-file cannot be used with multiple chains".to_owned())),
+        (None, None) => exit_with_error(CrunnerError::InvalidArgument("Error, requires --address to be set".to_owned())),
+    };
+    if args.watch.is_some() {
+        exit_with_error(CrunnerError::InvalidArgument("Error, --watch cannot be used with multiple chains".to_owned()));
+    }
+    if args.params_stdin {
+        exit_with_error(CrunnerError::InvalidArgument("Error, --params-stdin cannot be used with multiple chains".to_owned()));
+    }
+    // RPC endpoint, and its headers are specific to a chain; use CRUNNER_RPC_<CHAIN> instead
+    if !cmd_args.rpc_urls.is_empty() || !cmd_args.rpc_headers.is_empty() {
+        exit_with_error(CrunnerError::InvalidArgument("Error, --rpc-url, and --rpc-header cannot be used with multiple chains; set RPC endpoint per chain via environment variable i.e. CRUNNER_RPC_BSC".to_owned()));
+    }
+
+    let mut chain_values: Vec<String> = Vec::new();
+    if cmd_args.all_chains {
+        chain_values.extend(MAINNET_CHAINS.iter().map(|c| c.to_string()));
+    }
+    for chain_value in cmd_args.chains.iter().map(|c| c.to_lowercase()) {
+        if !chain_values.contains(&chain_value) {
+            chain_values.push(chain_value);
         }
     }
-    // -- the less of non-match cases handled by clap crate
+    let chains: Vec<ChainType> = chain_values.iter().map(|c| parse_chain(c)).collect();
+
+    let state_override = match args.state_override.as_deref().map(load_state_override).transpose() {
+        Ok(res) => res,
+        Err(e) => exit_with_error(e.context("Error parsing --state-override")),
+    };
+    let ret_types = parse_fn_ret_types(args.fn_ret_type.as_deref());
+    let results = futures::future::join_all(chains.iter().map(|chain| {
+        call_on_chain(cmd_args, *chain, args, address, ret_types.as_deref(), state_override.as_ref())
+    })).await;
+
+    print_call_results("chain", chain_values.as_slice(), &args.contract, results);
+}
+
+/// Make a call to getter method of the contract at the address on the chain; the
+/// chain is verified, and --block is resolved against it first.
+async fn call_on_chain(cmd_args: &CommandlineArgs, chain: ChainType, args: &CallArgs, address: &str, ret_types: Option<&[ParamType]>, state_override: Option<&StateOverride>) -> Result<Vec<(String, Token)>, CrunnerError> {
+    let mut client = create_client(chain, cmd_args, false);
+    if !cmd_args.skip_chain_check {
+        client.verify_chain_id().await?;
+    }
+    if let Some(block) = &args.block {
+        let block = client.resolve_block(block).await?;
+        client = client.with_block(block);
+    }
+    if let Some(state_override) = state_override {
+        client = client.with_state_override(state_override.clone());
+    }
+
+    // the same address may have no contract deployed on some chains
+    let address = client.resolve_address(address).await?;
+    if client.is_eoa(&address).await? {
+        return Err(CrunnerError::InvalidAddress("Error, no contract deployed at the address".to_owned()));
+    }
+    // template variables i.e. `{block.number}` differ per chain
+    let params = client.expand_params(args.contract.params.as_slice(), Some(&address), None).await?;
+    call_at_address(&client, &args.contract, &address, params.as_slice(), ret_types).await
+}
+
+/// Execute --alert-cmd, and post to --alert-webhook as set; failures are reported on
+/// stderr without stopping the watch.
+async fn fire_alert(args: &CallArgs, event: &AlertEvent<'_>) {
+    tracing::info!(value = event.value, condition = %event.condition, "alert triggered");
+    if let Some(cmd) = &args.alert_cmd {
+        if let Err(e) = run_alert_cmd(cmd, event).await {
+            print_error(&e);
+        }
+    }
+    if let Some(webhook) = &args.alert_webhook {
+        if let Err(e) = post_alert_webhook(webhook, event).await {
+            print_error(&e);
+        }
+    }
+}
+
+/// Parse --fn-ret-type into types to decode returned values against, otherwise exit
+/// the program. `None` if not set i.e. to infer from ABI.
+fn parse_fn_ret_types(fn_ret_type: Option<&str>) -> Option<Vec<ParamType>> {
+    let ret_types = match fn_ret_type {
+        Some(sig) if sig.starts_with('(') => parse_ret_types(sig),
+        Some(ret_type) => parse_ret_types(&format!("({})", solidity_type_of(ret_type))),
+        None => return None,
+    };
+    match ret_types {
+        Ok(res) => Some(res),
+        Err(e) => exit_with_error(e),
+    }
+}
+
+/// Print results of the same call made against several targets i.e. contracts, or
+/// chains; one line per target labeled as `label`. Exit the program with error if
+/// any of the calls failed.
+fn print_call_results(label: &str, targets: &[String], args: &ContractArgs, results: Vec<Result<Vec<(String, Token)>, CrunnerError>>) {
+    let fn_name = args.fn_name();
+    let params = args.params.as_slice();
+    let failure = aggregate_failure(results.iter().filter_map(|r| r.as_ref().err()), targets.len(), "calls");
+    if is_json() {
+        let results_json: Vec<serde_json::Value> = targets.iter().zip(results.iter()).map(|(target, result)| {
+            let mut result_json = json!({
+                label: target,
+                "function": fn_name,
+                "params": params,
+            });
+            match result {
+                Ok(outputs) => {
+                    result_json["success"] = json!(true);
+                    result_json["result"] = outputs_json(outputs);
+                },
+                Err(e) => {
+                    result_json["success"] = json!(false);
+                    result_json["error"] = json!(e.to_string());
+                },
+            }
+            result_json
+        }).collect();
+        print_json(&serde_json::Value::Array(results_json));
+    }
+    else if is_csv() {
+        print_csv_row(&["timestamp", label, "function", "params", "success", "value"]);
+        let timestamp = timestamp_now();
+        for (target, result) in targets.iter().zip(results) {
+            let (success, value) = match result {
+                Ok(outputs) => (true, format_tokens(outputs.into_iter().map(|(_, token)| token).collect())),
+                Err(e) => (false, e.to_string()),
+            };
+            print_csv_row(&[timestamp.clone(), target.to_owned(), fn_name.to_owned(), params.join(" "), success.to_string(), value]);
+        }
+    }
+    else {
+        for (target, result) in targets.iter().zip(results) {
+            let value = match result {
+                Ok(outputs) => format_tokens(outputs.into_iter().map(|(_, token)| token).collect()),
+                // keep one line per target so that the output lines up with the targets
+                Err(_) if is_quiet() => String::new(),
+                Err(e) => e.to_string(),
+            };
+            if is_quiet() {
+                println!("{}", value);
+            }
+            else {
+                println!("{}: {}", target, value);
+            }
+        }
+    }
+
+    if let Some(e) = failure {
+        exit_with_error(e);
+    }
+}
+
+/// Get error reporting how many of several calls, or entries failed; of the same category
+/// as the failures if they all share one, otherwise `RpcError` as partial failure.
+/// `None` if none failed.
+///
+/// # Arguments
+/// * `failures` - errors of the failed ones
+/// * `num_total` - number of all calls, or entries
+/// * `noun` - what failed i.e. `calls`
+fn aggregate_failure<'a>(mut failures: impl Iterator<Item = &'a CrunnerError>, num_total: usize, noun: &str) -> Option<CrunnerError> {
+    let first = failures.next()?;
+    let mut num_failed = 1;
+    let mut same_kind = true;
+    for e in failures {
+        num_failed += 1;
+        same_kind &= e.kind() == first.kind();
+    }
+    let msg = format!("Error, {} of {} {} failed", num_failed, num_total, noun);
+    if same_kind {
+        Some(first.with_message(msg))
+    }
+    else {
+        Some(CrunnerError::RpcError(msg))
+    }
+}
+
+/// Read contract addresses (or ENS names) from file, one per line; empty lines, and
+/// lines starting with `#` are skipped.
+fn load_addresses_file(filepath: &str) -> Result<Vec<String>, CrunnerError> {
+    let content = match std::fs::read_to_string(filepath) {
+        Ok(res) => res,
+        Err(e) => return Err(CrunnerError::InvalidArgument(format!("Error reading addresses file {}; err={}", filepath, e))),
+    };
+    let addresses: Vec<String> = content.lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| line.to_owned())
+        .collect();
+    if addresses.is_empty() {
+        return Err(CrunnerError::InvalidArgument(format!("Error, addresses file {} lists no address", filepath)));
+    }
+    Ok(addresses)
+}
+
+/// Make a call to getter method of the contract at the address; decode against
+/// `ret_types` if set, otherwise against function's outputs declared in ABI.
+async fn call_at_address(client: &CrunnerClient, args: &ContractArgs, address: &str, params: &[String], ret_types: Option<&[ParamType]>) -> Result<Vec<(String, Token)>, CrunnerError> {
+    let contract = contract_at_address(client, args, address, params).await?;
+    call_outputs(client, &contract, &args.fn_name(), params, ret_types).await
+}
+
+/// Create contract instance at the address (or ENS name) with its overload of the
+/// function selected against `params`.
+async fn contract_at_address(client: &CrunnerClient, args: &ContractArgs, address: &str, params: &[String]) -> Result<Contract<CrunnerTransport>, CrunnerError> {
+    let address = client.resolve_address(address).await?;
+    let contract = if args.abi_from_explorer {
+        client.contract_from_explorer(&address).await?
+    }
+    else {
+        client.contract(&address, args.abi_filepath.as_deref())?
+    };
+
+    // select the overload to call in case of overloaded functions
+    client.select_overload(&contract, &args.fn_name(), args.fn_sig.as_deref(), params)
+}
+
+/// Make a call to getter method; decode against `ret_types` if set, otherwise against
+/// function's outputs declared in ABI.
+async fn call_outputs(client: &CrunnerClient, contract: &Contract<CrunnerTransport>, fn_name: &str, params: &[String], ret_types: Option<&[ParamType]>) -> Result<Vec<(String, Token)>, CrunnerError> {
+    match ret_types {
+        Some(ret_types) => {
+            let tokens = client.call_tokens(contract, fn_name, params, ret_types).await?;
+            Ok(tokens.into_iter().map(|token| (String::new(), token)).collect())
+        },
+        None => client.call_inferred(contract, fn_name, params).await,
+    }
+}
+
+/// Make a call to getter method repeatedly at the interval, and print each returned
+/// value with its timestamp; only when it changes if --changes-only is set. Failed
+/// calls are reported on stderr without stopping.
+async fn handle_call_watch(client: &CrunnerClient, args: &CallArgs, interval: Duration) {
+    if interval.is_zero() {
+        exit_with_error(CrunnerError::InvalidArgument("Error, --watch interval must be greater than 0".to_owned()));
+    }
+    if args.alert_if.is_some() && args.alert_cmd.is_none() && args.alert_webhook.is_none() {
+        exit_with_error(CrunnerError::InvalidArgument("Error, --alert-if requires --alert-cmd, or --alert-webhook to be set".to_owned()));
+    }
+    let contract = prepare_contract(client, &args.contract).await;
+    let fn_name = args.contract.fn_name();
+    let params = args.contract.params.as_slice();
+    let ret_types = parse_fn_ret_types(args.fn_ret_type.as_deref());
+    let address = to_checksum_address(&contract.address());
+
+    if is_csv() {
+        print_csv_row(&["timestamp", "function", "params", "value"]);
+    }
+    let mut last_value: Option<String> = None;
+    let mut alert_active = false;
+    let mut ticker = tokio::time::interval(interval);
+    // don't burst to catch up with ticks missed while a slow call was in flight
+    ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    loop {
+        ticker.tick().await;
+        let outputs = match call_outputs(client, &contract, &fn_name, params, ret_types.as_deref()).await {
+            Ok(res) => res,
+            Err(e) => {
+                print_error(&e.context(format!("Error querying of method '{}'", fn_name)));
+                continue;
+            },
+        };
+        let value = format_tokens(outputs.iter().map(|(_, token)| token.clone()).collect());
+        let timestamp = timestamp_now();
+
+        if let Some(condition) = &args.alert_if {
+            match condition.is_met(&value) {
+                Ok(is_met) => {
+                    // alert only when the value crosses into the condition, not on every tick while it holds
+                    if is_met && !alert_active {
+                        let event = AlertEvent {
+                            timestamp: &timestamp,
+                            chain: chain_name(client.chain()),
+                            address: &address,
+                            function: &fn_name,
+                            value: &value,
+                            condition,
+                        };
+                        fire_alert(args, &event).await;
+                    }
+                    alert_active = is_met;
+                },
+                Err(e) => print_error(&e),
+            }
+        }
+
+        if args.changes_only && last_value.as_ref() == Some(&value) {
+            continue;
+        }
+        if is_json() {
+            print_json(&json!({ "timestamp": timestamp, "function": fn_name, "result": outputs_json(&outputs) }));
+        }
+        else if is_csv() {
+            print_csv_row(&[timestamp, fn_name.to_owned(), params.join(" "), value.to_owned()]);
+        }
+        else if is_quiet() {
+            println!("{}", value);
+        }
+        else {
+            println!("{} {}", timestamp, value);
+        }
+        last_value = Some(value);
+    }
+}
+
+/// Print CSV header, and row of the result of getter method as `timestamp,function,params,value`.
+/// Multiple returned values are formatted as a tuple.
+fn print_call_csv_row(fn_name: &str, params: &[String], tokens: Vec<Token>) {
+    print_csv_row(&["timestamp", "function", "params", "value"]);
+    print_csv_row(&[timestamp_now(), fn_name.to_owned(), params.join(" "), format_tokens(tokens)]);
+}
+
+/// Format returned values of getter method; multiple ones are formatted as a tuple.
+fn format_tokens(mut tokens: Vec<Token>) -> String {
+    if tokens.len() == 1 {
+        format_token(&tokens.remove(0))
+    }
+    else {
+        format_token(&Token::Tuple(tokens))
+    }
+}
+
+/// Convert outputs of getter method into JSON; single output as its value, otherwise
+/// object keyed by names if all are named, or array.
+fn outputs_json(outputs: &[(String, Token)]) -> serde_json::Value {
+    if outputs.len() == 1 {
+        token_to_json(&outputs[0].1)
+    }
+    else if outputs.iter().all(|(name, _)| !name.is_empty()) {
+        serde_json::Value::Object(outputs.iter().map(|(name, token)| (name.to_owned(), token_to_json(token))).collect())
+    }
+    else {
+        serde_json::Value::Array(outputs.iter().map(|(_, token)| token_to_json(token)).collect())
+    }
+}
+
+/// Get solidity type of the simple return type accepted by `--fn-ret-type` i.e. `U256[]` to `uint256[]`.
+fn solidity_type_of(fn_ret_type: &str) -> String {
+    let (base, suffix) = match fn_ret_type.strip_suffix("[]") {
+        Some(base) => (base, "[]"),
+        None => (fn_ret_type, ""),
+    };
+    let sol_type = match base {
+        "String" => "string",
+        "U256" => "uint256",
+        "I256" => "int256",
+        "bool" => "bool",
+        "Address" => "address",
+        "u8" => "uint8",
+        "u64" => "uint64",
+        "bytes" => "bytes",
+        other => other,
+    };
+    format!("{}{}", sol_type, suffix)
+}
+
+/// Make a call to getter method then print its result formatted by `format`,
+/// otherwise exit the program.
+async fn query_and_print<R, F>(client: &CrunnerClient, contract: &Contract<CrunnerTransport>, fn_name: &str, params: &[String], format: F)
+where
+    R: Detokenize,
+    F: Fn(R) -> String,
+{
+    match client.call::<R>(contract, fn_name, params).await {
+        Ok(res) => println!("{}", format(res)),
+        Err(e) => exit_with_error(e.context(format!("Error querying of method '{}'", fn_name))),
+    }
+}
+
+/// Format array of values into `[a, b, c]` form.
+fn format_array<T, F>(values: Vec<T>, format: F) -> String
+where
+    F: Fn(T) -> String,
+{
+    let items: Vec<String> = values.into_iter().map(format).collect();
+    format!("[{}]", items.join(", "))
+}
+
+/// Handle `send` subcommand; make a call to setter method.
+async fn handle_send(client: &CrunnerClient, mut args: SendArgs) {
+    if args.encode_only {
+        return handle_send_encode_only(client, args).await;
+    }
+
+    let is_local = matches!(client.chain(), ChainType::Local);
+    let confirmations = args.tx.block_confirmations.unwrap_or(if is_local { 0 } else { 20 });
+
+    // sign with external signer, keystore, or mnemonic if specified, otherwise send from
+    // unlocked account if explicitly specified, or on local chain without secret key
+    let is_node_signer = args.tx.signer == Some(SignerType::Node);
+    let signer = create_external_signer(&args.tx).await;
+    let prvk = if signer.is_none() { load_signing_key(&args.tx.key) } else { None };
+    let is_unlocked = signer.is_none() && prvk.is_none() && (is_node_signer || args.tx.from.is_some() || (is_local && std::env::var("CRUNNER_SETTER_SECRETKEY").is_err()));
+
+    if args.sign_only && is_unlocked {
+        exit_with_error(CrunnerError::InvalidArgument("Error, --sign-only requires the signing key, or --signer other than node".to_owned()));
+    }
+    let sender = if args.build_only || args.sign_only || params_refer_template(args.contract.params.as_slice(), "sender") {
+        match send_sender(client, signer.as_deref(), prvk.as_ref(), is_unlocked, args.tx.from.as_deref()).await {
+            Ok(res) => Some(res),
+            Err(e) => exit_with_error(e.context("Error getting the sender of the transaction")),
+        }
+    }
+    else {
+        None
+    };
+    args.contract.params = match expand_and_resolve_params(client, args.contract.params.as_slice(), args.contract.contract_address.as_deref(), sender.as_deref()).await {
+        Ok(res) => res,
+        Err(e) => exit_with_error(e),
+    };
+
+    args.contract.skip_code_check = args.sign_only;
+    let contract = prepare_contract(client, &args.contract).await;
+    let fn_name = &args.contract.fn_name();
+
+    let mut options = tx_options(args.tx.value.as_deref());
+    options.gas = args.tx.gas_limit.map(U256::from);
+    options.nonce = args.tx.nonce.map(U256::from);
+    if let Err(e) = set_fee_options(client, &mut options, args.tx.gas_price.as_deref(), args.tx.max_fee.as_deref(), args.tx.max_priority_fee.as_deref()).await {
+        exit_with_error(e);
+    }
+    if args.tx.from.is_some() && args.tx.signer.is_some() && !is_node_signer {
+        exit_with_error(CrunnerError::InvalidArgument("Error, --from can only be used with --signer node".to_owned()));
+    }
+    if args.build_only || args.sign_only {
+        let from = sender.unwrap_or_default();
+        let from_addr = match get_address_from_str(&from) {
+            Ok(res) => res,
+            Err(e) => exit_with_error(e),
+        };
+        let tx = match client.build_tx(&contract, fn_name, args.contract.params.as_slice(), from_addr, options).await {
+            Ok(res) => res,
+            Err(e) => exit_with_error(e.context(format!("Error building transaction of setter method '{}'", fn_name))),
+        };
+        if args.build_only {
+            return print_json(&unsigned_tx_json(&tx, &from));
+        }
+
+        let signature_res = match (&signer, &prvk) {
+            (Some(signer), _) => signer.sign_transaction(&tx).await,
+            (None, Some(prvk)) => tx.sign_with_key(prvk),
+            (None, None) => secret_key_from_env().and_then(|prvk| tx.sign_with_key(&prvk)),
+        };
+        let signature = match signature_res {
+            Ok(res) => res,
+            Err(e) => exit_with_error(e),
+        };
+        return print_raw_tx(&tx.rlp_signed(&signature));
+    }
+    let tx_receipt_res = if let Some(signer) = signer {
+        client.send_with_signer(&contract, fn_name, args.contract.params.as_slice(), signer.as_ref(), options, confirmations).await
+    }
+    else if let Some(prvk) = prvk {
+        client.send_with_key(&contract, fn_name, args.contract.params.as_slice(), options, &prvk, confirmations).await
+    }
+    else if is_unlocked {
+        client.send_unlocked(&contract, fn_name, args.contract.params.as_slice(), args.tx.from.as_deref(), options, confirmations).await
+    }
+    else {
+        client.send(&contract, fn_name, args.contract.params.as_slice(), options, confirmations).await
+    };
+    clear_progress();
+    let tx_receipt = match tx_receipt_res {
+        Ok(res) => res,
+        Err(e) => exit_with_error(e.context(format!("Error calling setter method '{}'", fn_name))),
+    };
+    if is_json() {
+        print_json(&receipt_json(&tx_receipt, Some(contract.abi())));
+    }
+    else if is_csv() {
+        let events: Vec<String> = tx_receipt.logs.iter().map(|log| format_receipt_log(log, Some(contract.abi()))).collect();
+        print_csv_row(&["timestamp", "function", "params", "transaction_hash", "status", "events"]);
+        print_csv_row(&[
+            timestamp_now(),
+            fn_name.to_owned(),
+            args.contract.params.join(" "),
+            format!("{:?}", tx_receipt.transaction_hash),
+            tx_receipt.status.map(|st| st.to_string()).unwrap_or_default(),
+            events.join("; "),
+        ]);
+    }
+    else {
+        print_receipt(&tx_receipt, Some(contract.abi()));
+    }
+
+    // the transaction has landed but reverted; report it via exit code
+    if tx_receipt.status.map(|st| st.is_zero()).unwrap_or(false) {
+        exit_with_error(CrunnerError::TxReverted(format!("Error, transaction {:?} reverted", tx_receipt.transaction_hash)));
+    }
+}
+
+/// Handle `send` subcommand with `--encode-only`; print calldata of the call to
+/// setter method without signing, or sending it.
+async fn handle_send_encode_only(client: &CrunnerClient, mut args: SendArgs) {
+    args.contract.params = match expand_and_resolve_params(client, args.contract.params.as_slice(), args.contract.contract_address.as_deref(), args.tx.from.as_deref()).await {
+        Ok(res) => res,
+        Err(e) => exit_with_error(e),
+    };
+
+    let contract = prepare_contract(client, &args.contract).await;
+    let fn_name = &args.contract.fn_name();
+    let calldata = match encode_function_call(&contract, fn_name, args.contract.params.as_slice()) {
+        Ok(res) => format!("0x{}", hex::encode(res)),
+        Err(e) => exit_with_error(e.context(format!("Error encoding call to setter method '{}'", fn_name))),
+    };
+    let to = to_checksum_address(&contract.address());
+
+    if is_json() {
+        print_json(&json!({
+            "to": to,
+            "function": fn_name,
+            "params": args.contract.params,
+            "calldata": calldata,
+        }));
+    }
+    else if is_csv() {
+        print_csv_row(&["to", "function", "params", "calldata"]);
+        print_csv_row(&[to, fn_name.to_owned(), args.contract.params.join(" "), calldata]);
+    }
+    else {
+        println!("{}", calldata);
+    }
+}
+
+/// Print signed raw transaction in hex along with its hash; only the raw transaction
+/// in text output.
+fn print_raw_tx(raw_tx: &[u8]) {
+    let raw_tx_hex = format!("0x{}", hex::encode(raw_tx));
+    let tx_hash = format!("{:?}", H256(keccak256(raw_tx)));
+    if is_json() {
+        print_json(&json!({ "raw_transaction": raw_tx_hex, "transaction_hash": tx_hash }));
+    }
+    else if is_csv() {
+        print_csv_row(&["raw_transaction", "transaction_hash"]);
+        print_csv_row(&[raw_tx_hex, tx_hash]);
+    }
+    else {
+        println!("{}", raw_tx_hex);
+    }
+}
+
+/// Convert unsigned transaction into JSON as accepted by `eth_sendTransaction`, or
+/// `eth_signTransaction`; quantities are in hex.
+fn unsigned_tx_json(tx: &UnsignedTx, from: &str) -> serde_json::Value {
+    let mut tx_json = json!({
+        "from": from,
+        "to": to_checksum_address(&tx.to),
+        "value": format!("{:#x}", tx.value),
+        "data": format!("0x{}", hex::encode(&tx.data)),
+        "gas": format!("{:#x}", tx.gas),
+        "nonce": format!("{:#x}", tx.nonce),
+        "chainId": format!("{:#x}", tx.chain_id),
+    });
+    match tx.eip1559_fees {
+        Some((max_fee_per_gas, max_priority_fee_per_gas)) => {
+            tx_json["type"] = json!("0x2");
+            tx_json["maxFeePerGas"] = json!(format!("{:#x}", max_fee_per_gas));
+            tx_json["maxPriorityFeePerGas"] = json!(format!("{:#x}", max_priority_fee_per_gas));
+        },
+        None => {
+            tx_json["type"] = json!("0x0");
+            tx_json["gasPrice"] = json!(format!("{:#x}", tx.gas_price));
+        },
+    }
+    tx_json
+}
+
+/// Get the address sending transaction of `send` subcommand; the external signer's,
+/// the secret key's, the unlocked account (first account of the node without --from),
+/// or the one of CRUNNER_SETTER_SECRETKEY.
+async fn send_sender(client: &CrunnerClient, signer: Option<&dyn ExternalSigner>, prvk: Option<&SecretKey>, is_unlocked: bool, from: Option<&str>) -> Result<String, CrunnerError> {
+    let address = if let Some(signer) = signer {
+        signer.address().await?
+    }
+    else if let Some(prvk) = prvk {
+        SecretKeyRef::new(prvk).address()
+    }
+    else if let (true, Some(from)) = (is_unlocked, from) {
+        get_address_from_str(from)?
+    }
+    else if is_unlocked {
+        let accounts = match client.web3().eth().accounts().await {
+            Ok(res) => res,
+            Err(e) => return Err(CrunnerError::RpcError(format!("Error querying accounts of the node; err={}", e))),
+        };
+        match accounts.first() {
+            Some(res) => *res,
+            None => return Err(CrunnerError::ConfigError("Error, the node has no unlocked account to send from".to_owned())),
+        }
+    }
+    else {
+        SecretKeyRef::new(&secret_key_from_env()?).address()
+    };
+    Ok(to_checksum_address(&address))
+}
+
+/// Summarize transaction receipt as JSON along with its emitted events decoded
+/// against `abi` (if any).
+fn receipt_json(tx_receipt: &TransactionReceipt, abi: Option<&ethabi::Contract>) -> serde_json::Value {
+    let events: Vec<serde_json::Value> = tx_receipt.logs.iter().map(|log| {
+        match abi.and_then(|abi| decode_log(abi, log)) {
+            Some(event) => json!({
+                "address": to_checksum_address(&log.address),
+                "event": event.name,
+                "params": named_params_json(&event.params),
+            }),
+            None => json!({
+                "address": to_checksum_address(&log.address),
+                "topics": log.topics.iter().map(|t| format!("{:?}", t)).collect::<Vec<String>>(),
+                "data": format!("0x{}", hex::encode(&log.data.0)),
+            }),
+        }
+    }).collect();
+    json!({
+        "transaction_hash": format!("{:?}", tx_receipt.transaction_hash),
+        "block_number": tx_receipt.block_number.map(|n| n.as_u64()),
+        "from": to_checksum_address(&tx_receipt.from),
+        "to": tx_receipt.to.map(|a| to_checksum_address(&a)),
+        "gas_used": tx_receipt.gas_used.map(|g| g.to_string()),
+        "effective_gas_price": tx_receipt.effective_gas_price.map(|g| g.to_string()),
+        "status": tx_receipt.status.map(|st| st.as_u64()),
+        "events": events,
+    })
+}
+
+/// Convert parameters of decoded event, or call into JSON object keyed by their names,
+/// or positions if unnamed.
+fn named_params_json(params: &[(String, Token)]) -> serde_json::Value {
+    let params: serde_json::Map<String, serde_json::Value> = params.iter().enumerate()
+        .map(|(i, (name, token))| (if name.is_empty() { i.to_string() } else { name.to_owned() }, token_to_json(token)))
+        .collect();
+    serde_json::Value::Object(params)
+}
+
+/// Format event log of transaction receipt as emitting contract address followed by
+/// the event decoded against `abi`, or its raw topics, and data if it can't be decoded.
+fn format_receipt_log(log: &Log, abi: Option<&ethabi::Contract>) -> String {
+    match abi.and_then(|abi| decode_log(abi, log)) {
+        Some(event) => format!("{} {}", to_checksum_address(&log.address), format_event(&event)),
+        None => {
+            let topics: Vec<String> = log.topics.iter().map(|t| format!("{:?}", t)).collect();
+            format!("{} {} 0x{}", to_checksum_address(&log.address), topics.join(","), hex::encode(&log.data.0))
+        },
+    }
+}
+
+/// Print transaction hash followed by its emitted events one per line; only the
+/// hash in quiet mode.
+fn print_receipt(tx_receipt: &TransactionReceipt, abi: Option<&ethabi::Contract>) {
+    println!("{:?}", tx_receipt.transaction_hash);
+    if is_quiet() {
+        return;
+    }
+    for log in tx_receipt.logs.iter() {
+        println!("{}", format_receipt_log(log, abi));
+    }
+}
+
+/// Handle `tx` subcommand; speed up, or cancel a pending transaction.
+async fn handle_tx(client: &CrunnerClient, args: TxArgs) {
+    let (args, cancel) = match args.command {
+        TxCommand::Speedup(args) => (args, false),
+        TxCommand::Cancel(args) => (args, true),
+    };
+    let tx_hash = match H256::from_str(args.tx_hash.trim_start_matches("0x")) {
+        Ok(res) => res,
+        Err(e) => exit_with_error(CrunnerError::InvalidArgument(format!("Error parsing transaction hash '{}'; err={}", args.tx_hash, e))),
+    };
+    let bump_percent = match args.bump.trim_end_matches('%').parse::<u64>() {
+        Ok(res) => res,
+        Err(e) => exit_with_error(CrunnerError::InvalidArgument(format!("Error parsing --bump '{}'; err={}", args.bump, e))),
+    };
+    let prvk = match load_signing_key(&args.key) {
+        Some(res) => res,
+        None => match secret_key_from_env() {
+            Ok(res) => res,
+            Err(e) => exit_with_error(e),
+        },
+    };
+
+    let is_local = matches!(client.chain(), ChainType::Local);
+    let confirmations = args.block_confirmations.unwrap_or(if is_local { 0 } else { 20 });
+    let tx_receipt_res = if cancel {
+        client.cancel_transaction(tx_hash, bump_percent, &prvk, confirmations).await
+    }
+    else {
+        client.speed_up_transaction(tx_hash, bump_percent, &prvk, confirmations).await
+    };
+    clear_progress();
+    let tx_receipt = match tx_receipt_res {
+        Ok(res) => res,
+        Err(e) => exit_with_error(e.context(format!("Error {} transaction {:?}", if cancel { "cancelling" } else { "speeding up" }, tx_hash))),
+    };
+    if is_json() {
+        print_json(&receipt_json(&tx_receipt, None));
+    }
+    else {
+        print_receipt(&tx_receipt, None);
+    }
+}
+
+/// Build transaction options from command line arguments, otherwise exit the program.
+fn tx_options(value: Option<&str>) -> Options {
+    let mut options = Options::default();
+    if let Some(value) = value {
+        options.value = match parse_amount(value) {
+            Ok(res) => Some(res),
+            Err(e) => exit_with_error(e.context("Error parsing --value")),
+        };
+    }
+    options
+}
+
+/// Parse gas price, or fee of the specified flag.
+fn parse_gas_price_arg(value: &str, flag: &str) -> Result<U256, CrunnerError> {
+    parse_gas_price(value).map_err(|e| e.context(format!("Error parsing {}", flag)))
+}
+
+/// Set gas price, or fees of EIP-1559 transaction in `options`. Legacy transaction is
+/// sent if `gas_price` is set. EIP-1559 transaction is sent if either `max_fee`, or
+/// `max_priority_fee` is set, or by default on chains supporting it; the one not set
+/// is determined by the client's gas strategy.
+///
+/// # Arguments
+/// * `client` - client to determine fees with
+/// * `options` - transaction options to set fees in
+/// * `gas_price` - value of --gas-price (if any)
+/// * `max_fee` - value of --max-fee (if any)
+/// * `max_priority_fee` - value of --max-priority-fee (if any)
+async fn set_fee_options(client: &CrunnerClient, options: &mut Options, gas_price: Option<&str>, max_fee: Option<&str>, max_priority_fee: Option<&str>) -> Result<(), CrunnerError> {
+    if let Some(gas_price) = gas_price {
+        options.gas_price = Some(parse_gas_price_arg(gas_price, "--gas-price")?);
+        return Ok(());
+    }
+
+    let max_fee = max_fee.map(|v| parse_gas_price_arg(v, "--max-fee")).transpose()?;
+    let max_priority_fee = max_priority_fee.map(|v| parse_gas_price_arg(v, "--max-priority-fee")).transpose()?;
+    let fees = match (max_fee, max_priority_fee) {
+        (Some(_), Some(_)) => None,
+        _ => match client.gas_fees().await {
+            Ok(res) => Some(res),
+            Err(e) => return Err(e.context("Error determining gas fees; set --max-fee, and --max-priority-fee, or --gas-price")),
+        },
+    };
+    let has_eip1559_fees = fees.is_some_and(|f| f.max_fee.is_some());
+    if max_fee.is_some() || max_priority_fee.is_some() || (has_eip1559_fees && supports_eip1559_by_default(client.chain())) {
+        let (max_fee, max_priority_fee) = match fees {
+            Some(fees) => {
+                let suggested_max_fee = fees.max_fee.unwrap_or(fees.gas_price);
+                let suggested_priority_fee = fees.max_priority_fee.unwrap_or(fees.gas_price);
+                let max_priority_fee = max_priority_fee.unwrap_or(suggested_priority_fee);
+                (max_fee.unwrap_or(suggested_max_fee - suggested_priority_fee + max_priority_fee), max_priority_fee)
+            },
+            None => (max_fee.unwrap_or_default(), max_priority_fee.unwrap_or_default()),
+        };
+        if max_priority_fee > max_fee {
+            return Err(CrunnerError::InvalidArgument("Error, --max-priority-fee cannot be greater than --max-fee".to_owned()));
+        }
+        options.transaction_type = Some(2.into());
+        options.max_fee_per_gas = Some(max_fee);
+        options.max_priority_fee_per_gas = Some(max_priority_fee);
+    }
+    else if let Some(fees) = fees {
+        options.gas_price = Some(fees.gas_price);
+    }
+    Ok(())
+}
+
+/// Create external signer as specified in `args`, otherwise exit the program.
+/// Return `None` if not specified, or the node signs the transaction itself.
+async fn create_external_signer(args: &TransactionArgs) -> Option<Box<dyn ExternalSigner>> {
+    match args.signer {
+        Some(SignerType::Ledger) => Some(create_ledger_signer(&args.key.hd_path)),
+        Some(SignerType::Trezor) => Some(create_trezor_signer(&args.key.hd_path)),
+        Some(SignerType::Kms) => Some(create_kms_signer(args.kms_key_id.as_deref().unwrap_or_default()).await),
+        Some(SignerType::Node) | None => None,
+    }
+}
+
+/// Connect to Ledger device, otherwise exit the program.
+#[cfg(feature = "ledger")]
+fn create_ledger_signer(hd_path: &str) -> Box<dyn ExternalSigner> {
+    match crunner::ledger::LedgerSigner::new(hd_path) {
+        Ok(res) => Box::new(res),
+        Err(e) => exit_with_error(e),
+    }
+}
+
+/// Ledger support is not compiled in; exit the program.
+#[cfg(not(feature = "ledger"))]
+fn create_ledger_signer(_hd_path: &str) -> Box<dyn ExternalSigner> {
+    exit_with_error(CrunnerError::ConfigError("Error, crunner is built without Ledger support; rebuild with --features ledger".to_owned()))
+}
+
+/// Connect to Trezor device, otherwise exit the program.
+#[cfg(feature = "trezor")]
+fn create_trezor_signer(hd_path: &str) -> Box<dyn ExternalSigner> {
+    match crunner::trezor::TrezorSigner::new(hd_path) {
+        Ok(res) => Box::new(res),
+        Err(e) => exit_with_error(e),
+    }
+}
+
+/// Trezor support is not compiled in; exit the program.
+#[cfg(not(feature = "trezor"))]
+fn create_trezor_signer(_hd_path: &str) -> Box<dyn ExternalSigner> {
+    exit_with_error(CrunnerError::ConfigError("Error, crunner is built without Trezor support; rebuild with --features trezor".to_owned()))
+}
+
+/// Create signer with AWS KMS key, otherwise exit the program.
+#[cfg(feature = "kms")]
+async fn create_kms_signer(key_id: &str) -> Box<dyn ExternalSigner> {
+    match crunner::kms::KmsSigner::new(key_id).await {
+        Ok(res) => Box::new(res),
+        Err(e) => exit_with_error(e),
+    }
+}
+
+/// AWS KMS support is not compiled in; exit the program.
+#[cfg(not(feature = "kms"))]
+async fn create_kms_signer(_key_id: &str) -> Box<dyn ExternalSigner> {
+    exit_with_error(CrunnerError::ConfigError("Error, crunner is built without AWS KMS support; rebuild with --features kms".to_owned()))
+}
+
+/// Load secret key to sign transaction with from keystore, OS secret store, private
+/// key file, or mnemonic phrase as specified in `args`, otherwise exit the program.
+/// Return `None` if none is specified.
+fn load_signing_key(args: &SigningKeyArgs) -> Option<SecretKey> {
+    if let Some(profile) = &args.from_profile {
+        return match secret_key_from_keyring(profile) {
+            Ok(res) => Some(res),
+            Err(e) => exit_with_error(e),
+        };
+    }
+
+    if let Some(private_key_file) = &args.private_key_file {
+        if std::env::var("CRUNNER_SETTER_SECRETKEY").is_ok() {
+            exit_with_error(CrunnerError::InvalidArgument("Error, --private-key-file cannot be used while CRUNNER_SETTER_SECRETKEY is set".to_owned()));
+        }
+        return match secret_key_from_file(Path::new(private_key_file)) {
+            Ok(res) => Some(res),
+            Err(e) => exit_with_error(e),
+        };
+    }
+
+    if let Some(keystore) = &args.keystore {
+        return match secret_key_from_keystore(Path::new(keystore), &keystore_password()) {
+            Ok(res) => Some(res),
+            Err(e) => exit_with_error(e),
+        };
+    }
+
+    let phrase = if let Some(mnemonic_env) = &args.mnemonic_env {
+        match std::env::var(mnemonic_env) {
+            Ok(res) => Zeroizing::new(res),
+            Err(_) => exit_with_error(CrunnerError::ConfigError(format!("Error, '{}' environment variable is required for --mnemonic-env", mnemonic_env))),
+        }
+    }
+    else if let Some(mnemonic_file) = &args.mnemonic_file {
+        match std::fs::read_to_string(mnemonic_file) {
+            Ok(res) => Zeroizing::new(res),
+            Err(e) => exit_with_error(CrunnerError::ConfigError(format!("Error reading mnemonic file {}; err={}", mnemonic_file, e))),
+        }
+    }
+    else {
+        return None;
+    };
+
+    let passphrase = mnemonic_passphrase();
+    match secret_key_from_mnemonic(&phrase, &args.hd_path, passphrase.as_deref().map(String::as_str)) {
+        Ok(res) => Some(res),
+        Err(e) => exit_with_error(e),
+    }
+}
+
+/// Get BIP-39 passphrase from CRUNNER_MNEMONIC_PASSPHRASE environment variable (if any).
+fn mnemonic_passphrase() -> Option<Zeroizing<String>> {
+    std::env::var("CRUNNER_MNEMONIC_PASSPHRASE").ok().map(Zeroizing::new)
+}
+
+/// Get password of keystore from CRUNNER_KEYSTORE_PASSWORD environment variable,
+/// otherwise prompt for it interactively; only once per run as the keystore may be
+/// decrypted more than once i.e. `permit --send`.
+fn keystore_password() -> Zeroizing<String> {
+    if let Ok(password) = std::env::var("CRUNNER_KEYSTORE_PASSWORD") {
+        return Zeroizing::new(password);
+    }
+    let password = KEYSTORE_PASSWORD.get_or_init(|| match rpassword::prompt_password("Keystore password: ") {
+        Ok(res) => Zeroizing::new(res),
+        Err(e) => exit_with_error(CrunnerError::ConfigError(format!("Error reading keystore password; err={}", e))),
+    });
+    password.clone()
+}
+
+/// Handle `history` subcommand; list recorded operations matching the filters.
+fn handle_history(cmd_args: &CommandlineArgs, args: &HistoryArgs) {
+    let filepath = match History::default_filepath() {
+        Some(res) => res,
+        None => exit_with_error(CrunnerError::ConfigError("Error, cannot locate home directory of history".to_owned())),
+    };
+    let since = match &args.since {
+        Some(since) => match parse_history_since(since) {
+            Ok(res) => Some(res),
+            Err(e) => exit_with_error(e),
+        },
+        None => None,
+    };
+    let filter = HistoryFilter {
+        chains: cmd_args.chains.clone(),
+        kind: args.kind.as_deref().map(|kind| if kind == "send" { HistoryKind::Send } else { HistoryKind::Call }),
+        address: args.address.clone(),
+        function: args.fn_name.clone(),
+        status: args.status.clone(),
+        since,
+        limit: if args.limit == 0 { None } else { Some(args.limit) },
+    };
+
+    let records = match History::open(&filepath).and_then(|history| history.list(&filter)) {
+        Ok(res) => res,
+        Err(e) => exit_with_error(e),
+    };
+    if is_json() {
+        let records_json: Vec<serde_json::Value> = records.iter().map(|record| json!({
+            "id": record.id,
+            "timestamp": record.timestamp,
+            "chain": record.chain,
+            "kind": record.kind,
+            "address": record.address,
+            "function": record.function,
+            "params": record.params,
+            "result": record.result,
+            "transaction_hash": record.tx_hash,
+            "gas_used": record.gas_used,
+            "status": record.status,
+            "error": record.error,
+        })).collect();
+        print_json(&serde_json::Value::Array(records_json));
+    }
+    else if is_csv() {
+        print_csv_row(&["id", "timestamp", "chain", "kind", "address", "function", "params", "result", "transaction_hash", "gas_used", "status", "error"]);
+        for record in records {
+            print_csv_row(&[
+                record.id.to_string(),
+                record.timestamp,
+                record.chain,
+                record.kind,
+                record.address,
+                record.function,
+                record.params.join(" "),
+                record.result.unwrap_or_default(),
+                record.tx_hash.unwrap_or_default(),
+                record.gas_used.unwrap_or_default(),
+                record.status,
+                record.error.unwrap_or_default(),
+            ]);
+        }
+    }
+    else {
+        for record in records.iter() {
+            println!("#{} {} {} {} {} {}({}): {}", record.id, record.timestamp, record.chain, record.kind, record.address, record.function, record.params.join(", "), format_history_outcome(record));
+        }
+    }
+}
+
+/// Parse value of --since of `history` subcommand into time in RFC 3339 format;
+/// either duration before now i.e. 7d, or time in RFC 3339 format.
+fn parse_history_since(since: &str) -> Result<String, CrunnerError> {
+    if let Ok(duration) = humantime::parse_duration(since) {
+        return match std::time::SystemTime::now().checked_sub(duration) {
+            Some(res) => Ok(humantime::format_rfc3339_seconds(res).to_string()),
+            None => Err(CrunnerError::InvalidArgument(format!("Error, --since duration is too long; value={}", since))),
+        };
+    }
+    match humantime::parse_rfc3339_weak(since) {
+        Ok(res) => Ok(humantime::format_rfc3339_seconds(res).to_string()),
+        Err(_) => Err(CrunnerError::InvalidArgument(format!("Error, --since must be a duration i.e. 7d, or time in RFC 3339 format; value={}", since))),
+    }
+}
+
+/// Format outcome of recorded operation; returned value of getter, or hash of
+/// sent transaction along with its status, and gas used.
+fn format_history_outcome(record: &HistoryRecord) -> String {
+    match (&record.result, &record.tx_hash, &record.error) {
+        (Some(result), _, _) => result.to_owned(),
+        (None, Some(tx_hash), _) => match &record.gas_used {
+            Some(gas_used) => format!("{} {} (gas used {})", tx_hash, record.status, gas_used),
+            None => format!("{} {}", tx_hash, record.status),
+        },
+        (None, None, Some(error)) => format!("{}: {}", record.status, error),
+        (None, None, None) => record.status.to_owned(),
+    }
+}
+
+/// Load environment variables from the dotenv file set via `--env-file` (if any), then
+/// from .env in the working directory if it exists. Neither overrides variables which
+/// are already set, so the former takes precedence over the latter.
+///
+/// # Arguments
+/// * `env_file` - filepath of dotenv file set via `--env-file`
+fn load_env_files(env_file: Option<&str>) -> Result<(), CrunnerError> {
+    if let Some(env_file) = env_file {
+        if let Err(e) = dotenvy::from_filename(env_file) {
+            return Err(CrunnerError::ConfigError(format!("Error loading env file {}; err={}", env_file, e)));
+        }
+    }
+
+    match dotenvy::dotenv() {
+        Ok(_) => Ok(()),
+        Err(e) if e.not_found() => Ok(()),
+        Err(e) => Err(CrunnerError::ConfigError(format!("Error loading .env; err={}", e))),
+    }
+}
+
+/// Handle `completions` subcommand; print completion script of the shell. Completion
+/// script is static, so chains (including user-defined ones), and function names of
+/// the built-in ABI known at the time of generation are suggested.
+fn handle_completions(args: &CompletionsArgs) {
+    // broken chains.toml only means user-defined chains are not suggested
+    if let Err(e) = load_chain_registry(None) {
+        tracing::warn!(error = %e, "user-defined chains are not included in completions");
+    }
+    let mut chains: Vec<String> = MAINNET_CHAINS.iter().chain(TESTNET_CHAINS.iter()).map(|c| c.to_string()).collect();
+    chains.extend(chain_registry().iter().map(|c| c.name.to_owned()));
+
+    let mut fn_names: Vec<String> = match load_abi(None) {
+        Ok(abi) => abi.functions().map(|f| f.name.to_owned()).collect(),
+        Err(_) => Vec::new(),
+    };
+    fn_names.dedup();
+
+    let mut cmd = CommandlineArgs::command()
+        .mut_arg("chains", |arg| arg.possible_values(chains.iter().map(|c| c.as_str())));
+    let contract_subcommands: Vec<String> = cmd.get_subcommands()
+        .filter(|subcmd| subcmd.get_arguments().any(|arg| arg.get_id() == "fn-name"))
+        .map(|subcmd| subcmd.get_name().to_owned())
+        .collect();
+    for name in contract_subcommands.iter() {
+        cmd = cmd.mut_subcommand(name.as_str(), |subcmd| subcmd.mut_arg("fn-name", |arg| arg.possible_values(fn_names.iter().map(|f| f.as_str()))));
+    }
+
+    clap_complete::generate(args.shell, &mut cmd, "crunner", &mut std::io::stdout());
+}
+
+/// Handle `key` subcommand; manage signing keys stored in OS secret store.
+fn handle_key(args: KeyArgs) {
+    match args.command {
+        KeyCommand::Import(args) => {
+            let secret_key = match SecretKey::from_str(read_secret_key_input().trim().trim_start_matches("0x")) {
+                Ok(res) => res,
+                Err(e) => exit_with_error(CrunnerError::InvalidArgument(format!("Error parsing private key; err={}", e))),
+            };
+            if let Err(e) = import_key_to_keyring(&args.profile, &secret_key) {
+                exit_with_error(e);
+            }
+
+            let address = to_checksum_address(&SecretKeyRef::new(&secret_key).address());
+            if is_json() {
+                print_json(&json!({ "profile": args.profile, "address": address }));
+            }
+            else {
+                println!("{}", address);
+            }
+        },
+        KeyCommand::Remove(args) => {
+            if let Err(e) = remove_key_from_keyring(&args.profile) {
+                exit_with_error(e);
+            }
+            if is_json() {
+                print_json(&json!({ "profile": args.profile, "removed": true }));
+            }
+        },
+    }
+}
+
+/// Read private key to import; prompt interactively, or read from stdin if piped.
+fn read_secret_key_input() -> Zeroizing<String> {
+    if std::io::stdin().is_terminal() {
+        return match rpassword::prompt_password("Private key: ") {
+            Ok(res) => Zeroizing::new(res),
+            Err(e) => exit_with_error(CrunnerError::InvalidArgument(format!("Error reading private key; err={}", e))),
+        };
+    }
+
+    let mut input = Zeroizing::new(String::new());
+    match std::io::stdin().read_line(&mut input) {
+        Ok(_) => input,
+        Err(e) => exit_with_error(CrunnerError::InvalidArgument(format!("Error reading private key from stdin; err={}", e))),
+    }
+}
+
+/// Handle `trace-call` subcommand; trace call to method, and print its internal call tree.
+async fn handle_trace_call(client: &CrunnerClient, args: TraceCallArgs) {
+    let contract = prepare_contract(client, &args.contract).await;
+    let fn_name = &args.contract.fn_name();
+
+    let frame = match client.trace_call(&contract, fn_name, args.contract.params.as_slice(), args.from.as_deref(), tx_options(args.value.as_deref())).await {
+        Ok(res) => res,
+        Err(e) => exit_with_error(e.context(format!("Error tracing call to method '{}'", fn_name))),
+    };
+
+    if is_json() {
+        match serde_json::to_value(&frame) {
+            Ok(res) => print_json(&res),
+            Err(e) => exit_with_error(CrunnerError::RpcError(format!("Error serializing trace; err={}", e))),
+        }
+        return;
+    }
+
+    let abi = contract.abi();
+    if is_csv() {
+        print_csv_row(&["depth", "type", "from", "to", "function", "value", "gas", "gas_used", "error"]);
+        let mut frames = vec![(0, &frame)];
+        while let Some((depth, frame)) = frames.pop() {
+            print_csv_row(&[
+                depth.to_string(),
+                frame.call_type.to_owned(),
+                format!("{:?}", frame.from),
+                frame.to.map(|to| format!("{:?}", to)).unwrap_or_default(),
+                call_frame_function(frame, abi),
+                frame.value.unwrap_or_default().to_string(),
+                frame.gas.to_string(),
+                frame.gas_used.to_string(),
+                call_frame_error(frame, abi).unwrap_or_default(),
+            ]);
+            frames.extend(frame.calls.iter().rev().map(|call| (depth + 1, call)));
+        }
+        return;
+    }
+
+    print_call_frame(&frame, abi, "", "");
+}
+
+/// Get signature of the function called in the frame if declared in ABI; its
+/// selector otherwise.
+fn call_frame_function(frame: &CallFrame, abi: &ethabi::Contract) -> String {
+    match function_signature_of(abi, &frame.input.0) {
+        Some(res) => res,
+        None if frame.input.0.len() >= 4 => format!("0x{}", hex::encode(&frame.input.0[..4])),
+        None => String::new(),
+    }
+}
+
+/// Get error of the frame along with decoded revert reason (if any).
+fn call_frame_error(frame: &CallFrame, abi: &ethabi::Contract) -> Option<String> {
+    let error = frame.error.as_ref()?;
+    let reason = frame.output.as_ref()
+        .and_then(|output| decode_revert_reason(&output.0, Some(abi)))
+        .or_else(|| frame.revert_reason.clone());
+    match reason {
+        Some(reason) => Some(format!("{}: {}", error, reason)),
+        None => Some(error.to_owned()),
+    }
+}
+
+/// Print the call frame, and its nested calls as a tree.
+fn print_call_frame(frame: &CallFrame, abi: &ethabi::Contract, prefix: &str, child_prefix: &str) {
+    let mut fields = vec![frame.call_type.to_owned()];
+    if let Some(to) = frame.to {
+        fields.push(to_checksum_address(&to));
+    }
+    let function = call_frame_function(frame, abi);
+    if !function.is_empty() {
+        fields.push(function);
+    }
+    match frame.value {
+        Some(value) if !value.is_zero() => fields.push(format!("value={}", value)),
+        _ => (),
+    }
+    fields.push(format!("gas_used={}", frame.gas_used));
+    if let Some(error) = call_frame_error(frame, abi) {
+        fields.push(format!("REVERTED: {}", error));
+    }
+    println!("{}{}", prefix, fields.join(" "));
+
+    for (i, call) in frame.calls.iter().enumerate() {
+        let is_last = i + 1 == frame.calls.len();
+        let (branch, indent) = if is_last { ("└─ ", "   ") } else { ("├─ ", "│  ") };
+        print_call_frame(call, abi, &format!("{}{}", child_prefix, branch), &format!("{}{}", child_prefix, indent));
+    }
+}
+
+/// Handle `multicall` subcommand; aggregate calls to getter methods into a single
+/// call via Multicall3.
+async fn handle_multicall(client: &CrunnerClient, args: MulticallArgs) {
+    // contract instance of each distinct address; its ABI is loaded once
+    let mut contracts: HashMap<String, Contract<CrunnerTransport>> = HashMap::new();
+    let mut calls: Vec<(Contract<CrunnerTransport>, String, Vec<String>)> = Vec::new();
+    for call in args.calls.iter() {
+        let mut parts = call.split_whitespace();
+        let (address, function) = match (parts.next(), parts.next()) {
+            (Some(address), Some(function)) => (address, function),
+            _ => exit_with_error(CrunnerError::InvalidArgument(format!("Error, call '{}' requires contract address, and function name or signature", call))),
+        };
+        let params: Vec<String> = parts.map(|p| p.to_owned()).collect();
+
+        if !contracts.contains_key(address) {
+            let contract_res = if args.abi_from_explorer {
+                client.contract_from_explorer(address).await
+            }
+            else {
+                client.contract(address, args.abi_filepath.as_deref())
+            };
+            match contract_res {
+                Ok(res) => contracts.insert(address.to_owned(), res),
+                Err(e) => exit_with_error(e.context(format!("Error creating a contract instance of {}", address))),
+            };
+        }
+
+        // select the overload to call in case of overloaded functions
+        let (fn_name, fn_sig) = match function.split_once('(') {
+            Some((fn_name, _)) => (fn_name.trim(), Some(function)),
+            None => (function, None),
+        };
+        match client.select_overload(&contracts[address], fn_name, fn_sig, params.as_slice()) {
+            Ok(res) => calls.push((res, fn_name.to_owned(), params)),
+            Err(e) => exit_with_error(e),
+        }
+    }
+
+    let call_refs: Vec<(&Contract<CrunnerTransport>, &str, &[String])> = calls.iter().map(|(contract, fn_name, params)| (contract, fn_name.as_str(), params.as_slice())).collect();
+    let results = match client.multicall(call_refs.as_slice(), !args.require_success).await {
+        Ok(res) => res,
+        Err(e) => exit_with_error(e.context("Error making multicall")),
+    };
+
+    let failure = aggregate_failure(results.iter().filter_map(|r| r.as_ref().err()), calls.len(), "calls");
+    if is_json() {
+        let results_json: Vec<serde_json::Value> = calls.iter().zip(results.iter()).map(|((contract, fn_name, params), result)| match result {
+            Ok(outputs) => json!({
+                "address": to_checksum_address(&contract.address()),
+                "function": fn_name,
+                "params": params,
+                "success": true,
+                "result": outputs_json(outputs),
+            }),
+            Err(e) => json!({
+                "address": to_checksum_address(&contract.address()),
+                "function": fn_name,
+                "params": params,
+                "success": false,
+                "error": e.to_string(),
+            }),
+        }).collect();
+        print_json(&serde_json::Value::Array(results_json));
+    }
+    else if is_csv() {
+        print_csv_row(&["timestamp", "address", "function", "params", "success", "value"]);
+        let timestamp = timestamp_now();
+        for ((contract, fn_name, params), result) in calls.iter().zip(results) {
+            let (success, value) = match result {
+                Ok(outputs) => (true, format_tokens(outputs.into_iter().map(|(_, token)| token).collect())),
+                Err(e) => (false, e.to_string()),
+            };
+            print_csv_row(&[timestamp.clone(), to_checksum_address(&contract.address()), fn_name.to_owned(), params.join(" "), success.to_string(), value]);
+        }
+    }
+    else {
+        for ((contract, fn_name, params), result) in calls.iter().zip(results) {
+            let value = match result {
+                Ok(outputs) => format_tokens(outputs.into_iter().map(|(_, token)| token).collect()),
+                // keep one line per call so that the output lines up with the calls
+                Err(_) if is_quiet() => String::new(),
+                Err(e) => e.to_string(),
+            };
+            if is_quiet() {
+                println!("{}", value);
+            }
+            else {
+                println!("{} {}({}): {}", to_checksum_address(&contract.address()), fn_name, params.join(", "), value);
+            }
+        }
+    }
+
+    if let Some(e) = failure {
+        exit_with_error(e);
+    }
+}
+
+/// Result of executing an entry of batch file
+struct BatchOutcome {
+    /// Result formatted as text
+    value: String,
+
+    /// Result as JSON
+    json: serde_json::Value,
+}
+
+/// Handle `batch` subcommand; execute calls specified in batch file, and print the
+/// result of each.
+async fn handle_batch(cmd_args: &CommandlineArgs, args: &BatchArgs) {
+    if args.concurrency == 0 {
+        exit_with_error(CrunnerError::InvalidArgument("Error, --concurrency must be greater than 0".to_owned()));
+    }
+    let entries = match load_batch_file(&args.file) {
+        Ok(res) => res,
+        Err(e) => exit_with_error(e),
+    };
+
+    // chain of each entry; --chain if not set
+    if cmd_args.all_chains || cmd_args.chains.len() > 1 {
+        exit_with_error(CrunnerError::InvalidArgument("Error, batch subcommand accepts only a single --chain as default chain of entries".to_owned()));
+    }
+    let default_chain = cmd_args.chains.first().map(|c| c.to_lowercase());
+    let mut entry_chains: Vec<String> = Vec::new();
+    for (i, entry) in entries.iter().enumerate() {
+        match entry.chain.as_deref().map(|c| c.to_lowercase()).or_else(|| default_chain.clone()) {
+            Some(res) => entry_chains.push(res),
+            None => exit_with_error(CrunnerError::InvalidArgument(format!("Error, entry #{} of batch file requires chain, or --chain to be set", i + 1))),
+        }
+    }
+
+    // client of each chain involved; --rpc-url, and --rpc-header apply only to --chain
+    let mut clients: HashMap<String, CrunnerClient> = HashMap::new();
+    for chain_value in entry_chains.iter() {
+        if clients.contains_key(chain_value) {
+            continue;
+        }
+        let client = create_client(parse_chain(chain_value), cmd_args, Some(chain_value) == default_chain.as_ref());
+        if !cmd_args.skip_chain_check {
+            if let Err(e) = client.verify_chain_id().await {
+                exit_with_error(e.context(format!("Error verifying chain '{}'", chain_value)));
+            }
+        }
+        clients.insert(chain_value.to_owned(), client);
+    }
+
+    // consecutive call entries are sent together in batch requests per chain; others
+    // run concurrently as usual, with each run finished before the next one starts
+    let is_call = |entry: &BatchEntry| entry.mode.unwrap_or(BatchMode::Call) == BatchMode::Call;
+    let mut results: Vec<Result<BatchOutcome, CrunnerError>> = Vec::new();
+    let mut start = 0;
+    while start < entries.len() {
+        let end = entries[start..].iter().position(|entry| is_call(entry) != is_call(&entries[start])).map_or(entries.len(), |i| start + i);
+        let run: Vec<(&BatchEntry, &String)> = entries[start..end].iter().zip(entry_chains[start..end].iter()).collect();
+        if is_call(&entries[start]) {
+            results.extend(run_batch_calls(&clients, run.as_slice(), args.concurrency).await);
+        }
+        else {
+            let run_results: Vec<Result<BatchOutcome, CrunnerError>> = futures::stream::iter(run)
+                .map(|(entry, chain_value)| run_batch_entry(&clients[chain_value], entry))
+                .buffered(args.concurrency)
+                .collect()
+                .await;
+            results.extend(run_results);
+        }
+        start = end;
+    }
+    clear_progress();
+
+    let mode_of = |entry: &BatchEntry| entry.mode.unwrap_or(BatchMode::Call).name();
+    let failure = aggregate_failure(results.iter().filter_map(|r| r.as_ref().err()), entries.len(), "entries");
+    if is_json() {
+        let results_json: Vec<serde_json::Value> = entries.iter().zip(entry_chains.iter()).zip(results.iter()).enumerate().map(|(i, ((entry, chain_value), result))| {
+            let mut entry_json = json!({
+                "index": i + 1,
+                "chain": chain_value,
+                "address": entry.address,
+                "function": entry.function,
+                "params": entry.params,
+                "mode": mode_of(entry),
+                "success": result.is_ok(),
+            });
+            match result {
+                Ok(outcome) => entry_json["result"] = outcome.json.clone(),
+                Err(e) => entry_json["error"] = json!(e.to_string()),
+            }
+            entry_json
+        }).collect();
+        print_json(&serde_json::Value::Array(results_json));
+    }
+    else if is_csv() {
+        print_csv_row(&["timestamp", "index", "chain", "address", "function", "params", "mode", "success", "value"]);
+        let timestamp = timestamp_now();
+        for (i, ((entry, chain_value), result)) in entries.iter().zip(entry_chains.iter()).zip(results.iter()).enumerate() {
+            let value = match result {
+                Ok(outcome) => outcome.value.clone(),
+                Err(e) => e.to_string(),
+            };
+            print_csv_row(&[timestamp.clone(), (i + 1).to_string(), chain_value.to_owned(), entry.address.to_owned(), entry.function.to_owned(), entry.params.join(" "), mode_of(entry).to_owned(), result.is_ok().to_string(), value]);
+        }
+    }
+    else {
+        for (i, ((entry, chain_value), result)) in entries.iter().zip(entry_chains.iter()).zip(results.iter()).enumerate() {
+            let value = match result {
+                Ok(outcome) => outcome.value.clone(),
+                // keep one line per entry so that the output lines up with the entries
+                Err(_) if is_quiet() => String::new(),
+                Err(e) => e.to_string(),
+            };
+            if is_quiet() {
+                println!("{}", value);
+            }
+            else {
+                println!("#{} {} {} {} {}({}): {}", i + 1, chain_value, mode_of(entry), entry.address, entry.function, entry.params.join(", "), value);
+            }
+        }
+    }
+
+    if let Some(e) = failure {
+        exit_with_error(e);
+    }
+}
+
+/// Execute call entries of batch file with calls on the same chain sent together in
+/// batch requests.
+async fn run_batch_calls(clients: &HashMap<String, CrunnerClient>, entries: &[(&BatchEntry, &String)], concurrency: usize) -> Vec<Result<BatchOutcome, CrunnerError>> {
+    let prepared: Vec<_> = futures::stream::iter(entries.iter())
+        .map(|(entry, chain_value)| prepare_batch_entry(&clients[*chain_value], entry))
+        .buffered(concurrency)
+        .collect()
+        .await;
+
+    let mut results: Vec<Option<Result<BatchOutcome, CrunnerError>>> = Vec::new();
+    let mut contracts: Vec<(usize, Contract<CrunnerTransport>, Vec<String>)> = Vec::new();
+    for (i, prepared) in prepared.into_iter().enumerate() {
+        match prepared {
+            Ok((contract, params)) => {
+                contracts.push((i, contract, params));
+                results.push(None);
+            },
+            Err(e) => results.push(Some(Err(e))),
+        }
+    }
+
+    let mut chain_values: Vec<&String> = entries.iter().map(|(_, chain_value)| *chain_value).collect();
+    chain_values.sort();
+    chain_values.dedup();
+    for chain_value in chain_values {
+        let chain_contracts: Vec<&(usize, Contract<CrunnerTransport>, Vec<String>)> = contracts.iter().filter(|(i, _, _)| entries[*i].1 == chain_value).collect();
+        let calls: Vec<(&Contract<CrunnerTransport>, &str, &[String])> = chain_contracts.iter()
+            .map(|(i, contract, params)| (contract, batch_fn_name(entries[*i].0), params.as_slice()))
+            .collect();
+        let outputs = clients[chain_value].call_many(calls.as_slice(), None).await;
+        for ((i, _, _), outputs) in chain_contracts.iter().zip(outputs) {
+            results[*i] = Some(outputs.map(call_outcome).map_err(|e| e.context(format!("Error querying of method '{}'", batch_fn_name(entries[*i].0)))));
+        }
+    }
+    results.into_iter().map(|result| result.unwrap_or_else(|| Err(CrunnerError::RpcError("Error, no result of the call".to_owned())))).collect()
+}
+
+/// Create contract instance of an entry of batch file with its overload of the
+/// function selected, along with its resolved parameters.
+async fn prepare_batch_entry(client: &CrunnerClient, entry: &BatchEntry) -> Result<(Contract<CrunnerTransport>, Vec<String>), CrunnerError> {
+    let address = client.resolve_address(&entry.address).await?;
+    let params = client.resolve_params(entry.params.as_slice()).await?;
+    let contract = client.contract(&address, entry.abi.as_deref())?;
+
+    // select the overload to call in case of overloaded functions
+    let contract = client.select_overload(&contract, batch_fn_name(entry), batch_fn_sig(entry), params.as_slice())?;
+    Ok((contract, params))
+}
+
+/// Execute an entry of batch file.
+async fn run_batch_entry(client: &CrunnerClient, entry: &BatchEntry) -> Result<BatchOutcome, CrunnerError> {
+    let (contract, params) = prepare_batch_entry(client, entry).await?;
+    let fn_name = batch_fn_name(entry);
+
+    let mut options = Options::default();
+    if let Some(value) = &entry.value {
+        options.value = Some(parse_amount(value).map_err(|e| e.context("Error parsing value"))?);
+    }
+
+    match entry.mode.unwrap_or(BatchMode::Call) {
+        BatchMode::Call => {
+            let outputs = client.call_inferred(&contract, fn_name, params.as_slice()).await
+                .map_err(|e| e.context(format!("Error querying of method '{}'", fn_name)))?;
+            Ok(call_outcome(outputs))
+        },
+        BatchMode::Estimate => {
+            let from = match &entry.from {
+                Some(res) => client.resolve_address(res).await?,
+                None => return Err(CrunnerError::InvalidArgument("Error, estimate requires from to be set".to_owned())),
+            };
+            let gas_used = client.estimate_gas(&contract, fn_name, params.as_slice(), &from, options).await
+                .map_err(|e| e.context(format!("Error estimating gas by calling a setter method '{}'", fn_name)))?;
+            Ok(BatchOutcome {
+                json: json!(gas_used.to_string()),
+                value: gas_used.to_string(),
+            })
+        },
+        BatchMode::Send => {
+            let is_local = matches!(client.chain(), ChainType::Local);
+            let confirmations = entry.block_confirmations.unwrap_or(if is_local { 0 } else { 20 });
+            options.gas = entry.gas_limit.map(U256::from);
+            set_fee_options(client, &mut options, None, None, None).await?;
+            let tx_receipt = client.send(&contract, fn_name, params.as_slice(), options, confirmations).await
+                .map_err(|e| e.context(format!("Error calling setter method '{}'", fn_name)))?;
+            Ok(BatchOutcome {
+                json: receipt_json(&tx_receipt, Some(contract.abi())),
+                value: format!("{:?}", tx_receipt.transaction_hash),
+            })
+        },
+    }
+}
+
+/// Get function name of an entry of batch file which may be given as signature.
+fn batch_fn_name(entry: &BatchEntry) -> &str {
+    match entry.function.split_once('(') {
+        Some((fn_name, _)) => fn_name.trim(),
+        None => entry.function.as_str(),
+    }
+}
+
+/// Get function signature of an entry of batch file, if given as such.
+fn batch_fn_sig(entry: &BatchEntry) -> Option<&str> {
+    if entry.function.contains('(') {
+        Some(entry.function.as_str())
+    }
+    else {
+        None
+    }
+}
+
+/// Outcome of call entry of batch file from its decoded outputs.
+fn call_outcome(outputs: Vec<(String, Token)>) -> BatchOutcome {
+    BatchOutcome {
+        json: outputs_json(&outputs),
+        value: format_tokens(outputs.into_iter().map(|(_, token)| token).collect()),
+    }
+}
+
+/// Endpoint of `serve` subcommand with its contract instance created once at startup
+struct ServeRoute {
+    /// Endpoint as configured
+    endpoint: ServeEndpoint,
+
+    /// Contract instance to interact with
+    contract: Contract<CrunnerTransport>,
+
+    /// Function name
+    fn_name: String,
+
+    /// Function signature to select among overloaded functions, if configured as such
+    fn_sig: Option<String>,
+
+    /// Maximum amount of native currency a request may send along
+    max_value: Option<U256>,
+}
+
+/// State of `serve` subcommand shared across requests
+struct ServeState {
+    /// Client whose connection is reused across requests
+    client: CrunnerClient,
+
+    /// GET endpoints keyed by path
+    get_routes: HashMap<String, ServeRoute>,
+
+    /// POST endpoints keyed by path
+    post_routes: HashMap<String, ServeRoute>,
+
+    /// Bearer token required by POST endpoints
+    token: Option<String>,
+
+    /// Transactions are sent one at a time so that concurrent requests don't race
+    /// for the same nonce
+    send_lock: tokio::sync::Mutex<()>,
+}
+
+/// Handle `serve` subcommand; expose configured getters as GET endpoints, and setters
+/// as POST endpoints guarded by bearer token over HTTP until interrupted.
+async fn handle_serve(client: CrunnerClient, args: ServeArgs) {
+    let config = match load_serve_config(&args.config) {
+        Ok(res) => res,
+        Err(e) => exit_with_error(e),
+    };
+    let token = std::env::var(SERVE_TOKEN_ENV).ok().filter(|t| !t.is_empty());
+    if !config.post.is_empty() && token.is_none() {
+        exit_with_error(CrunnerError::ConfigError(format!("Error, POST endpoints require {} environment variable to be set as bearer token", SERVE_TOKEN_ENV)));
+    }
+    let listen = match SocketAddr::from_str(&args.listen) {
+        Ok(res) => res,
+        Err(e) => exit_with_error(CrunnerError::InvalidArgument(format!("Error parsing --listen; err={}", e))),
+    };
+
+    let mut get_routes: HashMap<String, ServeRoute> = HashMap::new();
+    for endpoint in config.get {
+        get_routes.insert(endpoint.path.to_owned(), create_serve_route(&client, endpoint).await);
+    }
+    let mut post_routes: HashMap<String, ServeRoute> = HashMap::new();
+    for endpoint in config.post {
+        post_routes.insert(endpoint.path.to_owned(), create_serve_route(&client, endpoint).await);
+    }
+    let state = Arc::new(ServeState {
+        client,
+        get_routes,
+        post_routes,
+        token,
+        send_lock: tokio::sync::Mutex::new(()),
+    });
+
+    let make_service = hyper::service::make_service_fn(move |_| {
+        let state = state.clone();
+        async move {
+            Ok::<_, Infallible>(hyper::service::service_fn(move |req| serve_request(state.clone(), req)))
+        }
+    });
+    let server = match hyper::Server::try_bind(&listen) {
+        Ok(res) => res.serve(make_service),
+        Err(e) => exit_with_error(CrunnerError::InvalidArgument(format!("Error binding to {}; err={}", listen, e))),
+    };
+    if !is_quiet() {
+        eprintln!("Listening on http://{}", listen);
+    }
+    if let Err(e) = server.await {
+        exit_with_error(CrunnerError::RpcError(format!("Error serving HTTP; err={}", e)));
+    }
+}
+
+/// Create contract instance of the endpoint, and validate its configuration,
+/// otherwise exit the program.
+async fn create_serve_route(client: &CrunnerClient, endpoint: ServeEndpoint) -> ServeRoute {
+    let contract_res = match client.resolve_address(&endpoint.address).await {
+        Ok(address) => client.contract(&address, endpoint.abi.as_deref()),
+        Err(e) => Err(e),
+    };
+    let contract = match contract_res {
+        Ok(res) => res,
+        Err(e) => exit_with_error(e.context(format!("Error creating a contract instance of endpoint '{}'", endpoint.path))),
+    };
+
+    let (fn_name, fn_sig) = match endpoint.function.split_once('(') {
+        Some((fn_name, _)) => (fn_name.trim().to_owned(), Some(endpoint.function.to_owned())),
+        None => (endpoint.function.to_owned(), None),
+    };
+    if contract.abi().function(&fn_name).is_err() {
+        exit_with_error(CrunnerError::AbiError(format!("Error, function '{}' of endpoint '{}' not found in ABI", fn_name, endpoint.path)));
+    }
+
+    let max_value = match endpoint.max_value.as_deref().map(parse_amount).transpose() {
+        Ok(res) => res,
+        Err(e) => exit_with_error(e.context(format!("Error parsing max_value of endpoint '{}'", endpoint.path))),
+    };
+
+    ServeRoute {
+        endpoint,
+        contract,
+        fn_name,
+        fn_sig,
+        max_value,
+    }
+}
+
+/// Serve a request to endpoint of `serve` subcommand; respond with result as JSON, or
+/// error as `{"error": ..., "kind": ...}`.
+async fn serve_request(state: Arc<ServeState>, req: hyper::Request<hyper::Body>) -> Result<hyper::Response<hyper::Body>, Infallible> {
+    let method = req.method().clone();
+    let path = req.uri().path().to_owned();
+    let result = match (&method, state.get_routes.get(&path), state.post_routes.get(&path)) {
+        (&hyper::Method::GET, Some(route), _) => serve_get(&state, route, req.uri().query()).await,
+        (&hyper::Method::POST, _, Some(route)) => serve_post(&state, route, req).await,
+        (_, None, None) => Err((hyper::StatusCode::NOT_FOUND, CrunnerError::InvalidArgument(format!("Error, no endpoint at {}", path)))),
+        _ => Err((hyper::StatusCode::METHOD_NOT_ALLOWED, CrunnerError::InvalidArgument(format!("Error, {} is not allowed at {}", method, path)))),
+    };
+
+    let (status, body) = match result {
+        Ok(res) => (hyper::StatusCode::OK, res),
+        Err((status, e)) => (status, json!({ "error": e.to_string(), "kind": e.kind() })),
+    };
+    tracing::info!(%method, path, status = status.as_u16(), "served request");
+
+    let mut resp = hyper::Response::new(hyper::Body::from(body.to_string()));
+    *resp.status_mut() = status;
+    resp.headers_mut().insert(hyper::header::CONTENT_TYPE, hyper::header::HeaderValue::from_static("application/json"));
+    Ok(resp)
+}
+
+/// Serve a request to GET endpoint; make a call to the getter method with parameters
+/// appended from repeated `p` query parameter i.e. `?p=0x...&p=1`.
+async fn serve_get(state: &ServeState, route: &ServeRoute, query: Option<&str>) -> Result<serde_json::Value, (hyper::StatusCode, CrunnerError)> {
+    let bad_request = |e: CrunnerError| (hyper::StatusCode::BAD_REQUEST, e);
+
+    let mut params = route.endpoint.params.clone();
+    for (key, value) in url::form_urlencoded::parse(query.unwrap_or_default().as_bytes()) {
+        if key != "p" {
+            return Err(bad_request(CrunnerError::InvalidArgument(format!("Error, unknown query parameter '{}'; supply parameters via p", key))));
+        }
+        params.push(value.into_owned());
+    }
+    let params = state.client.resolve_params(params.as_slice()).await.map_err(bad_request)?;
+    let contract = state.client.select_overload(&route.contract, &route.fn_name, route.fn_sig.as_deref(), params.as_slice()).map_err(bad_request)?;
+
+    match state.client.call_inferred(&contract, &route.fn_name, params.as_slice()).await {
+        Ok(outputs) => Ok(json!({ "function": route.fn_name, "result": outputs_json(&outputs) })),
+        Err(e) => Err((hyper::StatusCode::BAD_GATEWAY, e.context(format!("Error querying of method '{}'", route.fn_name)))),
+    }
+}
+
+/// Serve a request to POST endpoint; send a transaction calling the setter method with
+/// parameters, and value in JSON body i.e. `{"params": ["0x...", "1"], "value": "0"}`.
+/// It requires bearer token set in `CRUNNER_SERVE_TOKEN` environment variable.
+async fn serve_post(state: &ServeState, route: &ServeRoute, req: hyper::Request<hyper::Body>) -> Result<serde_json::Value, (hyper::StatusCode, CrunnerError)> {
+    let bad_request = |e: CrunnerError| (hyper::StatusCode::BAD_REQUEST, e);
+
+    let authorization = req.headers().get(hyper::header::AUTHORIZATION).and_then(|v| v.to_str().ok()).unwrap_or_default();
+    let is_authorized = match (&state.token, authorization.strip_prefix("Bearer ")) {
+        (Some(token), Some(bearer)) => constant_time_eq(token.as_bytes(), bearer.trim().as_bytes()),
+        _ => false,
+    };
+    if !is_authorized {
+        return Err((hyper::StatusCode::UNAUTHORIZED, CrunnerError::InvalidArgument("Error, missing, or invalid bearer token".to_owned())));
+    }
+
+    let body = match hyper::body::to_bytes(req.into_body()).await {
+        Ok(res) => res,
+        Err(e) => return Err(bad_request(CrunnerError::InvalidArgument(format!("Error reading request body; err={}", e)))),
+    };
+    let send_req: SendRequest = if body.is_empty() {
+        SendRequest::default()
+    }
+    else {
+        serde_json::from_slice(&body).map_err(|e| bad_request(CrunnerError::InvalidArgument(format!("Error parsing request body; err={}", e))))?
+    };
+
+    let mut params = route.endpoint.params.clone();
+    params.extend(send_req.params);
+    let params = state.client.resolve_params(params.as_slice()).await.map_err(bad_request)?;
+    let contract = state.client.select_overload(&route.contract, &route.fn_name, route.fn_sig.as_deref(), params.as_slice()).map_err(bad_request)?;
+
+    let mut options = Options::default();
+    if let Some(value) = &send_req.value {
+        let value = parse_amount(value).map_err(|e| bad_request(e.context("Error parsing value")))?;
+        if !value.is_zero() && route.max_value.is_none_or(|max_value| value > max_value) {
+            return Err((hyper::StatusCode::FORBIDDEN, CrunnerError::InvalidArgument(format!("Error, value exceeds max_value of endpoint '{}'", route.endpoint.path))));
+        }
+        options.value = Some(value);
+    }
+    options.gas = route.endpoint.gas_limit.map(U256::from);
+    let is_local = matches!(state.client.chain(), ChainType::Local);
+    let confirmations = route.endpoint.block_confirmations.unwrap_or(if is_local { 0 } else { 20 });
+
+    let _send_guard = state.send_lock.lock().await;
+    let bad_gateway = |e: CrunnerError| (hyper::StatusCode::BAD_GATEWAY, e);
+    set_fee_options(&state.client, &mut options, None, None, None).await.map_err(bad_gateway)?;
+    match state.client.send(&contract, &route.fn_name, params.as_slice(), options, confirmations).await {
+        Ok(tx_receipt) => Ok(receipt_json(&tx_receipt, Some(contract.abi()))),
+        Err(e) => Err(bad_gateway(e.context(format!("Error calling setter method '{}'", route.fn_name)))),
+    }
+}
+
+/// Handle `script` subcommand; run Rhai script with `get`, `send`, `estimate`, and
+/// `balance` functions bound to the chain. Transactions are signed with the same key
+/// throughout the script.
+fn handle_script(client: CrunnerClient, args: ScriptArgs) {
+    let is_local = matches!(client.chain(), ChainType::Local);
+    let confirmations = args.block_confirmations.unwrap_or(if is_local { 0 } else { 20 });
+    let prvk = load_signing_key(&args.key);
+
+    let ctx = Rc::new(ScriptContext::new(client, args.abi_filepath.clone(), prvk, confirmations));
+    let engine = create_engine(ctx.clone());
+    if let Err(e) = engine.run_file(PathBuf::from(&args.script)) {
+        exit_with_error(ctx.script_error(&e, &args.script));
+    }
+}
+
+/// Compare secrets in time independent of where they differ.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b.iter()).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// Handle `raw-call` subcommand; make read-only call with calldata at hand, and print
+/// its returned data in hex, or decoded against `--types`.
+async fn handle_raw_call(client: &CrunnerClient, args: RawCallArgs) {
+    let data = match parse_hex_bytes(&args.data) {
+        Ok(res) => res,
+        Err(e) => exit_with_error(e.context("Error parsing --data")),
+    };
+    let to = match args.to.as_deref().map(get_address_from_str).transpose() {
+        Ok(res) => res,
+        Err(e) => exit_with_error(e.context("Error parsing --to")),
+    };
+    let from = match args.from.as_deref().map(get_address_from_str).transpose() {
+        Ok(res) => res,
+        Err(e) => exit_with_error(e.context("Error parsing --from")),
+    };
+    let abi = match load_abi(args.abi_filepath.as_deref()) {
+        Ok(res) => res,
+        Err(e) => exit_with_error(e),
+    };
+
+    let call_req = CallRequest {
+        from,
+        to,
+        value: tx_options(args.value.as_deref()).value,
+        data: Some(Bytes(data)),
+        ..Default::default()
+    };
+    let returned = match client.call_raw(call_req, Some(&abi)).await {
+        Ok(res) => res,
+        Err(e) => exit_with_error(e),
+    };
+
+    if let Some(types) = &args.types {
+        return print_decoded_values(types, &returned);
+    }
+    let returned = format!("0x{}", hex::encode(returned));
+    if is_json() {
+        print_json(&json!({ "result": returned }));
+    }
+    else if is_csv() {
+        print_csv_row(&["timestamp", "to", "data", "value"]);
+        print_csv_row(&[timestamp_now(), args.to.unwrap_or_default(), args.data, returned]);
+    }
+    else {
+        println!("{}", returned);
+    }
+}
+
+/// Handle `estimate-gas` subcommand; dry run of setter method to estimate gas used.
+async fn handle_estimate_gas(client: &CrunnerClient, args: EstimateGasArgs) {
+    let contract = prepare_contract(client, &args.contract).await;
+    let fn_name = &args.contract.fn_name();
+
+    let est_gas_used = client.estimate_gas(&contract, fn_name, args.contract.params.as_slice(), &args.from, tx_options(args.value.as_deref())).await;
+    let f_est_gas_used: f64;
+    let estimated_gas_used: U256;
+    match est_gas_used {
+        Ok(res) => {
+            estimated_gas_used = res;
+
+            // convert from base U256 to primitive_types's U256 which has floating point
+            // feature
+            f_est_gas_used = match primitive_types::U256::from_dec_str(&estimated_gas_used.to_string()) {
+                Ok(res) => res.to_f64_lossy(),
+                Err(e) => exit_with_error(CrunnerError::RpcError(format!("Error converting from base U256 to floating-point ready U256; err={}", e))),
+            };
+        },
+        Err(e) => exit_with_error(e.context(format!("Error estimating gas by calling a setter method '{}'", fn_name))),
+    };
+
+    // print the gas price
+    // so user can mutiply with the unit of gas used from prior
+    match client.gas_fees().await.map(|fees| fees.gas_price) {
+        Ok(gas_price) => {
+            // convert from base U256 to primitive_types's U256 which has floating point
+            // feature
+            let f_gas_price = match primitive_types::U256::from_dec_str(&gas_price.to_string()) {
+                Ok(res) => res,
+                Err(e) => exit_with_error(CrunnerError::RpcError(format!("Error converting from base U256 to floating-point ready U256; err={}", e))),
+            };
+
+            let gas_price_native = f_gas_price.to_f64_lossy() / 10_f64.powf(18_f64);
+
+            // fee suggestions are only available on chains supporting EIP-1559
+            let fee_tiers = match client.suggest_fee_tiers().await {
+                Ok(res) => res,
+                Err(e) => {
+                    tracing::debug!(error = %e, "no fee suggestions");
+                    Vec::new()
+                },
+            };
+            // total fee in native currency when paying base fee plus priority fee
+            let tier_fee = |tier: &FeeSuggestion| wei_to_f64(tier.base_fee + tier.priority_fee) / 10_f64.powf(18_f64) * f_est_gas_used;
+            let gwei = |wei: U256| wei_to_f64(wei) / 10_f64.powf(9_f64);
+
+            // price of native currency in USD; only queried when opted in via --usd
+            let usd_price = if args.usd {
+                match client.native_usd_price().await {
+                    Ok(res) => Some(res),
+                    Err(e) => exit_with_error(e.context(format!("Error querying price of {} in USD", unit_str(client.chain())))),
+                }
+            }
+            else {
+                None
+            };
+            let fee = gas_price_native * f_est_gas_used;
+
+            if is_json() {
+                let tiers_json: Vec<serde_json::Value> = fee_tiers.iter().map(|tier| {
+                    let mut tier_json = json!({
+                        "tier": tier.tier,
+                        "base_fee_wei": tier.base_fee.to_string(),
+                        "priority_fee_wei": tier.priority_fee.to_string(),
+                        "max_fee_wei": tier.max_fee.to_string(),
+                        "fee": tier_fee(tier),
+                    });
+                    if let Some(usd_price) = usd_price {
+                        tier_json["fee_usd"] = json!(tier_fee(tier) * usd_price);
+                    }
+                    tier_json
+                }).collect();
+                let mut res_json = json!({
+                    "gas_used": estimated_gas_used.to_string(),
+                    "gas_price_wei": gas_price.to_string(),
+                    "gas_price": gas_price_native,
+                    "fee": fee,
+                    "unit": unit_str(client.chain()),
+                    "fee_suggestions": tiers_json,
+                });
+                if let Some(usd_price) = usd_price {
+                    res_json["usd_price"] = json!(usd_price);
+                    res_json["fee_usd"] = json!(fee * usd_price);
+                }
+                print_json(&res_json);
+            }
+            else if is_csv() {
+                let mut header = vec!["timestamp", "function", "params", "gas_used", "gas_price", "fee", "slow_fee", "standard_fee", "fast_fee"];
+                let tier_fee_str = |name: &str| fee_tiers.iter().find(|t| t.tier == name).map(|t| tier_fee(t).to_string()).unwrap_or_default();
+                let mut row = vec![
+                    timestamp_now(),
+                    fn_name.to_owned(),
+                    args.contract.params.join(" "),
+                    estimated_gas_used.to_string(),
+                    gas_price_native.to_string(),
+                    fee.to_string(),
+                    tier_fee_str("slow"),
+                    tier_fee_str("standard"),
+                    tier_fee_str("fast"),
+                ];
+                if let Some(usd_price) = usd_price {
+                    header.extend(["usd_price", "fee_usd"]);
+                    row.extend([usd_price.to_string(), (fee * usd_price).to_string()]);
+                }
+                print_csv_row(&header);
+                print_csv_row(&row);
+            }
+            else if is_quiet() {
+                println!("{}", estimated_gas_used);
+            }
+            else {
+                println!("{:?} {} {}", estimated_gas_used, gas_price_native, fee);
+                if let Some(usd_price) = usd_price {
+                    println!("fee in USD: ${} (1 {} = ${})", format_usd(fee * usd_price), unit_str(client.chain()), format_usd(usd_price));
+                }
+                for tier in fee_tiers.iter() {
+                    let tier_usd = usd_price.map(|p| format!(" (${})", format_usd(tier_fee(tier) * p))).unwrap_or_default();
+                    println!("{}: base fee {} gwei + priority fee {} gwei (max fee {} gwei) = {} {}{}", tier.tier, gwei(tier.base_fee), gwei(tier.priority_fee), gwei(tier.max_fee), tier_fee(tier), unit_str(client.chain()), tier_usd);
+                }
+            }
+        },
+        Err(e) => exit_with_error(e),
+    }
+}
+
+/// Format amount in USD with cents, or more decimals for amount less than a cent.
+fn format_usd(value: f64) -> String {
+    if value >= 0.01 {
+        format!("{:.2}", value)
+    }
+    else {
+        format!("{:.6}", value)
+    }
+}
+
+/// Convert amount in Wei into floating point number, otherwise exit the program.
+fn wei_to_f64(value: U256) -> f64 {
+    // convert from base U256 to primitive_types's U256 which has floating point
+    // feature
+    match primitive_types::U256::from_dec_str(&value.to_string()) {
+        Ok(res) => res.to_f64_lossy(),
+        Err(e) => exit_with_error(CrunnerError::RpcError(format!("Error converting from base U256 to floating-point ready U256; err={}", e))),
+    }
+}
+
+/// Handle `balance` subcommand; query balance of the target address via RPC-ETH.
+async fn handle_balance(client: &CrunnerClient, args: BalanceArgs) {
+    let bal_res = client.balance(&args.address).await;
+    match bal_res {
+        Ok(bal) => {
+            let fready_bal = match primitive_types::U256::from_dec_str(&bal.to_string()) {
+                Ok(res) => res,
+                Err(e) => exit_with_error(CrunnerError::RpcError(format!("Error converting from base U256 to floating-point ready U256; err={}", e))),
+            };
+
+            let native_bal = fready_bal.to_f64_lossy() / 10_f64.powf(18_f64);
+            if is_json() {
+                print_json(&json!({
+                    "address": args.address,
+                    "balance_wei": bal.to_string(),
+                    "balance": native_bal,
+                    "unit": unit_str(client.chain()),
+                }));
+            }
+            else if is_csv() {
+                print_csv_row(&["timestamp", "address", "balance_wei", "balance"]);
+                print_csv_row(&[timestamp_now(), args.address, bal.to_string(), native_bal.to_string()]);
+            }
+            else if is_quiet() {
+                println!("{}", bal);
+            }
+            else {
+                println!("{:?} {:?}", bal, native_bal);
+            }
+        },
+        Err(e) => exit_with_error(e),
+    }
+}
+
+/// Handle `token-info` subcommand; print metadata of ERC-20 token along with its
+/// total supply scaled by decimals.
+async fn handle_token_info(client: &CrunnerClient, args: TokenInfoArgs) {
+    ensure_contract_address(client, &args.address).await;
+    let info = match client.token_info(&args.address).await {
+        Ok(res) => res,
+        Err(e) => exit_with_error(e),
+    };
+
+    let total_supply = info.total_supply.map(|v| v.to_string()).unwrap_or_default();
+    let total_supply_formatted = match (info.total_supply, info.decimals) {
+        (Some(total_supply), Some(decimals)) => format_units(total_supply, decimals),
+        _ => String::new(),
+    };
+    if is_json() {
+        print_json(&json!({
+            "address": args.address,
+            "name": info.name,
+            "symbol": info.symbol,
+            "decimals": info.decimals,
+            "total_supply": info.total_supply.map(|v| v.to_string()),
+            "total_supply_formatted": info.total_supply.and(info.decimals).map(|_| total_supply_formatted),
+        }));
+    }
+    else if is_csv() {
+        print_csv_row(&["timestamp", "address", "name", "symbol", "decimals", "total_supply", "total_supply_formatted"]);
+        print_csv_row(&[
+            timestamp_now(),
+            args.address,
+            info.name.unwrap_or_default(),
+            info.symbol.unwrap_or_default(),
+            info.decimals.map(|d| d.to_string()).unwrap_or_default(),
+            total_supply,
+            total_supply_formatted,
+        ]);
+    }
+    else {
+        let or_unknown = |value: Option<String>| value.unwrap_or_else(|| "-".to_owned());
+        println!("name: {}", or_unknown(info.name));
+        println!("symbol: {}", or_unknown(info.symbol));
+        println!("decimals: {}", or_unknown(info.decimals.map(|d| d.to_string())));
+        if total_supply_formatted.is_empty() {
+            println!("total supply: {}", or_unknown(info.total_supply.map(|v| v.to_string())));
+        }
+        else {
+            println!("total supply: {} ({})", total_supply_formatted, total_supply);
+        }
+    }
+}
+
+/// Handle `erc20` subcommand; query, or transfer ERC-20 token with amounts in units
+/// of the token.
+async fn handle_erc20(client: &CrunnerClient, args: Erc20Args) {
+    match args.command {
+        Erc20Command::BalanceOf(args) => handle_erc20_query(client, &args.token, "balanceOf", &[args.owner]).await,
+        Erc20Command::Allowance(args) => handle_erc20_query(client, &args.token, "allowance", &[args.owner, args.spender]).await,
+        Erc20Command::Approve(args) => {
+            if is_zero_address(&args.spender) {
+                exit_with_error(CrunnerError::InvalidArgument("Error, spender is the zero address".to_owned()));
+            }
+            let amount = if args.amount.eq_ignore_ascii_case("max") {
+                tracing::warn!(spender = %args.spender, "approving unlimited allowance");
+                U256::MAX.to_string()
+            }
+            else {
+                erc20_amount_param(&args.amount, args.raw)
+            };
+            send_token(client, TokenStandard::Erc20, args.token, "approve(address,uint256)", vec![args.spender, amount], args.tx).await
+        },
+        Erc20Command::Transfer(args) => {
+            // tokens sent to the zero address, or the token contract itself are usually lost
+            if is_zero_address(&args.to) {
+                exit_with_error(CrunnerError::InvalidArgument("Error, recipient is the zero address".to_owned()));
+            }
+            if args.to.eq_ignore_ascii_case(&args.token) {
+                exit_with_error(CrunnerError::InvalidArgument("Error, recipient is the token contract itself".to_owned()));
+            }
+            if args.amount.trim().trim_matches(|c| c == '0' || c == '.').is_empty() {
+                exit_with_error(CrunnerError::InvalidArgument("Error, amount to transfer is zero".to_owned()));
+            }
+            let amount = erc20_amount_param(&args.amount, args.raw);
+            send_token(client, TokenStandard::Erc20, args.token, "transfer(address,uint256)", vec![args.to, amount], args.tx).await
+        },
+    }
+}
+
+/// Query token amount i.e. `balanceOf` of ERC-20 token, and print it scaled by the
+/// token's decimals along with the raw amount.
+async fn handle_erc20_query(client: &CrunnerClient, token: &str, fn_name: &str, params: &[String]) {
+    let contract = standard_contract(client, token, TokenStandard::Erc20).await;
+    let (value, decimals) = query_token_amount(client, &contract, fn_name, params, None).await;
+    print_token_amount(fn_name, params, value, decimals);
+}
+
+/// Validate the token address then create a contract instance with the built-in ABI
+/// of the token standard, otherwise exit the program.
+async fn standard_contract(client: &CrunnerClient, token: &str, standard: TokenStandard) -> Contract<CrunnerTransport> {
+    ensure_contract_address(client, token).await;
+    match client.standard_contract(token, standard) {
+        Ok(res) => res,
+        Err(e) => exit_with_error(e.context("Error creating a contract instance")),
+    }
+}
+
+/// Send transaction to setter method of token with the built-in ABI of its standard as
+/// `send` subcommand does; `fn_sig` selects the overload in case of overloaded functions.
+async fn send_token(client: &CrunnerClient, standard: TokenStandard, token: String, fn_sig: &str, params: Vec<String>, tx: TransactionArgs) {
+    let contract = ContractArgs {
+        contract_address: Some(token),
+        fn_name: None,
+        fn_sig: Some(fn_sig.to_owned()),
+        params,
+        abi_filepath: None,
+        abi_from_explorer: false,
+        standard: Some(standard),
+        skip_code_check: false,
+    };
+    handle_send(client, SendArgs { contract, encode_only: false, build_only: false, sign_only: false, tx }).await
+}
+
+/// Convert amount of `erc20` subcommand into parameter; scaled by decimals of the
+/// token unless it's `raw`.
+fn erc20_amount_param(amount: &str, raw: bool) -> String {
+    if raw {
+        format!("u256:{}", amount.trim())
+    }
+    else {
+        format!("amount:{}", amount.trim())
+    }
+}
+
+/// Check whether the address is the zero address.
+fn is_zero_address(address: &str) -> bool {
+    get_address_from_str(address).map(|addr| addr.is_zero()).unwrap_or(false)
+}
+
+/// Handle `permit` subcommand; sign EIP-2612 permit of ERC-20 token with the secret key,
+/// and print its signature, or submit it on-chain with --send.
+async fn handle_permit(client: &CrunnerClient, args: PermitArgs) {
+    // permit is signed over its EIP-712 digest which external signers can't sign
+    if args.tx.signer.is_some() || args.tx.from.is_some() {
+        exit_with_error(CrunnerError::InvalidArgument("Error, permit requires a secret key to sign with; --signer, and --from are not supported".to_owned()));
+    }
+    let prvk = load_message_signing_key(&args.tx.key);
+    let owner = SecretKeyRef::new(&prvk).address();
+    let spender = match get_address_from_str(&args.spender) {
+        Ok(res) => res,
+        Err(e) => exit_with_error(e),
+    };
+    let deadline = match parse_deadline(&args.deadline) {
+        Ok(res) => res,
+        Err(e) => exit_with_error(e),
+    };
+
+    let contract = standard_contract(client, &args.token, TokenStandard::Erc20Permit).await;
+    let value = if args.amount.eq_ignore_ascii_case("max") {
+        tracing::warn!(spender = %args.spender, "permitting unlimited allowance");
+        U256::MAX
+    }
+    else if args.raw {
+        match parse_amount(args.amount.trim()) {
+            Ok(res) => res,
+            Err(e) => exit_with_error(e),
+        }
+    }
+    else {
+        let decimals = match client.token_decimals(&args.token).await {
+            Ok(res) => res,
+            Err(e) => exit_with_error(e),
+        };
+        match parse_units(args.amount.trim(), decimals) {
+            Ok(res) => res,
+            Err(e) => exit_with_error(e),
+        }
+    };
+
+    // domain is made of the token's name, version, chain id, and address
+    let name = match client.call::<String>(&contract, "name", &[]).await {
+        Ok(res) => res,
+        Err(e) => exit_with_error(e.context("Error querying name of the token")),
+    };
+    let version = match &args.domain_version {
+        Some(res) => res.clone(),
+        None => client.call::<String>(&contract, "version", &[]).await.unwrap_or_else(|_| "1".to_owned()),
+    };
+    let nonce = match client.call::<U256>(&contract, "nonces", &[to_checksum_address(&owner)]).await {
+        Ok(res) => res,
+        Err(e) => exit_with_error(e.context("Error querying permit nonce of the owner; the token may not support EIP-2612")),
+    };
+    let chain_id = match client.web3().eth().chain_id().await {
+        Ok(res) => res,
+        Err(e) => exit_with_error(CrunnerError::RpcError(format!("Error querying chain id; err={}", e))),
+    };
+    let domain = Eip712Domain {
+        name: Some(name),
+        version: Some(version),
+        chain_id: Some(chain_id),
+        verifying_contract: Some(contract.address()),
+        salt: None,
+    };
+
+    // signature against a domain other than the token's is rejected on-chain
+    let domain_separator = domain.separator();
+    if let Ok(token_separator) = client.call::<H256>(&contract, "DOMAIN_SEPARATOR", &[]).await {
+        if token_separator != domain_separator {
+            exit_with_error(CrunnerError::AbiError(format!("Error, domain separator of the token {:?} differs from the computed one {:?}; supply --domain-version", token_separator, domain_separator)));
+        }
+    }
+
+    let digest = typed_data_digest(domain_separator, permit_struct_hash(owner, spender, value, nonce, deadline));
+    let signature = match SecretKeyRef::new(&prvk).sign(digest.as_bytes(), None) {
+        Ok(res) => res,
+        Err(e) => exit_with_error(CrunnerError::InvalidArgument(format!("Error signing permit; err={}", e))),
+    };
+    let signature_hex = signature_to_hex(&signature);
+
+    if args.send {
+        let params = vec![
+            to_checksum_address(&owner),
+            to_checksum_address(&spender),
+            format!("u256:{}", value),
+            format!("u256:{}", deadline),
+            format!("uint8:{}", signature.v),
+            format!("bytes32:{:?}", signature.r),
+            format!("bytes32:{:?}", signature.s),
+        ];
+        return send_token(client, TokenStandard::Erc20Permit, args.token, "permit(address,address,uint256,uint256,uint8,bytes32,bytes32)", params, args.tx).await;
+    }
+
+    if is_json() {
+        print_json(&json!({
+            "owner": to_checksum_address(&owner),
+            "spender": to_checksum_address(&spender),
+            "value": value.to_string(),
+            "nonce": nonce.to_string(),
+            "deadline": deadline.to_string(),
+            "v": signature.v,
+            "r": format!("{:?}", signature.r),
+            "s": format!("{:?}", signature.s),
+            "signature": signature_hex,
+        }));
+    }
+    else if is_csv() {
+        print_csv_row(&["timestamp", "owner", "spender", "value", "nonce", "deadline", "v", "r", "s"]);
+        print_csv_row(&[
+            timestamp_now(),
+            to_checksum_address(&owner),
+            to_checksum_address(&spender),
+            value.to_string(),
+            nonce.to_string(),
+            deadline.to_string(),
+            signature.v.to_string(),
+            format!("{:?}", signature.r),
+            format!("{:?}", signature.s),
+        ]);
+    }
+    else if is_quiet() {
+        println!("{}", signature_hex);
+    }
+    else {
+        println!("owner: {}", to_checksum_address(&owner));
+        println!("spender: {}", to_checksum_address(&spender));
+        println!("value: {}", value);
+        println!("nonce: {}", nonce);
+        println!("deadline: {}", deadline);
+        println!("v: {}", signature.v);
+        println!("r: {:?}", signature.r);
+        println!("s: {:?}", signature.s);
+        println!("signature: {}", signature_hex);
+    }
+}
+
+/// Handle `sign-typed` subcommand; sign EIP-712 typed data read from file with the
+/// secret key, and print the signature.
+fn handle_sign_typed(args: &SignTypedArgs) {
+    let json_res = if args.file == "-" {
+        std::io::read_to_string(std::io::stdin())
+    }
+    else {
+        std::fs::read_to_string(&args.file)
+    };
+    let json = match json_res {
+        Ok(res) => res,
+        Err(e) => exit_with_error(CrunnerError::InvalidArgument(format!("Error reading typed data from '{}'; err={}", args.file, e))),
+    };
+    let digest = match TypedData::from_json(&json).and_then(|typed_data| typed_data.digest()) {
+        Ok(res) => res,
+        Err(e) => exit_with_error(e.context("Error hashing typed data")),
+    };
+
+    let prvk = load_message_signing_key(&args.key);
+    let signature = match SecretKeyRef::new(&prvk).sign(digest.as_bytes(), None) {
+        Ok(res) => res,
+        Err(e) => exit_with_error(CrunnerError::InvalidArgument(format!("Error signing typed data; err={}", e))),
+    };
+    print_signature(SecretKeyRef::new(&prvk).address(), digest, &signature);
+}
+
+/// Handle `sign-message` subcommand; sign message prefixed as per EIP-191 i.e.
+/// `personal_sign` with the secret key, and print the signature.
+fn handle_sign_message(args: &SignMessageArgs) {
+    let message = match message_bytes(&args.message, args.text) {
+        Ok(res) => res,
+        Err(e) => exit_with_error(e),
+    };
+    let digest = hash_message(&message);
+
+    let prvk = load_message_signing_key(&args.key);
+    let signature = match SecretKeyRef::new(&prvk).sign(digest.as_bytes(), None) {
+        Ok(res) => res,
+        Err(e) => exit_with_error(CrunnerError::InvalidArgument(format!("Error signing message; err={}", e))),
+    };
+    print_signature(SecretKeyRef::new(&prvk).address(), digest, &signature);
+}
+
+/// Handle `verify-sig` subcommand; recover the signer of signature over EIP-191 message,
+/// or raw hash, and print it.
+fn handle_verify_sig(args: &VerifySigArgs) {
+    let digest = match (&args.message, &args.hash) {
+        (Some(message), _) => match message_bytes(message, args.text) {
+            Ok(res) => hash_message(&res),
+            Err(e) => exit_with_error(e),
+        },
+        (None, Some(hash)) => match H256::from_str(hash) {
+            Ok(res) => res,
+            Err(e) => exit_with_error(CrunnerError::InvalidArgument(format!("Error parsing hash '{}' as 32 bytes in hex; err={}", hash, e))),
+        },
+        (None, None) => exit_with_error(CrunnerError::InvalidArgument("Error, requires either --message, or --hash to be set".to_owned())),
+    };
+    let signature = match parse_hex_bytes(&args.signature) {
+        Ok(res) if res.len() == 65 => res,
+        Ok(res) => exit_with_error(CrunnerError::InvalidArgument(format!("Error, signature must be 65 bytes, got {} bytes", res.len()))),
+        Err(e) => exit_with_error(e),
+    };
+
+    // v is either 27, or 28, y-parity itself, or has chain id as per EIP-155
+    let recovery_id = match signature[64] {
+        v @ (0 | 1) => v as i32,
+        v @ (27 | 28) => (v - 27) as i32,
+        v if v >= 35 => ((v - 35) % 2) as i32,
+        v => exit_with_error(CrunnerError::InvalidArgument(format!("Error, invalid v of signature; v={}", v))),
+    };
+    let signer = match recover(digest.as_bytes(), &signature[..64], recovery_id) {
+        Ok(res) => res,
+        Err(e) => exit_with_error(CrunnerError::InvalidArgument(format!("Error recovering signer of signature; err={:?}", e))),
+    };
+    let expected = match args.expected.as_deref().map(get_address_from_str) {
+        Some(Ok(res)) => Some(res),
+        Some(Err(e)) => exit_with_error(e),
+        None => None,
+    };
+
+    if is_json() {
+        let mut result = json!({ "signer": to_checksum_address(&signer), "digest": format!("{:?}", digest) });
+        if let Some(expected) = expected {
+            result["valid"] = json!(expected == signer);
+        }
+        print_json(&result);
+    }
+    else if is_csv() {
+        print_csv_row(&["timestamp", "signer", "digest"]);
+        print_csv_row(&[timestamp_now(), to_checksum_address(&signer), format!("{:?}", digest)]);
+    }
+    else if is_quiet() {
+        println!("{}", to_checksum_address(&signer));
+    }
+    else {
+        println!("signer: {}", to_checksum_address(&signer));
+        println!("digest: {:?}", digest);
+    }
+
+    if let Some(expected) = expected {
+        if expected != signer {
+            exit_with_error(CrunnerError::InvalidArgument(format!("Error, signature is signed by {}, not {}", to_checksum_address(&signer), to_checksum_address(&expected))));
+        }
+    }
+}
+
+/// Handle `keccak` subcommand; print Keccak-256 hash of the input.
+fn handle_keccak(args: &KeccakArgs) {
+    let input = match message_bytes(&args.input, args.text) {
+        Ok(res) => res,
+        Err(e) => exit_with_error(e),
+    };
+    let hash = format!("0x{}", hex::encode(keccak256(&input)));
+    if is_json() {
+        print_json(&json!({ "input": args.input, "hash": hash }));
+    }
+    else if is_csv() {
+        print_csv_row(&["input", "hash"]);
+        print_csv_row(&[args.input.clone(), hash]);
+    }
+    else {
+        println!("{}", hash);
+    }
+}
+
+/// Handle `selector` subcommand; print selector of function signature, or list
+/// selectors of all functions in ABI.
+fn handle_selector(args: &SelectorArgs) {
+    let selectors: Vec<(String, String)> = match &args.signature {
+        Some(signature) => {
+            let signature = match canonical_signature(signature) {
+                Ok(res) => res,
+                Err(e) => exit_with_error(e),
+            };
+            let selector = format!("0x{}", hex::encode(&keccak256(signature.as_bytes())[..4]));
+            vec![(selector, signature)]
+        },
+        None => {
+            let abi = match load_abi(args.abi_filepath.as_deref()) {
+                Ok(res) => res,
+                Err(e) => exit_with_error(e),
+            };
+            let mut selectors: Vec<(String, String)> = abi.functions()
+                .map(|f| {
+                    let signature = format!("{}({})", f.name, f.inputs.iter().map(|p| p.kind.to_string()).collect::<Vec<String>>().join(","));
+                    (format!("0x{}", hex::encode(f.short_signature())), signature)
+                })
+                .collect();
+            selectors.sort_by(|a, b| a.1.cmp(&b.1));
+            selectors
+        },
+    };
+
+    print_signature_hashes("selector", &selectors, args.signature.is_some());
+}
+
+/// Handle `topic` subcommand; print topic0 of event signature, or list topics of all
+/// events in ABI.
+fn handle_topic(args: &TopicArgs) {
+    let topics: Vec<(String, String)> = match &args.signature {
+        Some(signature) => {
+            let signature = match canonical_signature(signature) {
+                Ok(res) => res,
+                Err(e) => exit_with_error(e),
+            };
+            let topic = format!("0x{}", hex::encode(keccak256(signature.as_bytes())));
+            vec![(topic, signature)]
+        },
+        None => {
+            let abi = match load_abi(args.abi_filepath.as_deref()) {
+                Ok(res) => res,
+                Err(e) => exit_with_error(e),
+            };
+            let mut topics: Vec<(String, String)> = abi.events()
+                .filter(|e| !e.anonymous)
+                .map(|e| {
+                    let signature = format!("{}({})", e.name, e.inputs.iter().map(|p| p.kind.to_string()).collect::<Vec<String>>().join(","));
+                    (format!("{:?}", e.signature()), signature)
+                })
+                .collect();
+            topics.sort_by(|a, b| a.1.cmp(&b.1));
+            topics
+        },
+    };
+    print_signature_hashes("topic", &topics, args.signature.is_some());
+}
+
+/// Handle `decode-output` subcommand; ABI-decode returned data against the types, and
+/// print the values.
+fn handle_decode_output(args: &DecodeOutputArgs) {
+    let data = match parse_hex_bytes(&args.data) {
+        Ok(res) => res,
+        Err(e) => exit_with_error(e),
+    };
+    print_decoded_values(&args.types, &data);
+}
+
+/// Decode data against the types listed in `--types`, and print the values; otherwise
+/// exit the program.
+fn print_decoded_values(types_str: &str, data: &[u8]) {
+    let mut types = match parse_ret_types(&format!("({})", types_str)) {
+        Ok(res) => res,
+        Err(e) => exit_with_error(e.context("Error parsing --types")),
+    };
+    // composite return signature i.e. `(uint112,uint112,uint32)` lists the types as well
+    if let [ParamType::Tuple(components)] = types.as_slice() {
+        types = components.clone();
+    }
+    let tokens = match ethabi::decode(&types, data) {
+        Ok(res) => res,
+        Err(e) => exit_with_error(CrunnerError::AbiError(format!("Error decoding data against types '{}'; err={}", types_str, e))),
+    };
+
+    if is_json() {
+        let outputs: Vec<(String, Token)> = tokens.into_iter().map(|token| (String::new(), token)).collect();
+        print_json(&json!({ "result": outputs_json(&outputs) }));
+    }
+    else if is_csv() {
+        print_csv_row(&["types", "value"]);
+        print_csv_row(&[types_str.to_owned(), format_tokens(tokens)]);
+    }
+    else {
+        for token in tokens {
+            println!("{}", format_token(&token));
+        }
+    }
+}
+
+/// Handle `decode-calldata` subcommand; decode calldata against ABI, or function
+/// signature, and print its named arguments. Calldata of unknown selector is decoded
+/// against candidates from signature databases with `--lookup`, otherwise printed as
+/// raw 32-byte words.
+async fn handle_decode_calldata(args: &DecodeCalldataArgs) {
+    let calldata = match parse_hex_bytes(&args.calldata) {
+        Ok(res) => res,
+        Err(e) => exit_with_error(e),
+    };
+    let abi = match load_abi(args.abi_filepath.as_deref()) {
+        Ok(res) => res,
+        Err(e) => exit_with_error(e),
+    };
+    let call_res = if args.fn_sig.is_none() && calldata.len() >= 4 && function_signature_of(&abi, &calldata).is_none() {
+        let looked_up = if args.lookup { decode_with_looked_up_signature(&calldata, &abi).await } else { None };
+        match looked_up {
+            Some(res) => Ok(res),
+            None => {
+                tracing::warn!("No function of selector 0x{} in abi; supply --abi-filepath, --fn-sig, or --lookup to decode its arguments", hex::encode(&calldata[..4]));
+                return print_calldata_words(&calldata);
+            },
+        }
+    }
+    else {
+        decode_function_call(&calldata, &abi, args.fn_sig.as_deref())
+    };
+    let call = match call_res {
+        Ok(res) => res,
+        Err(e) => exit_with_error(e),
+    };
+
+    if is_json() {
+        print_json(&json!({
+            "selector": call.selector,
+            "function": call.signature,
+            "params": named_params_json(&call.params),
+        }));
+    }
+    else if is_csv() {
+        print_csv_row(&["selector", "function", "params"]);
+        print_csv_row(&[call.selector.clone(), call.signature.clone(), format_call(&call)]);
+    }
+    else if is_quiet() {
+        for (_, token) in call.params {
+            println!("{}", format_token(&token));
+        }
+    }
+    else {
+        println!("{}", call.signature);
+        for (i, (name, token)) in call.params.iter().enumerate() {
+            let name = if name.is_empty() { i.to_string() } else { name.to_owned() };
+            println!("{}: {}", name, format_token(token));
+        }
+    }
+}
+
+/// Look up candidate signatures of the selector of calldata from signature databases,
+/// and decode it against the first one that fits; `None` if none does.
+async fn decode_with_looked_up_signature(calldata: &[u8], abi: &ethabi::Contract) -> Option<DecodedCall> {
+    let db = match SignatureDb::default_filepath() {
+        Some(filepath) => SignatureDb::new(&filepath),
+        None => exit_with_error(CrunnerError::ConfigError("Error, cannot locate cache directory for --lookup".to_owned())),
+    };
+    let candidates = match db.lookup(&calldata[..4]).await {
+        Ok(res) => res,
+        Err(e) => {
+            tracing::warn!("{}", e.context("Error looking up signature of selector"));
+            return None;
+        },
+    };
+    let mut fitting = candidates.iter().filter_map(|candidate| decode_function_call(calldata, abi, Some(candidate)).ok());
+    let call = fitting.next()?;
+    if candidates.len() > 1 {
+        tracing::warn!("Selector 0x{} has {} candidate signatures; decoded against '{}' out of {}", hex::encode(&calldata[..4]), candidates.len(), call.signature, candidates.join(", "));
+    }
+    Some(call)
+}
+
+/// Amend error of revert with unknown custom error by its arguments decoded against
+/// signature looked up from signature databases; hook of `exit_with_error` with
+/// `--lookup-errors`. The error is left as is if none is found.
+fn lookup_custom_error(err: CrunnerError) -> CrunnerError {
+    let data = match unknown_custom_error_data(&err.to_string()) {
+        Some(res) if res.len() >= 4 => res,
+        _ => return err,
+    };
+    let handle = match tokio::runtime::Handle::try_current() {
+        Ok(res) => res,
+        Err(_) => return err,
+    };
+    // revert data of custom error is laid out the same as calldata
+    let looked_up = tokio::task::block_in_place(|| handle.block_on(decode_with_looked_up_signature(&data, &ethabi::Contract::default())));
+    match looked_up {
+        Some(call) => {
+            let msg = format!("{}; looked up as {}", err, format_call(&call));
+            err.with_message(msg)
+        },
+        None => err,
+    }
+}
+
+/// Handle `decode-tx` subcommand; decode signed raw transaction into its fields, and
+/// print them along with its recovered sender.
+fn handle_decode_tx(args: &DecodeTxArgs) {
+    let raw_tx = match parse_hex_bytes(&args.raw_tx) {
+        Ok(res) => res,
+        Err(e) => exit_with_error(e),
+    };
+    let tx = match DecodedTx::decode(&raw_tx) {
+        Ok(res) => res,
+        Err(e) => exit_with_error(e),
+    };
+
+    let mut fields: Vec<(&str, String)> = vec![
+        ("hash", format!("{:?}", tx.hash)),
+        ("type", tx.tx_type.to_string()),
+        ("chain_id", tx.chain_id.map(|id| id.to_string()).unwrap_or_default()),
+        ("from", to_checksum_address(&tx.from)),
+        ("to", tx.to.map(|to| to_checksum_address(&to)).unwrap_or_default()),
+        ("nonce", tx.nonce.to_string()),
+        ("value", tx.value.to_string()),
+        ("gas", tx.gas.to_string()),
+    ];
+    if let Some(gas_price) = tx.gas_price {
+        fields.push(("gas_price", gas_price.to_string()));
+    }
+    if let Some((max_fee_per_gas, max_priority_fee_per_gas)) = tx.eip1559_fees {
+        fields.push(("max_fee_per_gas", max_fee_per_gas.to_string()));
+        fields.push(("max_priority_fee_per_gas", max_priority_fee_per_gas.to_string()));
+    }
+    fields.push(("data", format!("0x{}", hex::encode(&tx.data))));
+    if tx.tx_type != 0 {
+        let access_list: Vec<String> = tx.access_list.iter()
+            .map(|(address, keys)| format!("{}[{}]", to_checksum_address(address), keys.iter().map(|k| format!("{:?}", k)).collect::<Vec<String>>().join(",")))
+            .collect();
+        fields.push(("access_list", access_list.join(" ")));
+    }
+    fields.push(("v", tx.signature.v.to_string()));
+    fields.push(("r", format!("{:?}", tx.signature.r)));
+    fields.push(("s", format!("{:?}", tx.signature.s)));
+
+    if is_json() {
+        let mut tx_json: serde_json::Map<String, serde_json::Value> = fields.into_iter()
+            .filter(|(name, _)| *name != "access_list")
+            .map(|(name, value)| (name.to_owned(), if value.is_empty() { serde_json::Value::Null } else { json!(value) }))
+            .collect();
+        if tx.tx_type != 0 {
+            tx_json.insert("access_list".to_owned(), tx.access_list.iter()
+                .map(|(address, keys)| json!({ "address": to_checksum_address(address), "storage_keys": keys.iter().map(|k| format!("{:?}", k)).collect::<Vec<String>>() }))
+                .collect());
+        }
+        print_json(&serde_json::Value::Object(tx_json));
+    }
+    else if is_csv() {
+        let (names, values): (Vec<&str>, Vec<String>) = fields.into_iter().unzip();
+        print_csv_row(&names);
+        print_csv_row(&values);
+    }
+    else {
+        for (name, value) in fields {
+            println!("{}: {}", name, value);
+        }
+    }
+}
+
+/// Print selector of calldata followed by its arguments as raw 32-byte words.
+fn print_calldata_words(calldata: &[u8]) {
+    let selector = format!("0x{}", hex::encode(&calldata[..4]));
+    let words = if calldata.len() > 4 {
+        match parse_256_method_arguments(&format!("0x{}", hex::encode(calldata))) {
+            Ok(res) => res,
+            Err(e) => exit_with_error(e),
+        }
+    }
+    else {
+        Vec::new()
+    };
+
+    if is_json() {
+        print_json(&json!({ "selector": selector, "words": words }));
+    }
+    else if is_csv() {
+        print_csv_row(&["selector", "words"]);
+        print_csv_row(&[selector, words.join(" ")]);
+    }
+    else {
+        println!("{}", selector);
+        for word in words {
+            println!("{}", word);
+        }
+    }
+}
+
+/// Print hash of each signature i.e. selector, or topic; only the hash if it's of a
+/// single signature given at command line.
+fn print_signature_hashes(hash_name: &str, hashes: &[(String, String)], single: bool) {
+    if is_json() {
+        let hashes_json: Vec<serde_json::Value> = hashes.iter().map(|(hash, signature)| json!({ hash_name: hash, "signature": signature })).collect();
+        if single {
+            print_json(&hashes_json[0]);
+        }
+        else {
+            print_json(&serde_json::Value::Array(hashes_json));
+        }
+    }
+    else if is_csv() {
+        print_csv_row(&[hash_name, "signature"]);
+        for (hash, signature) in hashes {
+            print_csv_row(&[hash.clone(), signature.clone()]);
+        }
+    }
+    else if single || is_quiet() {
+        for (hash, _) in hashes {
+            println!("{}", hash);
+        }
+    }
+    else {
+        for (hash, signature) in hashes {
+            println!("{} {}", hash, signature);
+        }
+    }
+}
+
+/// Get bytes of message; decoded from hex if it's prefixed with 0x unless `as_text`.
+fn message_bytes(message: &str, as_text: bool) -> Result<Vec<u8>, CrunnerError> {
+    if !as_text && message.starts_with("0x") {
+        parse_hex_bytes(message).map_err(|e| e.context("Error parsing input as hex; supply --text to take it as text"))
+    }
+    else {
+        Ok(message.as_bytes().to_vec())
+    }
+}
+
+/// Load secret key to sign off-chain message with as specified in `args`, or from
+/// CRUNNER_SETTER_SECRETKEY, otherwise exit the program.
+fn load_message_signing_key(args: &SigningKeyArgs) -> SecretKey {
+    match load_signing_key(args) {
+        Some(res) => res,
+        None => match secret_key_from_env() {
+            Ok(res) => res,
+            Err(e) => exit_with_error(e),
+        },
+    }
+}
+
+/// Format signature as 65 bytes of `r`, `s`, and `v` in hex.
+fn signature_to_hex(signature: &Signature) -> String {
+    format!("0x{}{}{:02x}", hex::encode(signature.r.as_bytes()), hex::encode(signature.s.as_bytes()), signature.v)
+}
+
+/// Print signature along with the signer, and the digest signed.
+fn print_signature(signer: Address, digest: H256, signature: &Signature) {
+    let signature_hex = signature_to_hex(signature);
+    if is_json() {
+        print_json(&json!({
+            "signer": to_checksum_address(&signer),
+            "digest": format!("{:?}", digest),
+            "v": signature.v,
+            "r": format!("{:?}", signature.r),
+            "s": format!("{:?}", signature.s),
+            "signature": signature_hex,
+        }));
+    }
+    else if is_csv() {
+        print_csv_row(&["timestamp", "signer", "digest", "signature"]);
+        print_csv_row(&[timestamp_now(), to_checksum_address(&signer), format!("{:?}", digest), signature_hex]);
+    }
+    else if is_quiet() {
+        println!("{}", signature_hex);
+    }
+    else {
+        println!("signer: {}", to_checksum_address(&signer));
+        println!("digest: {:?}", digest);
+        println!("signature: {}", signature_hex);
+    }
+}
+
+/// Parse deadline as either unix timestamp in seconds, or duration from now i.e. `30m`.
+fn parse_deadline(deadline: &str) -> Result<U256, CrunnerError> {
+    if let Ok(timestamp) = deadline.parse::<u64>() {
+        return Ok(U256::from(timestamp));
+    }
+    let duration = match humantime::parse_duration(deadline) {
+        Ok(res) => res,
+        Err(e) => return Err(CrunnerError::InvalidArgument(format!("Error parsing deadline '{}'; err={}", deadline, e))),
+    };
+    let now = match std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH) {
+        Ok(res) => res,
+        Err(e) => return Err(CrunnerError::InvalidArgument(format!("Error reading current time; err={}", e))),
+    };
+    Ok(U256::from((now + duration).as_secs()))
+}
+
+/// Handle `erc721` subcommand; query, or transfer ERC-721 token.
+async fn handle_erc721(client: &CrunnerClient, args: Erc721Args) {
+    match args.command {
+        Erc721Command::OwnerOf(args) => {
+            let contract = standard_contract(client, &args.token, TokenStandard::Erc721).await;
+            let params = [token_id_param(&args.token_id)];
+            match client.call_inferred(&contract, "ownerOf", &params).await {
+                Ok(outputs) => print_call_outputs("ownerOf", &params, outputs),
+                Err(e) => exit_with_error(e.context(format!("Error querying owner of token id {}", args.token_id))),
+            }
+        },
+        Erc721Command::TokenUri(args) => {
+            let contract = standard_contract(client, &args.token, TokenStandard::Erc721).await;
+            let params = [token_id_param(&args.token_id)];
+            let outputs = match client.call_inferred(&contract, "tokenURI", &params).await {
+                Ok(res) => res,
+                Err(e) => exit_with_error(e.context(format!("Error querying URI of token id {}", args.token_id))),
+            };
+            if !args.resolve {
+                return print_call_outputs("tokenURI", &params, outputs);
+            }
+
+            let uri = match outputs.as_slice() {
+                [(_, Token::String(uri))] => uri.clone(),
+                _ => exit_with_error(CrunnerError::AbiError("Error, tokenURI must return a single string".to_owned())),
+            };
+            let metadata = match fetch_token_metadata(&uri, &args.ipfs_gateway).await {
+                Ok(res) => res,
+                Err(e) => exit_with_error(e),
+            };
+            if is_json() {
+                print_json(&json!({ "token_uri": uri, "metadata": metadata }));
+            }
+            else {
+                println!("{}", serde_json::to_string_pretty(&metadata).unwrap_or_else(|_| metadata.to_string()));
+            }
+        },
+        Erc721Command::SafeTransfer(args) => {
+            // contracts not implementing onERC721Received revert the transfer, but
+            // the zero address, or the token contract itself is never the intended one
+            if is_zero_address(&args.to) {
+                exit_with_error(CrunnerError::InvalidArgument("Error, recipient is the zero address".to_owned()));
+            }
+            if args.to.eq_ignore_ascii_case(&args.token) {
+                exit_with_error(CrunnerError::InvalidArgument("Error, recipient is the token contract itself".to_owned()));
+            }
+            let from = args.owner.unwrap_or_else(|| "{sender}".to_owned());
+            let params = vec![from, args.to, token_id_param(&args.token_id)];
+            send_token(client, TokenStandard::Erc721, args.token, "safeTransferFrom(address,address,uint256)", params, args.tx).await
+        },
+        Erc721Command::Approve(args) => {
+            let params = vec![args.to, token_id_param(&args.token_id)];
+            send_token(client, TokenStandard::Erc721, args.token, "approve(address,uint256)", params, args.tx).await
+        },
+    }
+}
+
+/// Handle `erc1155` subcommand; query, or transfer ERC-1155 token, several token ids
+/// at once in batch.
+async fn handle_erc1155(client: &CrunnerClient, args: Erc1155Args) {
+    match args.command {
+        Erc1155Command::BalanceOf(args) => {
+            let contract = standard_contract(client, &args.token, TokenStandard::Erc1155).await;
+            let params = [args.owner, token_id_param(&args.token_id)];
+            match client.call_inferred(&contract, "balanceOf", &params).await {
+                Ok(outputs) => print_call_outputs("balanceOf", &params, outputs),
+                Err(e) => exit_with_error(e.context(format!("Error querying balance of token id {}", args.token_id))),
+            }
+        },
+        Erc1155Command::BalanceOfBatch(args) => {
+            // a single owner applies to all token ids
+            let owners = match args.owners.len() {
+                1 => vec![args.owners[0].clone(); args.ids.len()],
+                n if n == args.ids.len() => args.owners,
+                n => exit_with_error(CrunnerError::InvalidArgument(format!("Error, got {} owners for {} token ids; supply either one owner, or one per token id", n, args.ids.len()))),
+            };
+            let contract = standard_contract(client, &args.token, TokenStandard::Erc1155).await;
+            let params = [array_param("addr", &owners), array_param("u256", &args.ids)];
+            let balances = match client.call_inferred(&contract, "balanceOfBatch", &params).await {
+                Ok(outputs) => match outputs.into_iter().next() {
+                    Some((_, Token::Array(tokens))) if tokens.len() == args.ids.len() => tokens,
+                    _ => exit_with_error(CrunnerError::AbiError("Error, balanceOfBatch must return one balance per token id".to_owned())),
+                },
+                Err(e) => exit_with_error(e.context("Error querying balances of token ids")),
+            };
+            print_batch_balances(&owners, &args.ids, &balances);
+        },
+        Erc1155Command::SafeTransferFrom(args) => {
+            if args.ids.len() != args.amounts.len() {
+                exit_with_error(CrunnerError::InvalidArgument(format!("Error, got {} amounts for {} token ids", args.amounts.len(), args.ids.len())));
+            }
+            if is_zero_address(&args.to) {
+                exit_with_error(CrunnerError::InvalidArgument("Error, recipient is the zero address".to_owned()));
+            }
+            if args.to.eq_ignore_ascii_case(&args.token) {
+                exit_with_error(CrunnerError::InvalidArgument("Error, recipient is the token contract itself".to_owned()));
+            }
+            let from = args.owner.unwrap_or_else(|| "{sender}".to_owned());
+            let data = format!("bytes:{}", args.data);
+            if args.ids.len() == 1 {
+                let params = vec![from, args.to, token_id_param(&args.ids[0]), format!("u256:{}", args.amounts[0].trim()), data];
+                send_token(client, TokenStandard::Erc1155, args.token, "safeTransferFrom(address,address,uint256,uint256,bytes)", params, args.tx).await
+            }
+            else {
+                let params = vec![from, args.to, array_param("u256", &args.ids), array_param("u256", &args.amounts), data];
+                send_token(client, TokenStandard::Erc1155, args.token, "safeBatchTransferFrom(address,address,uint256[],uint256[],bytes)", params, args.tx).await
+            }
+        },
+    }
+}
+
+/// Print balance of each pair of owner, and token id; one line each.
+fn print_batch_balances(owners: &[String], ids: &[String], balances: &[Token]) {
+    let rows: Vec<(&String, &String, String)> = owners.iter()
+        .zip(ids.iter())
+        .zip(balances.iter())
+        .map(|((owner, id), balance)| (owner, id, format_token(balance)))
+        .collect();
+
+    if is_json() {
+        let result: Vec<serde_json::Value> = rows.iter()
+            .map(|(owner, id, balance)| json!({ "owner": owner, "id": id.trim(), "balance": balance }))
+            .collect();
+        print_json(&json!({ "function": "balanceOfBatch", "result": result }));
+    }
+    else if is_csv() {
+        print_csv_row(&["timestamp", "owner", "id", "balance"]);
+        let timestamp = timestamp_now();
+        for (owner, id, balance) in rows {
+            print_csv_row(&[timestamp.clone(), owner.clone(), id.trim().to_owned(), balance]);
+        }
+    }
+    else {
+        for (owner, id, balance) in rows {
+            if is_quiet() {
+                println!("{}", balance);
+            }
+            else {
+                println!("{} {} {}", owner, id.trim(), balance);
+            }
+        }
+    }
+}
+
+/// Convert values into array parameter annotated with the item type i.e. `u256[]:1,2`.
+fn array_param(item_type: &str, values: &[String]) -> String {
+    let items: Vec<&str> = values.iter().map(|v| v.trim()).collect();
+    format!("{}[]:{}", item_type, items.join(","))
+}
+
+/// Convert token id into parameter of uint256.
+fn token_id_param(token_id: &str) -> String {
+    format!("u256:{}", token_id.trim())
+}
+
+/// Handle `events` subcommand; query event logs emitted by the target contract.
+async fn handle_events(client: &CrunnerClient, args: EventsArgs) {
+    ensure_contract_address(client, &args.contract_address).await;
+
+    let abi = match load_abi(args.abi_filepath.as_deref()) {
+        Ok(res) => res,
+        Err(e) => exit_with_error(e),
+    };
+    let event = match args.event.as_deref().map(|sig| resolve_event_sig(sig, &abi)) {
+        Some(Ok(res)) => Some(res),
+        Some(Err(e)) => exit_with_error(e),
+        None => None,
+    };
+
+    let event_topic = event.as_ref().map(|(event, _)| event.signature());
+    let decode = |log: &Log| match &event {
+        Some((event, infer_indexed)) => decode_log_as(event, log, *infer_indexed),
+        None => decode_log(&abi, log),
+    };
+    let log_json = |log: &Log| {
+        let mut log_json = json!({
+            "block_number": log.block_number.map(|n| n.as_u64()),
+            "transaction_hash": log.transaction_hash.map(|h| format!("{:?}", h)),
+            "log_index": log.log_index.map(|i| i.as_u64()),
+            "topics": log.topics.iter().map(|t| format!("{:?}", t)).collect::<Vec<String>>(),
+            "data": format!("0x{}", hex::encode(&log.data.0)),
+        });
+        if let Some(event) = decode(log) {
+            log_json["event"] = json!(event.name);
+            log_json["params"] = named_params_json(&event.params);
+        }
+        log_json
+    };
+    let print_csv_log = |log: &Log| {
+        let topics: Vec<String> = log.topics.iter().map(|t| format!("{:?}", t)).collect();
+        print_csv_row(&[
+            log.block_number.map(|n| n.to_string()).unwrap_or_default(),
+            log.transaction_hash.map(|h| format!("{:?}", h)).unwrap_or_default(),
+            log.log_index.map(|i| i.to_string()).unwrap_or_default(),
+            topics.join(" "),
+            format!("0x{}", hex::encode(&log.data.0)),
+            decode(log).map(|event| format_event(&event)).unwrap_or_default(),
+        ]);
+    };
+    let print_text_log = |log: &Log| {
+        let block_number = log.block_number.map(|n| n.to_string()).unwrap_or_default();
+        let tx_hash = log.transaction_hash.map(|h| format!("{:?}", h)).unwrap_or_default();
+        match decode(log) {
+            Some(event) => println!("{} {} {}", block_number, tx_hash, format_event(&event)),
+            None => {
+                let topics: Vec<String> = log.topics.iter().map(|t| format!("{:?}", t)).collect();
+                println!("{} {} {} 0x{}", block_number, tx_hash, topics.join(","), hex::encode(&log.data.0));
+            },
+        }
+    };
+    let csv_header = ["block_number", "transaction_hash", "log_index", "topics", "data", "event"];
+
+    // in follow mode, logs are printed one per line as they arrive i.e. JSON lines
+    if args.follow {
+        if is_csv() {
+            print_csv_row(&csv_header);
+        }
+        let print_log = |log: &Log| {
+            if is_json() {
+                print_json(&log_json(log));
+            }
+            else if is_csv() {
+                print_csv_log(log);
+            }
+            else {
+                print_text_log(log);
+            }
+        };
+
+        if args.from_block.is_some() {
+            let logs = client.logs(&args.contract_address, event_topic, args.from_block, None).await;
+            clear_progress();
+            match logs {
+                Ok(res) => res.iter().for_each(print_log),
+                Err(e) => exit_with_error(e.context(format!("Error querying event logs of {}", &args.contract_address))),
+            }
+        }
+        if let Err(e) = client.follow_logs(&args.contract_address, event_topic, print_log).await {
+            exit_with_error(e.context(format!("Error following event logs of {}", &args.contract_address)));
+        }
+        return;
+    }
+
+    let logs = client.logs(&args.contract_address, event_topic, args.from_block, args.to_block).await;
+    clear_progress();
+    let logs = match logs {
+        Ok(res) => res,
+        Err(e) => exit_with_error(e.context(format!("Error querying event logs of {}", &args.contract_address))),
+    };
+
+    if is_json() {
+        print_json(&serde_json::Value::Array(logs.iter().map(log_json).collect()));
+        return;
+    }
+
+    if is_csv() {
+        print_csv_row(&csv_header);
+        logs.iter().for_each(print_csv_log);
+        return;
+    }
+
+    logs.iter().for_each(print_text_log);
 }
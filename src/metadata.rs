@@ -0,0 +1,71 @@
+use crate::error::CrunnerError;
+use base64::Engine;
+
+/// Default IPFS gateway to fetch `ipfs://` URIs through
+pub static DEFAULT_IPFS_GATEWAY: &str = "https://ipfs.io/ipfs/";
+
+/// Convert token URI into URL fetchable over HTTP; `ipfs://` URI is fetched through
+/// the IPFS gateway, others are left as is.
+///
+/// # Arguments
+/// * `uri` - token URI as returned by the token contract
+/// * `ipfs_gateway` - base URL of IPFS gateway i.e. `https://ipfs.io/ipfs/`
+pub fn token_uri_to_url(uri: &str, ipfs_gateway: &str) -> String {
+    match uri.strip_prefix("ipfs://") {
+        Some(path) => {
+            let path = path.strip_prefix("ipfs/").unwrap_or(path);
+            format!("{}/{}", ipfs_gateway.trim_end_matches('/'), path)
+        },
+        None => uri.to_owned(),
+    }
+}
+
+/// Fetch metadata JSON of token from its URI; either `http(s)://`, `ipfs://`, or
+/// inline `data:application/json` URI (base64-encoded or not).
+///
+/// # Arguments
+/// * `uri` - token URI as returned by the token contract
+/// * `ipfs_gateway` - base URL of IPFS gateway to fetch `ipfs://` URI through
+pub async fn fetch_token_metadata(uri: &str, ipfs_gateway: &str) -> Result<serde_json::Value, CrunnerError> {
+    if let Some(data) = uri.strip_prefix("data:") {
+        return decode_data_uri(data);
+    }
+
+    let url = token_uri_to_url(uri, ipfs_gateway);
+    if !url.starts_with("http://") && !url.starts_with("https://") {
+        return Err(CrunnerError::InvalidArgument(format!("Error, unsupported scheme of token URI '{}'", uri)));
+    }
+    let resp = match reqwest::Client::new().get(&url).send().await {
+        Ok(res) => res,
+        Err(e) => return Err(CrunnerError::RpcError(format!("Error requesting token metadata from {}; err={}", url, e))),
+    };
+    if !resp.status().is_success() {
+        return Err(CrunnerError::RpcError(format!("Error requesting token metadata from {}; status={}", url, resp.status())));
+    }
+    match resp.json().await {
+        Ok(res) => Ok(res),
+        Err(e) => Err(CrunnerError::RpcError(format!("Error parsing token metadata from {}; err={}", url, e))),
+    }
+}
+
+/// Decode JSON inlined in data URI (without `data:` prefix) i.e.
+/// `application/json;base64,eyJuYW1lIjoi...`.
+fn decode_data_uri(data: &str) -> Result<serde_json::Value, CrunnerError> {
+    let (media_type, payload) = match data.split_once(',') {
+        Some(res) => res,
+        None => return Err(CrunnerError::InvalidArgument("Error, malformed data URI of token metadata".to_owned())),
+    };
+    let bytes = if media_type.ends_with(";base64") {
+        match base64::engine::general_purpose::STANDARD.decode(payload) {
+            Ok(res) => res,
+            Err(e) => return Err(CrunnerError::InvalidArgument(format!("Error decoding base64 data URI of token metadata; err={}", e))),
+        }
+    }
+    else {
+        payload.as_bytes().to_vec()
+    };
+    match serde_json::from_slice(&bytes) {
+        Ok(res) => Ok(res),
+        Err(e) => Err(CrunnerError::InvalidArgument(format!("Error parsing token metadata in data URI; err={}", e))),
+    }
+}
@@ -0,0 +1,169 @@
+use crate::error::CrunnerError;
+use crate::json_store;
+use crate::transport::CrunnerTransport;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use web3::types::{Address, BlockNumber, U256};
+use web3::Web3;
+
+/// Name of the nonce store as mentioned in error messages
+static STORE_NAME: &str = "nonce store";
+
+/// Age in seconds after which tracked nonce on disk is considered stale, and the
+/// node's pending transaction count is trusted instead. Transactions not seen by
+/// the node by then are likely dropped, and would leave a nonce gap otherwise.
+static STALE_SECS: u64 = 300;
+
+/// Tracked nonce of a sender as stored on disk
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct TrackedNonce {
+    /// Next nonce to allocate
+    next_nonce: U256,
+
+    /// Unix timestamp in seconds of the last allocation
+    updated_at: u64,
+}
+
+/// Allocate sequential nonces per sender address so that several transactions can
+/// be sent quickly without waiting for each one to be seen by the node as pending.
+/// Allocated nonce is reconciled with the node's pending transaction count; the
+/// greater one wins. Tracked nonces can optionally be persisted on disk to be
+/// shared across runs.
+pub struct NonceManager {
+    next_nonces: Mutex<HashMap<Address, U256>>,
+    store: Option<PathBuf>,
+}
+
+impl Default for NonceManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NonceManager {
+    /// Create a nonce manager tracking nonces only in memory.
+    pub fn new() -> Self {
+        Self {
+            next_nonces: Mutex::new(HashMap::new()),
+            store: None,
+        }
+    }
+
+    /// Persist tracked nonces in the specified JSON file keyed by chain id, and
+    /// sender address.
+    ///
+    /// # Arguments
+    /// * `filepath` - filepath of the nonce store
+    pub fn with_store(mut self, filepath: &Path) -> Self {
+        self.store = Some(filepath.to_path_buf());
+        self
+    }
+
+    /// Get the default filepath of nonce store which is `~/.config/crunner/nonces.json`.
+    pub fn default_store_filepath() -> Option<PathBuf> {
+        dirs::home_dir().map(|home| home.join(".config").join("crunner").join("nonces.json"))
+    }
+
+    /// Allocate the next nonce of the sender.
+    ///
+    /// # Arguments
+    /// * `web3` - web3 instance
+    /// * `address` - address of the sender
+    pub async fn next_nonce(&self, web3: &Web3<CrunnerTransport>, address: Address) -> Result<U256, CrunnerError> {
+        let pending = match web3.eth().transaction_count(address, Some(BlockNumber::Pending)).await {
+            Ok(res) => res,
+            Err(e) => return Err(CrunnerError::RpcError(format!("Error querying nonce of {:?}; err={}", address, e))),
+        };
+        let store_key = match self.store {
+            Some(_) => match web3.eth().chain_id().await {
+                Ok(res) => Some(format!("{}:{:?}", res, address)),
+                Err(e) => return Err(CrunnerError::RpcError(format!("Error querying chain id; err={}", e))),
+            },
+            None => None,
+        };
+
+        // other processes sharing the store must not allocate the same nonce in between;
+        // store lock is taken before the in-memory one as it may block for long
+        let _store_lock = match &self.store {
+            Some(filepath) => Some(lock_store(filepath).await?),
+            None => None,
+        };
+        let mut next_nonces = self.lock()?;
+        let mut nonce = pending.max(next_nonces.get(&address).copied().unwrap_or_default());
+        if let (Some(filepath), Some(store_key)) = (&self.store, &store_key) {
+            let mut tracked: BTreeMap<String, TrackedNonce> = json_store::read(filepath, STORE_NAME)?;
+            let now = json_store::unix_now();
+            if let Some(entry) = tracked.get(store_key) {
+                if now.saturating_sub(entry.updated_at) < STALE_SECS {
+                    nonce = nonce.max(entry.next_nonce);
+                }
+            }
+            tracked.insert(store_key.clone(), TrackedNonce { next_nonce: nonce + 1, updated_at: now });
+            json_store::write(filepath, STORE_NAME, &tracked)?;
+        }
+        next_nonces.insert(address, nonce + 1);
+
+        tracing::debug!(address = ?address, %pending, %nonce, "allocated nonce");
+        Ok(nonce)
+    }
+
+    /// Release the allocated nonce after the transaction using it failed to be sent,
+    /// so it can be allocated again. It has no effect if a later nonce has been
+    /// allocated since.
+    ///
+    /// # Arguments
+    /// * `web3` - web3 instance
+    /// * `address` - address of the sender
+    /// * `nonce` - nonce to release
+    pub async fn release(&self, web3: &Web3<CrunnerTransport>, address: Address, nonce: U256) -> Result<(), CrunnerError> {
+        let store_key = match self.store {
+            Some(_) => match web3.eth().chain_id().await {
+                Ok(res) => Some(format!("{}:{:?}", res, address)),
+                Err(e) => return Err(CrunnerError::RpcError(format!("Error querying chain id; err={}", e))),
+            },
+            None => None,
+        };
+
+        let _store_lock = match &self.store {
+            Some(filepath) => Some(lock_store(filepath).await?),
+            None => None,
+        };
+        let mut next_nonces = self.lock()?;
+        if next_nonces.get(&address) == Some(&(nonce + 1)) {
+            next_nonces.insert(address, nonce);
+        }
+        if let (Some(filepath), Some(store_key)) = (&self.store, &store_key) {
+            let mut tracked: BTreeMap<String, TrackedNonce> = json_store::read(filepath, STORE_NAME)?;
+            if let Some(entry) = tracked.get_mut(store_key) {
+                if entry.next_nonce == nonce + 1 {
+                    entry.next_nonce = nonce;
+                    json_store::write(filepath, STORE_NAME, &tracked)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn lock(&self) -> Result<std::sync::MutexGuard<'_, HashMap<Address, U256>>, CrunnerError> {
+        match self.next_nonces.lock() {
+            Ok(res) => Ok(res),
+            Err(_) => Err(CrunnerError::ConfigError("Error, nonce manager is poisoned".to_owned())),
+        }
+    }
+}
+
+/// Lock the nonce store across processes on the blocking thread pool, so that
+/// waiting for other processes doesn't stall the async runtime.
+///
+/// # Arguments
+/// * `filepath` - filepath of the nonce store
+async fn lock_store(filepath: &Path) -> Result<File, CrunnerError> {
+    let filepath = filepath.to_path_buf();
+    match tokio::task::spawn_blocking(move || json_store::lock(&filepath, STORE_NAME)).await {
+        Ok(res) => res,
+        Err(e) => Err(CrunnerError::ConfigError(format!("Error locking {}; err={}", STORE_NAME, e))),
+    }
+}
@@ -0,0 +1,215 @@
+use clap::ArgEnum;
+use crunner::CrunnerError;
+use crunner::types::{ConfirmationProgress, LogScanProgress};
+use std::io::IsTerminal;
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tracing_subscriber::EnvFilter;
+
+/// Format of results printed on stdout, and errors printed on stderr
+#[derive(Debug, Clone, Copy, PartialEq, ArgEnum)]
+pub enum OutputFormat {
+    /// Human-readable text
+    Text,
+
+    /// Machine-readable JSON
+    Json,
+
+    /// CSV with header row; handy to be piped into spreadsheets
+    Csv,
+}
+
+/// Format of logs printed on stderr
+#[derive(Debug, Clone, Copy, PartialEq, ArgEnum)]
+pub enum LogFormat {
+    /// Human-readable text
+    Pretty,
+
+    /// One JSON object per line
+    Json,
+}
+
+/// Output format selected for this run; set once at startup
+static OUTPUT_FORMAT: OnceLock<OutputFormat> = OnceLock::new();
+
+/// Hook amending error before it's printed on exit; set once at startup
+static ERROR_HOOK: OnceLock<fn(CrunnerError) -> CrunnerError> = OnceLock::new();
+
+/// Whether or not to print only the raw value in text output
+static QUIET: AtomicBool = AtomicBool::new(false);
+
+/// Whether or not a progress line is currently shown on stderr
+static PROGRESS_SHOWN: AtomicBool = AtomicBool::new(false);
+
+/// Set output format for this run; subsequent calls have no effect.
+///
+/// # Arguments
+/// * `format` - output format
+pub fn set_output_format(format: OutputFormat) {
+    let _ = OUTPUT_FORMAT.set(format);
+}
+
+/// Get output format for this run; text if not set.
+pub fn output_format() -> OutputFormat {
+    *OUTPUT_FORMAT.get().unwrap_or(&OutputFormat::Text)
+}
+
+/// Set hook amending error before it's printed by `exit_with_error` i.e. to look up
+/// signature of unknown custom error; subsequent calls have no effect.
+///
+/// # Arguments
+/// * `hook` - function returning the amended error
+pub fn set_error_hook(hook: fn(CrunnerError) -> CrunnerError) {
+    let _ = ERROR_HOOK.set(hook);
+}
+
+/// Set whether or not to print only the raw value i.e. returned value of getter
+/// method, or transaction hash of setter method in text output.
+///
+/// # Arguments
+/// * `quiet` - true to print only the raw value
+pub fn set_quiet(quiet: bool) {
+    QUIET.store(quiet, Ordering::Relaxed);
+}
+
+/// Initialize logging on stderr. `RUST_LOG` takes precedence over verbosity level
+/// if set i.e. `RUST_LOG=crunner=trace`.
+///
+/// # Arguments
+/// * `verbosity` - verbosity level; 0 logs only warnings, 1 logs requests, 2 logs
+///   raw responses as well
+/// * `log_format` - format of logs
+pub fn init_logging(verbosity: u8, log_format: LogFormat) {
+    let filter = match EnvFilter::try_from_default_env() {
+        Ok(res) => res,
+        Err(_) => match verbosity {
+            0 => EnvFilter::new("warn"),
+            1 => EnvFilter::new("warn,crunner=debug"),
+            _ => EnvFilter::new("warn,crunner=trace"),
+        },
+    };
+
+    let builder = tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(std::io::stderr)
+        .with_ansi(std::io::stderr().is_terminal());
+    match log_format {
+        LogFormat::Pretty => builder.init(),
+        LogFormat::Json => builder.json().init(),
+    }
+}
+
+/// Check whether only the raw value should be printed.
+pub fn is_quiet() -> bool {
+    QUIET.load(Ordering::Relaxed)
+}
+
+/// Check whether results should be printed as JSON.
+pub fn is_json() -> bool {
+    output_format() == OutputFormat::Json
+}
+
+/// Check whether results should be printed as CSV.
+pub fn is_csv() -> bool {
+    output_format() == OutputFormat::Csv
+}
+
+/// Print JSON value as a single line on stdout.
+///
+/// # Arguments
+/// * `value` - JSON value to print
+pub fn print_json(value: &serde_json::Value) {
+    println!("{}", value);
+}
+
+/// Print a CSV row on stdout; fields are quoted if necessary.
+///
+/// # Arguments
+/// * `fields` - fields of the row
+pub fn print_csv_row<T: AsRef<str>>(fields: &[T]) {
+    let escaped: Vec<String> = fields.iter().map(|f| escape_csv_field(f.as_ref())).collect();
+    println!("{}", escaped.join(","));
+}
+
+/// Quote CSV field if it contains comma, quote, or newline.
+fn escape_csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    }
+    else {
+        field.to_owned()
+    }
+}
+
+/// Get the current time in RFC 3339 format i.e. `2022-05-01T08:00:00Z` for CSV rows.
+pub fn timestamp_now() -> String {
+    humantime::format_rfc3339_seconds(std::time::SystemTime::now()).to_string()
+}
+
+/// Show progress of waiting for block confirmations as a single line on stderr,
+/// updated in place. It is shown only if stderr is a terminal, and not in quiet mode.
+///
+/// # Arguments
+/// * `progress` - progress of waiting for block confirmations
+pub fn print_confirmation_progress(progress: &ConfirmationProgress) {
+    if is_quiet() || !std::io::stderr().is_terminal() {
+        return;
+    }
+
+    let status = match progress.confirmations {
+        Some(confirmations) => format!("{}/{} confirmations", confirmations.min(progress.target), progress.target),
+        None => "pending".to_owned(),
+    };
+    eprint!("\r\x1b[2KWaiting for transaction {:?}: {}, {}s elapsed", progress.tx_hash, status, progress.elapsed.as_secs());
+    PROGRESS_SHOWN.store(true, Ordering::Relaxed);
+}
+
+/// Show progress of scanning event logs as a single line on stderr, updated in
+/// place. It is shown only if stderr is a terminal, and not in quiet mode.
+///
+/// # Arguments
+/// * `progress` - progress of scanning event logs
+pub fn print_log_scan_progress(progress: &LogScanProgress) {
+    if is_quiet() || !std::io::stderr().is_terminal() || progress.scanned_block >= progress.to_block {
+        return;
+    }
+
+    let total = progress.to_block - progress.from_block + 1;
+    let scanned = progress.scanned_block - progress.from_block + 1;
+    eprint!("\r\x1b[2KScanning blocks {}-{}: {}/{} ({}%), {} logs found", progress.from_block, progress.to_block, scanned, total, scanned * 100 / total, progress.logs);
+    PROGRESS_SHOWN.store(true, Ordering::Relaxed);
+}
+
+/// Clear progress line on stderr (if any) so that subsequent output starts on a fresh line.
+pub fn clear_progress() {
+    if PROGRESS_SHOWN.swap(false, Ordering::Relaxed) {
+        eprint!("\r\x1b[2K");
+    }
+}
+
+/// Print error on stderr in the selected output format without exiting the program
+/// i.e. for a failure which shouldn't stop a long-running operation.
+///
+/// # Arguments
+/// * `err` - error
+pub fn print_error(err: &CrunnerError) {
+    clear_progress();
+    match output_format() {
+        OutputFormat::Json => eprintln!("{}", serde_json::json!({ "error": err.to_string(), "kind": err.kind(), "exit_code": err.exit_code() })),
+        OutputFormat::Text | OutputFormat::Csv => eprintln!("{}", err),
+    }
+}
+
+/// Print error on stderr in the selected output format then exit the program
+/// with exit code of the error's category.
+///
+/// # Arguments
+/// * `err` - error
+pub fn exit_with_error(err: CrunnerError) -> ! {
+    let err = match ERROR_HOOK.get() {
+        Some(hook) => hook(err),
+        None => err,
+    };
+    print_error(&err);
+    std::process::exit(err.exit_code());
+}
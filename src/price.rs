@@ -0,0 +1,134 @@
+use crate::chains::chain_config;
+use crate::error::CrunnerError;
+use crate::transport::CrunnerTransport;
+use crate::types::ChainType;
+use crate::util::{chain_name, get_address_from_str, unit_str};
+use ethabi::{ParamType, Token};
+use std::str::FromStr;
+use web3::types::{Address, Bytes, CallRequest, U256};
+use web3::Web3;
+
+/// Selector of Chainlink aggregator's `latestRoundData()`
+static LATEST_ROUND_DATA_SELECTOR: [u8; 4] = [0xfe, 0xaf, 0x96, 0x8c];
+
+/// Selector of Chainlink aggregator's `decimals()`
+static DECIMALS_SELECTOR: [u8; 4] = [0x31, 0x3c, 0xe5, 0x67];
+
+/// Source of the price of native currency in USD
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PriceSource {
+    /// Chainlink price feed (aggregator) of native currency / USD on the chain
+    Chainlink(Address),
+
+    /// HTTP price API; `{symbol}` in the url is replaced with symbol of native currency
+    Http(String),
+}
+
+/// Get address of Chainlink price feed of native currency / USD on the chain; either
+/// the known one of built-in chain, or `price_feed` of user-defined chain.
+///
+/// # Arguments
+/// * `chain` - `ChainType`
+pub fn default_price_feed(chain: ChainType) -> Result<Address, CrunnerError> {
+    let feed = match chain {
+        ChainType::BSC => "0x0567F2323251f0Aab15c8dFb1967E4e8A7D42aeE",
+        ChainType::Ethereum => "0x5f4eC3Df9cbd43714FE2740f5E3616155c5b8419",
+        ChainType::Polygon => "0xAB594600376Ec9fD91F8e1dC08e0aB9C3Bf5aE1c",
+        ChainType::Arbitrum => "0x639Fe6ab55C921f74e7fac1ee960C0B6293ba612",
+        ChainType::Optimism => "0x13e3Ee699D1909E989722E753853AE30b17e08c5",
+        ChainType::Base => "0x71041dddad3595F9CEd3DcCFBe3D1F4b0a16Bb70",
+        ChainType::Avalanche => "0x0A77230d17318075983913bC2145DB16C7366156",
+        ChainType::Custom(index) => match chain_config(index).and_then(|c| c.price_feed.as_deref()) {
+            Some(res) => return get_address_from_str(res),
+            None => return Err(CrunnerError::ConfigError(format!("Error, chain '{}' has no price feed configured", chain_name(chain)))),
+        },
+        _ => return Err(CrunnerError::ConfigError(format!("Error, no known price feed on chain '{}'", chain_name(chain)))),
+    };
+    match Address::from_str(feed) {
+        Ok(res) => Ok(res),
+        Err(e) => Err(CrunnerError::ConfigError(format!("Error parsing price feed address '{}'; err={}", feed, e))),
+    }
+}
+
+/// Query the price of native currency in USD.
+///
+/// # Arguments
+/// * `web3` - web3 instance
+/// * `chain` - `ChainType` whose native currency to query the price of
+/// * `source` - source of the price
+pub async fn native_usd_price(web3: &Web3<CrunnerTransport>, chain: ChainType, source: &PriceSource) -> Result<f64, CrunnerError> {
+    match source {
+        PriceSource::Chainlink(feed) => chainlink_price(web3, *feed).await,
+        PriceSource::Http(url) => http_price(&url.replace("{symbol}", unit_str(chain))).await,
+    }
+}
+
+/// Query the latest answer of Chainlink price feed scaled by its decimals.
+async fn chainlink_price(web3: &Web3<CrunnerTransport>, feed: Address) -> Result<f64, CrunnerError> {
+    let call = |selector: &[u8; 4]| CallRequest {
+        to: Some(feed),
+        data: Some(Bytes(selector.to_vec())),
+        ..Default::default()
+    };
+
+    let round_data = match web3.eth().call(call(&LATEST_ROUND_DATA_SELECTOR), None).await {
+        Ok(res) => res,
+        Err(e) => return Err(CrunnerError::RpcError(format!("Error querying latest round data of price feed {:?}; err={}", feed, e))),
+    };
+    let decimals = match web3.eth().call(call(&DECIMALS_SELECTOR), None).await {
+        Ok(res) => res,
+        Err(e) => return Err(CrunnerError::RpcError(format!("Error querying decimals of price feed {:?}; err={}", feed, e))),
+    };
+
+    // (roundId, answer, startedAt, updatedAt, answeredInRound)
+    let round_types = [ParamType::Uint(80), ParamType::Int(256), ParamType::Uint(256), ParamType::Uint(256), ParamType::Uint(80)];
+    let answer = match ethabi::decode(&round_types, &round_data.0) {
+        Ok(tokens) => match tokens.get(1) {
+            Some(Token::Int(res)) => *res,
+            _ => U256::zero(),
+        },
+        Err(e) => return Err(CrunnerError::AbiError(format!("Error decoding latest round data of price feed {:?}; err={}", feed, e))),
+    };
+    let decimals = match ethabi::decode(&[ParamType::Uint(8)], &decimals.0) {
+        Ok(tokens) => match tokens.first() {
+            Some(Token::Uint(res)) => res.low_u32(),
+            _ => 0,
+        },
+        Err(e) => return Err(CrunnerError::AbiError(format!("Error decoding decimals of price feed {:?}; err={}", feed, e))),
+    };
+
+    // answer is int256; anything non-positive, or absurdly large is not a valid price
+    if answer.is_zero() || answer.bits() > 128 {
+        return Err(CrunnerError::RpcError(format!("Error, price feed {:?} returned invalid answer; answer={}", feed, answer)));
+    }
+    Ok(answer.low_u128() as f64 / 10_f64.powi(decimals as i32))
+}
+
+/// Query the price from HTTP price API. Its JSON response is either a number, or an
+/// object with `usd`, `price`, or `amount` field (as number, or numeric string) at
+/// its top level, or nested one level deep i.e. `{"ethereum":{"usd":2500.1}}`.
+async fn http_price(url: &str) -> Result<f64, CrunnerError> {
+    let resp = match reqwest::Client::new().get(url).send().await {
+        Ok(res) => res,
+        Err(e) => return Err(CrunnerError::RpcError(format!("Error requesting price from {}; err={}", url, e))),
+    };
+    let body: serde_json::Value = match resp.json().await {
+        Ok(res) => res,
+        Err(e) => return Err(CrunnerError::RpcError(format!("Error parsing price response from {}; err={}", url, e))),
+    };
+
+    let as_price = |value: &serde_json::Value| match value {
+        serde_json::Value::Number(n) => n.as_f64(),
+        serde_json::Value::String(s) => s.parse::<f64>().ok(),
+        _ => None,
+    };
+    let field_price = |value: &serde_json::Value| ["usd", "price", "amount"].iter().find_map(|field| value.get(field).and_then(as_price));
+    let price = as_price(&body)
+        .or_else(|| field_price(&body))
+        .or_else(|| body.as_object().and_then(|o| o.values().find_map(field_price)));
+
+    match price {
+        Some(res) if res > 0.0 => Ok(res),
+        _ => Err(CrunnerError::RpcError(format!("Error, no price found in response from {}; response={}", url, body))),
+    }
+}
@@ -0,0 +1,320 @@
+use crate::output::clear_progress;
+use crate::set_fee_options;
+use crunner::{CrunnerClient, CrunnerError};
+use crunner::transport::CrunnerTransport;
+use crunner::types::ChainType;
+use crunner::util::{format_token, parse_amount};
+use crunner::wallet::secret_key_from_env;
+use ethabi::Token;
+use rhai::{Array, Dynamic, Engine, EvalAltResult, Map, INT};
+use secp256k1::SecretKey;
+use std::cell::RefCell;
+use std::future::Future;
+use std::rc::Rc;
+use web3::contract::{Contract, Options};
+use web3::signing::{Key, SecretKeyRef};
+use web3::types::U256;
+
+type ScriptResult<T> = Result<T, Box<EvalAltResult>>;
+type UintComparison = fn(&U256, &U256) -> bool;
+type UintArithmetic = fn(U256, U256) -> Option<U256>;
+
+/// Comparison operators registered for `Uint` in script
+static UINT_COMPARISONS: [(&str, UintComparison); 6] = [
+    ("<", |a, b| a < b),
+    ("<=", |a, b| a <= b),
+    (">", |a, b| a > b),
+    (">=", |a, b| a >= b),
+    ("==", |a, b| a == b),
+    ("!=", |a, b| a != b),
+];
+
+/// Arithmetic operators registered for `Uint` in script; `None` on overflow, or
+/// division by zero
+static UINT_ARITHMETICS: [(&str, UintArithmetic); 5] = [
+    ("+", |a, b| a.checked_add(b)),
+    ("-", |a, b| a.checked_sub(b)),
+    ("*", |a, b| a.checked_mul(b)),
+    ("/", |a, b| a.checked_div(b)),
+    ("%", |a, b| a.checked_rem(b)),
+];
+
+/// Chain, and signing key which functions exposed to script work with
+pub struct ScriptContext {
+    client: CrunnerClient,
+    abi_filepath: Option<String>,
+    prvk: Option<SecretKey>,
+    confirmations: u64,
+
+    /// Error of the latest failed function; kept to exit with its category if it
+    /// aborts the script
+    last_error: RefCell<Option<CrunnerError>>,
+}
+
+impl ScriptContext {
+    /// Create context of script.
+    ///
+    /// # Arguments
+    /// * `client` - client of the chain
+    /// * `abi_filepath` - optional ABI json filepath merged with the built-in ABI for all contracts
+    /// * `prvk` - secret key to sign transactions with; CRUNNER_SETTER_SECRETKEY is used if `None`
+    /// * `confirmations` - number of block confirmations to wait for each sent transaction
+    pub fn new(client: CrunnerClient, abi_filepath: Option<String>, prvk: Option<SecretKey>, confirmations: u64) -> Self {
+        Self {
+            client,
+            abi_filepath,
+            prvk,
+            confirmations,
+            last_error: RefCell::new(None),
+        }
+    }
+
+    /// Get error to exit with when the script aborted with `err`. Errors raised by
+    /// functions exposed to script keep their category.
+    ///
+    /// # Arguments
+    /// * `err` - error of the script
+    /// * `script` - filepath of the script
+    pub fn script_error(&self, err: &EvalAltResult, script: &str) -> CrunnerError {
+        let context = if err.position().is_none() {
+            format!("Error running script {}", script)
+        }
+        else {
+            format!("Error running script {} ({})", script, err.position())
+        };
+        if let EvalAltResult::ErrorRuntime(value, _) = err.unwrap_inner() {
+            if let Some(e) = self.last_error.borrow_mut().take() {
+                if value.to_string() == e.to_string() {
+                    return e.context(context);
+                }
+            }
+        }
+        CrunnerError::InvalidArgument(format!("{}; err={}", context, err))
+    }
+
+    /// Wait for the operation, and raise its error in script.
+    fn run<T>(&self, fut: impl Future<Output = Result<T, CrunnerError>>) -> ScriptResult<T> {
+        // script runs synchronously within the runtime; block the current thread
+        // without stalling other tasks
+        let res = tokio::task::block_in_place(|| tokio::runtime::Handle::current().block_on(fut));
+        res.map_err(|e| self.raise(e))
+    }
+
+    /// Raise the error in script.
+    fn raise(&self, e: CrunnerError) -> Box<EvalAltResult> {
+        let msg = e.to_string();
+        *self.last_error.borrow_mut() = Some(e);
+        msg.into()
+    }
+
+    /// Create contract instance, and select the function to call along with its parameters.
+    async fn prepare(&self, address: &str, function: &str, params: &Array) -> Result<(Contract<CrunnerTransport>, String, Vec<String>), CrunnerError> {
+        let address = self.client.resolve_address(address).await?;
+        let contract = self.client.contract(&address, self.abi_filepath.as_deref())?;
+        let (fn_name, fn_sig) = match function.split_once('(') {
+            Some((fn_name, _)) => (fn_name.trim().to_owned(), Some(function)),
+            None => (function.to_owned(), None),
+        };
+
+        let params: Vec<String> = params.iter().map(param_string).collect::<Result<Vec<String>, CrunnerError>>()?;
+        let params = self.client.resolve_params(params.as_slice()).await?;
+        let contract = self.client.select_overload(&contract, &fn_name, fn_sig, params.as_slice())?;
+        Ok((contract, fn_name, params))
+    }
+
+    async fn call(&self, address: &str, function: &str, params: &Array) -> Result<Dynamic, CrunnerError> {
+        let (contract, fn_name, params) = self.prepare(address, function, params).await?;
+        let outputs = match self.client.call_inferred(&contract, &fn_name, params.as_slice()).await {
+            Ok(res) => res,
+            Err(e) => return Err(e.context(format!("Error querying of method '{}'", fn_name))),
+        };
+
+        let mut values: Vec<Dynamic> = outputs.into_iter().map(|(_, token)| token_to_dynamic(token)).collect();
+        if values.len() == 1 {
+            Ok(values.remove(0))
+        }
+        else {
+            Ok(Dynamic::from_array(values))
+        }
+    }
+
+    async fn send(&self, address: &str, function: &str, params: &Array, value: Option<U256>) -> Result<Map, CrunnerError> {
+        let (contract, fn_name, params) = self.prepare(address, function, params).await?;
+        let mut options = Options { value, ..Default::default() };
+        set_fee_options(&self.client, &mut options, None, None, None).await?;
+
+        let tx_receipt_res = if let Some(prvk) = &self.prvk {
+            self.client.send_with_key(&contract, &fn_name, params.as_slice(), options, prvk, self.confirmations).await
+        }
+        else if self.is_unlocked() {
+            self.client.send_unlocked(&contract, &fn_name, params.as_slice(), None, options, self.confirmations).await
+        }
+        else {
+            self.client.send(&contract, &fn_name, params.as_slice(), options, self.confirmations).await
+        };
+        clear_progress();
+        let tx_receipt = match tx_receipt_res {
+            Ok(res) => res,
+            Err(e) => return Err(e.context(format!("Error calling setter method '{}'", fn_name))),
+        };
+        // later steps usually depend on this one; abort unless the script catches it
+        if tx_receipt.status.map(|st| st.is_zero()).unwrap_or(false) {
+            return Err(CrunnerError::TxReverted(format!("Error, transaction {:?} reverted", tx_receipt.transaction_hash)));
+        }
+
+        let mut receipt = Map::new();
+        receipt.insert("tx_hash".into(), format!("{:?}", tx_receipt.transaction_hash).into());
+        receipt.insert("block_number".into(), (tx_receipt.block_number.unwrap_or_default().as_u64() as INT).into());
+        receipt.insert("gas_used".into(), Dynamic::from(tx_receipt.gas_used.unwrap_or_default()));
+        Ok(receipt)
+    }
+
+    async fn estimate(&self, address: &str, function: &str, params: &Array, value: Option<U256>) -> Result<U256, CrunnerError> {
+        let (contract, fn_name, params) = self.prepare(address, function, params).await?;
+        let options = Options { value, ..Default::default() };
+        let from = self.sender().await?;
+        match self.client.estimate_gas(&contract, &fn_name, params.as_slice(), &from, options).await {
+            Ok(res) => Ok(res),
+            Err(e) => Err(e.context(format!("Error estimating gas by calling a setter method '{}'", fn_name))),
+        }
+    }
+
+    async fn balance(&self, address: &str) -> Result<U256, CrunnerError> {
+        let address = self.client.resolve_address(address).await?;
+        self.client.balance(&address).await
+    }
+
+    /// Whether transactions are sent from the first unlocked account of local node.
+    fn is_unlocked(&self) -> bool {
+        matches!(self.client.chain(), ChainType::Local) && std::env::var("CRUNNER_SETTER_SECRETKEY").is_err()
+    }
+
+    /// Get address which sends transactions of the script.
+    async fn sender(&self) -> Result<String, CrunnerError> {
+        if let Some(prvk) = &self.prvk {
+            return Ok(format!("{:?}", SecretKeyRef::new(prvk).address()));
+        }
+        if self.is_unlocked() {
+            let accounts = match self.client.web3().eth().accounts().await {
+                Ok(res) => res,
+                Err(e) => return Err(CrunnerError::RpcError(format!("Error querying accounts of the node; err={}", e))),
+            };
+            return match accounts.first() {
+                Some(account) => Ok(format!("{:?}", account)),
+                None => Err(CrunnerError::ConfigError("Error, the node has no unlocked account to send from".to_owned())),
+            };
+        }
+        let prvk = secret_key_from_env()?;
+        Ok(format!("{:?}", SecretKeyRef::new(&prvk).address()))
+    }
+}
+
+/// Create script engine exposing `get`, `send`, `estimate`, and `balance` functions
+/// bound to the context, along with `Uint` type holding 256-bit unsigned integers.
+/// Getter is exposed as `get` as `call` is reserved by Rhai for calling function pointers.
+///
+/// # Arguments
+/// * `ctx` - context of script
+pub fn create_engine(ctx: Rc<ScriptContext>) -> Engine {
+    let mut engine = Engine::new();
+    register_uint(&mut engine);
+
+    let c = ctx.clone();
+    engine.register_fn("get", move |address: &str, function: &str| c.run(c.call(address, function, &Array::new())));
+    let c = ctx.clone();
+    engine.register_fn("get", move |address: &str, function: &str, params: Array| c.run(c.call(address, function, &params)));
+
+    let c = ctx.clone();
+    engine.register_fn("send", move |address: &str, function: &str, params: Array| c.run(c.send(address, function, &params, None)));
+    let c = ctx.clone();
+    engine.register_fn("send", move |address: &str, function: &str, params: Array, value: &str| {
+        let value = parse_amount(value).map_err(|e| c.raise(e.context("Error parsing value")))?;
+        c.run(c.send(address, function, &params, Some(value)))
+    });
+    let c = ctx.clone();
+    engine.register_fn("send", move |address: &str, function: &str, params: Array, value: U256| c.run(c.send(address, function, &params, Some(value))));
+
+    let c = ctx.clone();
+    engine.register_fn("estimate", move |address: &str, function: &str, params: Array| c.run(c.estimate(address, function, &params, None)));
+    let c = ctx.clone();
+    engine.register_fn("estimate", move |address: &str, function: &str, params: Array, value: &str| {
+        let value = parse_amount(value).map_err(|e| c.raise(e.context("Error parsing value")))?;
+        c.run(c.estimate(address, function, &params, Some(value)))
+    });
+
+    let c = ctx;
+    engine.register_fn("balance", move |address: &str| c.run(c.balance(address)));
+
+    engine
+}
+
+/// Register `Uint` type, its constructors, conversions, and operators.
+fn register_uint(engine: &mut Engine) {
+    engine.register_type_with_name::<U256>("Uint")
+        .register_fn("uint", |value: &str| parse_amount(value).map_err(|e| -> Box<EvalAltResult> { e.to_string().into() }))
+        .register_fn("uint", int_to_uint)
+        .register_fn("to_int", |value: U256| -> ScriptResult<INT> {
+            if value.bits() < INT::BITS as usize {
+                Ok(value.low_u64() as INT)
+            }
+            else {
+                Err(format!("Error, {} is too large for integer", value).into())
+            }
+        })
+        .register_fn("to_string", |value: &mut U256| value.to_string())
+        .register_fn("to_debug", |value: &mut U256| value.to_string());
+
+    // integers on either side are converted to Uint
+    for (op, cmp) in UINT_COMPARISONS {
+        engine.register_fn(op, move |a: U256, b: U256| cmp(&a, &b));
+        engine.register_fn(op, move |a: U256, b: INT| -> ScriptResult<bool> { Ok(cmp(&a, &int_to_uint(b)?)) });
+        engine.register_fn(op, move |a: INT, b: U256| -> ScriptResult<bool> { Ok(cmp(&int_to_uint(a)?, &b)) });
+    }
+    for (op, arith) in UINT_ARITHMETICS {
+        let checked = move |a: U256, b: U256| -> ScriptResult<U256> {
+            match arith(a, b) {
+                Some(res) => Ok(res),
+                None => Err(format!("Error, Uint overflows, or divides by zero; {} {} {}", a, op, b).into()),
+            }
+        };
+        engine.register_fn(op, checked);
+        engine.register_fn(op, move |a: U256, b: INT| checked(a, int_to_uint(b)?));
+        engine.register_fn(op, move |a: INT, b: U256| checked(int_to_uint(a)?, b));
+    }
+}
+
+fn int_to_uint(value: INT) -> ScriptResult<U256> {
+    match u64::try_from(value) {
+        Ok(res) => Ok(U256::from(res)),
+        Err(_) => Err(format!("Error, negative integer {} cannot be Uint", value).into()),
+    }
+}
+
+/// Convert value of script into parameter string as supplied to `--params`.
+fn param_string(value: &Dynamic) -> Result<String, CrunnerError> {
+    if let Some(value) = value.clone().try_cast::<U256>() {
+        return Ok(value.to_string());
+    }
+    if value.is_string() || value.is_int() || value.is_bool() {
+        return Ok(value.to_string());
+    }
+    Err(CrunnerError::InvalidArgument(format!("Error, parameter of type '{}' is not supported; supply string, integer, bool, or Uint, and array as string with type prefix i.e. \"addr[]:0xAAA,0xBBB\"", value.type_name())))
+}
+
+/// Convert returned value into value of script. Unsigned integers become `Uint`,
+/// signed integers become integer if they fit, and others are as printed.
+fn token_to_dynamic(token: Token) -> Dynamic {
+    match token {
+        Token::Uint(value) => Dynamic::from(value),
+        Token::Bool(value) => value.into(),
+        Token::Array(tokens) | Token::FixedArray(tokens) | Token::Tuple(tokens) => Dynamic::from_array(tokens.into_iter().map(token_to_dynamic).collect()),
+        Token::Int(_) => {
+            let formatted = format_token(&token);
+            match formatted.parse::<INT>() {
+                Ok(res) => res.into(),
+                Err(_) => formatted.into(),
+            }
+        },
+        _ => format_token(&token).into(),
+    }
+}
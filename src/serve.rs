@@ -0,0 +1,91 @@
+use crunner::CrunnerError;
+use serde::Deserialize;
+
+/// Contract method exposed as an endpoint of `serve` subcommand
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ServeEndpoint {
+    /// Path of the endpoint i.e. `/usdc/decimals`
+    pub path: String,
+
+    /// Target contract address (or ENS name)
+    pub address: String,
+
+    /// Function name, or signature i.e. `transfer(address,uint256)`
+    #[serde(rename = "fn")]
+    pub function: String,
+
+    /// Leading parameters fixed for the function; parameters of the request are
+    /// appended to them
+    #[serde(default)]
+    pub params: Vec<String>,
+
+    /// ABI filepath describing the function; built-in ABI if not set
+    pub abi: Option<String>,
+
+    /// (setter only) Gas limit of sent transaction; estimated by the node if not set
+    pub gas_limit: Option<u64>,
+
+    /// (setter only) Number of block confirmations to wait for; default to 20, or 0
+    /// on local chain
+    pub block_confirmations: Option<u64>,
+
+    /// (setter only) Maximum amount of native currency a request may send along; in
+    /// Wei, or with unit suffix. Requests with value are rejected if not set.
+    pub max_value: Option<String>,
+}
+
+/// Layout of serve config file
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ServeConfig {
+    /// Getters exposed as GET endpoints
+    #[serde(default)]
+    pub get: Vec<ServeEndpoint>,
+
+    /// Setters exposed as POST endpoints
+    #[serde(default)]
+    pub post: Vec<ServeEndpoint>,
+}
+
+/// Body of request to POST endpoint
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SendRequest {
+    /// Parameters appended to the endpoint's fixed parameters
+    #[serde(default)]
+    pub params: Vec<String>,
+
+    /// Amount of native currency to send along; in Wei, or with unit suffix
+    pub value: Option<String>,
+}
+
+/// Load serve config from TOML file with `[[get]]`, and `[[post]]` tables.
+///
+/// # Arguments
+/// * `filepath` - filepath of serve config file
+pub fn load_serve_config(filepath: &str) -> Result<ServeConfig, CrunnerError> {
+    let content = match std::fs::read_to_string(filepath) {
+        Ok(res) => res,
+        Err(e) => return Err(CrunnerError::ConfigError(format!("Error reading serve config {}; err={}", filepath, e))),
+    };
+    let config: ServeConfig = match toml::from_str(&content) {
+        Ok(res) => res,
+        Err(e) => return Err(CrunnerError::ConfigError(format!("Error parsing serve config {}; err={}", filepath, e))),
+    };
+
+    if config.get.is_empty() && config.post.is_empty() {
+        return Err(CrunnerError::ConfigError(format!("Error, serve config {} has no endpoint", filepath)));
+    }
+    for (method, endpoints) in [("GET", &config.get), ("POST", &config.post)] {
+        for (i, endpoint) in endpoints.iter().enumerate() {
+            if !endpoint.path.starts_with('/') {
+                return Err(CrunnerError::ConfigError(format!("Error, path of {} endpoint '{}' must start with '/'", method, endpoint.path)));
+            }
+            if endpoints[..i].iter().any(|e| e.path == endpoint.path) {
+                return Err(CrunnerError::ConfigError(format!("Error, duplicate {} endpoint '{}'", method, endpoint.path)));
+            }
+        }
+    }
+    Ok(config)
+}
@@ -0,0 +1,143 @@
+use crate::error::CrunnerError;
+use crate::json_store;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Name of the signature cache as mentioned in error messages
+static STORE_NAME: &str = "signature cache";
+
+/// Lookup endpoint of openchain.xyz signature database
+static OPENCHAIN_LOOKUP_URL: &str = "https://api.openchain.xyz/signature-database/v1/lookup";
+
+/// Lookup endpoint of 4byte.directory signature database
+static FOURBYTE_LOOKUP_URL: &str = "https://www.4byte.directory/api/v1/signatures/";
+
+/// Timeout of a lookup request to signature database
+static LOOKUP_TIMEOUT_SECS: u64 = 10;
+
+/// Response of openchain.xyz lookup
+#[derive(Debug, Deserialize)]
+struct OpenchainResponse {
+    ok: bool,
+    result: Option<OpenchainResult>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenchainResult {
+    /// Candidates by selector; null if none is known
+    function: BTreeMap<String, Option<Vec<OpenchainSignature>>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenchainSignature {
+    name: String,
+}
+
+/// Response of 4byte.directory lookup
+#[derive(Debug, Deserialize)]
+struct FourbyteResponse {
+    results: Vec<FourbyteSignature>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FourbyteSignature {
+    text_signature: String,
+}
+
+/// Public signature databases (openchain.xyz, then 4byte.directory) to suggest
+/// candidate signatures of function, or custom error selector not declared in ABI.
+/// Found candidates are cached on disk as they never change; failure to read, or
+/// write the cache never fails the lookup.
+pub struct SignatureDb {
+    store: PathBuf,
+}
+
+impl SignatureDb {
+    /// Create signature database whose lookups are cached in the specified JSON file.
+    ///
+    /// # Arguments
+    /// * `filepath` - filepath of the cache
+    pub fn new(filepath: &Path) -> Self {
+        Self { store: filepath.to_path_buf() }
+    }
+
+    /// Get the default filepath of the cache which is `~/.cache/crunner/signatures.json`
+    /// on Linux, or platform's equivalent.
+    pub fn default_filepath() -> Option<PathBuf> {
+        dirs::cache_dir().map(|dir| dir.join("crunner").join("signatures.json"))
+    }
+
+    /// Look up candidate signatures of the 4-byte selector i.e. `transfer(address,uint256)`;
+    /// empty if neither database knows it. Collided selectors yield several candidates.
+    ///
+    /// # Arguments
+    /// * `selector` - 4-byte selector of function, or custom error
+    pub async fn lookup(&self, selector: &[u8]) -> Result<Vec<String>, CrunnerError> {
+        let selector_hex = format!("0x{}", hex::encode(selector));
+        let mut entries: BTreeMap<String, Vec<String>> = match json_store::read(&self.store, STORE_NAME) {
+            Ok(res) => res,
+            Err(e) => {
+                tracing::debug!(error = %e, "ignored unreadable signature cache");
+                BTreeMap::new()
+            },
+        };
+        if let Some(candidates) = entries.get(&selector_hex) {
+            tracing::debug!(selector = %selector_hex, "signature cache hit");
+            return Ok(candidates.clone());
+        }
+
+        let candidates = match lookup_openchain(&selector_hex).await {
+            Ok(res) if !res.is_empty() => res,
+            Ok(_) => lookup_fourbyte(&selector_hex).await?,
+            Err(e) => {
+                tracing::debug!(error = %e, "falling back to 4byte.directory");
+                lookup_fourbyte(&selector_hex).await?
+            },
+        };
+        // unknown selector may be submitted later, so only found ones are cached
+        if !candidates.is_empty() {
+            entries.insert(selector_hex, candidates.clone());
+            if let Err(e) = json_store::write(&self.store, STORE_NAME, &entries) {
+                tracing::debug!(error = %e, "ignored unwritable signature cache");
+            }
+        }
+        Ok(candidates)
+    }
+}
+
+async fn lookup_openchain(selector_hex: &str) -> Result<Vec<String>, CrunnerError> {
+    let resp: OpenchainResponse = get_json(OPENCHAIN_LOOKUP_URL, &[("function", selector_hex), ("filter", "true")]).await?;
+    if !resp.ok {
+        return Err(CrunnerError::RpcError(format!("Error, openchain.xyz failed looking up selector {}", selector_hex)));
+    }
+    let candidates = resp.result
+        .and_then(|mut result| result.function.remove(selector_hex).flatten())
+        .unwrap_or_default();
+    Ok(candidates.into_iter().map(|s| s.name).collect())
+}
+
+async fn lookup_fourbyte(selector_hex: &str) -> Result<Vec<String>, CrunnerError> {
+    let resp: FourbyteResponse = get_json(FOURBYTE_LOOKUP_URL, &[("hex_signature", selector_hex)]).await?;
+    // oldest submission first as it's most likely the genuine one
+    Ok(resp.results.into_iter().rev().map(|s| s.text_signature).collect())
+}
+
+async fn get_json<T: serde::de::DeserializeOwned>(url: &str, query: &[(&str, &str)]) -> Result<T, CrunnerError> {
+    let req = reqwest::Client::new()
+        .get(url)
+        .query(query)
+        .timeout(Duration::from_secs(LOOKUP_TIMEOUT_SECS));
+    let resp = match req.send().await {
+        Ok(res) => res,
+        Err(e) => return Err(CrunnerError::RpcError(format!("Error requesting signature database {}; err={}", url, e))),
+    };
+    if !resp.status().is_success() {
+        return Err(CrunnerError::RpcError(format!("Error requesting signature database {}; status={}", url, resp.status())));
+    }
+    match resp.json().await {
+        Ok(res) => Ok(res),
+        Err(e) => Err(CrunnerError::RpcError(format!("Error parsing response of signature database {}; err={}", url, e))),
+    }
+}
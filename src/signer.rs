@@ -0,0 +1,314 @@
+use crate::error::CrunnerError;
+use futures::future::BoxFuture;
+use rlp::{Rlp, RlpStream};
+use secp256k1::SecretKey;
+use web3::signing::{keccak256, recover, Key, SecretKeyRef};
+use web3::types::{Address, H256, U256};
+
+/// Type of EIP-2930 (access list) transaction as per EIP-2718
+static EIP2930_TX_TYPE: u8 = 0x01;
+
+/// Type of EIP-1559 transaction as per EIP-2718
+static EIP1559_TX_TYPE: u8 = 0x02;
+
+/// Unsigned transaction to be signed. It is legacy transaction with replay protection
+/// as per EIP-155, or EIP-1559 (type-2) transaction if its fees are set.
+#[derive(Debug, Clone)]
+pub struct UnsignedTx {
+    pub nonce: U256,
+    pub gas_price: U256,
+    pub gas: U256,
+    pub to: Address,
+    pub value: U256,
+    pub data: Vec<u8>,
+    pub chain_id: u64,
+    /// Fees of EIP-1559 transaction i.e. `(max_fee_per_gas, max_priority_fee_per_gas)`;
+    /// `gas_price` is ignored if set
+    pub eip1559_fees: Option<(U256, U256)>,
+}
+
+/// Signature of transaction; `v` already includes chain id as per EIP-155 for legacy
+/// transaction, or it is y-parity for EIP-1559 transaction
+#[derive(Debug, Clone, Copy)]
+pub struct TxSignature {
+    pub v: u64,
+    pub r: H256,
+    pub s: H256,
+}
+
+impl UnsignedTx {
+    /// RLP-encode the transaction as signing payload as per EIP-155, or EIP-1559.
+    pub fn rlp(&self) -> Vec<u8> {
+        if self.eip1559_fees.is_some() {
+            let mut stream = RlpStream::new_list(9);
+            self.append_eip1559_fields(&mut stream);
+            return [&[EIP1559_TX_TYPE], stream.out().as_ref()].concat();
+        }
+
+        let mut stream = RlpStream::new_list(9);
+        self.append_fields(&mut stream);
+        stream.append(&self.chain_id);
+        stream.append(&0u8);
+        stream.append(&0u8);
+        stream.out().to_vec()
+    }
+
+    /// Get `v` of signature from y-parity of the signature as appropriate to type of
+    /// the transaction.
+    ///
+    /// # Arguments
+    /// * `parity` - y-parity of the signature; either 0, or 1
+    pub fn signature_v(&self, parity: u64) -> u64 {
+        if self.eip1559_fees.is_some() {
+            parity
+        }
+        else {
+            self.chain_id * 2 + 35 + parity
+        }
+    }
+
+    /// Get hash of signing payload to be signed.
+    pub fn sighash(&self) -> [u8; 32] {
+        keccak256(&self.rlp())
+    }
+
+    /// Sign the transaction with the local secret key; no RPC is involved.
+    ///
+    /// # Arguments
+    /// * `prvk` - secret key to sign with
+    pub fn sign_with_key(&self, prvk: &SecretKey) -> Result<TxSignature, CrunnerError> {
+        let signature = match SecretKeyRef::new(prvk).sign_message(&self.sighash()) {
+            Ok(res) => res,
+            Err(e) => return Err(CrunnerError::SignerError(format!("Error signing transaction; err={}", e))),
+        };
+        Ok(TxSignature {
+            v: self.signature_v(signature.v),
+            r: signature.r,
+            s: signature.s,
+        })
+    }
+
+    /// RLP-encode the transaction along with its signature; ready to be broadcast.
+    ///
+    /// # Arguments
+    /// * `signature` - signature of the transaction
+    pub fn rlp_signed(&self, signature: &TxSignature) -> Vec<u8> {
+        if self.eip1559_fees.is_some() {
+            let mut stream = RlpStream::new_list(12);
+            self.append_eip1559_fields(&mut stream);
+            stream.append(&signature.v);
+            stream.append(&U256::from_big_endian(signature.r.as_bytes()));
+            stream.append(&U256::from_big_endian(signature.s.as_bytes()));
+            return [&[EIP1559_TX_TYPE], stream.out().as_ref()].concat();
+        }
+
+        let mut stream = RlpStream::new_list(9);
+        self.append_fields(&mut stream);
+        stream.append(&signature.v);
+        stream.append(&U256::from_big_endian(signature.r.as_bytes()));
+        stream.append(&U256::from_big_endian(signature.s.as_bytes()));
+        stream.out().to_vec()
+    }
+
+    fn append_fields(&self, stream: &mut RlpStream) {
+        stream.append(&self.nonce);
+        stream.append(&self.gas_price);
+        stream.append(&self.gas);
+        stream.append(&self.to);
+        stream.append(&self.value);
+        stream.append(&self.data);
+    }
+
+    fn append_eip1559_fields(&self, stream: &mut RlpStream) {
+        let (max_fee_per_gas, max_priority_fee_per_gas) = self.eip1559_fees.unwrap_or_default();
+        stream.append(&self.chain_id);
+        stream.append(&self.nonce);
+        stream.append(&max_priority_fee_per_gas);
+        stream.append(&max_fee_per_gas);
+        stream.append(&self.gas);
+        stream.append(&self.to);
+        stream.append(&self.value);
+        stream.append(&self.data);
+        // empty access list
+        stream.begin_list(0);
+    }
+}
+
+/// Signed raw transaction decoded into its fields; either legacy, EIP-2930, or EIP-1559
+/// transaction
+#[derive(Debug, Clone)]
+pub struct DecodedTx {
+    /// Type of the transaction as per EIP-2718; 0 for legacy transaction
+    pub tx_type: u8,
+
+    /// Chain id the transaction is valid on; `None` for legacy transaction without
+    /// replay protection
+    pub chain_id: Option<u64>,
+    pub nonce: U256,
+
+    /// Gas price of legacy, or EIP-2930 transaction
+    pub gas_price: Option<U256>,
+
+    /// Fees of EIP-1559 transaction i.e. `(max_fee_per_gas, max_priority_fee_per_gas)`
+    pub eip1559_fees: Option<(U256, U256)>,
+    pub gas: U256,
+
+    /// Recipient; `None` for contract creation
+    pub to: Option<Address>,
+    pub value: U256,
+    pub data: Vec<u8>,
+
+    /// Accessed addresses along with their storage keys of EIP-2930, or EIP-1559 transaction
+    pub access_list: Vec<(Address, Vec<H256>)>,
+
+    /// Signature as encoded in the transaction
+    pub signature: TxSignature,
+
+    /// Sender recovered from the signature
+    pub from: Address,
+
+    /// Hash of the transaction
+    pub hash: H256,
+}
+
+impl DecodedTx {
+    /// Decode signed raw transaction i.e. as printed by `send --sign-only`, and recover
+    /// its sender.
+    ///
+    /// # Arguments
+    /// * `raw_tx` - RLP-encoded signed transaction prefixed with its type (if typed)
+    pub fn decode(raw_tx: &[u8]) -> Result<DecodedTx, CrunnerError> {
+        let (tx_type, payload) = match raw_tx.first() {
+            Some(res) if *res == EIP2930_TX_TYPE || *res == EIP1559_TX_TYPE => (*res, &raw_tx[1..]),
+            Some(res) if *res >= 0xc0 => (0, raw_tx),
+            Some(res) => return Err(CrunnerError::InvalidArgument(format!("Error, unsupported transaction type 0x{:02x}", res))),
+            None => return Err(CrunnerError::InvalidArgument("Error, raw transaction is empty".to_owned())),
+        };
+        let rlp = Rlp::new(payload);
+        let decode_err = |e: rlp::DecoderError| CrunnerError::InvalidArgument(format!("Error decoding RLP of raw transaction; err={}", e));
+
+        // signing payload consists of the leading fields; signature is the last 3
+        let field_count = rlp.item_count().map_err(decode_err)?;
+        let expected_count = match tx_type {
+            0 => 9,
+            1 => 11,
+            _ => 12,
+        };
+        if field_count != expected_count {
+            return Err(CrunnerError::InvalidArgument(format!("Error, raw transaction of type {} has {} fields instead of {}", tx_type, field_count, expected_count)));
+        }
+        let signed_count = field_count - 3;
+        let v: u64 = rlp.val_at(signed_count).map_err(decode_err)?;
+        let r: U256 = rlp.val_at(signed_count + 1).map_err(decode_err)?;
+        let s: U256 = rlp.val_at(signed_count + 2).map_err(decode_err)?;
+
+        // fields preceding the common ones i.e. nonce, fees, gas, to, value, and data
+        let offset = if tx_type == 0 { 0 } else { 1 };
+        let chain_id = match tx_type {
+            0 if v >= 35 => Some((v - 35) / 2),
+            0 => None,
+            _ => Some(rlp.val_at(0).map_err(decode_err)?),
+        };
+        let (gas_price, eip1559_fees, gas_index) = if tx_type == EIP1559_TX_TYPE {
+            let max_priority_fee: U256 = rlp.val_at(offset + 1).map_err(decode_err)?;
+            let max_fee: U256 = rlp.val_at(offset + 2).map_err(decode_err)?;
+            (None, Some((max_fee, max_priority_fee)), offset + 3)
+        }
+        else {
+            (Some(rlp.val_at(offset + 1).map_err(decode_err)?), None, offset + 2)
+        };
+        let to_rlp = rlp.at(gas_index + 1).map_err(decode_err)?;
+        let to = if to_rlp.is_empty() { None } else { Some(to_rlp.as_val().map_err(decode_err)?) };
+
+        let mut access_list: Vec<(Address, Vec<H256>)> = Vec::new();
+        if tx_type != 0 {
+            for item in rlp.at(gas_index + 4).map_err(decode_err)?.iter() {
+                access_list.push((item.val_at(0).map_err(decode_err)?, item.list_at(1).map_err(decode_err)?));
+            }
+        }
+
+        let mut stream = RlpStream::new_list(if tx_type == 0 && chain_id.is_some() { 9 } else { signed_count });
+        for i in 0..signed_count {
+            stream.append_raw(rlp.at(i).map_err(decode_err)?.as_raw(), 1);
+        }
+        if let (0, Some(chain_id)) = (tx_type, chain_id) {
+            stream.append(&chain_id);
+            stream.append(&0u8);
+            stream.append(&0u8);
+        }
+        let sighash = match tx_type {
+            0 => keccak256(&stream.out()),
+            _ => keccak256(&[&[tx_type], stream.out().as_ref()].concat()),
+        };
+        let parity = match (tx_type, chain_id) {
+            (0, Some(chain_id)) => v - 35 - chain_id * 2,
+            (0, None) => v.saturating_sub(27),
+            _ => v,
+        };
+        let mut compact = [0u8; 64];
+        r.to_big_endian(&mut compact[..32]);
+        s.to_big_endian(&mut compact[32..]);
+        let from = match recover(&sighash, &compact, parity as i32) {
+            Ok(res) => res,
+            Err(e) => return Err(CrunnerError::InvalidArgument(format!("Error recovering sender of raw transaction; err={}", e))),
+        };
+
+        Ok(DecodedTx {
+            tx_type,
+            chain_id,
+            nonce: rlp.val_at(offset).map_err(decode_err)?,
+            gas_price,
+            eip1559_fees,
+            gas: rlp.val_at(gas_index).map_err(decode_err)?,
+            to,
+            value: rlp.val_at(gas_index + 2).map_err(decode_err)?,
+            data: rlp.val_at(gas_index + 3).map_err(decode_err)?,
+            access_list,
+            signature: TxSignature {
+                v,
+                r: H256::from_slice(&compact[..32]),
+                s: H256::from_slice(&compact[32..]),
+            },
+            from,
+            hash: H256(keccak256(raw_tx)),
+        })
+    }
+}
+
+/// Signer which holds the key outside of crunner i.e. hardware wallet, or remote
+/// signing service. Transaction is built by crunner, then only signed by the signer.
+pub trait ExternalSigner: Send + Sync {
+    /// Get address of the signing account.
+    fn address(&self) -> BoxFuture<'_, Result<Address, CrunnerError>>;
+
+    /// Sign the transaction.
+    ///
+    /// # Arguments
+    /// * `tx` - unsigned transaction
+    fn sign_transaction<'a>(&'a self, tx: &'a UnsignedTx) -> BoxFuture<'a, Result<TxSignature, CrunnerError>>;
+}
+
+/// Parse BIP-32 derivation path i.e. `m/44'/60'/0'/0/0` into its indexes; hardened
+/// index has its highest bit set.
+///
+/// # Arguments
+/// * `hd_path` - derivation path
+pub fn parse_hd_path(hd_path: &str) -> Result<Vec<u32>, CrunnerError> {
+    let mut indexes: Vec<u32> = Vec::new();
+    for (i, component) in hd_path.split('/').enumerate() {
+        if i == 0 && component == "m" {
+            continue;
+        }
+
+        let (index_str, hardened) = match component.strip_suffix('\'').or_else(|| component.strip_suffix('h')) {
+            Some(res) => (res, true),
+            None => (component, false),
+        };
+        let index = match index_str.parse::<u32>() {
+            Ok(res) if res < 0x8000_0000 => res,
+            _ => return Err(CrunnerError::InvalidArgument(format!("Error invalid component '{}' of HD path '{}'", component, hd_path))),
+        };
+        indexes.push(if hardened { index | 0x8000_0000 } else { index });
+    }
+    Ok(indexes)
+}
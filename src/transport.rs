@@ -0,0 +1,469 @@
+use crate::error::CrunnerError;
+use futures::future::BoxFuture;
+use std::future::Future;
+use jsonrpc_core::{Call, Value};
+use rand::Rng;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+use tokio::sync::OnceCell;
+use tracing::Instrument;
+
+use web3::{
+    BatchTransport,
+    RequestId,
+    Transport,
+    helpers::build_request,
+    error::TransportError,
+    transports::{http::Http, ipc::Ipc, ws::WebSocket},
+};
+
+/// RPC methods which broadcast a transaction; these are never retried as such
+/// transaction may have already landed.
+static BROADCAST_METHODS: [&str; 2] = ["eth_sendRawTransaction", "eth_sendTransaction"];
+
+/// Policy to retry requests on transient errors i.e. rate limited (429), server
+/// errors (5xx), or timeouts with exponential backoff.
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    /// Maximum number of retries after the first attempt; 0 to disable retry
+    pub max_retries: u32,
+
+    /// Delay before the first retry; it's doubled for each subsequent retry
+    pub base_delay: Duration,
+
+    /// Maximum random delay added on top of each backoff delay
+    pub max_jitter: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            base_delay: Duration::from_millis(500),
+            max_jitter: Duration::from_millis(250),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Get delay before making the specified retry.
+    ///
+    /// # Arguments
+    /// * `retry` - retry number starting from 0
+    fn backoff_delay(&self, retry: u32) -> Duration {
+        let delay = self.base_delay.saturating_mul(2u32.saturating_pow(retry));
+        let max_jitter_ms = self.max_jitter.as_millis() as u64;
+        if max_jitter_ms == 0 {
+            return delay;
+        }
+        delay + Duration::from_millis(rand::thread_rng().gen_range(0..=max_jitter_ms))
+    }
+}
+
+/// Transport connecting to RPC endpoint; either HTTP(S), WebSocket, or IPC for local nodes.
+/// With multiple endpoints, it fails over to the next endpoint whenever the
+/// current one fails to deliver a response. Requests failed with transient errors
+/// are retried as per `RetryPolicy`.
+#[derive(Debug, Clone)]
+pub struct CrunnerTransport {
+    id: Arc<AtomicUsize>,
+    endpoints: Arc<Vec<Endpoint>>,
+    /// Index of endpoint to send request to first
+    current: Arc<AtomicUsize>,
+    retry_policy: RetryPolicy,
+    /// Timeout of each request to an endpoint; no timeout if `None`
+    request_timeout: Option<Duration>,
+}
+
+/// RPC endpoint
+#[derive(Debug)]
+struct Endpoint {
+    url: String,
+    /// Extra HTTP headers sent along with each request; not applicable to WebSocket, and IPC
+    headers: Vec<(String, String)>,
+    kind: TransportKind,
+}
+
+#[derive(Debug)]
+enum TransportKind {
+    /// HTTP(S) transport
+    Http(Http),
+
+    /// IPC path, and IPC transport which is connected on the first request
+    Ipc(PathBuf, Arc<OnceCell<Ipc>>),
+
+    /// WebSocket url, and WebSocket transport which is connected on the first request
+    Ws(String, Arc<OnceCell<WebSocket>>),
+}
+
+impl CrunnerTransport {
+    /// Create a transport for the specified RPC endpoint. Endpoint which is neither
+    /// `http(s)://`, nor `ws(s)://` url i.e. `/path/to/geth.ipc` is treated as IPC path.
+    ///
+    /// # Arguments
+    /// * `rpc_url` - RPC endpoint url, or IPC path
+    pub fn new(rpc_url: &str) -> Result<Self, CrunnerError> {
+        Self::with_endpoints(&[rpc_url.to_owned()])
+    }
+
+    /// Create a transport for the specified RPC endpoints in order of preference.
+    ///
+    /// # Arguments
+    /// * `rpc_urls` - RPC endpoint urls, or IPC paths
+    pub fn with_endpoints(rpc_urls: &[String]) -> Result<Self, CrunnerError> {
+        if rpc_urls.is_empty() {
+            return Err(CrunnerError::InvalidArgument("Error, requires at least one RPC endpoint".to_owned()));
+        }
+
+        let mut endpoints: Vec<Endpoint> = Vec::new();
+        for rpc_url in rpc_urls {
+            endpoints.push(Endpoint::new(rpc_url, &[])?);
+        }
+
+        Ok(Self {
+            id: Arc::new(AtomicUsize::new(1)),
+            endpoints: Arc::new(endpoints),
+            current: Arc::new(AtomicUsize::new(0)),
+            retry_policy: RetryPolicy::default(),
+            request_timeout: None,
+        })
+    }
+
+    /// Time out each request to an endpoint after the specified duration. Timed out
+    /// request is treated as transient error thus it fails over, and is retried.
+    ///
+    /// # Arguments
+    /// * `request_timeout` - timeout of each request
+    pub fn with_request_timeout(mut self, request_timeout: Duration) -> Self {
+        self.request_timeout = Some(request_timeout);
+        self
+    }
+
+    /// Send the specified HTTP headers along with each request i.e. `Authorization`
+    /// header required by RPC provider. Headers are added on top of existing ones.
+    ///
+    /// # Arguments
+    /// * `headers` - pairs of header name, and value
+    pub fn with_headers(mut self, headers: &[(String, String)]) -> Result<Self, CrunnerError> {
+        let mut endpoints: Vec<Endpoint> = Vec::new();
+        for endpoint in self.endpoints.iter() {
+            let mut merged_headers = endpoint.headers.clone();
+            merged_headers.extend_from_slice(headers);
+            endpoints.push(Endpoint::new(&endpoint.url, &merged_headers)?);
+        }
+        self.endpoints = Arc::new(endpoints);
+        Ok(self)
+    }
+
+    /// Use the specified policy to retry requests on transient errors.
+    ///
+    /// # Arguments
+    /// * `retry_policy` - retry policy
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Get url of RPC endpoint which requests are sent to first.
+    pub fn current_url(&self) -> &str {
+        &self.endpoints[self.current.load(Ordering::Acquire)].url
+    }
+}
+
+impl Endpoint {
+    fn new(rpc_url: &str, headers: &[(String, String)]) -> Result<Self, CrunnerError> {
+        let kind = if is_ws_url(rpc_url) {
+            TransportKind::Ws(rpc_url.to_owned(), Arc::new(OnceCell::new()))
+        }
+        else if is_ipc_path(rpc_url) {
+            if !cfg!(unix) {
+                return Err(CrunnerError::InvalidArgument(format!("Error, IPC transport is only available on Unix for {}", rpc_url)));
+            }
+            TransportKind::Ipc(PathBuf::from(rpc_url), Arc::new(OnceCell::new()))
+        }
+        else if headers.is_empty() {
+            match Http::new(rpc_url) {
+                Ok(http) => TransportKind::Http(http),
+                Err(e) => return Err(CrunnerError::InvalidArgument(format!("Error creating web3 for RPC endpoint {}; err={}", rpc_url, e))),
+            }
+        }
+        else {
+            TransportKind::Http(create_http_with_headers(rpc_url, headers)?)
+        };
+
+        Ok(Self {
+            url: rpc_url.to_owned(),
+            headers: headers.to_vec(),
+            kind,
+        })
+    }
+
+    async fn send(&self, id: RequestId, request: Call, timeout: Option<Duration>) -> web3::error::Result<Value> {
+        self.with_timeout(self.send_without_timeout(id, request), timeout).await
+    }
+
+    async fn send_batch(&self, requests: Vec<(RequestId, Call)>, timeout: Option<Duration>) -> web3::error::Result<Vec<web3::error::Result<Value>>> {
+        self.with_timeout(self.send_batch_without_timeout(requests), timeout).await
+    }
+
+    async fn with_timeout<T>(&self, request: impl Future<Output = web3::error::Result<T>>, timeout: Option<Duration>) -> web3::error::Result<T> {
+        let timeout = match timeout {
+            Some(res) => res,
+            None => return request.await,
+        };
+
+        match tokio::time::timeout(timeout, request).await {
+            Ok(res) => res,
+            Err(_) => Err(web3::Error::Transport(TransportError::Message(format!("request to {} timed out after {} secs", self.url, timeout.as_secs_f64())))),
+        }
+    }
+
+    async fn send_without_timeout(&self, id: RequestId, request: Call) -> web3::error::Result<Value> {
+        match &self.kind {
+            TransportKind::Http(http) => http.send(id, request).await,
+            TransportKind::Ipc(path, ipc) => {
+                let ipc = ipc.get_or_try_init(|| connect_ipc(path.clone())).await?;
+                ipc.send(id, request).await
+            },
+            TransportKind::Ws(url, ws) => {
+                let ws = ws.get_or_try_init(|| WebSocket::new(url)).await?;
+                ws.send(id, request).await
+            },
+        }
+    }
+
+    async fn send_batch_without_timeout(&self, requests: Vec<(RequestId, Call)>) -> web3::error::Result<Vec<web3::error::Result<Value>>> {
+        match &self.kind {
+            TransportKind::Http(http) => http.send_batch(requests).await,
+            TransportKind::Ipc(path, ipc) => {
+                let ipc = ipc.get_or_try_init(|| connect_ipc(path.clone())).await?;
+                ipc.send_batch(requests).await
+            },
+            TransportKind::Ws(url, ws) => {
+                let ws = ws.get_or_try_init(|| WebSocket::new(url)).await?;
+                ws.send_batch(requests).await
+            },
+        }
+    }
+}
+
+/// Create HTTP transport which sends the specified headers along with each request.
+fn create_http_with_headers(rpc_url: &str, headers: &[(String, String)]) -> Result<Http, CrunnerError> {
+    let mut header_map = HeaderMap::new();
+    for (name, value) in headers {
+        let header_name = match HeaderName::from_bytes(name.as_bytes()) {
+            Ok(res) => res,
+            Err(e) => return Err(CrunnerError::InvalidArgument(format!("Error invalid RPC header name '{}'; err={}", name, e))),
+        };
+        let header_value = match HeaderValue::from_str(value) {
+            Ok(res) => res,
+            Err(e) => return Err(CrunnerError::InvalidArgument(format!("Error invalid value of RPC header '{}'; err={}", name, e))),
+        };
+        header_map.insert(header_name, header_value);
+    }
+
+    let client = match reqwest::Client::builder().user_agent("web3.rs").default_headers(header_map).build() {
+        Ok(res) => res,
+        Err(e) => return Err(CrunnerError::InvalidArgument(format!("Error creating http client for RPC endpoint {}; err={}", rpc_url, e))),
+    };
+    let url = match rpc_url.parse() {
+        Ok(res) => res,
+        Err(e) => return Err(CrunnerError::InvalidArgument(format!("Error creating web3 for RPC endpoint {}; err={}", rpc_url, e))),
+    };
+    Ok(Http::with_client(client, url))
+}
+
+/// Parse RPC header in format `Name: value`.
+///
+/// # Arguments
+/// * `header` - header string
+pub fn parse_rpc_header(header: &str) -> Result<(String, String), CrunnerError> {
+    match header.split_once(':') {
+        Some((name, value)) if !name.trim().is_empty() => Ok((name.trim().to_owned(), value.trim().to_owned())),
+        _ => Err(CrunnerError::InvalidArgument(format!("Error invalid RPC header '{}'; expected format 'Name: value'", header))),
+    }
+}
+
+/// Check whether the specified RPC endpoint is IPC path.
+///
+/// # Arguments
+/// * `rpc_url` - RPC endpoint url, or IPC path
+pub fn is_ipc_path(rpc_url: &str) -> bool {
+    let lower = rpc_url.to_lowercase();
+    !lower.starts_with("http://") && !lower.starts_with("https://") && !is_ws_url(rpc_url)
+}
+
+/// Check whether the specified RPC endpoint is WebSocket url i.e. `wss://...`.
+///
+/// # Arguments
+/// * `rpc_url` - RPC endpoint url, or IPC path
+pub fn is_ws_url(rpc_url: &str) -> bool {
+    let lower = rpc_url.to_lowercase();
+    lower.starts_with("ws://") || lower.starts_with("wss://")
+}
+
+/// Check whether the error indicates that the endpoint failed to deliver a response,
+/// as opposed to an error response from the node i.e. execution reverted.
+fn is_endpoint_failure(err: &web3::Error) -> bool {
+    matches!(err, web3::Error::Unreachable | web3::Error::Transport(_) | web3::Error::Io(_) | web3::Error::InvalidResponse(_))
+}
+
+/// Check whether the error indicates that the request never reached the endpoint.
+fn is_connect_failure(err: &web3::Error) -> bool {
+    match err {
+        web3::Error::Transport(TransportError::Message(msg)) => msg.contains("error trying to connect"),
+        web3::Error::Io(e) => matches!(e.kind(), std::io::ErrorKind::ConnectionRefused | std::io::ErrorKind::NotFound),
+        _ => false,
+    }
+}
+
+/// Check whether the error is transient thus worth retrying i.e. rate limited (429),
+/// server errors (5xx), or timeouts.
+fn is_transient(err: &web3::Error) -> bool {
+    match err {
+        web3::Error::Unreachable | web3::Error::Io(_) => true,
+        web3::Error::Transport(TransportError::Code(code)) => *code == 429 || (500..=599).contains(code),
+        web3::Error::Transport(TransportError::Message(msg)) => {
+            let msg = msg.to_lowercase();
+            msg.contains("timed out") || msg.contains("timeout") || msg.contains("error trying to connect")
+        },
+        _ => false,
+    }
+}
+
+/// Get RPC method of the request.
+fn method_of(request: &Call) -> Option<&str> {
+    match request {
+        Call::MethodCall(call) => Some(call.method.as_str()),
+        Call::Notification(notification) => Some(notification.method.as_str()),
+        Call::Invalid { .. } => None,
+    }
+}
+
+impl Transport for CrunnerTransport {
+    type Out = BoxFuture<'static, web3::error::Result<Value>>;
+
+    fn prepare(&self, method: &str, params: Vec<Value>) -> (RequestId, Call) {
+        let id = self.id.fetch_add(1, Ordering::AcqRel);
+        (id, build_request(id, method, params))
+    }
+
+    fn send(&self, id: RequestId, request: Call) -> Self::Out {
+        let endpoints = self.endpoints.clone();
+        let current = self.current.clone();
+        let retry_policy = self.retry_policy;
+        let request_timeout = self.request_timeout;
+        let method = method_of(&request).unwrap_or_default().to_owned();
+        let is_broadcast = BROADCAST_METHODS.contains(&method.as_str());
+        let span = tracing::debug_span!("rpc.call", id, method = %method);
+
+        Box::pin(async move {
+            tracing::debug!(request = %serde_json::to_string(&request).unwrap_or_default(), "sending request");
+
+            let res = send_with_retry(retry_policy, is_broadcast, || {
+                send_with_failover(&endpoints, &current, is_broadcast, |endpoint| endpoint.send(id, request.clone(), request_timeout))
+            }).await;
+            if let Ok(value) = &res {
+                tracing::trace!(response = %value, "received response");
+            }
+            res
+        }.instrument(span))
+    }
+}
+
+impl BatchTransport for CrunnerTransport {
+    type Batch = BoxFuture<'static, web3::error::Result<Vec<web3::error::Result<Value>>>>;
+
+    fn send_batch<T>(&self, requests: T) -> Self::Batch
+    where
+        T: IntoIterator<Item = (RequestId, Call)>,
+    {
+        let endpoints = self.endpoints.clone();
+        let current = self.current.clone();
+        let retry_policy = self.retry_policy;
+        let request_timeout = self.request_timeout;
+        let requests: Vec<(RequestId, Call)> = requests.into_iter().collect();
+        let is_broadcast = requests.iter().any(|(_, request)| BROADCAST_METHODS.contains(&method_of(request).unwrap_or_default()));
+        let span = tracing::debug_span!("rpc.batch", size = requests.len());
+
+        Box::pin(async move {
+            let calls: Vec<&Call> = requests.iter().map(|(_, request)| request).collect();
+            tracing::debug!(requests = %serde_json::to_string(&calls).unwrap_or_default(), "sending batch request");
+
+            send_with_retry(retry_policy, is_broadcast, || {
+                send_with_failover(&endpoints, &current, is_broadcast, |endpoint| endpoint.send_batch(requests.clone(), request_timeout))
+            }).await
+        }.instrument(span))
+    }
+}
+
+/// Send request via `send`, and retry it on transient errors as per the policy.
+/// Broadcast is never retried as such transaction may have already landed.
+async fn send_with_retry<T, F, Fut>(retry_policy: RetryPolicy, is_broadcast: bool, send: F) -> web3::error::Result<T>
+where
+    F: Fn() -> Fut,
+    Fut: Future<Output = web3::error::Result<T>>,
+{
+    let mut retry = 0;
+    loop {
+        let err = match send().await {
+            Ok(res) => return Ok(res),
+            Err(e) => e,
+        };
+        tracing::debug!(error = %err, "request failed");
+
+        if is_broadcast || retry >= retry_policy.max_retries || !is_transient(&err) {
+            return Err(err);
+        }
+        let delay = retry_policy.backoff_delay(retry);
+        tracing::info!(retry = retry + 1, delay_ms = delay.as_millis() as u64, "retrying request");
+        tokio::time::sleep(delay).await;
+        retry += 1;
+    }
+}
+
+/// Send request to endpoints starting from the current one, and fail over to the
+/// next endpoint whenever it fails to deliver a response. Broadcast only fails over
+/// if the request never reached the endpoint.
+async fn send_with_failover<'a, T, F, Fut>(endpoints: &'a [Endpoint], current: &AtomicUsize, is_broadcast: bool, send: F) -> web3::error::Result<T>
+where
+    F: Fn(&'a Endpoint) -> Fut,
+    Fut: Future<Output = web3::error::Result<T>>,
+{
+    let start = current.load(Ordering::Acquire);
+    let mut last_err: Option<web3::Error> = None;
+
+    for i in 0..endpoints.len() {
+        let index = (start + i) % endpoints.len();
+        match send(&endpoints[index]).await {
+            Ok(res) => {
+                // stick with the working endpoint for subsequent requests
+                if index != start {
+                    tracing::info!(endpoint = %endpoints[index].url, "failed over to endpoint");
+                    current.store(index, Ordering::Release);
+                }
+                return Ok(res);
+            },
+            Err(e) if is_broadcast && !is_connect_failure(&e) => return Err(e),
+            Err(e) if is_endpoint_failure(&e) => last_err = Some(e),
+            Err(e) => return Err(e),
+        }
+    }
+
+    Err(last_err.unwrap_or(web3::Error::Unreachable))
+}
+
+/// Connect to IPC path.
+#[cfg(unix)]
+async fn connect_ipc(path: PathBuf) -> web3::error::Result<Ipc> {
+    Ipc::new(path).await
+}
+
+/// Connect to IPC path; not available on this platform.
+#[cfg(not(unix))]
+async fn connect_ipc(path: PathBuf) -> web3::error::Result<Ipc> {
+    Err(web3::error::Error::Transport(web3::error::TransportError::Message(format!("IPC transport is not available for {}", path.display()))))
+}
@@ -0,0 +1,327 @@
+use crate::error::CrunnerError;
+use crate::signer::{parse_hd_path, ExternalSigner, TxSignature, UnsignedTx};
+use futures::future::BoxFuture;
+use hidapi::{HidApi, HidDevice};
+use std::str::FromStr;
+use std::sync::Mutex;
+use web3::types::{Address, H256, U256};
+
+/// USB vendor id, and product id of Trezor One
+static TREZOR_ONE_USB_ID: (u16, u16) = (0x534c, 0x0001);
+
+/// Size of HID report
+static REPORT_LEN: usize = 64;
+
+/// Timeout of reading a report; long enough for user to confirm on device
+static READ_TIMEOUT_MS: i32 = 5 * 60 * 1000;
+
+/// Maximum length of calldata sent along with `EthereumSignTx`, or each `EthereumTxAck`
+static MAX_DATA_CHUNK_LEN: usize = 1024;
+
+// message types of Trezor wire protocol
+static MSG_FAILURE: u16 = 3;
+static MSG_PIN_MATRIX_REQUEST: u16 = 18;
+static MSG_BUTTON_REQUEST: u16 = 26;
+static MSG_BUTTON_ACK: u16 = 27;
+static MSG_PASSPHRASE_REQUEST: u16 = 41;
+static MSG_ETHEREUM_GET_ADDRESS: u16 = 56;
+static MSG_ETHEREUM_ADDRESS: u16 = 57;
+static MSG_ETHEREUM_SIGN_TX: u16 = 58;
+static MSG_ETHEREUM_TX_REQUEST: u16 = 59;
+static MSG_ETHEREUM_TX_ACK: u16 = 60;
+static MSG_ETHEREUM_SIGN_TX_EIP1559: u16 = 452;
+
+/// Signer on Trezor hardware wallet over USB HID. Transaction details are shown
+/// on device for confirmation before signing. Device has to be unlocked beforehand.
+pub struct TrezorSigner {
+    device: Mutex<HidDevice>,
+    path: Vec<u32>,
+}
+
+/// Field value of protobuf message
+enum ProtoValue {
+    Varint(u64),
+    Bytes(Vec<u8>),
+}
+
+impl TrezorSigner {
+    /// Connect to the first Trezor device found.
+    ///
+    /// # Arguments
+    /// * `hd_path` - derivation path of the signing account i.e. `m/44'/60'/0'/0/0`
+    pub fn new(hd_path: &str) -> Result<Self, CrunnerError> {
+        let path = parse_hd_path(hd_path)?;
+        let api = match HidApi::new() {
+            Ok(res) => res,
+            Err(e) => return Err(CrunnerError::SignerError(format!("Error initializing USB HID; err={}", e))),
+        };
+        let device = match api.open(TREZOR_ONE_USB_ID.0, TREZOR_ONE_USB_ID.1) {
+            Ok(res) => res,
+            Err(e) => return Err(CrunnerError::SignerError(format!("Error connecting to Trezor device; err={}", e))),
+        };
+
+        Ok(Self {
+            device: Mutex::new(device),
+            path,
+        })
+    }
+
+    /// Write message to device in chunks of HID reports.
+    fn write_message(&self, device: &HidDevice, msg_type: u16, payload: &[u8]) -> Result<(), CrunnerError> {
+        // header: magic `##`, message type, and payload length
+        let mut data = b"##".to_vec();
+        data.extend_from_slice(&msg_type.to_be_bytes());
+        data.extend_from_slice(&(payload.len() as u32).to_be_bytes());
+        data.extend_from_slice(payload);
+
+        // each report starts with `?`; prefixed with report id 0 as required by hidapi
+        for chunk in data.chunks(REPORT_LEN - 1) {
+            let mut report = vec![0x00, b'?'];
+            report.extend_from_slice(chunk);
+            report.resize(REPORT_LEN + 1, 0);
+            if let Err(e) = device.write(&report) {
+                return Err(CrunnerError::SignerError(format!("Error writing to Trezor device; err={}", e)));
+            }
+        }
+        Ok(())
+    }
+
+    /// Read message from device; return its type, and payload.
+    fn read_message(&self, device: &HidDevice) -> Result<(u16, Vec<u8>), CrunnerError> {
+        let mut data: Vec<u8> = Vec::new();
+        let mut expected_len: Option<usize> = None;
+
+        loop {
+            let mut report = [0u8; 64];
+            let read = match device.read_timeout(&mut report, READ_TIMEOUT_MS) {
+                Ok(0) => return Err(CrunnerError::SignerError("Error timed out waiting for Trezor device".to_owned())),
+                Ok(res) => res,
+                Err(e) => return Err(CrunnerError::SignerError(format!("Error reading from Trezor device; err={}", e))),
+            };
+            if report[0] != b'?' {
+                continue;
+            }
+            data.extend_from_slice(&report[1..read]);
+
+            if expected_len.is_none() && data.len() >= 8 {
+                if &data[..2] != b"##" {
+                    return Err(CrunnerError::SignerError("Error invalid message header from Trezor device".to_owned()));
+                }
+                expected_len = Some(u32::from_be_bytes([data[4], data[5], data[6], data[7]]) as usize);
+            }
+            if let Some(len) = expected_len {
+                if data.len() >= 8 + len {
+                    let msg_type = u16::from_be_bytes([data[2], data[3]]);
+                    return Ok((msg_type, data[8..8 + len].to_vec()));
+                }
+            }
+        }
+    }
+
+    /// Send message, and return the response other than button request which
+    /// is acknowledged while user confirms on device.
+    fn call(&self, msg_type: u16, payload: &[u8]) -> Result<(u16, Vec<(u32, ProtoValue)>), CrunnerError> {
+        let device = match self.device.lock() {
+            Ok(res) => res,
+            Err(_) => return Err(CrunnerError::SignerError("Error Trezor device is poisoned".to_owned())),
+        };
+
+        self.write_message(&device, msg_type, payload)?;
+        loop {
+            let (resp_type, resp_payload) = self.read_message(&device)?;
+            let fields = decode_proto(&resp_payload);
+            match resp_type {
+                t if t == MSG_BUTTON_REQUEST => self.write_message(&device, MSG_BUTTON_ACK, &[])?,
+                t if t == MSG_FAILURE => {
+                    let msg = proto_bytes(&fields, 2).map(|b| String::from_utf8_lossy(b).to_string()).unwrap_or_default();
+                    return Err(CrunnerError::SignerError(format!("Error from Trezor device; err={}", msg)));
+                },
+                t if t == MSG_PIN_MATRIX_REQUEST || t == MSG_PASSPHRASE_REQUEST => {
+                    return Err(CrunnerError::SignerError("Error, Trezor device is locked; unlock it with Trezor Suite first".to_owned()));
+                },
+                t => return Ok((t, fields)),
+            }
+        }
+    }
+
+    fn get_address(&self) -> Result<Address, CrunnerError> {
+        let mut payload: Vec<u8> = Vec::new();
+        for index in self.path.iter() {
+            encode_varint_field(&mut payload, 1, *index as u64);
+        }
+
+        let (resp_type, fields) = self.call(MSG_ETHEREUM_GET_ADDRESS, &payload)?;
+        if resp_type != MSG_ETHEREUM_ADDRESS {
+            return Err(CrunnerError::SignerError(format!("Error unexpected response type {} from Trezor device", resp_type)));
+        }
+
+        // newer firmware returns address as hex string, older one returns raw bytes
+        if let Some(addr_str) = proto_bytes(&fields, 2) {
+            let addr_str = String::from_utf8_lossy(addr_str);
+            return match Address::from_str(addr_str.trim_start_matches("0x")) {
+                Ok(res) => Ok(res),
+                Err(e) => Err(CrunnerError::SignerError(format!("Error parsing address from Trezor device; err={}", e))),
+            };
+        }
+        match proto_bytes(&fields, 1) {
+            Some(bytes) if bytes.len() == 20 => Ok(Address::from_slice(bytes)),
+            _ => Err(CrunnerError::SignerError("Error invalid address response from Trezor device".to_owned())),
+        }
+    }
+
+    fn sign(&self, tx: &UnsignedTx) -> Result<TxSignature, CrunnerError> {
+        let initial_len = tx.data.len().min(MAX_DATA_CHUNK_LEN);
+        let mut payload: Vec<u8> = Vec::new();
+        for index in self.path.iter() {
+            encode_varint_field(&mut payload, 1, *index as u64);
+        }
+        encode_bytes_field(&mut payload, 2, &u256_bytes(tx.nonce));
+        let msg_type = match tx.eip1559_fees {
+            Some((max_fee_per_gas, max_priority_fee_per_gas)) => {
+                encode_bytes_field(&mut payload, 3, &u256_bytes(max_fee_per_gas));
+                encode_bytes_field(&mut payload, 4, &u256_bytes(max_priority_fee_per_gas));
+                encode_bytes_field(&mut payload, 5, &u256_bytes(tx.gas));
+                encode_bytes_field(&mut payload, 6, format!("{:?}", tx.to).as_bytes());
+                encode_bytes_field(&mut payload, 7, &u256_bytes(tx.value));
+                encode_bytes_field(&mut payload, 8, &tx.data[..initial_len]);
+                encode_varint_field(&mut payload, 9, tx.data.len() as u64);
+                encode_varint_field(&mut payload, 10, tx.chain_id);
+                MSG_ETHEREUM_SIGN_TX_EIP1559
+            },
+            None => {
+                encode_bytes_field(&mut payload, 3, &u256_bytes(tx.gas_price));
+                encode_bytes_field(&mut payload, 4, &u256_bytes(tx.gas));
+                encode_bytes_field(&mut payload, 6, &u256_bytes(tx.value));
+                encode_bytes_field(&mut payload, 7, &tx.data[..initial_len]);
+                encode_varint_field(&mut payload, 8, tx.data.len() as u64);
+                encode_varint_field(&mut payload, 9, tx.chain_id);
+                encode_bytes_field(&mut payload, 11, format!("{:?}", tx.to).as_bytes());
+                MSG_ETHEREUM_SIGN_TX
+            },
+        };
+
+        let (mut resp_type, mut fields) = self.call(msg_type, &payload)?;
+        let mut offset = initial_len;
+        loop {
+            if resp_type != MSG_ETHEREUM_TX_REQUEST {
+                return Err(CrunnerError::SignerError(format!("Error unexpected response type {} from Trezor device", resp_type)));
+            }
+
+            // device requests the next chunk of calldata until it has all of them
+            match proto_varint(&fields, 1) {
+                Some(len) if len > 0 => {
+                    let end = (offset + len as usize).min(tx.data.len());
+                    let mut ack: Vec<u8> = Vec::new();
+                    encode_bytes_field(&mut ack, 1, &tx.data[offset..end]);
+                    offset = end;
+                    (resp_type, fields) = self.call(MSG_ETHEREUM_TX_ACK, &ack)?;
+                },
+                _ => break,
+            }
+        }
+
+        let (v, r, s) = match (proto_varint(&fields, 2), proto_bytes(&fields, 3), proto_bytes(&fields, 4)) {
+            (Some(v), Some(r), Some(s)) if r.len() == 32 && s.len() == 32 => (v, r, s),
+            _ => return Err(CrunnerError::SignerError("Error invalid signature response from Trezor device".to_owned())),
+        };
+
+        // firmware returns either just the parity, or v already including chain id
+        let v = if v <= 1 { tx.signature_v(v) } else { v };
+        Ok(TxSignature {
+            v,
+            r: H256::from_slice(r),
+            s: H256::from_slice(s),
+        })
+    }
+}
+
+impl ExternalSigner for TrezorSigner {
+    fn address(&self) -> BoxFuture<'_, Result<Address, CrunnerError>> {
+        Box::pin(async move { self.get_address() })
+    }
+
+    fn sign_transaction<'a>(&'a self, tx: &'a UnsignedTx) -> BoxFuture<'a, Result<TxSignature, CrunnerError>> {
+        Box::pin(async move { self.sign(tx) })
+    }
+}
+
+/// Get big-endian bytes of the number without leading zeros as expected by Trezor.
+fn u256_bytes(value: U256) -> Vec<u8> {
+    let mut bytes = [0u8; 32];
+    value.to_big_endian(&mut bytes);
+    let start = bytes.iter().position(|b| *b != 0).unwrap_or(32);
+    bytes[start..].to_vec()
+}
+
+fn encode_varint(out: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        out.push((value as u8 & 0x7f) | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+fn encode_varint_field(out: &mut Vec<u8>, field: u32, value: u64) {
+    encode_varint(out, (field as u64) << 3);
+    encode_varint(out, value);
+}
+
+fn encode_bytes_field(out: &mut Vec<u8>, field: u32, value: &[u8]) {
+    encode_varint(out, ((field as u64) << 3) | 2);
+    encode_varint(out, value.len() as u64);
+    out.extend_from_slice(value);
+}
+
+fn decode_varint(data: &[u8], pos: &mut usize) -> Option<u64> {
+    let mut value: u64 = 0;
+    for shift in (0..64).step_by(7) {
+        let byte = *data.get(*pos)?;
+        *pos += 1;
+        value |= ((byte & 0x7f) as u64) << shift;
+        if byte & 0x80 == 0 {
+            return Some(value);
+        }
+    }
+    None
+}
+
+/// Decode protobuf message into its fields; decoding stops at malformed field.
+fn decode_proto(data: &[u8]) -> Vec<(u32, ProtoValue)> {
+    let mut fields: Vec<(u32, ProtoValue)> = Vec::new();
+    let mut pos = 0;
+    while pos < data.len() {
+        let key = match decode_varint(data, &mut pos) {
+            Some(res) => res,
+            None => break,
+        };
+        let value = match key & 0x07 {
+            0 => decode_varint(data, &mut pos).map(ProtoValue::Varint),
+            2 => decode_varint(data, &mut pos).and_then(|len| {
+                let end = pos + len as usize;
+                let bytes = data.get(pos..end)?.to_vec();
+                pos = end;
+                Some(ProtoValue::Bytes(bytes))
+            }),
+            _ => None,
+        };
+        match value {
+            Some(value) => fields.push(((key >> 3) as u32, value)),
+            None => break,
+        }
+    }
+    fields
+}
+
+fn proto_varint(fields: &[(u32, ProtoValue)], field: u32) -> Option<u64> {
+    fields.iter().find_map(|(f, v)| match v {
+        ProtoValue::Varint(value) if *f == field => Some(*value),
+        _ => None,
+    })
+}
+
+fn proto_bytes(fields: &[(u32, ProtoValue)], field: u32) -> Option<&[u8]> {
+    fields.iter().find_map(|(f, v)| match v {
+        ProtoValue::Bytes(value) if *f == field => Some(value.as_slice()),
+        _ => None,
+    })
+}
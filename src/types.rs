@@ -1,63 +1,14 @@
-use clap::Parser;
-pub use web3::{
-    types::{Address, U256},
-};
-
-/// Commandline's arguments type
-#[derive(Debug, Parser)]
-#[clap(author="Wasin Thonkaew (wasin@wasin.io)")]
-#[clap(name="crunner")]
-#[clap(about="Runner/Executor of target smart contract on EVM-based chain at command line")]
-pub struct CommandlineArgs {
-    /// Target contract address to interact with
-    #[clap(long="address", short='a', required=true, multiple_values=false)]
-    pub contract_address: String,
-
-    /// Which chain to work with
-    #[clap(long="chain", short='c', required=true, multiple_values=false, possible_values=["bsc", "ethereum", "polygon"], ignore_case=true)]
-    pub chain: String,
-
-    /// Function name of target smart contract to make a call to.
-    /// To make a query to basic RPC-ETH call, then supply --rpc-eth flag.
-    #[clap(long="fn-name", short='f', required=true, multiple_values=false)]
-    pub fn_name: String,
-
-    /// Toggle the RPC-ETH call then --fn-name provides the name of query.
-    #[clap(long="rpc-eth", multiple_values=false, default_missing_value="true", takes_value=false, conflicts_with_all=&["ensure-setter", "dry-run-estimate-gas"])]
-    pub rpc_eth: bool,
-
-    /// Function's returning type
-    #[clap(long="fn-ret-type", short='r', multiple_values=false, takes_value=true, possible_values=["String", "U256"], required_unless_present_any=&["ensure-setter", "dry-run-estimate-gas"])]
-    pub fn_ret_type: Option<String>,
-
-    /// To ensure that the function to be called is a setter function
-    #[clap(long="ensure-setter", multiple_values=false, default_missing_value="true", takes_value=false)]
-    pub ensure_setter: bool,
-
-    /// Multiple parameters to be supplied to the function
-    #[clap(long="params", short='p', multiple_values=true, takes_value=true)]
-    pub params: Vec<String>,
-
-    /// Dry run to estimate gas used for setter method.
-    /// It needs --ensure-setter to be set.
-    #[clap(long="dry-run-estimate-gas", multiple_values=false, default_missing_value="true", takes_value=false)]
-    pub dry_run_estimate_gas: bool,
-    
-    /// From address used only for dry-run for estimating gas.
-    #[clap(long="estimate-gas-from-addr", multiple_values=false, takes_value=true, required_if_eq("dry-run-estimate-gas", "true"))]
-    pub estimate_gas_from_addr: Option<String>,
-
-    /// Number of block confirmations when making call to setter methods
-    #[clap(long="block-confirmations", multiple_values=false, takes_value=true, default_value="20", required_if_eq("ensure-setter", "true"))]
-    pub block_confirmations: u64,
-
-    /// ABI filepath to describe function signatures of smart contract
-    #[clap(long="abi-filepath", multiple_values=false, takes_value=true, required_unless_present="rpc-eth")]
-    pub abi_filepath: Option<String>,
-}
+pub use web3::types::U256;
+use crate::error::CrunnerError;
+use ethabi::Token;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::time::Duration;
+use web3::types::{Address, Bytes, H256, U64};
 
 /// Chain type
 #[derive(Clone, Copy)]
+#[allow(clippy::upper_case_acronyms)]
 pub enum ChainType {
     /// BSC - Binance Smart Chain
     BSC,
@@ -67,6 +18,30 @@ pub enum ChainType {
 
     /// Polygon
     Polygon,
+    /// Arbitrum One
+    Arbitrum,
+    /// Optimism
+    Optimism,
+    /// Base
+    Base,
+    /// Avalanche C-Chain
+    Avalanche,
+    /// Fantom Opera
+    Fantom,
+    /// Gnosis Chain
+    Gnosis,
+    /// BSC testnet
+    BSCTestnet,
+    /// Sepolia - Ethereum testnet
+    Sepolia,
+    /// Holesky - Ethereum testnet
+    Holesky,
+    /// Polygon Amoy - Polygon testnet
+    PolygonAmoy,
+    /// Local development chain i.e. anvil, hardhat, or ganache
+    Local,
+    /// User-defined chain; index into the chain registry loaded from chains.toml
+    Custom(usize),
 }
 
 /// Type of parameter passed into the method for further processing
@@ -79,4 +54,208 @@ pub enum FnParamType {
     String,
     HU256,
     DU256,
+    DI256,
+    /// amount with unit suffix i.e. `1.5ether`, or in scientific notation i.e. `10e18`
+    AU256,
+    Bool,
+}
+
+/// Progress of waiting for block confirmations of a sent transaction
+#[derive(Debug, Clone)]
+pub struct ConfirmationProgress {
+    /// Hash of the transaction
+    pub tx_hash: H256,
+
+    /// Current number of block confirmations; `None` if not mined yet
+    pub confirmations: Option<u64>,
+
+    /// Number of block confirmations to wait for
+    pub target: u64,
+
+    /// Time elapsed since the transaction was sent
+    pub elapsed: Duration,
+}
+
+/// Calldata of function call decoded against the function declared in ABI, or its signature
+#[derive(Debug, Clone)]
+pub struct DecodedCall {
+    /// 4-byte selector of the function in hex i.e. `0xa9059cbb`
+    pub selector: String,
+
+    /// Canonical signature of the function i.e. `transfer(address,uint256)`
+    pub signature: String,
+
+    /// Name of each argument (empty if unnamed) along with its decoded value
+    pub params: Vec<(String, Token)>,
+}
+
+/// Event log decoded against event declared in ABI
+#[derive(Debug, Clone)]
+pub struct DecodedEvent {
+    /// Name of the event i.e. `Transfer`
+    pub name: String,
+
+    /// Name of each parameter (empty if unnamed) along with its decoded value
+    pub params: Vec<(String, Token)>,
+}
+
+/// Progress of scanning event logs over a block range in chunks
+#[derive(Debug, Clone)]
+pub struct LogScanProgress {
+    /// First block of the range
+    pub from_block: u64,
+
+    /// Last block of the range (inclusive)
+    pub to_block: u64,
+
+    /// Last block scanned so far
+    pub scanned_block: u64,
+
+    /// Number of logs found so far
+    pub logs: usize,
+}
+
+/// State overrides of accounts applied to `eth_call` keyed by account address
+pub type StateOverride = BTreeMap<Address, AccountOverride>;
+
+/// Overrides of an account's state applied to `eth_call` as accepted by geth,
+/// and most other nodes
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct AccountOverride {
+    /// Balance in Wei
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub balance: Option<U256>,
+
+    /// Nonce
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<U64>,
+
+    /// Runtime bytecode replacing the account's code
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code: Option<Bytes>,
+
+    /// Storage slots replacing the whole storage of the account
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state: Option<BTreeMap<H256, H256>>,
+
+    /// Storage slots replacing only such slots of the account's storage
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state_diff: Option<BTreeMap<H256, H256>>,
 }
+
+/// Frame of a call as traced by `debug_traceCall` with `callTracer`; nested calls
+/// made by it are in `calls`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallFrame {
+    /// Type of the call i.e. `CALL`, `STATICCALL`, `DELEGATECALL`, or `CREATE`
+    #[serde(rename = "type")]
+    pub call_type: String,
+
+    /// Caller address
+    pub from: Address,
+
+    /// Callee address
+    #[serde(default)]
+    pub to: Option<Address>,
+
+    /// Amount of native currency sent along in Wei
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<U256>,
+
+    /// Gas provided to the call
+    #[serde(default)]
+    pub gas: U256,
+
+    /// Gas used by the call including its nested calls
+    #[serde(default)]
+    pub gas_used: U256,
+
+    /// Input data of the call
+    #[serde(default)]
+    pub input: Bytes,
+
+    /// Returned data, or revert data of the call
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output: Option<Bytes>,
+
+    /// Error of the call if it failed i.e. `execution reverted`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+
+    /// Decoded revert reason as reported by the node (if any)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revert_reason: Option<String>,
+
+    /// Nested calls made by this call
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub calls: Vec<CallFrame>,
+}
+
+/// Suggested fees of EIP-1559 transaction for a speed tier
+#[derive(Debug, Clone)]
+pub struct FeeSuggestion {
+    /// Name of the tier i.e. `slow`, `standard`, or `fast`
+    pub tier: &'static str,
+
+    /// Base fee per gas of the next block in Wei
+    pub base_fee: U256,
+
+    /// Priority fee per gas in Wei
+    pub priority_fee: U256,
+
+    /// Max fee per gas in Wei allowing base fee to double
+    pub max_fee: U256,
+}
+
+/// Token standard whose ABI is built in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenStandard {
+    /// ERC-20 fungible token
+    Erc20,
+    /// ERC-20 token with EIP-2612 permit extension
+    Erc20Permit,
+    /// ERC-721 non-fungible token
+    Erc721,
+    /// ERC-1155 multi-token
+    Erc1155,
+}
+
+/// Metadata of ERC-20 token; `None` if the token doesn't implement such method, or
+/// it returns a value of unexpected type i.e. `bytes32` name
+#[derive(Debug, Clone, Default)]
+pub struct TokenInfo {
+    /// Name of the token i.e. `USD Coin`
+    pub name: Option<String>,
+
+    /// Symbol of the token i.e. `USDC`
+    pub symbol: Option<String>,
+
+    /// Number of decimals amounts of the token are scaled by
+    pub decimals: Option<usize>,
+
+    /// Total supply in the token's smallest unit
+    pub total_supply: Option<U256>,
+}
+
+/// Values of template variables substituted into parameters i.e. `{self}`; `None`
+/// if not available to the operation
+#[derive(Debug, Clone, Default)]
+pub struct TemplateVars {
+    /// Address of the contract called; `{self}`
+    pub self_address: Option<String>,
+
+    /// Address the call, or transaction is made from; `{sender}`
+    pub sender: Option<String>,
+
+    /// Number of the block; `{block.number}`
+    pub block_number: Option<U256>,
+
+    /// Timestamp of the block in seconds; `{block.timestamp}`
+    pub block_timestamp: Option<U256>,
+}
+
+/// Result of a call aggregated via Multicall3; either name of each output (empty if
+/// unnamed) along with its decoded `Token`, or error if the call reverted
+pub type MulticallResult = Result<Vec<(String, Token)>, CrunnerError>;
@@ -1,15 +1,83 @@
-use crate::types::{FnParamType, ChainType};
-use ethabi::token::Token;
-use std::str::FromStr;
+use crate::chains::{address_alias, chain_config, configured_rpc_headers};
+use crate::error::CrunnerError;
+use crate::signer::{ExternalSigner, UnsignedTx};
+use crate::transport::CrunnerTransport;
+use crate::types::{FnParamType, CallFrame, ChainType, DecodedCall, DecodedEvent, FeeSuggestion, LogScanProgress, MulticallResult, StateOverride, TemplateVars, TokenStandard};
+use ethabi::{ParamType, token::Token};
 
 use web3::{
+    BatchTransport,
+    Transport,
     Web3,
-    types::{Address, U256, TransactionReceipt},
-    transports::http::Http,
+    types::{Address, BlockId, BlockNumber, Bytes, CallRequest, FilterBuilder, H256, Log, U256, TransactionId, TransactionParameters, TransactionReceipt},
     contract::{Contract, Options, tokens::{Detokenize, Tokenizable}},
+    signing::{keccak256, Key, SecretKeyRef},
 };
+use futures::StreamExt;
 use regex::Regex;
 use std::io::Read;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+/// Selector of Solidity's standard revert `Error(string)`
+static ERROR_STRING_SELECTOR: [u8; 4] = [0x08, 0xc3, 0x79, 0xa0];
+
+/// Selector of Solidity's `Panic(uint256)` i.e. on failed assertion, or arithmetic overflow
+static PANIC_SELECTOR: [u8; 4] = [0x4e, 0x48, 0x7b, 0x71];
+
+/// Selector of Multicall3's `aggregate3((address,bool,bytes)[])`
+static AGGREGATE3_SELECTOR: [u8; 4] = [0x82, 0xad, 0x56, 0xcb];
+
+/// Address of Multicall3 which is deployed at the same address on most EVM chains
+pub static MULTICALL3_ADDRESS: &str = "0xcA11bde05977b3631167028862bE2a173976CA11";
+
+/// Default maximum number of calls sent in a single JSON-RPC batch request
+pub static DEFAULT_RPC_BATCH_SIZE: usize = 100;
+
+/// Prefix of placeholder names given to unnamed event parameters while decoding
+static UNNAMED_PARAM_PREFIX: &str = "__crunner_param_";
+
+/// Pattern of revert data of unknown custom error in error message as described
+/// by `describe_rpc_error`
+static UNKNOWN_CUSTOM_ERROR_PATTERN: &str = r#"unknown custom error 0x[0-9a-f]{8}; data=0x([0-9a-f]+)"#;
+
+/// Number of recent blocks to look at when suggesting EIP-1559 fees
+static FEE_HISTORY_BLOCKS: u64 = 10;
+
+/// Speed tiers of suggested fees along with percentile of priority fees paid in
+/// recent blocks for each
+static FEE_TIERS: [(&str, f64); 3] = [("slow", 10.0), ("standard", 50.0), ("fast", 90.0)];
+
+/// Default number of blocks to query event logs for per request
+static DEFAULT_LOGS_CHUNK_SIZE: u64 = 2000;
+
+/// Number of successive chunks of logs queried without rejection before chunk size
+/// is doubled back towards the configured one
+static LOGS_CHUNK_GROW_AFTER: u32 = 5;
+
+/// Substrings (lowercase) of error messages of RPC endpoints rejecting log query
+/// because its block range is too wide, or it would return too many results
+static LOGS_RANGE_ERRORS: [&str; 7] = [
+    "too many results",
+    "returned more than",
+    "limit exceeded",
+    "block range",
+    "range is too",
+    "range too",
+    "response size",
+];
+
+/// Maximum delay in seconds between attempts to resubscribe to logs
+static FOLLOW_MAX_BACKOFF_SECS: u64 = 30;
+
+/// Maximum exponent of amount in scientific notation i.e. `10e18`; larger one
+/// overflows `U256` anyway
+static MAX_AMOUNT_EXPONENT: usize = 78;
+
+/// Pattern of template variable in parameter i.e. `{block.timestamp+3600}`; its
+/// name, along with optional offset is captured
+static PARAM_TEMPLATE_PATTERN: &str = r#"\{(self|sender|block\.(?:timestamp|number)(?:\s*[+-]\s*[0-9a-z]+)?|env:[A-Za-z_][A-Za-z0-9_]*)\}"#;
 
 /// RPC endpoint of BSC chain
 pub(crate) static BSC_RPC_ENDPOINT: &str = "https://bsc-dataseed.binance.org/";
@@ -17,24 +85,527 @@ pub(crate) static BSC_RPC_ENDPOINT: &str = "https://bsc-dataseed.binance.org/";
 pub(crate) static ETHEREUM_RPC_ENDPOINT: &str = "https://rpc.ankr.com/eth";
 /// RPC endpoint of Polygon chain
 pub(crate) static POLYGON_RPC_ENDPOINT: &str = "https://polygon-rpc.com/";
+/// RPC endpoint of Arbitrum One chain
+pub(crate) static ARBITRUM_RPC_ENDPOINT: &str = "https://arb1.arbitrum.io/rpc";
+/// RPC endpoint of Optimism chain
+pub(crate) static OPTIMISM_RPC_ENDPOINT: &str = "https://mainnet.optimism.io/";
+/// RPC endpoint of Base chain
+pub(crate) static BASE_RPC_ENDPOINT: &str = "https://mainnet.base.org/";
+/// RPC endpoint of Avalanche C-Chain chain
+pub(crate) static AVALANCHE_RPC_ENDPOINT: &str = "https://api.avax.network/ext/bc/C/rpc";
+/// RPC endpoint of Fantom Opera chain
+pub(crate) static FANTOM_RPC_ENDPOINT: &str = "https://rpc.ftm.tools/";
+/// RPC endpoint of Gnosis Chain chain
+pub(crate) static GNOSIS_RPC_ENDPOINT: &str = "https://rpc.gnosischain.com/";
+/// RPC endpoint of BSC testnet
+pub(crate) static BSC_TESTNET_RPC_ENDPOINT: &str = "https://data-seed-prebsc-1-s1.binance.org:8545/";
+/// RPC endpoint of Sepolia testnet
+pub(crate) static SEPOLIA_RPC_ENDPOINT: &str = "https://rpc.sepolia.org/";
+/// RPC endpoint of Holesky testnet
+pub(crate) static HOLESKY_RPC_ENDPOINT: &str = "https://ethereum-holesky-rpc.publicnode.com/";
+/// RPC endpoint of Polygon Amoy testnet
+pub(crate) static POLYGON_AMOY_RPC_ENDPOINT: &str = "https://rpc-amoy.polygon.technology/";
+/// RPC endpoint of local development chain
+pub(crate) static LOCAL_RPC_ENDPOINT: &str = "http://127.0.0.1:8545";
+
+/// Whether or not to validate EIP-55 checksum of input addresses
+static CHECKSUM_VALIDATION: AtomicBool = AtomicBool::new(true);
+
+/// Block explorer API endpoint of BSC chain
+pub(crate) static BSC_EXPLORER_API_ENDPOINT: &str = "https://api.bscscan.com/api";
+/// Block explorer API endpoint of Ethereum chain
+pub(crate) static ETHEREUM_EXPLORER_API_ENDPOINT: &str = "https://api.etherscan.io/api";
+/// Block explorer API endpoint of Polygon chain
+pub(crate) static POLYGON_EXPLORER_API_ENDPOINT: &str = "https://api.polygonscan.com/api";
+/// Block explorer API endpoint of Arbitrum One chain
+pub(crate) static ARBITRUM_EXPLORER_API_ENDPOINT: &str = "https://api.arbiscan.io/api";
+/// Block explorer API endpoint of Optimism chain
+pub(crate) static OPTIMISM_EXPLORER_API_ENDPOINT: &str = "https://api-optimistic.etherscan.io/api";
+/// Block explorer API endpoint of Base chain
+pub(crate) static BASE_EXPLORER_API_ENDPOINT: &str = "https://api.basescan.org/api";
+/// Block explorer API endpoint of Avalanche C-Chain chain
+pub(crate) static AVALANCHE_EXPLORER_API_ENDPOINT: &str = "https://api.snowtrace.io/api";
+/// Block explorer API endpoint of Fantom Opera chain
+pub(crate) static FANTOM_EXPLORER_API_ENDPOINT: &str = "https://api.ftmscan.com/api";
+/// Block explorer API endpoint of Gnosis Chain chain
+pub(crate) static GNOSIS_EXPLORER_API_ENDPOINT: &str = "https://api.gnosisscan.io/api";
+/// Block explorer API endpoint of BSC testnet
+pub(crate) static BSC_TESTNET_EXPLORER_API_ENDPOINT: &str = "https://api-testnet.bscscan.com/api";
+/// Block explorer API endpoint of Sepolia testnet
+pub(crate) static SEPOLIA_EXPLORER_API_ENDPOINT: &str = "https://api-sepolia.etherscan.io/api";
+/// Block explorer API endpoint of Holesky testnet
+pub(crate) static HOLESKY_EXPLORER_API_ENDPOINT: &str = "https://api-holesky.etherscan.io/api";
+/// Block explorer API endpoint of Polygon Amoy testnet
+pub(crate) static POLYGON_AMOY_EXPLORER_API_ENDPOINT: &str = "https://api-amoy.polygonscan.com/api";
+
+/// Environment variable name holding an (optional) API key of block explorer
+pub(crate) static EXPLORER_API_KEY_ENV: &str = "CRUNNER_EXPLORER_API_KEY";
+
+/// Built-in ABI which is always available for calling to smart contract.
+/// It covers common ERC-20 methods, and events; entries from ABI file will be merged on top of it.
+pub(crate) static ABI_STR: &str = r#"[
+    {"constant":true,"inputs":[],"name":"name","outputs":[{"name":"","type":"string"}],"payable":false,"stateMutability":"view","type":"function"},
+    {"constant":true,"inputs":[],"name":"decimals","outputs":[{"name":"","type":"uint8"}],"payable":false,"stateMutability":"view","type":"function"},
+    {"constant":true,"inputs":[{"name":"owner","type":"address"},{"name":"spender","type":"address"}],"name":"allowance","outputs":[{"name":"","type":"uint256"}],"payable":false,"stateMutability":"view","type":"function"},
+    {"constant":false,"inputs":[{"name":"spender","type":"address"},{"name":"amount","type":"uint256"}],"name":"approve","outputs":[{"name":"","type":"bool"}],"payable":false,"stateMutability":"nonpayable","type":"function"},
+    {"anonymous":false,"inputs":[{"indexed":true,"name":"from","type":"address"},{"indexed":true,"name":"to","type":"address"},{"indexed":false,"name":"value","type":"uint256"}],"name":"Transfer","type":"event"},
+    {"anonymous":false,"inputs":[{"indexed":true,"name":"owner","type":"address"},{"indexed":true,"name":"spender","type":"address"},{"indexed":false,"name":"value","type":"uint256"}],"name":"Approval","type":"event"}
+]"#;
+
+/// ABI of ERC-20 token standard used by `erc20` subcommand
+pub(crate) static ERC20_ABI_STR: &str = r#"[
+    {"inputs":[],"name":"name","outputs":[{"name":"","type":"string"}],"stateMutability":"view","type":"function"},
+    {"inputs":[],"name":"symbol","outputs":[{"name":"","type":"string"}],"stateMutability":"view","type":"function"},
+    {"inputs":[],"name":"decimals","outputs":[{"name":"","type":"uint8"}],"stateMutability":"view","type":"function"},
+    {"inputs":[],"name":"totalSupply","outputs":[{"name":"","type":"uint256"}],"stateMutability":"view","type":"function"},
+    {"inputs":[{"name":"account","type":"address"}],"name":"balanceOf","outputs":[{"name":"","type":"uint256"}],"stateMutability":"view","type":"function"},
+    {"inputs":[{"name":"owner","type":"address"},{"name":"spender","type":"address"}],"name":"allowance","outputs":[{"name":"","type":"uint256"}],"stateMutability":"view","type":"function"},
+    {"inputs":[{"name":"spender","type":"address"},{"name":"amount","type":"uint256"}],"name":"approve","outputs":[{"name":"","type":"bool"}],"stateMutability":"nonpayable","type":"function"},
+    {"inputs":[{"name":"to","type":"address"},{"name":"amount","type":"uint256"}],"name":"transfer","outputs":[{"name":"","type":"bool"}],"stateMutability":"nonpayable","type":"function"},
+    {"inputs":[{"name":"from","type":"address"},{"name":"to","type":"address"},{"name":"amount","type":"uint256"}],"name":"transferFrom","outputs":[{"name":"","type":"bool"}],"stateMutability":"nonpayable","type":"function"},
+    {"anonymous":false,"inputs":[{"indexed":true,"name":"from","type":"address"},{"indexed":true,"name":"to","type":"address"},{"indexed":false,"name":"value","type":"uint256"}],"name":"Transfer","type":"event"},
+    {"anonymous":false,"inputs":[{"indexed":true,"name":"owner","type":"address"},{"indexed":true,"name":"spender","type":"address"},{"indexed":false,"name":"value","type":"uint256"}],"name":"Approval","type":"event"}
+]"#;
+
+/// ABI of EIP-2612 permit extension of ERC-20 token used by `permit` subcommand
+pub(crate) static ERC2612_ABI_STR: &str = r#"[
+    {"inputs":[{"name":"owner","type":"address"},{"name":"spender","type":"address"},{"name":"value","type":"uint256"},{"name":"deadline","type":"uint256"},{"name":"v","type":"uint8"},{"name":"r","type":"bytes32"},{"name":"s","type":"bytes32"}],"name":"permit","outputs":[],"stateMutability":"nonpayable","type":"function"},
+    {"inputs":[{"name":"owner","type":"address"}],"name":"nonces","outputs":[{"name":"","type":"uint256"}],"stateMutability":"view","type":"function"},
+    {"inputs":[],"name":"DOMAIN_SEPARATOR","outputs":[{"name":"","type":"bytes32"}],"stateMutability":"view","type":"function"},
+    {"inputs":[],"name":"version","outputs":[{"name":"","type":"string"}],"stateMutability":"view","type":"function"}
+]"#;
+
+/// ABI of ERC-721 token standard used by `erc721` subcommand
+pub(crate) static ERC721_ABI_STR: &str = r#"[
+    {"inputs":[],"name":"name","outputs":[{"name":"","type":"string"}],"stateMutability":"view","type":"function"},
+    {"inputs":[],"name":"symbol","outputs":[{"name":"","type":"string"}],"stateMutability":"view","type":"function"},
+    {"inputs":[{"name":"tokenId","type":"uint256"}],"name":"tokenURI","outputs":[{"name":"","type":"string"}],"stateMutability":"view","type":"function"},
+    {"inputs":[{"name":"owner","type":"address"}],"name":"balanceOf","outputs":[{"name":"","type":"uint256"}],"stateMutability":"view","type":"function"},
+    {"inputs":[{"name":"tokenId","type":"uint256"}],"name":"ownerOf","outputs":[{"name":"","type":"address"}],"stateMutability":"view","type":"function"},
+    {"inputs":[{"name":"tokenId","type":"uint256"}],"name":"getApproved","outputs":[{"name":"","type":"address"}],"stateMutability":"view","type":"function"},
+    {"inputs":[{"name":"owner","type":"address"},{"name":"operator","type":"address"}],"name":"isApprovedForAll","outputs":[{"name":"","type":"bool"}],"stateMutability":"view","type":"function"},
+    {"inputs":[{"name":"to","type":"address"},{"name":"tokenId","type":"uint256"}],"name":"approve","outputs":[],"stateMutability":"nonpayable","type":"function"},
+    {"inputs":[{"name":"operator","type":"address"},{"name":"approved","type":"bool"}],"name":"setApprovalForAll","outputs":[],"stateMutability":"nonpayable","type":"function"},
+    {"inputs":[{"name":"from","type":"address"},{"name":"to","type":"address"},{"name":"tokenId","type":"uint256"}],"name":"transferFrom","outputs":[],"stateMutability":"nonpayable","type":"function"},
+    {"inputs":[{"name":"from","type":"address"},{"name":"to","type":"address"},{"name":"tokenId","type":"uint256"}],"name":"safeTransferFrom","outputs":[],"stateMutability":"nonpayable","type":"function"},
+    {"inputs":[{"name":"from","type":"address"},{"name":"to","type":"address"},{"name":"tokenId","type":"uint256"},{"name":"data","type":"bytes"}],"name":"safeTransferFrom","outputs":[],"stateMutability":"nonpayable","type":"function"},
+    {"anonymous":false,"inputs":[{"indexed":true,"name":"from","type":"address"},{"indexed":true,"name":"to","type":"address"},{"indexed":true,"name":"tokenId","type":"uint256"}],"name":"Transfer","type":"event"},
+    {"anonymous":false,"inputs":[{"indexed":true,"name":"owner","type":"address"},{"indexed":true,"name":"approved","type":"address"},{"indexed":true,"name":"tokenId","type":"uint256"}],"name":"Approval","type":"event"},
+    {"anonymous":false,"inputs":[{"indexed":true,"name":"owner","type":"address"},{"indexed":true,"name":"operator","type":"address"},{"indexed":false,"name":"approved","type":"bool"}],"name":"ApprovalForAll","type":"event"}
+]"#;
+
+/// ABI of ERC-1155 token standard used by `erc1155` subcommand
+pub(crate) static ERC1155_ABI_STR: &str = r#"[
+    {"inputs":[{"name":"account","type":"address"},{"name":"id","type":"uint256"}],"name":"balanceOf","outputs":[{"name":"","type":"uint256"}],"stateMutability":"view","type":"function"},
+    {"inputs":[{"name":"accounts","type":"address[]"},{"name":"ids","type":"uint256[]"}],"name":"balanceOfBatch","outputs":[{"name":"","type":"uint256[]"}],"stateMutability":"view","type":"function"},
+    {"inputs":[{"name":"id","type":"uint256"}],"name":"uri","outputs":[{"name":"","type":"string"}],"stateMutability":"view","type":"function"},
+    {"inputs":[{"name":"account","type":"address"},{"name":"operator","type":"address"}],"name":"isApprovedForAll","outputs":[{"name":"","type":"bool"}],"stateMutability":"view","type":"function"},
+    {"inputs":[{"name":"operator","type":"address"},{"name":"approved","type":"bool"}],"name":"setApprovalForAll","outputs":[],"stateMutability":"nonpayable","type":"function"},
+    {"inputs":[{"name":"from","type":"address"},{"name":"to","type":"address"},{"name":"id","type":"uint256"},{"name":"amount","type":"uint256"},{"name":"data","type":"bytes"}],"name":"safeTransferFrom","outputs":[],"stateMutability":"nonpayable","type":"function"},
+    {"inputs":[{"name":"from","type":"address"},{"name":"to","type":"address"},{"name":"ids","type":"uint256[]"},{"name":"amounts","type":"uint256[]"},{"name":"data","type":"bytes"}],"name":"safeBatchTransferFrom","outputs":[],"stateMutability":"nonpayable","type":"function"},
+    {"anonymous":false,"inputs":[{"indexed":true,"name":"operator","type":"address"},{"indexed":true,"name":"from","type":"address"},{"indexed":true,"name":"to","type":"address"},{"indexed":false,"name":"id","type":"uint256"},{"indexed":false,"name":"value","type":"uint256"}],"name":"TransferSingle","type":"event"},
+    {"anonymous":false,"inputs":[{"indexed":true,"name":"operator","type":"address"},{"indexed":true,"name":"from","type":"address"},{"indexed":true,"name":"to","type":"address"},{"indexed":false,"name":"ids","type":"uint256[]"},{"indexed":false,"name":"values","type":"uint256[]"}],"name":"TransferBatch","type":"event"},
+    {"anonymous":false,"inputs":[{"indexed":true,"name":"account","type":"address"},{"indexed":true,"name":"operator","type":"address"},{"indexed":false,"name":"approved","type":"bool"}],"name":"ApprovalForAll","type":"event"},
+    {"anonymous":false,"inputs":[{"indexed":false,"name":"value","type":"string"},{"indexed":true,"name":"id","type":"uint256"}],"name":"URI","type":"event"}
+]"#;
 
 /// Parse the input param string into type
 pub fn parse_param_type(param_str: &str) -> FnParamType {
-    // check if it's Address type
-    if validate_address_format(param_str) {
-        return FnParamType::Address;
+    // check if it's Address type, or alias of address in the address book
+    if validate_address_format(param_str) || address_alias(param_str).is_some() {
+        FnParamType::Address
+    }
+    // check if it's boolean type
+    else if validate_bool_format(param_str) {
+        FnParamType::Bool
     }
     // check if it's hexadecimal type
     else if validate_hexadecimal_format(param_str) {
-        return FnParamType::HU256;
+        FnParamType::HU256
+    }
+    // check if it's amount with unit suffix, or in scientific notation
+    else if validate_amount_format(param_str) {
+        FnParamType::AU256
+    }
+    // check if it's negative decimal type
+    else if validate_decimal_format(param_str) && param_str.starts_with('-') {
+        FnParamType::DI256
     }
     // check if it's decimal type
     else if validate_decimal_format(param_str) {
-        return FnParamType::DU256;
+        FnParamType::DU256
     }
     // else it would be string
     else {
-        return FnParamType::String;
+        FnParamType::String
+    }
+}
+
+/// Parse type annotation prefixed to the input param string i.e. `addr:0x...`,
+/// `u256:1000`, `str:foo`, `bytes32:0x...`, `addr[]:0xAAA,0xBBB`, or `tuple:(0xAAA,3000)`.
+/// Type is either one of the short aliases (`addr`, `u256`, `i256`, `str`) or
+/// any ABI type name i.e. `uint8`, `bytes`, `bool`; optionally with array suffix.
+///
+/// # Arguments
+/// * `param_str` - input param string
+///
+/// # Return
+/// Return annotated type along with the value part, or `None` if the param
+/// string is not annotated.
+pub fn parse_param_annotation(param_str: &str) -> Option<(ParamType, &str)> {
+    let (type_str, value) = param_str.split_once(':')?;
+
+    // resolve alias of base type while keeping array suffix i.e. `addr[]`
+    let (base_type_str, array_suffix) = match type_str.find('[') {
+        Some(i) => type_str.split_at(i),
+        None => (type_str, ""),
+    };
+    let abi_base_type_str = match base_type_str {
+        "addr" => "address",
+        "u256" => "uint256",
+        "i256" => "int256",
+        "str" => "string",
+        other => other,
+    };
+
+    match ethabi::param_type::Reader::read(&format!("{}{}", abi_base_type_str, array_suffix)) {
+        Ok(param_type) => Some((param_type, value)),
+        Err(_) => None,
+    }
+}
+
+/// Tokenize the input value string into `Token` of the specified type.
+///
+/// # Arguments
+/// * `param_type` - ABI type to tokenize into
+/// * `value` - value string; numbers can be either decimal or hexadecimal prefixed with `0x`,
+///   bytes are hexadecimal prefixed with `0x`
+pub fn tokenize_param(param_type: &ParamType, value: &str) -> Result<Token, CrunnerError> {
+    match param_type {
+        ParamType::Address => get_address_from_str(value).map(Token::Address),
+        ParamType::String => Ok(Token::String(value.to_owned())),
+        ParamType::Bool => match value.to_lowercase().as_str() {
+            "true" | "1" => Ok(Token::Bool(true)),
+            "false" | "0" => Ok(Token::Bool(false)),
+            _ => Err(CrunnerError::InvalidArgument(format!("Error value is not a boolean; value={}", value))),
+        },
+        ParamType::Bytes => parse_hex_bytes(value).map(Token::Bytes),
+        ParamType::FixedBytes(len) => {
+            let mut bytes = parse_hex_bytes(value)?;
+            if bytes.len() > *len {
+                return Err(CrunnerError::InvalidArgument(format!("Error value is longer than {} bytes; value={}", len, value)));
+            }
+            // fixed bytes are left-aligned, so pad zeros to the right
+            bytes.resize(*len, 0);
+            Ok(Token::FixedBytes(bytes))
+        },
+        ParamType::Uint(bits) => {
+            let val = parse_amount(value)?;
+            if *bits < 256 && val.bits() > *bits {
+                return Err(CrunnerError::InvalidArgument(format!("Error value overflows uint{}; value={}", bits, value)));
+            }
+            Ok(Token::Uint(val))
+        },
+        ParamType::Int(bits) => {
+            let negative = value.starts_with('-');
+            let abs = parse_u256(value.trim_start_matches('-'))?;
+            // magnitude limit of intN is 2^(N-1) - 1 for positive, and 2^(N-1) for negative
+            let limit = U256::one() << (*bits - 1);
+            if (negative && abs > limit) || (!negative && abs >= limit) {
+                return Err(CrunnerError::InvalidArgument(format!("Error value overflows int{}; value={}", bits, value)));
+            }
+            if negative {
+                // two's complement
+                Ok(Token::Int((!abs).overflowing_add(U256::one()).0))
+            }
+            else {
+                Ok(Token::Int(abs))
+            }
+        },
+        ParamType::Array(item_type) => {
+            let tokens = tokenize_param_items(item_type, value)?;
+            Ok(Token::Array(tokens))
+        },
+        ParamType::FixedArray(item_type, len) => {
+            let tokens = tokenize_param_items(item_type, value)?;
+            if tokens.len() != *len {
+                return Err(CrunnerError::InvalidArgument(format!("Error expected {} elements for fixed array, got {}; value={}", len, tokens.len(), value)));
+            }
+            Ok(Token::FixedArray(tokens))
+        },
+        ParamType::Tuple(component_types) => {
+            let trimmed_value = value.trim();
+            let is_enclosed = trimmed_value.starts_with('(') && trimmed_value.ends_with(')') && split_top_level(trimmed_value).len() == 1;
+            if !is_enclosed {
+                return Err(CrunnerError::InvalidArgument(format!("Error tuple value is not enclosed in parentheses; value={}", value)));
+            }
+
+            let inner = &trimmed_value[1..trimmed_value.len()-1];
+            let items = if inner.trim().is_empty() { Vec::new() } else { split_top_level(inner) };
+            if items.len() != component_types.len() {
+                return Err(CrunnerError::InvalidArgument(format!("Error expected {} components for tuple, got {}; value={}", component_types.len(), items.len(), value)));
+            }
+
+            let tokens = component_types.iter()
+                .zip(items.iter())
+                .map(|(t, item)| tokenize_param(t, item.trim()))
+                .collect::<Result<Vec<Token>, CrunnerError>>()?;
+            Ok(Token::Tuple(tokens))
+        },
+    }
+}
+
+/// Check whether the input param string is an array enclosed in `[` and `]` i.e. `[1,2,3]`.
+fn is_array_literal(param_str: &str) -> bool {
+    let trimmed = param_str.trim();
+    trimmed.starts_with('[') && trimmed.ends_with(']')
+}
+
+/// Tokenize comma-separated elements (optionally enclosed in `[` and `]`) into
+/// `Token` of the specified item type.
+///
+/// # Arguments
+/// * `item_type` - ABI type of each element
+/// * `value` - comma-separated elements i.e. `0xAAA,0xBBB` or `[0xAAA,0xBBB]`
+fn tokenize_param_items(item_type: &ParamType, value: &str) -> Result<Vec<Token>, CrunnerError> {
+    let trimmed_value = value.trim();
+    // only strip brackets which enclose the whole value i.e. not `[1,2],[3]`
+    let is_enclosed = trimmed_value.starts_with('[') && trimmed_value.ends_with(']') && split_top_level(trimmed_value).len() == 1;
+    let inner = if is_enclosed {
+        &trimmed_value[1..trimmed_value.len()-1]
+    }
+    else {
+        trimmed_value
+    };
+    if inner.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+
+    split_top_level(inner)
+        .iter()
+        .map(|item| tokenize_param(item_type, item.trim()))
+        .collect()
+}
+
+/// Split the input string by commas which are not enclosed within `[]` or `()`.
+///
+/// # Arguments
+/// * `s` - string to split
+pub fn split_top_level(s: &str) -> Vec<&str> {
+    let mut items: Vec<&str> = Vec::new();
+    let mut depth: i32 = 0;
+    let mut start: usize = 0;
+
+    for (i, c) in s.char_indices() {
+        match c {
+            '[' | '(' => depth += 1,
+            ']' | ')' => depth -= 1,
+            ',' if depth == 0 => {
+                items.push(&s[start..i]);
+                start = i + 1;
+            },
+            _ => (),
+        }
+    }
+    items.push(&s[start..]);
+
+    items
+}
+
+/// Parse the input string into `U256`; hexadecimal if prefixed with `0x`,
+/// otherwise decimal.
+///
+/// # Arguments
+/// * `s` - numeric string to parse
+pub fn parse_u256(s: &str) -> Result<U256, CrunnerError> {
+    let res = match s.strip_prefix("0x") {
+        Some(hex_str) => U256::from_str_radix(hex_str, 16).map_err(|e| e.to_string()),
+        None => U256::from_dec_str(s).map_err(|e| e.to_string()),
+    };
+    res.map_err(|e| CrunnerError::InvalidArgument(format!("Error parsing '{}' as number; err={}", s, e)))
+}
+
+/// Parse amount of native currency into Wei. It is either a plain number in Wei,
+/// decimal number suffixed with unit i.e. `0.1eth`, `1.5ether`, `5gwei`, or `100wei`,
+/// or in scientific notation i.e. `10e18`, or `2.5e3gwei`.
+///
+/// # Arguments
+/// * `s` - amount string to parse
+pub fn parse_amount(s: &str) -> Result<U256, CrunnerError> {
+    let lower = s.trim().to_lowercase();
+    let (number, unit_decimals) = if lower.starts_with("0x") {
+        return parse_u256(&lower);
+    }
+    else if let Some(n) = lower.strip_suffix("gwei") {
+        (n, 9)
+    }
+    else if let Some(n) = lower.strip_suffix("wei") {
+        (n, 0)
+    }
+    else if let Some(n) = lower.strip_suffix("ether").or_else(|| lower.strip_suffix("eth")) {
+        (n, 18)
+    }
+    else if lower.contains('e') {
+        (lower.as_str(), 0)
+    }
+    else {
+        return parse_u256(&lower);
+    };
+
+    let invalid = || CrunnerError::InvalidArgument(format!("Error parsing '{}' as amount; expect i.e. 0.1eth, 5gwei, 100wei, or 10e18", s));
+    // exponent of scientific notation shifts the decimal point further
+    let (number, exponent) = match number.trim().split_once('e') {
+        Some((n, exp)) => match exp.parse::<usize>() {
+            Ok(res) if res <= MAX_AMOUNT_EXPONENT => (n, res),
+            _ => return Err(invalid()),
+        },
+        None => (number.trim(), 0),
+    };
+    parse_units(number, unit_decimals + exponent).map_err(|_| invalid())
+}
+
+/// Parse decimal number i.e. `100.25` into integer scaled by the number of decimals
+/// i.e. `100250000` for 6 decimals.
+///
+/// # Arguments
+/// * `number` - decimal number string to parse
+/// * `decimals` - number of decimals to shift the decimal point by
+pub fn parse_units(number: &str, decimals: usize) -> Result<U256, CrunnerError> {
+    let invalid = |reason: &str| CrunnerError::InvalidArgument(format!("Error parsing '{}' with {} decimals; {}", number, decimals, reason));
+    let number = number.trim();
+    let (int_part, frac_part) = number.split_once('.').unwrap_or((number, ""));
+    if (int_part.is_empty() && frac_part.is_empty())
+        || !int_part.chars().all(|c| c.is_ascii_digit())
+        || !frac_part.chars().all(|c| c.is_ascii_digit()) {
+        return Err(invalid("expect decimal number i.e. 100.25"));
+    }
+    if frac_part.len() > decimals {
+        return Err(invalid("too many fractional digits"));
+    }
+
+    // shift the decimal point by padding fractional part up to the decimals
+    let digits = format!("{}{:0<width$}", int_part, frac_part, width = decimals);
+    match U256::from_dec_str(&digits) {
+        Ok(res) => Ok(res),
+        Err(_) => Err(invalid("out of range")),
+    }
+}
+
+/// Format integer amount into decimal number scaled down by the number of decimals
+/// i.e. `100.25` for `100250000` with 6 decimals; trailing zeros of fractional part
+/// are trimmed.
+///
+/// # Arguments
+/// * `value` - integer amount
+/// * `decimals` - number of decimals to shift the decimal point by
+pub fn format_units(value: U256, decimals: usize) -> String {
+    let digits = format!("{:0>width$}", value.to_string(), width = decimals + 1);
+    let (int_part, frac_part) = digits.split_at(digits.len() - decimals);
+    let frac_part = frac_part.trim_end_matches('0');
+    if frac_part.is_empty() {
+        int_part.to_owned()
+    }
+    else {
+        format!("{}.{}", int_part, frac_part)
+    }
+}
+
+/// Parse gas price into Wei. Plain number is in Gwei i.e. `5`, or `0.1`, otherwise
+/// with unit suffix i.e. `5000000000wei`.
+///
+/// # Arguments
+/// * `s` - gas price string to parse
+pub fn parse_gas_price(s: &str) -> Result<U256, CrunnerError> {
+    if s.trim().ends_with(|c: char| c.is_ascii_alphabetic()) {
+        parse_amount(s)
+    }
+    else {
+        parse_amount(&format!("{}gwei", s.trim()))
+    }
+}
+
+/// Parse hexadecimal string prefixed with `0x` into bytes.
+///
+/// # Arguments
+/// * `s` - hexadecimal string to parse
+pub fn parse_hex_bytes(s: &str) -> Result<Vec<u8>, CrunnerError> {
+    let hex_str = match s.strip_prefix("0x") {
+        Some(res) => res,
+        None => return Err(CrunnerError::InvalidArgument(format!("Error bytes value is not prefixed with 0x; value={}", s))),
+    };
+
+    match hex::decode(hex_str) {
+        Ok(res) => Ok(res),
+        Err(e) => Err(CrunnerError::InvalidArgument(format!("Error hex decoding of '{}'; err={}", s, e))),
+    }
+}
+
+/// Parse composite return signature i.e. `(uint112,uint112,uint32)` into types.
+///
+/// # Arguments
+/// * `sig` - return signature enclosed in parentheses
+///
+/// # Return
+/// Return type of each component in case of success.
+pub fn parse_ret_types(sig: &str) -> Result<Vec<ParamType>, CrunnerError> {
+    let trimmed_sig: String = sig.chars().filter(|c| !c.is_whitespace()).collect();
+    if trimmed_sig == "()" {
+        return Ok(Vec::new());
+    }
+
+    match ethabi::param_type::Reader::read(&trimmed_sig) {
+        Ok(ParamType::Tuple(types)) => Ok(types),
+        Ok(_) => Err(CrunnerError::InvalidArgument(format!("Error return signature is not enclosed in parentheses; sig={}", sig))),
+        Err(e) => Err(CrunnerError::InvalidArgument(format!("Error parsing return signature '{}'; err={}", sig, e))),
+    }
+}
+
+/// Format a decoded `Token` into human-readable string.
+/// Numbers are in decimal, addresses and bytes are in hexadecimal prefixed with `0x`,
+/// arrays are in form of `[a, b]`, and tuples are in form of `(a, b)`.
+///
+/// # Arguments
+/// * `token` - token to format
+pub fn format_token(token: &Token) -> String {
+    match token {
+        Token::Address(addr) => to_checksum_address(addr),
+        Token::FixedBytes(bytes) | Token::Bytes(bytes) => format!("0x{}", hex::encode(bytes)),
+        Token::Uint(val) => val.to_string(),
+        Token::Int(val) => {
+            // interpret as two's complement
+            if val.bit(255) {
+                format!("-{}", (!*val).overflowing_add(U256::one()).0)
+            }
+            else {
+                val.to_string()
+            }
+        },
+        Token::Bool(val) => val.to_string(),
+        Token::String(val) => val.to_owned(),
+        Token::FixedArray(tokens) | Token::Array(tokens) => {
+            let items: Vec<String> = tokens.iter().map(format_token).collect();
+            format!("[{}]", items.join(", "))
+        },
+        Token::Tuple(tokens) => {
+            let items: Vec<String> = tokens.iter().map(format_token).collect();
+            format!("({})", items.join(", "))
+        },
+    }
+}
+
+/// Convert a decoded `Token` into JSON value.
+/// Numbers are in decimal strings to preserve precision, addresses and bytes are in
+/// hexadecimal strings prefixed with `0x`, and both arrays and tuples are JSON arrays.
+///
+/// # Arguments
+/// * `token` - token to convert
+pub fn token_to_json(token: &Token) -> serde_json::Value {
+    match token {
+        Token::Bool(val) => serde_json::Value::Bool(*val),
+        Token::FixedArray(tokens) | Token::Array(tokens) | Token::Tuple(tokens) => {
+            serde_json::Value::Array(tokens.iter().map(token_to_json).collect())
+        },
+        _ => serde_json::Value::String(format_token(token)),
     }
 }
 
@@ -50,15 +621,107 @@ pub fn validate_address_format(address: &str) -> bool {
     regex.is_match(&lowercase_address)
 }
 
+/// Check whether any of the parameters has template variable whose name starts
+/// with `prefix` i.e. `block.` for `{block.timestamp+3600}`.
+///
+/// # Arguments
+/// * `params` - parameter strings
+/// * `prefix` - prefix of the template variable's name
+pub fn params_refer_template(params: &[String], prefix: &str) -> bool {
+    let regex: Regex = Regex::new(PARAM_TEMPLATE_PATTERN).unwrap();
+    params.iter().any(|p| regex.captures_iter(p).any(|caps| caps[1].starts_with(prefix)))
+}
+
+/// Substitute template variables in parameter strings with their values before the
+/// parameters are tokenized. Supported ones are `{self}`, `{sender}`, `{env:NAME}`,
+/// `{block.timestamp}`, and `{block.number}`; the block ones take an optional offset
+/// i.e. `{block.timestamp+3600}`, or `{block.timestamp+1h}`. Other text is left as it is.
+///
+/// # Arguments
+/// * `params` - parameter strings
+/// * `vars` - values of template variables
+pub fn expand_param_templates(params: &[String], vars: &TemplateVars) -> Result<Vec<String>, CrunnerError> {
+    let regex: Regex = Regex::new(PARAM_TEMPLATE_PATTERN).unwrap();
+
+    let mut expanded_params: Vec<String> = Vec::new();
+    for p in params {
+        let mut expanded = String::new();
+        let mut last_end = 0;
+        for caps in regex.captures_iter(p) {
+            let whole = caps.get(0).unwrap();
+            expanded.push_str(&p[last_end..whole.start()]);
+            expanded.push_str(&template_value(&caps[1], vars)?);
+            last_end = whole.end();
+        }
+        expanded.push_str(&p[last_end..]);
+        expanded_params.push(expanded);
+    }
+
+    Ok(expanded_params)
+}
+
+/// Get value of template variable by its name as captured by `PARAM_TEMPLATE_PATTERN`.
+fn template_value(name: &str, vars: &TemplateVars) -> Result<String, CrunnerError> {
+    let unavailable = |what: &str| CrunnerError::InvalidArgument(format!("Error, template {{{}}} requires {}", name, what));
+    match name {
+        "self" => return vars.self_address.clone().ok_or_else(|| unavailable("--address of a single contract")),
+        "sender" => return vars.sender.clone().ok_or_else(|| unavailable("the address to send from i.e. --from")),
+        _ => (),
+    }
+    if let Some(env_name) = name.strip_prefix("env:") {
+        return match std::env::var(env_name) {
+            Ok(res) => Ok(res),
+            Err(_) => Err(CrunnerError::InvalidArgument(format!("Error, environment variable {} of template {{{}}} is not set", env_name, name))),
+        };
+    }
+
+    // block.timestamp, or block.number followed by optional offset
+    let (field, offset) = match name.find(['+', '-']) {
+        Some(i) => (name[..i].trim(), Some((&name[i..i + 1], name[i + 1..].trim()))),
+        None => (name, None),
+    };
+    let is_timestamp = field == "block.timestamp";
+    let value = if is_timestamp { vars.block_timestamp } else { vars.block_number };
+    let value = value.ok_or_else(|| unavailable("the block"))?;
+    let (sign, amount_str) = match offset {
+        Some(res) => res,
+        None => return Ok(value.to_string()),
+    };
+
+    // offset of timestamp can be a duration i.e. `30m`, or `1h`
+    let amount = match amount_str.parse::<u64>() {
+        Ok(res) => res,
+        Err(_) if is_timestamp => match humantime::parse_duration(amount_str) {
+            Ok(res) => res.as_secs(),
+            Err(e) => return Err(CrunnerError::InvalidArgument(format!("Error parsing offset of template {{{}}}; err={}", name, e))),
+        },
+        Err(e) => return Err(CrunnerError::InvalidArgument(format!("Error parsing offset of template {{{}}}; err={}", name, e))),
+    };
+    let res = if sign == "+" { value.checked_add(U256::from(amount)) } else { value.checked_sub(U256::from(amount)) };
+    match res {
+        Some(res) => Ok(res.to_string()),
+        None => Err(CrunnerError::InvalidArgument(format!("Error, template {{{}}} is out of range", name))),
+    }
+}
+
+/// Validate whether the specified string is a boolean literal
+/// i.e. `true` or `false` (case-insensitive).
+///
+/// # Arguments
+/// - `s` - string to check
+pub fn validate_bool_format(s: &str) -> bool {
+    s.eq_ignore_ascii_case("true") || s.eq_ignore_ascii_case("false")
+}
+
 /// Validate whether the specified string is in hexadecimal format.
 ///
 /// # Arguments
 /// - `s` - numeric string to parse
 pub fn validate_hexadecimal_format(s: &str) -> bool {
     let lowercase_s = s.to_lowercase();
-    let regex: Regex = Regex::new(r#"0x[0-9a-fA-F]+"#).unwrap();
+    let regex: Regex = Regex::new(r#"^0x[0-9a-f]+$"#).unwrap();
 
-    return regex.is_match(&lowercase_s);
+    regex.is_match(&lowercase_s)
 }
 
 /// Validate whether the specified string is in octal format.
@@ -69,7 +732,19 @@ pub fn validate_octal_format(s: &str) -> bool {
     let lowercase_s = s.to_lowercase();
     let regex: Regex = Regex::new(r#"0o[0-7]+"#).unwrap();
 
-    return regex.is_match(&lowercase_s);
+    regex.is_match(&lowercase_s)
+}
+
+/// Validate whether the specified string is an amount with unit suffix i.e. `1.5ether`,
+/// `2500gwei`, `100wei`, or in scientific notation i.e. `10e18`.
+///
+/// # Arguments
+/// - `s` - numeric string to parse
+pub fn validate_amount_format(s: &str) -> bool {
+    let lowercase_s = s.to_lowercase();
+    let regex: Regex = Regex::new(r#"^([0-9]+(\.[0-9]*)?|\.[0-9]+)(e[0-9]+)?(ether|eth|gwei|wei)$|^([0-9]+(\.[0-9]*)?|\.[0-9]+)e[0-9]+$"#).unwrap();
+
+    regex.is_match(&lowercase_s)
 }
 
 /// Validate whether the specified string is in decimal format.
@@ -78,9 +753,9 @@ pub fn validate_octal_format(s: &str) -> bool {
 /// - `s` - numeric string to parse
 pub fn validate_decimal_format(s: &str) -> bool {
     let lowercase_s = s.to_lowercase();
-    let regex: Regex = Regex::new(r#"[-]?[1-9][0-9]*"#).unwrap();
+    let regex: Regex = Regex::new(r#"^-?(0|[1-9][0-9]*)$"#).unwrap();
 
-    return regex.is_match(&lowercase_s);
+    regex.is_match(&lowercase_s)
 }
 
 /// Perform check whether the specified address is an EOA.
@@ -89,323 +764,2273 @@ pub fn validate_decimal_format(s: &str) -> bool {
 /// # Arguments
 /// * `web3` - instance of web3
 /// * `address` - address to check; in format `0x...`.
-pub async fn perform_check_is_eoa(web3: &Web3<Http>, address: &str) -> Result<bool, String> {
-    if !validate_address_format(address) {
-        return Err(format!("Error address is not in the correct format; addr={}", address));
-    }
+pub async fn perform_check_is_eoa(web3: &Web3<CrunnerTransport>, address: &str) -> Result<bool, CrunnerError> {
+    let addr = get_address_from_str(address)?;
 
-    // convert into hex bytes in order to create `web3::Address`
-    let address_hexbytes_decoded = match hex::decode(&address[2..]) {
+    // query for code
+    let code_bytes = match web3.eth().code(addr, None).await {
         Ok(res) => res,
         Err(e) => {
-            let err_msg = format!("Error hex decoding of address ({}); err={}", address, e);
-            return Err(err_msg);
+            let err_msg = format!("Error awaiting result for code from address ({}); err={}", address, e);
+            return Err(CrunnerError::RpcError(err_msg));
         }
     };
-    
-    // query for code
-    let code_bytes = match web3.eth().code(Address::from_slice(address_hexbytes_decoded.as_slice()), None).await {
+
+    // encode hex bytes into hex string
+    let code_str = hex::encode(code_bytes.0.as_slice());
+
+    if !code_str.is_empty() {
+        // it is a contract address
+        return Ok(false);
+    }
+
+    Ok(true)
+}
+
+/// Parse block to read state at; either block number in decimal, or hexadecimal
+/// prefixed with '0x', block hash, or one of block tags `latest`, `earliest`, and
+/// `pending`. See `web3_resolve_block` for `safe`, and `finalized` tags.
+///
+/// # Arguments
+/// * `block` - block number, hash, or tag string literal
+pub fn parse_block_id(block: &str) -> Result<BlockId, CrunnerError> {
+    let block = block.trim();
+    let parsed = if block.eq_ignore_ascii_case("latest") {
+        Some(BlockId::Number(BlockNumber::Latest))
+    }
+    else if block.eq_ignore_ascii_case("earliest") {
+        Some(BlockId::Number(BlockNumber::Earliest))
+    }
+    else if block.eq_ignore_ascii_case("pending") {
+        Some(BlockId::Number(BlockNumber::Pending))
+    }
+    else if block.starts_with("0x") && block.len() == 66 {
+        H256::from_str(&block[2..]).ok().map(BlockId::Hash)
+    }
+    else if let Some(hex_str) = block.strip_prefix("0x") {
+        u64::from_str_radix(hex_str, 16).ok().map(|n| BlockId::Number(BlockNumber::Number(n.into())))
+    }
+    else {
+        block.parse::<u64>().ok().map(|n| BlockId::Number(BlockNumber::Number(n.into())))
+    };
+
+    match parsed {
+        Some(res) => Ok(res),
+        None => Err(CrunnerError::InvalidArgument(format!("Error, block '{}' is neither block number, hash, nor one of latest, earliest, pending, safe, or finalized", block))),
+    }
+}
+
+/// Resolve block to read state at. Besides what `parse_block_id` accepts, block
+/// tags `safe`, and `finalized` are resolved into the number of such block as
+/// reported by the node since they can't be passed along to calls as-is.
+///
+/// # Arguments
+/// * `web3` - web3 instance
+/// * `block` - block number, hash, or tag string literal
+pub async fn web3_resolve_block(web3: &Web3<CrunnerTransport>, block: &str) -> Result<BlockId, CrunnerError> {
+    let tag = block.trim().to_lowercase();
+    if tag != "safe" && tag != "finalized" {
+        return parse_block_id(block);
+    }
+
+    let value = match web3.transport().execute("eth_getBlockByNumber", vec![serde_json::json!(tag), serde_json::json!(false)]).await {
+        Ok(res) => res,
+        Err(e) => return Err(CrunnerError::RpcError(format!("Error querying {} block; err={}", tag, e))),
+    };
+    let number = value.get("number")
+        .and_then(|n| n.as_str())
+        .and_then(|n| u64::from_str_radix(n.trim_start_matches("0x"), 16).ok());
+    match number {
+        Some(n) => {
+            tracing::debug!(tag = %tag, block = n, "resolved block tag");
+            Ok(BlockId::Number(BlockNumber::Number(n.into())))
+        },
+        None => Err(CrunnerError::RpcError(format!("Error, RPC endpoint doesn't support {} block tag", tag))),
+    }
+}
+
+/// Get `Address` from string literal, or alias of address in the address book.
+/// If checksum validation is enabled (default), mixed-case address must have
+/// correct EIP-55 checksum.
+///
+/// # Arguments
+/// * `address` - address string literal prefixed with '0x', or its alias
+pub fn get_address_from_str(address: &str) -> Result<Address, CrunnerError> {
+    let address = address_alias(address).unwrap_or(address);
+    if !validate_address_format(address) {
+        return Err(CrunnerError::InvalidAddress(format!("Error address is not in the correct format; addr={}", address)));
+    }
+
+    let addr = match hex::decode(strip_address_prefix(address)) {
+        Ok(res) => Address::from_slice(res.as_slice()),
+        Err(e) => return Err(CrunnerError::InvalidAddress(format!("Error hex decoding of address; addr={}, err={}", address, e))),
+    };
+    if CHECKSUM_VALIDATION.load(Ordering::Relaxed) && !validate_address_checksum(address, &addr) {
+        return Err(CrunnerError::InvalidAddress(format!("Error address has invalid EIP-55 checksum; addr={}, expected={}", address, to_checksum_address(&addr))));
+    }
+
+    Ok(addr)
+}
+
+/// Strip `0x` prefix of address string in either case; as accepted by
+/// `validate_address_format`.
+fn strip_address_prefix(address: &str) -> &str {
+    if address.len() >= 2 && address[..2].eq_ignore_ascii_case("0x") {
+        &address[2..]
+    }
+    else {
+        address
+    }
+}
+
+/// Enable or disable EIP-55 checksum validation of input addresses.
+/// It is enabled by default.
+///
+/// # Arguments
+/// * `enabled` - whether or not to validate checksum
+pub fn set_checksum_validation(enabled: bool) {
+    CHECKSUM_VALIDATION.store(enabled, Ordering::Relaxed);
+}
+
+/// Validate EIP-55 checksum of the address string.
+/// All lowercase or all uppercase address has no checksum thus always valid.
+///
+/// # Arguments
+/// * `address` - address string literal
+/// * `addr` - parsed `Address` of such string
+pub fn validate_address_checksum(address: &str, addr: &Address) -> bool {
+    let hex_str = strip_address_prefix(address);
+    if hex_str == hex_str.to_lowercase() || hex_str == hex_str.to_uppercase() {
+        return true;
+    }
+
+    to_checksum_address(addr)[2..] == *hex_str
+}
+
+/// Format address in EIP-55 checksummed form prefixed with `0x`.
+///
+/// # Arguments
+/// * `addr` - address to format
+pub fn to_checksum_address(addr: &Address) -> String {
+    let lowercase_hex = hex::encode(addr.as_bytes());
+    let hash = keccak256(lowercase_hex.as_bytes());
+
+    let checksummed: String = lowercase_hex.chars().enumerate().map(|(i, c)| {
+        // uppercase the letter if corresponding nibble of hash is >= 8
+        let nibble = if i % 2 == 0 { hash[i / 2] >> 4 } else { hash[i / 2] & 0x0f };
+        if nibble >= 8 { c.to_ascii_uppercase() } else { c }
+    }).collect();
+
+    format!("0x{}", checksummed)
+}
+
+/// Get name of the chain as supplied to `--chain` i.e. `bsc-testnet`.
+///
+/// # Arguments
+/// - `chain` - `ChainType`
+pub fn chain_name(chain: ChainType) -> &'static str {
+    match chain {
+        ChainType::BSC => "bsc",
+        ChainType::Ethereum => "ethereum",
+        ChainType::Polygon => "polygon",
+        ChainType::Arbitrum => "arbitrum",
+        ChainType::Optimism => "optimism",
+        ChainType::Base => "base",
+        ChainType::Avalanche => "avalanche",
+        ChainType::Fantom => "fantom",
+        ChainType::Gnosis => "gnosis",
+        ChainType::BSCTestnet => "bsc-testnet",
+        ChainType::Sepolia => "sepolia",
+        ChainType::Holesky => "holesky",
+        ChainType::PolygonAmoy => "polygon-amoy",
+        ChainType::Local => "local",
+        ChainType::Custom(index) => chain_config(index).map(|c| c.name.as_str()).unwrap_or("custom"),
+    }
+}
+
+/// Get the expected chain id of the chain.
+///
+/// # Arguments
+/// - `chain` - `ChainType`
+///
+/// # Return
+/// Return `None` for local chain as its chain id varies across development nodes.
+pub fn chain_id(chain: ChainType) -> Option<u64> {
+    match chain {
+        ChainType::BSC => Some(56),
+        ChainType::Ethereum => Some(1),
+        ChainType::Polygon => Some(137),
+        ChainType::Arbitrum => Some(42161),
+        ChainType::Optimism => Some(10),
+        ChainType::Base => Some(8453),
+        ChainType::Avalanche => Some(43114),
+        ChainType::Fantom => Some(250),
+        ChainType::Gnosis => Some(100),
+        ChainType::BSCTestnet => Some(97),
+        ChainType::Sepolia => Some(11155111),
+        ChainType::Holesky => Some(17000),
+        ChainType::PolygonAmoy => Some(80002),
+        ChainType::Local => None,
+        ChainType::Custom(index) => chain_config(index).map(|c| c.chain_id),
+    }
+}
+
+/// Get number of blocks to query event logs for per request which the chain's
+/// public RPC endpoint accepts.
+///
+/// # Arguments
+/// - `chain` - `ChainType`
+pub fn logs_chunk_size(chain: ChainType) -> u64 {
+    match chain {
+        ChainType::BSC | ChainType::BSCTestnet => 5000,
+        ChainType::Polygon | ChainType::PolygonAmoy => 3000,
+        ChainType::Local => 100_000,
+        ChainType::Custom(index) => chain_config(index).and_then(|c| c.logs_chunk_size).unwrap_or(DEFAULT_LOGS_CHUNK_SIZE),
+        _ => DEFAULT_LOGS_CHUNK_SIZE,
+    }
+}
+
+/// Get name of environment variable which overrides the RPC endpoint of the chain
+/// i.e. `CRUNNER_RPC_BSC`, `CRUNNER_RPC_BSC_TESTNET`.
+///
+/// # Arguments
+/// - `chain` - `ChainType`
+pub fn rpc_endpoint_env(chain: ChainType) -> String {
+    format!("CRUNNER_RPC_{}", chain_name(chain).to_uppercase().replace('-', "_"))
+}
+
+/// Get RPC endpoints of the chain in order of preference. The per-chain environment
+/// variable (comma-separated for multiple endpoints) takes precedence over the
+/// built-in RPC endpoint.
+///
+/// # Arguments
+/// - `chain` - `ChainType`
+pub fn rpc_endpoints(chain: ChainType) -> Result<Vec<String>, CrunnerError> {
+    if let Ok(endpoints) = std::env::var(rpc_endpoint_env(chain)) {
+        let endpoints: Vec<String> = endpoints.split(',')
+            .map(|e| e.trim())
+            .filter(|e| !e.is_empty())
+            .map(|e| e.to_owned())
+            .collect();
+        if !endpoints.is_empty() {
+            return Ok(endpoints);
+        }
+    }
+
+    let rpc_endpoint = match chain {
+        ChainType::BSC => BSC_RPC_ENDPOINT,
+        ChainType::Ethereum => ETHEREUM_RPC_ENDPOINT,
+        ChainType::Polygon => POLYGON_RPC_ENDPOINT,
+        ChainType::Arbitrum => ARBITRUM_RPC_ENDPOINT,
+        ChainType::Optimism => OPTIMISM_RPC_ENDPOINT,
+        ChainType::Base => BASE_RPC_ENDPOINT,
+        ChainType::Avalanche => AVALANCHE_RPC_ENDPOINT,
+        ChainType::Fantom => FANTOM_RPC_ENDPOINT,
+        ChainType::Gnosis => GNOSIS_RPC_ENDPOINT,
+        ChainType::BSCTestnet => BSC_TESTNET_RPC_ENDPOINT,
+        ChainType::Sepolia => SEPOLIA_RPC_ENDPOINT,
+        ChainType::Holesky => HOLESKY_RPC_ENDPOINT,
+        ChainType::PolygonAmoy => POLYGON_AMOY_RPC_ENDPOINT,
+        ChainType::Local => LOCAL_RPC_ENDPOINT,
+        ChainType::Custom(index) => match chain_config(index) {
+            Some(config) => return Ok(config.rpc.clone()),
+            None => return Err(CrunnerError::ConfigError(format!("Error, unknown user-defined chain at index {}", index))),
+        },
+    };
+    Ok(vec![rpc_endpoint.to_owned()])
+}
+
+/// Create a web3 instance
+///
+/// # Arguments
+/// - `chain` - `ChainType`
+pub fn create_web3(chain: ChainType) -> Result<Web3<CrunnerTransport>, CrunnerError> {
+    let web3 = create_web3_from_urls(&rpc_endpoints(chain)?)?;
+
+    let headers = configured_rpc_headers(chain_name(chain));
+    if headers.is_empty() {
+        return Ok(web3);
+    }
+    let transport = web3.transport().clone().with_headers(&headers)?;
+    Ok(Web3::new(transport))
+}
+
+/// Create a web3 instance connecting to the specified RPC endpoint
+///
+/// # Arguments
+/// - `rpc_url` - RPC endpoint url, or IPC path i.e. `/path/to/geth.ipc`
+pub fn create_web3_from_url(rpc_url: &str) -> Result<Web3<CrunnerTransport>, CrunnerError> {
+    let transport = CrunnerTransport::new(rpc_url)?;
+    Ok(Web3::new(transport))
+}
+
+/// Create a web3 instance connecting to the specified RPC endpoints; it fails over
+/// to the next endpoint whenever the current one fails to deliver a response.
+///
+/// # Arguments
+/// - `rpc_urls` - RPC endpoint urls, or IPC paths in order of preference
+pub fn create_web3_from_urls(rpc_urls: &[String]) -> Result<Web3<CrunnerTransport>, CrunnerError> {
+    let transport = CrunnerTransport::with_endpoints(rpc_urls)?;
+    Ok(Web3::new(transport))
+}
+
+/// Get unit string from the specified `ChainType`.
+///
+/// # Arguments
+/// - `chain` - `ChainType`
+///
+/// # Return
+/// Return static string representing of the chain.
+pub fn unit_str(chain: ChainType) -> &'static str {
+    match chain {
+        ChainType::BSC => "BNB",
+        ChainType::Ethereum => "ETH",
+        ChainType::Polygon => "MATIC",
+        ChainType::Arbitrum => "ETH",
+        ChainType::Optimism => "ETH",
+        ChainType::Base => "ETH",
+        ChainType::Avalanche => "AVAX",
+        ChainType::Fantom => "FTM",
+        ChainType::Gnosis => "xDAI",
+        ChainType::BSCTestnet => "tBNB",
+        ChainType::Sepolia => "SepoliaETH",
+        ChainType::Holesky => "HoleskyETH",
+        ChainType::PolygonAmoy => "POL",
+        ChainType::Local => "ETH",
+        ChainType::Custom(index) => chain_config(index).map(|c| c.symbol.as_str()).unwrap_or("ETH"),
+    }
+}
+
+/// Parse a long hex string into vector of hex string of 64 characters in length (256 bit)
+/// excluding the prefixed method-id which has 8 characters in length (32 bit).
+/// Return a vector of hex string of 64 characters in length (256 bit);
+///
+/// # Arguments
+/// * `long_hex_str` - input long hex string to parse; included a prefix of `0x`
+pub fn parse_256_method_arguments(long_hex_str: &str) -> Result<Vec<String>, CrunnerError> {
+    if long_hex_str.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    // get slice excluding prefix of method-id
+    let arguments_hex_str = &long_hex_str[10..];
+
+    // the length of input stringis not long enough to get at least one element
+    if arguments_hex_str.len() < 64 {
+        return Err(CrunnerError::InvalidArgument("Input hex string length is not long enough to be parsed.
+It needs to have at least 64 characters in length included with prefix of 0x".to_owned()));
+    }
+
+    let mut offset_i: usize = 0;
+    let mut res_vec: Vec<String> = Vec::new();
+
+    while offset_i + 64 <= arguments_hex_str.len() {
+        res_vec.push(arguments_hex_str[offset_i..offset_i+64].to_owned());
+        offset_i += 64;
+    }
+
+    Ok(res_vec)
+}
+
+/// Create a contract
+///
+/// # Arguments
+/// * `web3` - web3 instance
+/// * `contract_address_str` - contract address string
+/// * `abi_str` - abi
+pub fn create_contract(web3: &Web3<CrunnerTransport>, contract_address_str: &str, abi_str: &str) -> Result<Contract<CrunnerTransport>, CrunnerError> {
+    let contract_address: Address = match get_address_from_str(contract_address_str) {
+        Ok(res) => res,
+        Err(e) => return Err(CrunnerError::InvalidAddress(format!("Error address is in wrong format ({}); err={}", contract_address_str, e))),
+    };
+
+    // create a contract from contract address, and abi
+    match Contract::from_json(web3.eth(), contract_address, abi_str.as_bytes()) {
+        Ok(res) => Ok(res),
+        Err(e) => {
+            let err_msg = format!("Error creating contract associated with abi for {}; err={}", contract_address_str, e);
+            Err(CrunnerError::AbiError(err_msg))
+        }
+    }
+}
+
+/// Create a contract from the built-in ABI merged with entries from ABI file (if any).
+///
+/// # Arguments
+/// * `web3` - web3 instance
+/// * `contract_address_str` - contract address string
+/// * `abi_filepath` - optional ABI json filepath whose entries are merged with the built-in ABI
+pub fn create_contract_from_abifile(web3: &Web3<CrunnerTransport>, contract_address_str: &str, abi_filepath: Option<&str>) -> Result<Contract<CrunnerTransport>, CrunnerError> {
+    let abi = load_abi(abi_filepath)?;
+    create_contract_with_abi(web3, contract_address_str, abi)
+}
+
+/// Create a contract from already parsed ABI.
+///
+/// # Arguments
+/// * `web3` - web3 instance
+/// * `contract_address_str` - contract address string
+/// * `abi` - parsed ABI
+pub fn create_contract_with_abi(web3: &Web3<CrunnerTransport>, contract_address_str: &str, abi: ethabi::Contract) -> Result<Contract<CrunnerTransport>, CrunnerError> {
+    let contract_address: Address = match get_address_from_str(contract_address_str) {
+        Ok(res) => res,
+        Err(e) => return Err(CrunnerError::InvalidAddress(format!("Error address is in wrong format ({}); err={}", contract_address_str, e))),
+    };
+
+    // create a contract from contract address, and abi
+    Ok(Contract::new(web3.eth(), contract_address, abi))
+}
+
+/// Load the built-in ABI, then merge entries from ABI file on top of it if
+/// `abi_filepath` is specified.
+///
+/// # Arguments
+/// * `abi_filepath` - optional ABI json filepath
+///
+/// # Return
+/// Return the merged `ethabi::Contract` in case of success.
+pub fn load_abi(abi_filepath: Option<&str>) -> Result<ethabi::Contract, CrunnerError> {
+    let mut abi = match ethabi::Contract::load(ABI_STR.as_bytes()) {
+        Ok(res) => res,
+        Err(e) => return Err(CrunnerError::AbiError(format!("Error parsing built-in abi; err={}", e))),
+    };
+
+    if let Some(abi_filepath) = abi_filepath {
+        let mut abi_buffer = Vec::<u8>::new();
+        if let Err(e) = read_abi(abi_filepath, &mut abi_buffer) {
+            return Err(CrunnerError::AbiError(format!("Error reading abi file at '{}'; err={}", abi_filepath, e)));
+        }
+
+        let file_abi = match ethabi::Contract::load(abi_buffer.as_slice()) {
+            Ok(res) => res,
+            Err(e) => return Err(CrunnerError::AbiError(format!("Error parsing abi file at '{}'; err={}", abi_filepath, e))),
+        };
+        merge_abi(&mut abi, file_abi);
+    }
+
+    Ok(abi)
+}
+
+/// Load the built-in ABI of the token standard.
+///
+/// # Arguments
+/// * `standard` - token standard
+pub fn load_standard_abi(standard: TokenStandard) -> Result<ethabi::Contract, CrunnerError> {
+    let load = |abi_str: &str| match ethabi::Contract::load(abi_str.as_bytes()) {
+        Ok(res) => Ok(res),
+        Err(e) => Err(CrunnerError::AbiError(format!("Error parsing built-in abi of {:?}; err={}", standard, e))),
+    };
+    match standard {
+        TokenStandard::Erc20 => load(ERC20_ABI_STR),
+        TokenStandard::Erc20Permit => {
+            let mut abi = load(ERC20_ABI_STR)?;
+            merge_abi(&mut abi, load(ERC2612_ABI_STR)?);
+            Ok(abi)
+        },
+        TokenStandard::Erc721 => load(ERC721_ABI_STR),
+        TokenStandard::Erc1155 => load(ERC1155_ABI_STR),
+    }
+}
+
+/// Load the built-in ABI, then merge the verified ABI of `address` fetched from
+/// block explorer of the specified chain on top of it.
+///
+/// # Arguments
+/// * `chain` - `ChainType` to select which block explorer to fetch from
+/// * `address` - contract address whose verified ABI to be fetched
+///
+/// # Return
+/// Return the merged `ethabi::Contract` in case of success.
+pub async fn load_abi_from_explorer(chain: ChainType, address: &str) -> Result<ethabi::Contract, CrunnerError> {
+    let mut abi = load_abi(None)?;
+
+    let abi_str = fetch_abi_from_explorer(chain, address).await?;
+    let explorer_abi = match ethabi::Contract::load(abi_str.as_bytes()) {
+        Ok(res) => res,
+        Err(e) => return Err(CrunnerError::AbiError(format!("Error parsing abi fetched from block explorer for {}; err={}", address, e))),
+    };
+    merge_abi(&mut abi, explorer_abi);
+
+    Ok(abi)
+}
+
+/// Get Etherscan-compatible block explorer API endpoint of the chain.
+///
+/// # Arguments
+/// * `chain` - `ChainType`
+pub fn explorer_api_endpoint(chain: ChainType) -> Result<&'static str, CrunnerError> {
+    let api_endpoint = match chain {
+        ChainType::BSC => BSC_EXPLORER_API_ENDPOINT,
+        ChainType::Ethereum => ETHEREUM_EXPLORER_API_ENDPOINT,
+        ChainType::Polygon => POLYGON_EXPLORER_API_ENDPOINT,
+        ChainType::Arbitrum => ARBITRUM_EXPLORER_API_ENDPOINT,
+        ChainType::Optimism => OPTIMISM_EXPLORER_API_ENDPOINT,
+        ChainType::Base => BASE_EXPLORER_API_ENDPOINT,
+        ChainType::Avalanche => AVALANCHE_EXPLORER_API_ENDPOINT,
+        ChainType::Fantom => FANTOM_EXPLORER_API_ENDPOINT,
+        ChainType::Gnosis => GNOSIS_EXPLORER_API_ENDPOINT,
+        ChainType::BSCTestnet => BSC_TESTNET_EXPLORER_API_ENDPOINT,
+        ChainType::Sepolia => SEPOLIA_EXPLORER_API_ENDPOINT,
+        ChainType::Holesky => HOLESKY_EXPLORER_API_ENDPOINT,
+        ChainType::PolygonAmoy => POLYGON_AMOY_EXPLORER_API_ENDPOINT,
+        ChainType::Local => return Err(CrunnerError::ConfigError("Error, local chain has no block explorer".to_owned())),
+        ChainType::Custom(index) => match chain_config(index).and_then(|c| c.explorer_api.as_deref()) {
+            Some(res) => res,
+            None => return Err(CrunnerError::ConfigError(format!("Error, chain '{}' has no block explorer api configured", chain_name(chain)))),
+        },
+    };
+    Ok(api_endpoint)
+}
+
+/// Fetch the verified ABI of contract address from block explorer API
+/// i.e. BscScan, Etherscan, or Polygonscan depending on the chain.
+/// API key is read from `CRUNNER_EXPLORER_API_KEY` environment variable if set.
+///
+/// # Arguments
+/// * `chain` - `ChainType` to select which block explorer to fetch from
+/// * `address` - contract address whose verified ABI to be fetched
+///
+/// # Return
+/// Return ABI json string in case of success.
+pub async fn fetch_abi_from_explorer(chain: ChainType, address: &str) -> Result<String, CrunnerError> {
+    get_address_from_str(address)?;
+
+    let api_endpoint = explorer_api_endpoint(chain)?;
+
+    let mut query = vec![("module", "contract".to_owned()), ("action", "getabi".to_owned()), ("address", address.to_owned())];
+    if let Ok(api_key) = std::env::var(EXPLORER_API_KEY_ENV) {
+        query.push(("apikey", api_key));
+    }
+
+    let resp = match reqwest::Client::new().get(api_endpoint).query(&query).send().await {
+        Ok(res) => res,
+        Err(e) => return Err(CrunnerError::AbiError(format!("Error requesting abi from block explorer for {}; err={}", address, e))),
+    };
+    let body: serde_json::Value = match resp.json().await {
+        Ok(res) => res,
+        Err(e) => return Err(CrunnerError::AbiError(format!("Error parsing response from block explorer for {}; err={}", address, e))),
+    };
+
+    // status of "1" indicates success, otherwise `result` holds the error message
+    let result = body["result"].as_str().unwrap_or_default();
+    if body["status"].as_str() != Some("1") {
+        return Err(CrunnerError::AbiError(format!("Error fetching abi from block explorer for {}; err={}", address, result)));
+    }
+
+    Ok(result.to_owned())
+}
+
+/// Merge entries of `other` ABI into `base` ABI.
+/// Functions, events, and errors which have the same signature as the one
+/// already existing in `base` will replace such entry, otherwise they are added
+/// as another overload.
+///
+/// # Arguments
+/// * `base` - ABI to merge into
+/// * `other` - ABI whose entries to be merged
+pub fn merge_abi(base: &mut ethabi::Contract, other: ethabi::Contract) {
+    if other.constructor.is_some() {
+        base.constructor = other.constructor;
+    }
+
+    for (name, functions) in other.functions {
+        let entries = base.functions.entry(name).or_default();
+        for f in functions {
+            entries.retain(|e| e.signature() != f.signature());
+            entries.push(f);
+        }
+    }
+
+    for (name, events) in other.events {
+        let entries = base.events.entry(name).or_default();
+        for ev in events {
+            entries.retain(|e| e.signature() != ev.signature());
+            entries.push(ev);
+        }
+    }
+
+    for (name, errors) in other.errors {
+        let entries = base.errors.entry(name).or_default();
+        for err in errors {
+            entries.retain(|e| e.inputs != err.inputs);
+            entries.push(err);
+        }
+    }
+
+    base.receive |= other.receive;
+    base.fallback |= other.fallback;
+}
+
+/// Parse function signature i.e. `transfer(address,uint256)` into its name, and
+/// input types.
+///
+/// # Arguments
+/// - `fn_sig` - function signature
+pub fn parse_fn_sig(fn_sig: &str) -> Result<(String, Vec<ParamType>), CrunnerError> {
+    let (name, types_str) = match fn_sig.find('(') {
+        Some(i) => fn_sig.split_at(i),
+        None => return Err(CrunnerError::InvalidArgument(format!("Error function signature is missing parameter types; sig={}", fn_sig))),
+    };
+
+    Ok((name.trim().to_owned(), parse_ret_types(types_str)?))
+}
+
+/// Canonicalize function, event, or error signature for hashing i.e.
+/// `transfer(address to, uint amount)` into `transfer(address,uint256)`; parameter
+/// names, and keywords such as `indexed`, or `memory` are dropped.
+///
+/// # Arguments
+/// * `sig` - signature in format `name(type [name],...)`
+pub fn canonical_signature(sig: &str) -> Result<String, CrunnerError> {
+    let (name, params_str) = match (sig.find('('), sig.rfind(')')) {
+        (Some(start), Some(end)) if start < end && sig[end + 1..].trim().is_empty() => (sig[..start].trim(), &sig[start + 1..end]),
+        _ => return Err(CrunnerError::InvalidArgument(format!("Error signature is not in format 'name(type,...)'; sig={}", sig))),
+    };
+    if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$') {
+        return Err(CrunnerError::InvalidArgument(format!("Error invalid name in signature; sig={}", sig)));
+    }
+    Ok(format!("{}({})", name, canonical_params(params_str)?))
+}
+
+/// Canonicalize comma-separated parameters i.e. `address to, uint amount` into
+/// `address,uint256`.
+fn canonical_params(params_str: &str) -> Result<String, CrunnerError> {
+    let types = split_top_level(params_str).into_iter()
+        .map(str::trim)
+        .filter(|p| !p.is_empty())
+        .map(canonical_param_type)
+        .collect::<Result<Vec<String>, CrunnerError>>()?;
+    Ok(types.join(","))
+}
+
+/// Canonicalize type of a parameter which is optionally followed by its name.
+fn canonical_param_type(param_str: &str) -> Result<String, CrunnerError> {
+    let type_str = if param_str.starts_with('(') {
+        // tuple whose components may be named as well; find its closing parenthesis
+        let mut depth: i32 = 0;
+        let end = param_str.char_indices().find(|(_, c)| {
+            match c {
+                '(' => depth += 1,
+                ')' => depth -= 1,
+                _ => (),
+            }
+            depth == 0
+        });
+        let end = match end {
+            Some((i, _)) => i,
+            None => return Err(CrunnerError::InvalidArgument(format!("Error unbalanced parentheses in parameter '{}'", param_str))),
+        };
+        let rest = &param_str[end + 1..];
+        let array_suffix = rest.split_whitespace().next().filter(|s| s.starts_with('[')).unwrap_or_default();
+        format!("({}){}", canonical_params(&param_str[1..end])?, array_suffix)
+    }
+    else {
+        param_str.split_whitespace().next().unwrap_or_default().to_owned()
+    };
+
+    match ethabi::param_type::Reader::read(&type_str) {
+        Ok(res) => Ok(res.to_string()),
+        Err(e) => Err(CrunnerError::InvalidArgument(format!("Error parsing type of parameter '{}'; err={}", param_str, e))),
+    }
+}
+
+/// Resolve which function to call among overloaded functions of the same name.
+/// If `fn_sig` is specified, select the overload whose input types match.
+/// Otherwise, select the only overload that the input parameters can be
+/// tokenized against.
+///
+/// # Arguments
+/// - `abi` - ABI to look up the function
+/// - `fn_name` - name of the function
+/// - `fn_sig` - optional function signature i.e. `transfer(address,uint256)`
+/// - `params` - input parameter strings
+pub fn resolve_function_overload(abi: &ethabi::Contract, fn_name: &str, fn_sig: Option<&str>, params: &[String]) -> Result<ethabi::Function, CrunnerError> {
+    let overloads = match abi.functions_by_name(fn_name) {
+        Ok(res) => res,
+        Err(e) => return Err(CrunnerError::AbiError(format!("Error finding function '{}' in abi; err={}", fn_name, e))),
+    };
+
+    if let Some(fn_sig) = fn_sig {
+        let (_, input_types) = parse_fn_sig(fn_sig)?;
+        return match overloads.iter().find(|f| f.inputs.iter().map(|input| &input.kind).eq(input_types.iter())) {
+            Some(f) => Ok(f.clone()),
+            None => Err(CrunnerError::AbiError(format!("Error no function matches signature '{}' in abi", fn_sig))),
+        };
+    }
+
+    if overloads.len() == 1 {
+        return Ok(overloads[0].clone());
+    }
+
+    let candidates: Vec<&ethabi::Function> = overloads.iter()
+        .filter(|f| f.inputs.len() == params.len())
+        .filter(|f| {
+            let input_types: Vec<ParamType> = f.inputs.iter().map(|input| input.kind.clone()).collect();
+            // parameters have to fit the ABI types, and also resemble them in shape
+            // because `5` fits `string` as well as `uint256`
+            if prepare_params(params, Some(&input_types), false).is_err() {
+                return false;
+            }
+            match prepare_params_impl(params, Some(&input_types), false, false) {
+                Ok(tokens) => Token::types_check(&tokens, &input_types),
+                Err(_) => false,
+            }
+        })
+        .collect();
+
+    match candidates.len() {
+        1 => Ok(candidates[0].clone()),
+        0 => Err(CrunnerError::InvalidArgument(format!("Error no overload of function '{}' matches the input parameters", fn_name))),
+        _ => {
+            let sigs: Vec<String> = candidates.iter().map(|f| format!("{}({})", f.name, f.inputs.iter().map(|input| input.kind.to_string()).collect::<Vec<_>>().join(","))).collect();
+            Err(CrunnerError::InvalidArgument(format!("Error ambiguous overloads of function '{}'; supply --fn-sig with one of {}", fn_name, sigs.join(", "))))
+        }
+    }
+}
+
+/// Create a contract instance whose ABI prioritizes the resolved overload of
+/// the function, so subsequent calls by function name use such overload.
+///
+/// # Arguments
+/// - `web3` - web3 instance
+/// - `contract` - contract instance
+/// - `fn_name` - name of the function
+/// - `fn_sig` - optional function signature i.e. `transfer(address,uint256)`
+/// - `params` - input parameter strings
+pub fn select_function_overload(web3: &Web3<CrunnerTransport>, contract: &Contract<CrunnerTransport>, fn_name: &str, fn_sig: Option<&str>, params: &[String]) -> Result<Contract<CrunnerTransport>, CrunnerError> {
+    let mut abi = contract.abi().clone();
+    let function = resolve_function_overload(&abi, fn_name, fn_sig, params)?;
+
+    if let Some(overloads) = abi.functions.get_mut(fn_name) {
+        overloads.retain(|f| f.inputs != function.inputs);
+        overloads.insert(0, function);
+    }
+
+    Ok(Contract::new(web3.eth(), contract.address(), abi))
+}
+
+/// Get input types of the function as declared in ABI of the contract.
+/// Return `None` if there is no such function.
+///
+/// # Arguments
+/// - `contract` - contract instance whose ABI to look up
+/// - `fn_name` - name of the function
+pub fn function_input_types(contract: &Contract<CrunnerTransport>, fn_name: &str) -> Option<Vec<ParamType>> {
+    contract.abi().function(fn_name).ok().map(|f| f.inputs.iter().map(|input| input.kind.clone()).collect())
+}
+
+/// Resolve component types of `tuple` in annotated type from the type declared
+/// in ABI, as `tuple:` annotation itself doesn't describe its components.
+/// Annotated type without `tuple` is returned as it is.
+///
+/// # Arguments
+/// - `annotated_type` - type from parameter's annotation
+/// - `abi_type` - type of such parameter as declared in ABI if available
+pub fn resolve_tuple_type(annotated_type: &ParamType, abi_type: Option<&ParamType>) -> Result<ParamType, CrunnerError> {
+    match (annotated_type, abi_type) {
+        (ParamType::Tuple(components), _) if !components.is_empty() => Ok(annotated_type.clone()),
+        (ParamType::Tuple(_), Some(ParamType::Tuple(abi_components))) => Ok(ParamType::Tuple(abi_components.clone())),
+        (ParamType::Tuple(_), _) => Err(CrunnerError::InvalidArgument("tuple parameter requires its component types declared in ABI".to_owned())),
+        (ParamType::Array(item_type), abi_type) => {
+            let abi_item_type = match abi_type {
+                Some(ParamType::Array(t)) | Some(ParamType::FixedArray(t, _)) => Some(t.as_ref()),
+                _ => None,
+            };
+            Ok(ParamType::Array(Box::new(resolve_tuple_type(item_type, abi_item_type)?)))
+        },
+        (ParamType::FixedArray(item_type, len), abi_type) => {
+            let abi_item_type = match abi_type {
+                Some(ParamType::Array(t)) | Some(ParamType::FixedArray(t, _)) => Some(t.as_ref()),
+                _ => None,
+            };
+            Ok(ParamType::FixedArray(Box::new(resolve_tuple_type(item_type, abi_item_type)?), *len))
+        },
+        _ => Ok(annotated_type.clone()),
+    }
+}
+
+/// Prepare parameters for supplying to smart contract's method.
+///
+/// # Arguments
+/// - `params` - input parameter strings as slice
+/// - `input_types` - optional input types of the method as declared in ABI; parameter
+///   without annotation is tokenized against its type, and this is required to resolve
+///   component types of `tuple:` annotated parameter
+/// - `print_param_type` - whether or not to also log each parameter type at debug level
+///
+/// # Return
+/// Return a slice of parsed `Token` in case of success.
+pub fn prepare_params(params: &[String], input_types: Option<&[ParamType]>, print_param_type: bool) -> Result<Vec<Token>, CrunnerError> {
+    prepare_params_impl(params, input_types, print_param_type, true)
+}
+
+/// Prepare parameters as `prepare_params` does, but if `scalars_from_abi` is false,
+/// scalar parameter without annotation is tokenized by guessing from its shape
+/// regardless of its ABI type. This is used to tell overloads apart.
+fn prepare_params_impl(params: &[String], input_types: Option<&[ParamType]>, print_param_type: bool, scalars_from_abi: bool) -> Result<Vec<Token>, CrunnerError> {
+    let mut parsed_params: Vec<Token> = Vec::new();
+
+    for (i, p) in params.iter().enumerate() {
+        // type annotated parameter takes precedence over guessing from its shape
+        if let Some((annotated_type, value)) = parse_param_annotation(p) {
+            let abi_type = input_types.and_then(|types| types.get(i));
+            let param_type = match resolve_tuple_type(&annotated_type, abi_type) {
+                Ok(res) => res,
+                Err(e) => {
+                    let err_msg = format!("Error parsing parameter '{}'; err={}", &p, e);
+                    return Err(CrunnerError::InvalidArgument(err_msg));
+                }
+            };
+
+            if print_param_type {
+                tracing::debug!(param = %p, param_type = %param_type, "parsed parameter");
+            }
+
+            match tokenize_param(&param_type, value) {
+                Ok(token) => parsed_params.push(token),
+                Err(e) => {
+                    let err_msg = format!("Error parsing parameter '{}' for {} type; err={}", &p, param_type, e);
+                    return Err(CrunnerError::InvalidArgument(err_msg));
+                }
+            }
+            continue;
+        }
+
+        // parameter without annotation takes its type from ABI if known, so it gets range
+        // checked i.e. `256` for `uint8`; otherwise only array value i.e. `[1,2,3]` does
+        if let Some(abi_type) = input_types.and_then(|types| types.get(i)) {
+            let is_array_type = matches!(abi_type, ParamType::Array(_) | ParamType::FixedArray(_, _));
+            if scalars_from_abi || (is_array_type && is_array_literal(p)) {
+                if print_param_type {
+                    tracing::debug!(param = %p, param_type = %abi_type, "parsed parameter");
+                }
+
+                match tokenize_param(abi_type, p) {
+                    Ok(token) => parsed_params.push(token),
+                    Err(e) => {
+                        let err_msg = format!("Error parsing parameter '{}' for {} type; err={}", &p, abi_type, e);
+                        return Err(e.with_message(err_msg));
+                    }
+                }
+                continue;
+            }
+        }
+
+        match parse_param_type(p) {
+            FnParamType::Address => {
+                if print_param_type {
+                    tracing::debug!(param = %p, param_type = "Address", "parsed parameter");
+                }
+                
+                let addr = match get_address_from_str(p) {
+                    Ok(addr) => addr,
+                    Err(e) => {
+                        let err_msg = format!("Error parsing parameter '{}' for Address type; err={}", &p, e);
+                        return Err(CrunnerError::InvalidAddress(err_msg));
+                    }
+                };
+                parsed_params.push(addr.into_token());
+            },
+            FnParamType::HU256 => {
+                if print_param_type {
+                    tracing::debug!(param = %p, param_type = "U256", "parsed parameter");
+                }
+
+                let trimmed_prefix = p.trim_start_matches("0x");
+                let u256_val = match U256::from_str_radix(trimmed_prefix, 16) {
+                    Ok(res) => res,
+                    Err(e) => {
+                        let err_msg = format!("Error creating U256 from hexadecimal string; e={}", e);
+                        return Err(CrunnerError::InvalidArgument(err_msg));
+                    }
+                };
+                parsed_params.push(u256_val.into_token());
+            }
+            FnParamType::DU256 => {
+                if print_param_type {
+                    tracing::debug!(param = %p, param_type = "Decimal", "parsed parameter");
+                }
+
+                let u256_val = match U256::from_dec_str(p) {
+                    Ok(res) => res,
+                    Err(e) => {
+                        let err_msg = format!("Error creating U256 from decimal string; e={}", e);
+                        return Err(CrunnerError::InvalidArgument(err_msg));
+                    }
+                };
+                parsed_params.push(u256_val.into_token());
+            },
+            FnParamType::AU256 => {
+                if print_param_type {
+                    tracing::debug!(param = %p, param_type = "Amount", "parsed parameter");
+                }
+
+                let u256_val = match parse_amount(p) {
+                    Ok(res) => res,
+                    Err(e) => {
+                        let err_msg = format!("Error creating U256 from amount string; e={}", e);
+                        return Err(CrunnerError::InvalidArgument(err_msg));
+                    }
+                };
+                parsed_params.push(u256_val.into_token());
+            },
+            FnParamType::DI256 => {
+                if print_param_type {
+                    tracing::debug!(param = %p, param_type = "Signed Decimal", "parsed parameter");
+                }
+
+                match tokenize_param(&ParamType::Int(256), p) {
+                    Ok(token) => parsed_params.push(token),
+                    Err(e) => {
+                        let err_msg = format!("Error creating I256 from decimal string; e={}", e);
+                        return Err(CrunnerError::InvalidArgument(err_msg));
+                    }
+                }
+            },
+            FnParamType::Bool => {
+                if print_param_type {
+                    tracing::debug!(param = %p, param_type = "Bool", "parsed parameter");
+                }
+                parsed_params.push(p.eq_ignore_ascii_case("true").into_token());
+            },
+            FnParamType::String => {
+                if print_param_type {
+                    tracing::debug!(param = %p, param_type = "String", "parsed parameter");
+                }
+                parsed_params.push(p.to_owned().into_token());
+            }
+        }
+    }
+
+    Ok(parsed_params)
+}
+
+/// Get signature i.e. `transfer(address,uint256)` of the function declared in ABI
+/// whose selector matches the first 4 bytes of call data.
+///
+/// # Arguments
+/// - `abi` - ABI to look up the function in
+/// - `input` - call data
+pub fn function_signature_of(abi: &ethabi::Contract, input: &[u8]) -> Option<String> {
+    if input.len() < 4 {
+        return None;
+    }
+    abi.functions()
+        .find(|f| f.short_signature() == input[..4])
+        .map(|f| format!("{}({})", f.name, f.inputs.iter().map(|p| p.kind.to_string()).collect::<Vec<String>>().join(",")))
+}
+
+/// Decode revert data of standard `Error(string)`, `Panic(uint256)`, or custom
+/// error declared in ABI into human-readable reason i.e.
+/// `InsufficientBalance(available: 5, required: 10)` for custom error.
+///
+/// # Arguments
+/// - `data` - revert data returned by the node
+/// - `abi` - ABI to look up custom errors in (if any)
+///
+/// # Return
+/// Return `None` if the data is neither of standard revert, nor of known custom error.
+pub fn decode_revert_reason(data: &[u8], abi: Option<&ethabi::Contract>) -> Option<String> {
+    if data.len() < 4 {
+        return None;
+    }
+    let (selector, payload) = data.split_at(4);
+    if selector == ERROR_STRING_SELECTOR {
+        return match ethabi::decode(&[ParamType::String], payload) {
+            Ok(tokens) => tokens.into_iter().next().and_then(|t| t.into_string()),
+            Err(_) => None,
+        };
+    }
+    if selector == PANIC_SELECTOR {
+        let code = match ethabi::decode(&[ParamType::Uint(256)], payload) {
+            Ok(tokens) => tokens.into_iter().next().and_then(|t| t.into_uint())?,
+            Err(_) => return None,
+        };
+        let cause = match code.low_u64() {
+            0x01 => "assertion failed",
+            0x11 => "arithmetic overflow, or underflow",
+            0x12 => "division, or modulo by zero",
+            0x21 => "invalid enum value",
+            0x22 => "invalid storage byte array",
+            0x31 => "pop on empty array",
+            0x32 => "array index out of bounds",
+            0x41 => "out of memory",
+            0x51 => "call to uninitialized function",
+            _ => "unknown panic",
+        };
+        return Some(format!("panic 0x{:02x} ({})", code, cause));
+    }
+
+    let error = abi?.errors().find(|e| &e.signature()[..4] == selector)?;
+    let tokens = error.decode(payload).ok()?;
+    let args = error.inputs.iter().map(|input| input.name.as_str()).zip(tokens.iter());
+    Some(format!("{}({})", error.name, format_named_tokens(args)))
+}
+
+/// Format tokens along with their names (if any) i.e. `from: 0x..., value: 5`.
+fn format_named_tokens<'a>(args: impl Iterator<Item = (&'a str, &'a Token)>) -> String {
+    let items: Vec<String> = args
+        .map(|(name, token)| {
+            if name.is_empty() {
+                format_token(token)
+            }
+            else {
+                format!("{}: {}", name, format_token(token))
+            }
+        })
+        .collect();
+    items.join(", ")
+}
+
+/// Decode event log against events declared in ABI by matching its first topic
+/// with signature of the event. Anonymous events are not matched.
+///
+/// # Arguments
+/// - `abi` - ABI to look up events in
+/// - `log` - event log i.e. from transaction receipt
+///
+/// # Return
+/// Return `None` if no event in ABI matches the log.
+pub fn decode_log(abi: &ethabi::Contract, log: &Log) -> Option<DecodedEvent> {
+    let topic0 = log.topics.first()?;
+    let event = abi.events().find(|e| !e.anonymous && e.signature() == *topic0)?;
+    decode_log_as(event, log, false)
+}
+
+/// Decode event log as the specified event.
+///
+/// # Arguments
+/// - `event` - event to decode the log as
+/// - `log` - event log
+/// - `infer_indexed` - whether to infer which parameters are indexed from number of
+///   topics of the log; the leading parameters are assumed to be indexed
+///
+/// # Return
+/// Return `None` if the log doesn't match the event.
+pub fn decode_log_as(event: &ethabi::Event, log: &Log, infer_indexed: bool) -> Option<DecodedEvent> {
+    let mut event = event.clone();
+    let num_indexed = log.topics.len().saturating_sub(1);
+    for (i, input) in event.inputs.iter_mut().enumerate() {
+        if infer_indexed {
+            input.indexed = i < num_indexed;
+        }
+        // ethabi matches decoded values to parameters by name; make unnamed ones unique
+        if input.name.is_empty() {
+            input.name = format!("{}{}", UNNAMED_PARAM_PREFIX, i);
+        }
+    }
+
+    let raw_log = ethabi::RawLog { topics: log.topics.clone(), data: log.data.0.clone() };
+    let parsed = event.parse_log(raw_log).ok()?;
+    Some(DecodedEvent {
+        name: event.name,
+        params: parsed.params.into_iter()
+            .map(|p| (if p.name.starts_with(UNNAMED_PARAM_PREFIX) { String::new() } else { p.name }, p.value))
+            .collect(),
+    })
+}
+
+/// Parse event signature i.e. `Transfer(address,address,uint256)`. Parameters can be
+/// annotated with `indexed`, and names as in Solidity i.e.
+/// `Transfer(address indexed from, address indexed to, uint256 value)`. Without any
+/// `indexed` annotation, the event of the same signature declared in `abi` is used if any.
+///
+/// # Arguments
+/// - `event_sig` - event signature
+/// - `abi` - ABI to look up the event in
+///
+/// # Return
+/// Return the event, and whether its indexed parameters are unknown, and have to be
+/// inferred from each log.
+pub fn resolve_event_sig(event_sig: &str, abi: &ethabi::Contract) -> Result<(ethabi::Event, bool), CrunnerError> {
+    let (name, inputs) = parse_named_sig(event_sig)?;
+    let any_indexed = inputs.iter().any(|p| p.indexed);
+
+    let event = ethabi::Event { name, inputs, anonymous: false };
+    if any_indexed {
+        return Ok((event, false));
+    }
+    match abi.events().find(|e| !e.anonymous && e.signature() == event.signature()) {
+        Some(res) => Ok((res.clone(), false)),
+        None => Ok((event, true)),
+    }
+}
+
+/// Parse signature i.e. `Transfer(address indexed from, address to, uint256)` into its
+/// name, and parameters whose names are empty if unnamed; keywords i.e. `calldata` are
+/// skipped, and `indexed` is only meaningful to event.
+fn parse_named_sig(sig: &str) -> Result<(String, Vec<ethabi::EventParam>), CrunnerError> {
+    let (name, params_str) = match (sig.find('('), sig.rfind(')')) {
+        (Some(start), Some(end)) if start < end && sig[end + 1..].trim().is_empty() => (sig[..start].trim(), &sig[start + 1..end]),
+        _ => return Err(CrunnerError::InvalidArgument(format!("Error signature is not in format 'name(type,...)'; sig={}", sig))),
+    };
+
+    let mut params: Vec<ethabi::EventParam> = Vec::new();
+    for param_str in split_top_level(params_str).into_iter().map(str::trim).filter(|p| !p.is_empty()) {
+        // type ends at the first whitespace outside of parentheses i.e. tuple type
+        let mut depth: i32 = 0;
+        let type_end = param_str.char_indices()
+            .find(|(_, c)| {
+                match c {
+                    '(' => depth += 1,
+                    ')' => depth -= 1,
+                    _ => (),
+                }
+                c.is_whitespace() && depth == 0
+            })
+            .map(|(i, _)| i)
+            .unwrap_or(param_str.len());
+        let type_str: String = param_str[..type_end].chars().filter(|c| !c.is_whitespace()).collect();
+        let kind = match ethabi::param_type::Reader::read(&type_str) {
+            Ok(res) => res,
+            Err(e) => return Err(CrunnerError::InvalidArgument(format!("Error parsing type '{}' of signature '{}'; err={}", type_str, sig, e))),
+        };
+
+        let mut indexed = false;
+        let mut param_name = String::new();
+        for word in param_str[type_end..].split_whitespace() {
+            match word {
+                "indexed" => indexed = true,
+                "calldata" | "memory" | "storage" | "payable" => (),
+                _ => param_name = word.to_owned(),
+            }
+        }
+        params.push(ethabi::EventParam { name: param_name, kind, indexed });
+    }
+    Ok((name.to_owned(), params))
+}
+
+/// Decode calldata of function call into the function's signature, and its named
+/// arguments; dynamic types i.e. string, bytes, and arrays are decoded as well.
+///
+/// # Arguments
+/// * `calldata` - calldata starting with 4-byte selector
+/// * `abi` - ABI to find the function of the selector in
+/// * `fn_sig` - function signature to decode against instead of ABI i.e. `transfer(address to, uint256 amount)`
+pub fn decode_function_call(calldata: &[u8], abi: &ethabi::Contract, fn_sig: Option<&str>) -> Result<DecodedCall, CrunnerError> {
+    if calldata.len() < 4 {
+        return Err(CrunnerError::InvalidArgument("Error, calldata is shorter than 4-byte selector".to_owned()));
+    }
+    let (selector, args_data) = calldata.split_at(4);
+
+    let (name, params) = match fn_sig {
+        Some(fn_sig) => {
+            let (name, params) = parse_named_sig(fn_sig)?;
+            let params: Vec<(String, ParamType)> = params.into_iter().map(|p| (p.name, p.kind)).collect();
+            let types: Vec<ParamType> = params.iter().map(|(_, kind)| kind.clone()).collect();
+            if ethabi::short_signature(&name, &types) != selector {
+                return Err(CrunnerError::InvalidArgument(format!("Error, selector 0x{} of calldata doesn't match signature '{}'", hex::encode(selector), fn_sig)));
+            }
+            (name, params)
+        },
+        None => match abi.functions().find(|f| f.short_signature() == selector) {
+            Some(f) => (f.name.clone(), f.inputs.iter().map(|p| (p.name.clone(), p.kind.clone())).collect()),
+            None => return Err(CrunnerError::AbiError(format!("Error, no function of selector 0x{} in abi", hex::encode(selector)))),
+        },
+    };
+
+    let types: Vec<ParamType> = params.iter().map(|(_, kind)| kind.clone()).collect();
+    let tokens = match ethabi::decode(&types, args_data) {
+        Ok(res) => res,
+        Err(e) => return Err(CrunnerError::AbiError(format!("Error decoding calldata of function '{}'; err={}", name, e))),
+    };
+    let signature = format!("{}({})", name, types.iter().map(|t| t.to_string()).collect::<Vec<String>>().join(","));
+    Ok(DecodedCall {
+        selector: format!("0x{}", hex::encode(selector)),
+        signature,
+        params: params.into_iter().map(|(name, _)| name).zip(tokens).collect(),
+    })
+}
+
+/// Format decoded function call i.e. `transfer(to: 0x..., amount: 5)`.
+///
+/// # Arguments
+/// - `call` - decoded function call
+pub fn format_call(call: &DecodedCall) -> String {
+    let name = call.signature.split('(').next().unwrap_or_default();
+    format!("{}({})", name, format_named_tokens(call.params.iter().map(|(name, token)| (name.as_str(), token))))
+}
+
+/// Format decoded event i.e. `Transfer(from: 0x..., to: 0x..., value: 5)`.
+///
+/// # Arguments
+/// - `event` - decoded event
+pub fn format_event(event: &DecodedEvent) -> String {
+    format!("{}({})", event.name, format_named_tokens(event.params.iter().map(|(name, token)| (name.as_str(), token))))
+}
+
+/// Extract revert data from error response of the node. It is usually in `data`
+/// field of the error as hex string, or nested in an object by some nodes.
+fn revert_data_of(err: &web3::Error) -> Option<Vec<u8>> {
+    let data = match err {
+        web3::Error::Rpc(e) => e.data.as_ref()?,
+        _ => return None,
+    };
+    let hex_str = match data {
+        serde_json::Value::String(s) => s.as_str(),
+        serde_json::Value::Object(o) => o.get("data")?.as_str()?,
+        _ => return None,
+    };
+    hex::decode(hex_str.trim_start_matches("0x")).ok()
+}
+
+/// Describe error of RPC call; with human-readable revert reason if the call reverted.
+/// Selector of unknown custom error is shown along with its raw data instead.
+///
+/// # Arguments
+/// - `err` - web3 error
+/// - `abi` - ABI to look up custom errors in (if any)
+pub fn describe_rpc_error(err: &web3::Error, abi: Option<&ethabi::Contract>) -> String {
+    let data = match revert_data_of(err) {
+        Some(res) if res.len() >= 4 => res,
+        _ => return err.to_string(),
+    };
+    match decode_revert_reason(&data, abi) {
+        Some(reason) => format!("execution reverted: {}", reason),
+        None => format!("execution reverted with unknown custom error 0x{}; data=0x{}", hex::encode(&data[..4]), hex::encode(&data)),
+    }
+}
+
+/// Extract revert data of unknown custom error from error message as described by
+/// `describe_rpc_error` i.e. to look up its signature elsewhere.
+///
+/// # Arguments
+/// - `msg` - error message
+///
+/// # Return
+/// Return `None` if the message doesn't describe revert of unknown custom error.
+pub fn unknown_custom_error_data(msg: &str) -> Option<Vec<u8>> {
+    let regex: Regex = Regex::new(UNKNOWN_CUSTOM_ERROR_PATTERN).unwrap();
+    let caps = regex.captures(msg)?;
+    hex::decode(&caps[1]).ok()
+}
+
+/// Describe error of contract call; with human-readable revert reason if the call reverted.
+///
+/// # Arguments
+/// - `err` - web3 contract error
+/// - `abi` - ABI to look up custom errors in (if any)
+pub fn describe_contract_error(err: &web3::contract::Error, abi: Option<&ethabi::Contract>) -> String {
+    match err {
+        web3::contract::Error::Api(e) => describe_rpc_error(e, abi),
+        _ => err.to_string(),
+    }
+}
+
+/// Make a web3 query depending on the method name, and number of method's arguments.
+///
+/// # Arguments
+/// - `contract` - `web3::contract::Contract` for contract instance to interact with
+/// - `fn_name` - name of the function to make a call
+/// - `params` - slice of parameter strings that required to pass to such method to make a call
+/// - `block` - block to read state at; latest block if `None`
+#[tracing::instrument(name = "contract.query", skip_all, fields(contract = ?contract.address(), function = fn_name))]
+pub async fn web3_query_get<R>(contract: &Contract<CrunnerTransport>, fn_name: &str, params: &[String], block: Option<BlockId>) -> Result<R, CrunnerError>
+where
+    R: Detokenize
+{
+    let parsed_params = match prepare_params(params, function_input_types(contract, fn_name).as_deref(), false) {
+        Ok(res) => res,
+        Err(e) => return Err(e),
+    };
+
+    let res = contract.query(fn_name, parsed_params.as_slice(), None, Options::default(), block).await;
+
+    match res {
+        Ok(val_res) => Ok(val_res),
+        Err(e) => Err(CrunnerError::RpcError(format!("Error querying via RPC for function '{}'; err={}", fn_name, describe_contract_error(&e, Some(contract.abi()))))),
+    }
+}
+
+/// Load state overrides applied to `eth_call` from either inline JSON, or filepath
+/// of JSON file. It's a JSON object keyed by account address with `balance`, `nonce`,
+/// `code`, `state`, and `stateDiff` as fields as accepted by geth.
+///
+/// # Arguments
+/// - `input` - inline JSON, or filepath of JSON file
+pub fn load_state_override(input: &str) -> Result<StateOverride, CrunnerError> {
+    let content = if input.trim_start().starts_with('{') {
+        input.to_owned()
+    }
+    else {
+        match std::fs::read_to_string(input) {
+            Ok(res) => res,
+            Err(e) => return Err(CrunnerError::InvalidArgument(format!("Error reading state override file {}; err={}", input, e))),
+        }
+    };
+
+    let state_override: StateOverride = match serde_json::from_str(&content) {
+        Ok(res) => res,
+        Err(e) => return Err(CrunnerError::InvalidArgument(format!("Error parsing state override; err={}", e))),
+    };
+    for (address, account) in state_override.iter() {
+        if account.state.is_some() && account.state_diff.is_some() {
+            return Err(CrunnerError::InvalidArgument(format!("Error, state override of {:?} cannot have both state, and stateDiff", address)));
+        }
+    }
+    Ok(state_override)
+}
+
+/// Make `eth_call` with state overrides if any; plain `eth_call` otherwise.
+///
+/// # Arguments
+/// - `web3` - web3 instance
+/// - `call_req` - call request
+/// - `block` - block to read state at; latest block if `None`
+/// - `state_override` - state overrides of accounts (if any)
+async fn web3_eth_call(web3: &Web3<CrunnerTransport>, call_req: CallRequest, block: Option<BlockId>, state_override: Option<&StateOverride>) -> Result<Bytes, web3::Error> {
+    let state_override = match state_override {
+        Some(res) => res,
+        None => return web3.eth().call(call_req, block).await,
+    };
+
+    let params = eth_call_params(&call_req, block, Some(state_override))?;
+    let value = web3.transport().execute("eth_call", params).await?;
+    serde_json::from_value(value).map_err(|e| web3::Error::Decoder(e.to_string()))
+}
+
+/// Build parameters of raw `eth_call` request; call request, block, and state
+/// overrides if any.
+fn eth_call_params(call_req: &CallRequest, block: Option<BlockId>, state_override: Option<&StateOverride>) -> Result<Vec<serde_json::Value>, web3::Error> {
+    let block = block.unwrap_or(BlockId::Number(BlockNumber::Latest));
+    let to_value = |value: serde_json::Result<serde_json::Value>| value.map_err(|e| web3::Error::Decoder(e.to_string()));
+    let mut params = vec![
+        to_value(serde_json::to_value(call_req))?,
+        to_value(serde_json::to_value(block))?,
+    ];
+    if let Some(state_override) = state_override {
+        params.push(to_value(serde_json::to_value(state_override))?);
+    }
+    Ok(params)
+}
+
+/// Make a web3 query then decode its returned data against the specified types
+/// instead of the function's outputs declared in ABI.
+/// This supports multi-value returns, and tuples.
+///
+/// # Arguments
+/// - `web3` - web3 instance
+/// - `contract` - `web3::contract::Contract` for contract instance to interact with
+/// - `fn_name` - name of the function to make a call
+/// - `params` - slice of parameter strings that required to pass to such method to make a call
+/// - `ret_types` - types to decode the returned data into
+/// - `block` - block to read state at; latest block if `None`
+/// - `state_override` - state overrides of accounts applied to the call (if any)
+///
+/// # Return
+/// On success, return decoded `Token` for each of `ret_types`.
+#[tracing::instrument(name = "contract.query", skip_all, fields(contract = ?contract.address(), function = fn_name))]
+pub async fn web3_query_get_tokens(web3: &Web3<CrunnerTransport>, contract: &Contract<CrunnerTransport>, fn_name: &str, params: &[String], ret_types: &[ParamType], block: Option<BlockId>, state_override: Option<&StateOverride>) -> Result<Vec<Token>, CrunnerError>
+{
+    let data = encode_function_call(contract, fn_name, params)?;
+    let ret_bytes = web3_query_get_raw(web3, contract, fn_name, data, block, state_override).await?;
+
+    match ethabi::decode(ret_types, &ret_bytes) {
+        Ok(res) => Ok(res),
+        Err(e) => Err(CrunnerError::AbiError(format!("Error decoding returned data of function '{}'; err={}", fn_name, e))),
+    }
+}
+
+/// Make a web3 query with already encoded calldata, and return its returned data as is.
+///
+/// # Arguments
+/// - `web3` - web3 instance
+/// - `contract` - `web3::contract::Contract` for contract instance to interact with
+/// - `fn_name` - name of the function to make a call; used in error message
+/// - `calldata` - encoded calldata as by `encode_function_call`
+/// - `block` - block to read state at; latest block if `None`
+/// - `state_override` - state overrides of accounts applied to the call (if any)
+pub async fn web3_query_get_raw(web3: &Web3<CrunnerTransport>, contract: &Contract<CrunnerTransport>, fn_name: &str, calldata: Vec<u8>, block: Option<BlockId>, state_override: Option<&StateOverride>) -> Result<Vec<u8>, CrunnerError>
+{
+    let call_req = CallRequest {
+        to: Some(contract.address()),
+        data: Some(Bytes(calldata)),
+        ..Default::default()
+    };
+    match web3_eth_call(web3, call_req, block, state_override).await {
+        Ok(res) => Ok(res.0),
+        Err(e) => Err(CrunnerError::RpcError(format!("Error querying via RPC for function '{}'; err={}", fn_name, describe_rpc_error(&e, Some(contract.abi()))))),
+    }
+}
+
+/// Make `eth_call` with the call request as is i.e. with calldata already at hand, and
+/// return its returned data.
+///
+/// # Arguments
+/// - `web3` - web3 instance
+/// - `call_req` - call request
+/// - `block` - block to read state at; latest block if `None`
+/// - `state_override` - state overrides of accounts applied to the call (if any)
+/// - `abi` - ABI to look up custom errors in if the call reverts (if any)
+pub async fn web3_call_raw(web3: &Web3<CrunnerTransport>, call_req: CallRequest, block: Option<BlockId>, state_override: Option<&StateOverride>, abi: Option<&ethabi::Contract>) -> Result<Vec<u8>, CrunnerError> {
+    match web3_eth_call(web3, call_req, block, state_override).await {
+        Ok(res) => Ok(res.0),
+        Err(e) => Err(CrunnerError::RpcError(format!("Error making raw call via RPC; err={}", describe_rpc_error(&e, abi)))),
+    }
+}
+
+/// Encode calldata of calling method of the contract with the specified parameters.
+///
+/// # Arguments
+/// - `contract` - `web3::contract::Contract` for contract instance to interact with
+/// - `fn_name` - name of the function to make a call
+/// - `params` - slice of parameter strings that required to pass to such method to make a call
+pub fn encode_function_call(contract: &Contract<CrunnerTransport>, fn_name: &str, params: &[String]) -> Result<Vec<u8>, CrunnerError> {
+    let parsed_params = prepare_params(params, function_input_types(contract, fn_name).as_deref(), false)?;
+
+    let function = match contract.abi().function(fn_name) {
+        Ok(res) => res,
+        Err(e) => return Err(CrunnerError::AbiError(format!("Error finding function '{}' in abi; err={}", fn_name, e))),
+    };
+    match function.encode_input(parsed_params.as_slice()) {
+        Ok(res) => Ok(res),
+        Err(e) => Err(CrunnerError::InvalidArgument(format!("Error encoding parameters for function '{}'; err={}", fn_name, e))),
+    }
+}
+
+/// Aggregate calls to getter methods of (possibly different) contracts into a single
+/// call to Multicall3's `aggregate3`, then decode returned data of each against
+/// the function's outputs as declared in ABI.
+///
+/// # Arguments
+/// - `web3` - web3 instance
+/// - `multicall` - address of Multicall3 contract
+/// - `calls` - slice of contract instance, function name, and parameter strings of each call
+/// - `allow_failure` - whether other calls still succeed when some of them revert; otherwise
+///   the whole aggregated call reverts
+/// - `block` - block to read state at; latest block if `None`
+///
+/// # Return
+/// On success, return the result of each call in the same order; either name of each
+/// output (empty if unnamed) along with its decoded `Token`, or its error.
+#[tracing::instrument(name = "contract.multicall", skip_all, fields(calls = calls.len()))]
+pub async fn web3_multicall(web3: &Web3<CrunnerTransport>, multicall: Address, calls: &[(&Contract<CrunnerTransport>, &str, &[String])], allow_failure: bool, block: Option<BlockId>) -> Result<Vec<MulticallResult>, CrunnerError>
+{
+    let mut call_tokens: Vec<Token> = Vec::new();
+    for (contract, fn_name, params) in calls.iter() {
+        let data = encode_function_call(contract, fn_name, params)?;
+        call_tokens.push(Token::Tuple(vec![Token::Address(contract.address()), Token::Bool(allow_failure), Token::Bytes(data)]));
+    }
+    let mut data = AGGREGATE3_SELECTOR.to_vec();
+    data.extend(ethabi::encode(&[Token::Array(call_tokens)]));
+
+    let call_req = CallRequest {
+        to: Some(multicall),
+        data: Some(Bytes(data)),
+        ..Default::default()
+    };
+    let ret_bytes = match web3_eth_call(web3, call_req, block, None).await {
+        Ok(res) => res,
+        Err(e) => return Err(CrunnerError::RpcError(format!("Error querying via RPC for multicall; err={}", describe_rpc_error(&e, None)))),
+    };
+    if ret_bytes.0.is_empty() {
+        return Err(CrunnerError::RpcError(format!("Error, no Multicall3 contract deployed at {:?}", multicall)));
+    }
+
+    // (bool success, bytes returnData)[]
+    let ret_type = ParamType::Array(Box::new(ParamType::Tuple(vec![ParamType::Bool, ParamType::Bytes])));
+    let results = match ethabi::decode(&[ret_type], &ret_bytes.0) {
+        Ok(mut res) => match res.pop() {
+            Some(Token::Array(res)) => res,
+            _ => Vec::new(),
+        },
+        Err(e) => return Err(CrunnerError::AbiError(format!("Error decoding returned data of multicall; err={}", e))),
+    };
+    if results.len() != calls.len() {
+        return Err(CrunnerError::AbiError(format!("Error, multicall returned {} results for {} calls", results.len(), calls.len())));
+    }
+
+    Ok(calls.iter().zip(results).map(|((contract, fn_name, _), result)| {
+        let (success, return_data) = match result {
+            Token::Tuple(mut res) if res.len() == 2 => match (res.remove(0), res.remove(0)) {
+                (Token::Bool(success), Token::Bytes(return_data)) => (success, return_data),
+                _ => (false, Vec::new()),
+            },
+            _ => (false, Vec::new()),
+        };
+        if !success {
+            let reason = match decode_revert_reason(&return_data, Some(contract.abi())) {
+                Some(res) => res,
+                None if return_data.is_empty() => "none".to_owned(),
+                None => format!("0x{}", hex::encode(&return_data)),
+            };
+            return Err(CrunnerError::RpcError(format!("Error, call to function '{}' reverted; reason={}", fn_name, reason)));
+        }
+
+        decode_outputs(contract, fn_name, &return_data, None)
+    }).collect())
+}
+
+/// Make calls to getter methods of (possibly different) contracts in a single JSON-RPC
+/// batch request instead of a request per call, then decode returned data of each.
+///
+/// # Arguments
+/// - `web3` - web3 instance
+/// - `calls` - slice of contract instance, function name, and parameter strings of each call
+/// - `ret_types` - types to decode returned data of every call into; function's outputs
+///   declared in ABI if `None`
+/// - `block` - block to read state at; latest block if `None`
+/// - `state_override` - state overrides of accounts applied to the calls (if any)
+///
+/// # Return
+/// On success, return the result of each call in the same order; either name of each
+/// output (empty if unnamed) along with its decoded `Token`, or its error.
+#[tracing::instrument(name = "contract.batch_query", skip_all, fields(calls = calls.len()))]
+pub async fn web3_batch_query_get(web3: &Web3<CrunnerTransport>, calls: &[(&Contract<CrunnerTransport>, &str, &[String])], ret_types: Option<&[ParamType]>, block: Option<BlockId>, state_override: Option<&StateOverride>) -> Result<Vec<MulticallResult>, CrunnerError>
+{
+    // calls failed to encode are reported as is without being sent
+    let mut results: Vec<Option<MulticallResult>> = Vec::new();
+    let mut requests = Vec::new();
+    for (contract, fn_name, params) in calls.iter() {
+        let data = match encode_function_call(contract, fn_name, params) {
+            Ok(res) => res,
+            Err(e) => {
+                results.push(Some(Err(e)));
+                continue;
+            },
+        };
+        let call_req = CallRequest {
+            to: Some(contract.address()),
+            data: Some(Bytes(data)),
+            ..Default::default()
+        };
+        let params = match eth_call_params(&call_req, block, state_override) {
+            Ok(res) => res,
+            Err(e) => return Err(CrunnerError::RpcError(format!("Error building request of function '{}'; err={}", fn_name, e))),
+        };
+        requests.push(web3.transport().prepare("eth_call", params));
+        results.push(None);
+    }
+    if requests.is_empty() {
+        return Ok(results.into_iter().flatten().collect());
+    }
+
+    let responses = match web3.transport().send_batch(requests).await {
+        Ok(res) => res,
+        Err(e) => return Err(CrunnerError::RpcError(format!("Error sending JSON-RPC batch request, the endpoint may not support batch requests; err={}", e))),
+    };
+    let mut responses = responses.into_iter();
+    Ok(calls.iter().zip(results).map(|((contract, fn_name, _), result)| {
+        if let Some(result) = result {
+            return result;
+        }
+        let ret_bytes = match responses.next() {
+            Some(Ok(value)) => match serde_json::from_value::<Bytes>(value) {
+                Ok(res) => res,
+                Err(e) => return Err(CrunnerError::RpcError(format!("Error parsing returned data of function '{}'; err={}", fn_name, e))),
+            },
+            Some(Err(e)) => return Err(CrunnerError::RpcError(format!("Error querying via RPC for function '{}'; err={}", fn_name, describe_rpc_error(&e, Some(contract.abi()))))),
+            None => return Err(CrunnerError::RpcError(format!("Error, JSON-RPC batch response has no result for function '{}'", fn_name))),
+        };
+        decode_outputs(contract, fn_name, &ret_bytes.0, ret_types)
+    }).collect())
+}
+
+/// Decode returned data of the function against the specified types, otherwise
+/// against the function's outputs declared in ABI along with their names.
+///
+/// # Arguments
+/// - `contract` - `web3::contract::Contract` for contract instance declaring the function
+/// - `fn_name` - name of the function
+/// - `data` - returned data of the function
+/// - `ret_types` - types to decode returned data into; function's outputs if `None`
+///
+/// # Return
+/// On success, return name of each output (empty if unnamed, or decoded against
+/// `ret_types`) along with its decoded `Token`.
+pub fn decode_outputs(contract: &Contract<CrunnerTransport>, fn_name: &str, data: &[u8], ret_types: Option<&[ParamType]>) -> Result<Vec<(String, Token)>, CrunnerError> {
+    let (names, ret_types): (Vec<String>, Vec<ParamType>) = match ret_types {
+        Some(ret_types) => (vec![String::new(); ret_types.len()], ret_types.to_vec()),
+        None => match contract.abi().function(fn_name) {
+            Ok(res) => res.outputs.iter().map(|o| (o.name.to_owned(), o.kind.clone())).unzip(),
+            Err(e) => return Err(CrunnerError::AbiError(format!("Error finding function '{}' in abi; err={}", fn_name, e))),
+        },
+    };
+    match ethabi::decode(&ret_types, data) {
+        Ok(tokens) => Ok(names.into_iter().zip(tokens).collect()),
+        Err(e) => Err(CrunnerError::AbiError(format!("Error decoding returned data of function '{}'; err={}", fn_name, e))),
+    }
+}
+
+/// Make a web3 query then decode its returned data against the function's
+/// outputs as declared in ABI.
+///
+/// # Arguments
+/// - `web3` - web3 instance
+/// - `contract` - `web3::contract::Contract` for contract instance to interact with
+/// - `fn_name` - name of the function to make a call
+/// - `params` - slice of parameter strings that required to pass to such method to make a call
+/// - `block` - block to read state at; latest block if `None`
+/// - `state_override` - state overrides of accounts applied to the call (if any)
+///
+/// # Return
+/// On success, return name of each output (empty if unnamed) along with its decoded `Token`.
+#[tracing::instrument(name = "contract.query", skip_all, fields(contract = ?contract.address(), function = fn_name))]
+pub async fn web3_query_get_inferred(web3: &Web3<CrunnerTransport>, contract: &Contract<CrunnerTransport>, fn_name: &str, params: &[String], block: Option<BlockId>, state_override: Option<&StateOverride>) -> Result<Vec<(String, Token)>, CrunnerError>
+{
+    let outputs = match contract.abi().function(fn_name) {
+        Ok(res) => res.outputs.clone(),
+        Err(e) => return Err(CrunnerError::AbiError(format!("Error finding function '{}' in abi; err={}", fn_name, e))),
+    };
+    let ret_types: Vec<ParamType> = outputs.iter().map(|o| o.kind.clone()).collect();
+
+    let tokens = web3_query_get_tokens(web3, contract, fn_name, params, ret_types.as_slice(), block, state_override).await?;
+    Ok(outputs.into_iter().map(|o| o.name).zip(tokens).collect())
+}
+
+/// Make a web3 set depending on the function name, and number of function's arguments.
+///
+/// # Arguments
+/// - `web3` - web3 instance
+/// - `contract` - `web3::contract::Contract` for contract instance to interact with
+/// - `fn_name` - name of the function to make a call
+/// - `params` - slice of parameter strings that required to pass to such method to make a call
+/// - `options` - transaction options i.e. value in Wei to send along
+///   effectively made)
+/// - `prvk` - secret key to sign the transaction with
+///
+/// # Return
+/// On success, return hash of the sent transaction.
+#[tracing::instrument(name = "tx.send", skip_all, fields(contract = ?contract.address(), function = fn_name))]
+pub async fn web3_query_set(web3: &Web3<CrunnerTransport>, contract: &Contract<CrunnerTransport>, fn_name: &str, params: &[String], options: Options, prvk: &secp256k1::SecretKey) -> Result<H256, CrunnerError>
+{
+    let parsed_params = match prepare_params(params, function_input_types(contract, fn_name).as_deref(), false) {
+        Ok(res) => res,
+        Err(e) => return Err(e),
+    };
+    let data = match contract.abi().function(fn_name).and_then(|f| f.encode_input(parsed_params.as_slice())) {
+        Ok(res) => res,
+        Err(e) => return Err(CrunnerError::AbiError(format!("Error encoding parameters for function '{}'; err={}", fn_name, e))),
+    };
+
+    // build the transaction here instead of web3's signed call which only builds
+    // legacy transaction
+    let mut tx = TransactionParameters {
+        nonce: options.nonce,
+        to: Some(contract.address()),
+        gas_price: options.gas_price,
+        data: Bytes(data),
+        transaction_type: options.transaction_type,
+        max_fee_per_gas: options.max_fee_per_gas,
+        max_priority_fee_per_gas: options.max_priority_fee_per_gas,
+        ..Default::default()
+    };
+    if let Some(gas) = options.gas {
+        tx.gas = gas;
+    }
+    if let Some(value) = options.value {
+        tx.value = value;
+    }
+    let signed = match web3.accounts().sign_transaction(tx, prvk).await {
+        Ok(res) => res,
+        Err(e) => return Err(CrunnerError::RpcError(format!("Error signing transaction of setter method namely '{}'; err={}", fn_name, e))),
+    };
+
+    match web3.eth().send_raw_transaction(signed.raw_transaction).await {
+        Ok(tx_hash) => {
+            tracing::info!(tx_hash = ?tx_hash, "transaction sent");
+            Ok(tx_hash)
+        },
+        Err(e) => {
+            let err_msg = format!("Error calling setter method namely '{}'; err={}", fn_name, e);
+            Err(CrunnerError::RpcError(err_msg))
+        },
+    }
+}
+
+/// Make a web3 set from an account unlocked on the node; the node signs the transaction.
+/// This is usually the case for local development chain i.e. anvil, hardhat, or ganache.
+///
+/// # Arguments
+/// - `web3` - web3 instance
+/// - `contract` - `web3::contract::Contract` for contract instance to interact with
+/// - `fn_name` - name of the function to make a call
+/// - `params` - slice of parameter strings that required to pass to such method to make a call
+/// - `from` - unlocked account to send from; first account of the node if `None`
+/// - `options` - transaction options i.e. value in Wei to send along
+///
+/// # Return
+/// On success, return hash of the sent transaction.
+#[tracing::instrument(name = "tx.send", skip_all, fields(contract = ?contract.address(), function = fn_name))]
+pub async fn web3_query_set_unlocked(web3: &Web3<CrunnerTransport>, contract: &Contract<CrunnerTransport>, fn_name: &str, params: &[String], from: Option<&str>, options: Options) -> Result<H256, CrunnerError>
+{
+    let parsed_params = match prepare_params(params, function_input_types(contract, fn_name).as_deref(), false) {
         Ok(res) => res,
-        Err(e) => {
-            let err_msg = format!("Error awaiting result for code from address ({}); err={}", address, e);
-            return Err(err_msg);
-        }
+        Err(e) => return Err(e),
     };
 
-    // encode hex bytes into hex string
-    let code_str = hex::encode(code_bytes.0.as_slice());
+    let from_addr = match from {
+        Some(from) => get_address_from_str(from)?,
+        None => {
+            let accounts = match web3.eth().accounts().await {
+                Ok(res) => res,
+                Err(e) => return Err(CrunnerError::RpcError(format!("Error querying accounts of the node; err={}", e))),
+            };
+            match accounts.first() {
+                Some(addr) => *addr,
+                None => return Err(CrunnerError::RpcError("Error, the node has no unlocked account to send from".to_owned())),
+            }
+        }
+    };
 
-    if code_str.len() > 0 {
-        // it is a contract address
-        return Ok(false);
+    match contract.call(fn_name, parsed_params.as_slice(), from_addr, options).await {
+        Ok(tx_hash) => {
+            tracing::info!(tx_hash = ?tx_hash, "transaction sent");
+            Ok(tx_hash)
+        },
+        Err(e) => {
+            let err_msg = format!("Error calling setter method namely '{}'; err={}", fn_name, describe_contract_error(&e, Some(contract.abi())));
+            Err(CrunnerError::RpcError(err_msg))
+        },
     }
-
-    Ok(true)
 }
 
-/// Get `Address` from string literal.
+/// Make a web3 set with the transaction signed by external signer i.e. hardware wallet.
+/// The transaction is built here, then only signed by the signer.
 ///
 /// # Arguments
-/// * `address` - address string literal prefixed with '0x'
-pub fn get_address_from_str(address: &str) -> Result<Address, String> {
-    if !validate_address_format(address) {
-        return Err(format!("Error address is not in the correct format; addr={}", address));
+/// - `web3` - web3 instance
+/// - `contract` - `web3::contract::Contract` for contract instance to interact with
+/// - `fn_name` - name of the function to make a call
+/// - `params` - slice of parameter strings that required to pass to such method to make a call
+/// - `signer` - external signer to sign the transaction
+/// - `options` - transaction options i.e. value in Wei to send along
+///
+/// # Return
+/// On success, return hash of the sent transaction.
+#[tracing::instrument(name = "tx.send", skip_all, fields(contract = ?contract.address(), function = fn_name))]
+pub async fn web3_query_set_external(web3: &Web3<CrunnerTransport>, contract: &Contract<CrunnerTransport>, fn_name: &str, params: &[String], signer: &dyn ExternalSigner, options: Options) -> Result<H256, CrunnerError>
+{
+    let from = signer.address().await?;
+    let tx = web3_build_set_tx(web3, contract, fn_name, params, from, options, None).await?;
+    let signature = signer.sign_transaction(&tx).await?;
+
+    match web3.eth().send_raw_transaction(Bytes(tx.rlp_signed(&signature))).await {
+        Ok(tx_hash) => {
+            tracing::info!(tx_hash = ?tx_hash, "transaction sent");
+            Ok(tx_hash)
+        },
+        Err(e) => Err(CrunnerError::RpcError(format!("Error calling setter method namely '{}'; err={}", fn_name, e))),
     }
-    
-    Ok(Address::from_slice(hex::decode(&address[2..]).unwrap().as_slice()))
 }
 
-/// Create a web3 instance
+/// Build unsigned transaction calling setter method of the contract. Nonce, gas price,
+/// gas limit, and chain id which are not set are queried from the node.
 ///
 /// # Arguments
-/// - `chain` - `ChainType`
-pub fn create_web3(chain: ChainType) -> Web3<Http> {
-    let rpc_endpoint = match chain {
-        ChainType::BSC => BSC_RPC_ENDPOINT,
-        ChainType::Ethereum => ETHEREUM_RPC_ENDPOINT,
-        ChainType::Polygon => POLYGON_RPC_ENDPOINT,
+/// - `web3` - web3 instance
+/// - `contract` - `web3::contract::Contract` for contract instance to interact with
+/// - `fn_name` - name of the function to make a call
+/// - `params` - slice of parameter strings that required to pass to such method to make a call
+/// - `from` - address to send the transaction from
+/// - `options` - transaction options i.e. value in Wei to send along
+/// - `chain_id` - chain id of the chain the transaction is for (if known)
+pub async fn web3_build_set_tx(web3: &Web3<CrunnerTransport>, contract: &Contract<CrunnerTransport>, fn_name: &str, params: &[String], from: Address, options: Options, chain_id: Option<u64>) -> Result<UnsignedTx, CrunnerError>
+{
+    let parsed_params = prepare_params(params, function_input_types(contract, fn_name).as_deref(), false)?;
+    let data = match contract.abi().function(fn_name).and_then(|f| f.encode_input(parsed_params.as_slice())) {
+        Ok(res) => res,
+        Err(e) => return Err(CrunnerError::AbiError(format!("Error encoding parameters for function '{}'; err={}", fn_name, e))),
+    };
+
+    let nonce = match options.nonce {
+        Some(res) => res,
+        None => match web3.eth().transaction_count(from, Some(BlockNumber::Pending)).await {
+            Ok(res) => res,
+            Err(e) => return Err(CrunnerError::RpcError(format!("Error querying nonce of {:?}; err={}", from, e))),
+        },
     };
-    let http = Http::new(rpc_endpoint).unwrap();
-    Web3::new(http)
+    let gas_price = match options.gas_price.or(options.max_fee_per_gas) {
+        Some(res) => res,
+        None => match web3.eth().gas_price().await {
+            Ok(res) => res,
+            Err(e) => return Err(CrunnerError::RpcError(format!("Error in querying gas price; err={}", e))),
+        },
+    };
+    let value = options.value.unwrap_or_default();
+    let call_req = CallRequest {
+        from: Some(from),
+        to: Some(contract.address()),
+        value: Some(value),
+        data: Some(Bytes(data.clone())),
+        ..Default::default()
+    };
+    let gas = match options.gas {
+        Some(res) => res,
+        None => match web3.eth().estimate_gas(call_req, None).await {
+            Ok(res) => res,
+            Err(e) => return Err(CrunnerError::RpcError(format!("Error estimating gas of setter method namely '{}'; err={}", fn_name, describe_rpc_error(&e, Some(contract.abi()))))),
+        },
+    };
+    let chain_id = match chain_id {
+        Some(res) => res,
+        None => match web3.eth().chain_id().await {
+            Ok(res) => res.as_u64(),
+            Err(e) => return Err(CrunnerError::RpcError(format!("Error querying chain id; err={}", e))),
+        },
+    };
+
+    Ok(UnsignedTx {
+        nonce,
+        gas_price,
+        gas,
+        to: contract.address(),
+        value,
+        data,
+        chain_id,
+        eip1559_fees: options.max_fee_per_gas.zip(options.max_priority_fee_per_gas),
+    })
 }
 
-/// Get unit string from the specified `ChainType`.
+/// Rebuild the pending transaction with the same nonce, and fees bumped by the
+/// specified percentage, then broadcast it to replace the original one. For
+/// cancellation, it is rebuilt as zero-value self-transfer instead.
 ///
 /// # Arguments
-/// - `chain` - `ChainType`
+/// - `web3` - web3 instance
+/// - `tx_hash` - hash of the pending transaction to replace
+/// - `cancel` - whether to cancel the transaction instead of speeding it up
+/// - `bump_percent` - percentage to bump fees by; nodes usually require at least 10
+/// - `prvk` - secret key of the sender of the pending transaction
 ///
 /// # Return
-/// Return static string representing of the chain.
-pub fn unit_str(chain: ChainType) -> &'static str {
-    match chain {
-        ChainType::BSC => "BNB",
-        ChainType::Ethereum => "ETH",
-        ChainType::Polygon => "MATIC",
+/// On success, return hash of the replacement transaction.
+#[tracing::instrument(name = "tx.replace", skip_all, fields(tx_hash = ?tx_hash, cancel))]
+pub async fn web3_replace_transaction(web3: &Web3<CrunnerTransport>, tx_hash: H256, cancel: bool, bump_percent: u64, prvk: &secp256k1::SecretKey) -> Result<H256, CrunnerError>
+{
+    let tx = match web3.eth().transaction(TransactionId::Hash(tx_hash)).await {
+        Ok(Some(res)) => res,
+        Ok(None) => return Err(CrunnerError::InvalidArgument(format!("Error, transaction {:?} is not found", tx_hash))),
+        Err(e) => return Err(CrunnerError::RpcError(format!("Error querying transaction {:?}; err={}", tx_hash, e))),
+    };
+    if tx.block_number.is_some() {
+        return Err(CrunnerError::InvalidArgument(format!("Error, transaction {:?} is already mined", tx_hash)));
     }
-}
-
-/// Parse a long hex string into vector of hex string of 64 characters in length (256 bit)
-/// excluding the prefixed method-id which has 8 characters in length (32 bit).
-/// Return a vector of hex string of 64 characters in length (256 bit);
-///
-/// # Arguments
-/// * `long_hex_str` - input long hex string to parse; included a prefix of `0x`
-pub fn parse_256_method_arguments(long_hex_str: &str) -> Result<Vec<String>, String> {
-    if long_hex_str.len() == 0 {
-        return Ok(Vec::new());
+    let from = SecretKeyRef::new(prvk).address();
+    if tx.from != Some(from) {
+        return Err(CrunnerError::InvalidArgument(format!("Error, transaction {:?} is not sent by the signing key's address {:?}", tx_hash, from)));
     }
 
-    // get slice excluding prefix of method-id
-    let arguments_hex_str = &long_hex_str[10..];
-
-    // the length of input stringis not long enough to get at least one element
-    if arguments_hex_str.len() < 64 {
-        return Err("Input hex string length is not long enough to be parsed.
-It needs to have at least 64 characters in length included with prefix of 0x".to_owned());
+    let bump = |fee: U256| (fee * (100 + bump_percent) / 100).max(fee + 1);
+    let mut replacement = TransactionParameters {
+        nonce: Some(tx.nonce),
+        to: tx.to,
+        gas: tx.gas,
+        value: tx.value,
+        data: tx.input,
+        transaction_type: tx.transaction_type,
+        ..Default::default()
+    };
+    if cancel {
+        replacement.to = Some(from);
+        replacement.gas = U256::from(21000);
+        replacement.value = U256::zero();
+        replacement.data = Bytes::default();
     }
-
-    let mut offset_i: usize = 0;
-    let mut res_vec: Vec<String> = Vec::new();
-
-    while offset_i + 64 <= arguments_hex_str.len() {
-        res_vec.push((&arguments_hex_str[offset_i..offset_i+64]).to_owned());
-        offset_i = offset_i + 64;
+    if tx.transaction_type == Some(2.into()) {
+        replacement.max_fee_per_gas = tx.max_fee_per_gas.map(bump);
+        replacement.max_priority_fee_per_gas = tx.max_priority_fee_per_gas.map(bump);
+    }
+    else {
+        // never go below the current gas price; the original one may be long outdated
+        let current_gas_price = match web3.eth().gas_price().await {
+            Ok(res) => res,
+            Err(e) => return Err(CrunnerError::RpcError(format!("Error in querying gas price; err={}", e))),
+        };
+        replacement.gas_price = Some(bump(tx.gas_price.unwrap_or_default()).max(current_gas_price));
     }
 
-    Ok(res_vec)
+    let signed = match web3.accounts().sign_transaction(replacement, prvk).await {
+        Ok(res) => res,
+        Err(e) => return Err(CrunnerError::RpcError(format!("Error signing replacement of transaction {:?}; err={}", tx_hash, e))),
+    };
+    match web3.eth().send_raw_transaction(signed.raw_transaction).await {
+        Ok(replacement_tx_hash) => {
+            tracing::info!(tx_hash = ?replacement_tx_hash, "replacement transaction sent");
+            Ok(replacement_tx_hash)
+        },
+        Err(e) => Err(CrunnerError::RpcError(format!("Error replacing transaction {:?}; err={}", tx_hash, e))),
+    }
 }
 
-/// Create a contract
+/// Wait for the sent transaction to be mined, and confirmed by the specified number
+/// of blocks on top of its block. Poll its receipt, and the latest block every second.
 ///
 /// # Arguments
-/// * `web3` - web3 instance
-/// * `contract_address_str` - contract address string
-/// * `abi_str` - abi
-pub fn create_contract(web3: &Web3<Http>, contract_address_str: &str, abi_str: &str) -> Result<Contract<Http>, String> {
-    if !validate_address_format(contract_address_str) {
-        let err_msg = format!("Error address is in wrong format ({}).", contract_address_str);
-        return Err(err_msg);
-    }
-    let contract_address_hbytes = match hex::decode(&contract_address_str[2..]) {
-        Ok(res) => res,
-        Err(e) => return Err(format!("Error converting from literal string of contract address into hex bytes; err={}", e)),
-    };
-    let contract_address: Address = Address::from_slice(contract_address_hbytes.as_slice());
+/// - `web3` - web3 instance
+/// - `tx_hash` - hash of the sent transaction
+/// - `confirmations` - number of blocks to be confirmed
+/// - `on_poll` - called on each poll with the current number of confirmations, or `None` if not mined yet
+///
+/// # Return
+/// On success, return `TransactionReceipt`.
+#[tracing::instrument(name = "tx.confirm", skip_all, fields(tx_hash = ?tx_hash, confirmations))]
+pub async fn web3_wait_for_confirmations<F>(web3: &Web3<CrunnerTransport>, tx_hash: H256, confirmations: u64, mut on_poll: F) -> Result<TransactionReceipt, CrunnerError>
+where
+    F: FnMut(Option<u64>)
+{
+    let mut interval = tokio::time::interval(Duration::from_secs(1));
+    loop {
+        interval.tick().await;
 
-    // create a contract from contract address, and abi
-    match Contract::from_json(web3.eth(), contract_address, abi_str.as_bytes()) {
-        Ok(res) => Ok(res),
-        Err(e) => {
-            let err_msg = format!("Error creating contract associated with abi for {}; err={}", contract_address_str, e);
-            Err(err_msg)
+        let tx_receipt = match web3.eth().transaction_receipt(tx_hash).await {
+            Ok(res) => res,
+            Err(e) => return Err(CrunnerError::RpcError(format!("Error querying receipt of transaction {:?}; err={}", tx_hash, e))),
+        };
+        let (tx_receipt, mined_block) = match tx_receipt {
+            Some(res @ TransactionReceipt { block_number: Some(block_number), .. }) => (res, block_number.as_u64()),
+            _ => {
+                on_poll(None);
+                continue;
+            },
+        };
+
+        let current_confirmations = if confirmations == 0 {
+            0
+        }
+        else {
+            match web3.eth().block_number().await {
+                Ok(res) => res.as_u64().saturating_sub(mined_block),
+                Err(e) => return Err(CrunnerError::RpcError(format!("Error querying block number; err={}", e))),
+            }
+        };
+        on_poll(Some(current_confirmations));
+        if current_confirmations >= confirmations {
+            tracing::info!(block_number = mined_block, "transaction confirmed");
+            return Ok(tx_receipt);
         }
     }
 }
 
-/// Create a contract
+/// Query base fee of the next block, and the median of each percentile of priority
+/// fees paid in recent blocks from fee history.
 ///
 /// # Arguments
-/// * `web3` - web3 instance
-/// * `contract_address_str` - contract address string
-/// * `abi_filepath` - ABI json filepath
-pub fn create_contract_from_abifile(web3: &Web3<Http>, contract_address_str: &str, abi_filepath: &str) -> Result<Contract<Http>, String> {
-    if !validate_address_format(contract_address_str) {
-        let err_msg = format!("Error address is in wrong format ({}).", contract_address_str);
-        return Err(err_msg);
-    }
-    let contract_address_hbytes = match hex::decode(&contract_address_str[2..]) {
+/// - `web3` - web3 instance
+/// - `percentiles` - percentiles of priority fees to query
+///
+/// # Return
+/// On success, return tuple of `(base_fee_per_gas, priority_fees)` in Wei whereas
+/// `priority_fees` holds the median of each percentile in the same order.
+pub async fn web3_fee_history(web3: &Web3<CrunnerTransport>, percentiles: &[f64]) -> Result<(U256, Vec<U256>), CrunnerError>
+{
+    let fee_history = match web3.eth().fee_history(U256::from(FEE_HISTORY_BLOCKS), BlockNumber::Latest, Some(percentiles.to_vec())).await {
         Ok(res) => res,
-        Err(e) => return Err(format!("Error converting from literal string of contract address into hex bytes; err={}", e)),
+        Err(e) => return Err(CrunnerError::RpcError(format!("Error querying fee history; err={}", e))),
     };
-    let contract_address: Address = Address::from_slice(contract_address_hbytes.as_slice());
 
-    let mut abi_buffer = Vec::<u8>::new();
-    if let Err(_e) = read_abi(abi_filepath, &mut abi_buffer) {
-        return Err(format!("Error reading abi file at '{}'", abi_filepath));
-    }
+    // the last one is base fee of the next block
+    let base_fee = match fee_history.base_fee_per_gas.last() {
+        Some(res) => *res,
+        None => return Err(CrunnerError::RpcError("Error, fee history has no base fee; the chain may not support EIP-1559".to_owned())),
+    };
+    let rewards = fee_history.reward.unwrap_or_default();
+    let priority_fees = (0..percentiles.len()).map(|i| {
+        let mut percentile_rewards: Vec<U256> = rewards.iter().filter_map(|r| r.get(i).copied()).collect();
+        percentile_rewards.sort();
+        percentile_rewards.get(percentile_rewards.len() / 2).copied().unwrap_or_default()
+    }).collect();
 
-    // create a contract from contract address, and abi
-    match Contract::from_json(web3.eth(), contract_address, abi_buffer.as_slice()) {
-        Ok(res) => Ok(res),
-        Err(e) => {
-            let err_msg = format!("Error creating contract associated with abi for {}; err={}", contract_address_str, e);
-            Err(err_msg)
-        }
-    }
+    Ok((base_fee, priority_fees))
 }
 
-/// Prepare parameters for supplying to smart contract's method.
+/// Suggest fees of EIP-1559 transaction from fee history of recent blocks. Priority
+/// fee is the median of 50th percentile of priority fees paid in recent blocks, and
+/// max fee is twice the base fee of the next block plus such priority fee.
 ///
 /// # Arguments
-/// - `params` - input parameter strings as slice
-/// - `print_param_type` - whether or not to also print each parameter type
+/// - `web3` - web3 instance
 ///
 /// # Return
-/// Return a slice of parsed `Token` in case of success.
-fn prepare_params(params: &[String], print_param_type: bool) -> Result<Vec<Token>, String> {
-    let mut parsed_params: Vec<Token> = Vec::new();
-
-    for p in params {
-        if print_param_type {
-            print!("param = {}", p);
-        }
+/// On success, return tuple of `(max_fee_per_gas, max_priority_fee_per_gas)` in Wei.
+pub async fn web3_suggest_eip1559_fees(web3: &Web3<CrunnerTransport>) -> Result<(U256, U256), CrunnerError>
+{
+    let (base_fee, priority_fees) = web3_fee_history(web3, &[50.0]).await?;
+    let priority_fee = priority_fees.first().copied().unwrap_or_default();
 
-        match parse_param_type(&p) {
-            FnParamType::Address => {
-                if print_param_type {
-                    println!(" is Address");
-                }
-                
-                let addr = match get_address_from_str(&p) {
-                    Ok(addr) => addr,
-                    Err(e) => {
-                        let err_msg = format!("Error parsing parameter '{}' for Address type; err={}", &p, e);
-                        return Err(err_msg);
-                    }
-                };
-                parsed_params.push(addr.into_token());
-            },
-            FnParamType::HU256 => {
-                if print_param_type {
-                    println!(" is U256");
-                }
+    Ok((base_fee * 2 + priority_fee, priority_fee))
+}
 
-                let trimmed_prefix = p.trim_start_matches("0x");
-                let u256_val = match U256::from_str_radix(&trimmed_prefix, 16) {
-                    Ok(res) => res,
-                    Err(e) => {
-                        let err_msg = format!("Error creating U256 from hexadecimal string; e={}", e);
-                        return Err(err_msg);
-                    }
-                };
-                parsed_params.push(u256_val.into_token());
-            }
-            FnParamType::DU256 => {
-                if print_param_type {
-                    println!(" is Decimal");
-                }
+/// Suggest fees of EIP-1559 transaction for slow, standard, and fast tiers from fee
+/// history of recent blocks. Priority fee of each tier is the median of 10th, 50th,
+/// and 90th percentile respectively of priority fees paid in recent blocks.
+///
+/// # Arguments
+/// - `web3` - web3 instance
+///
+/// # Return
+/// On success, return suggested fees of each tier from slowest to fastest.
+pub async fn web3_suggest_fee_tiers(web3: &Web3<CrunnerTransport>) -> Result<Vec<FeeSuggestion>, CrunnerError>
+{
+    let percentiles: Vec<f64> = FEE_TIERS.iter().map(|(_, p)| *p).collect();
+    let (base_fee, priority_fees) = web3_fee_history(web3, &percentiles).await?;
 
-                let u256_val = match U256::from_dec_str(&p) {
-                    Ok(res) => res,
-                    Err(e) => {
-                        let err_msg = format!("Error creating U256 from decimal string; e={}", e);
-                        return Err(err_msg);
-                    }
-                };
-                parsed_params.push(u256_val.into_token());
-            },
-            FnParamType::String => {
-                if print_param_type {
-                    println!(" is String");
-                }
-                parsed_params.push(p.to_owned().into_token());
-            }
+    Ok(FEE_TIERS.iter().zip(priority_fees).map(|((tier, _), priority_fee)| {
+        FeeSuggestion {
+            tier,
+            base_fee,
+            priority_fee,
+            max_fee: base_fee * 2 + priority_fee,
         }
-    }
+    }).collect())
+}
 
-    Ok(parsed_params)
+/// Whether the chain sends EIP-1559 transaction by default.
+///
+/// # Arguments
+/// - `chain` - `ChainType`
+pub fn supports_eip1559_by_default(chain: ChainType) -> bool {
+    matches!(chain, ChainType::Ethereum | ChainType::Polygon | ChainType::Sepolia | ChainType::Holesky | ChainType::PolygonAmoy)
 }
 
-/// Make a web3 query depending on the method name, and number of method's arguments.
+/// Make a web3 (dry-run for estimate gas) set depending on the function name, and number of function's arguments.
 ///
 /// # Arguments
 /// - `contract` - `web3::contract::Contract` for contract instance to interact with
 /// - `fn_name` - name of the function to make a call
 /// - `params` - slice of parameter strings that required to pass to such method to make a call
-pub async fn web3_query_get<R>(contract: &Contract<Http>, fn_name: &str, params: &[String]) -> Result<R, String>
-where
-    R: Detokenize
+/// - `from` - address from
+/// - `options` - transaction options i.e. value in Wei to send along
+///
+/// # Return
+/// On success, return `U256` indicating gas used.
+pub async fn web3_query_estimate_gas(contract: &Contract<CrunnerTransport>, fn_name: &str, params: &[String], from: &str, options: Options) -> Result<U256, CrunnerError>
 {
-    let parsed_params = match prepare_params(params, false) {
+    let parsed_params = match prepare_params(params, function_input_types(contract, fn_name).as_deref(), false) {
         Ok(res) => res,
         Err(e) => return Err(e),
     };
 
-    let res = contract.query(fn_name, parsed_params.as_slice(), None, Options::default(), None).await;
+    let from_addr = match get_address_from_str(from) {
+        Ok(addr) => addr,
+        Err(e) => return Err(e),
+    };
 
-    match res {
-        Ok(val_res) => Ok(val_res),
-        Err(e) => Err(format!("Error querying via RPC for function '{}'; err={}", fn_name, e)),
+    match contract.estimate_gas(fn_name, parsed_params.as_slice(), from_addr, options).await {
+        Ok(estimated_gas_used) => Ok(estimated_gas_used),
+        Err(e) => {
+            let err_msg = format!("Error calling setter method namely '{}'; err={}", fn_name, describe_contract_error(&e, Some(contract.abi())));
+            Err(CrunnerError::RpcError(err_msg))
+        },
     }
 }
 
-/// Make a web3 set depending on the function name, and number of function's arguments.
+/// Trace calling method of the contract via `debug_traceCall` with `callTracer`
+/// without sending a transaction. It requires the node to expose `debug` namespace.
 ///
 /// # Arguments
+/// - `web3` - web3 instance
 /// - `contract` - `web3::contract::Contract` for contract instance to interact with
 /// - `fn_name` - name of the function to make a call
 /// - `params` - slice of parameter strings that required to pass to such method to make a call
-/// - `confirmations` - number of confirmations or number of blocks to be confirmed to report
-/// effectively made)
+/// - `from` - address to call from (if any)
+/// - `options` - transaction options i.e. value in Wei to send along
+/// - `block` - block to trace the call at; latest block if `None`
 ///
 /// # Return
-/// On success, return `TransactionReceipt`.
-pub async fn web3_query_set(contract: &Contract<Http>, fn_name: &str, params: &[String], confirmations: u64) -> Result<TransactionReceipt, String>
+/// On success, return the top-level call frame with nested calls.
+#[tracing::instrument(name = "contract.trace", skip_all, fields(contract = ?contract.address(), function = fn_name))]
+pub async fn web3_trace_call(web3: &Web3<CrunnerTransport>, contract: &Contract<CrunnerTransport>, fn_name: &str, params: &[String], from: Option<&str>, options: Options, block: Option<BlockId>) -> Result<CallFrame, CrunnerError>
 {
-    let parsed_params = match prepare_params(params, false) {
+    let parsed_params = prepare_params(params, function_input_types(contract, fn_name).as_deref(), false)?;
+    let data = match contract.abi().function(fn_name).and_then(|f| f.encode_input(parsed_params.as_slice())) {
         Ok(res) => res,
-        Err(e) => return Err(e),
+        Err(e) => return Err(CrunnerError::AbiError(format!("Error encoding parameters for function '{}'; err={}", fn_name, e))),
+    };
+    let from = match from {
+        Some(from) => Some(get_address_from_str(from)?),
+        None => None,
     };
 
-    let prvk = secp256k1::SecretKey::from_str(&std::env::var("CRUNNER_SETTER_SECRETKEY").expect("'CRUNNER_SETTER_SECRETKEY' environment variable is required")).unwrap();
-    match contract.signed_call_with_confirmations(fn_name, parsed_params.as_slice(), Options::default(), confirmations.try_into().unwrap(), &prvk).await {
-        Ok(tx_receipt) => Ok(tx_receipt),
-        Err(e) => {
-            let err_msg = format!("Error calling setter method namely '{}'; err={}", fn_name, e);
-            return Err(err_msg);
+    let call_req = CallRequest {
+        from,
+        to: Some(contract.address()),
+        value: options.value,
+        gas: options.gas,
+        data: Some(Bytes(data)),
+        ..Default::default()
+    };
+    let block = block.unwrap_or(BlockId::Number(BlockNumber::Latest));
+    let params = vec![
+        serde_json::to_value(&call_req).unwrap_or_default(),
+        serde_json::to_value(block).unwrap_or_default(),
+        serde_json::json!({ "tracer": "callTracer" }),
+    ];
+
+    let value = match web3.transport().execute("debug_traceCall", params).await {
+        Ok(res) => res,
+        Err(web3::Error::Rpc(e)) if e.code == jsonrpc_core::ErrorCode::MethodNotFound => {
+            return Err(CrunnerError::RpcError(format!("Error, RPC endpoint doesn't support debug_traceCall; use a node with debug namespace enabled; err={}", e)));
         },
+        Err(e) => return Err(CrunnerError::RpcError(format!("Error tracing call to function '{}'; err={}", fn_name, describe_rpc_error(&e, Some(contract.abi()))))),
+    };
+    match serde_json::from_value(value) {
+        Ok(res) => Ok(res),
+        Err(e) => Err(CrunnerError::RpcError(format!("Error parsing trace of function '{}'; err={}", fn_name, e))),
     }
 }
 
-/// Make a web3 (dry-run for estimate gas) set depending on the function name, and number of function's arguments.
+/// Stream event logs emitted by the specified contract address as they arrive via
+/// WebSocket subscription. On disconnect, it reconnects with backoff, queries logs
+/// emitted in the meantime, and resubscribes. Logs removed by chain reorganization
+/// are skipped. It runs until the initial connection, or subscription fails.
 ///
 /// # Arguments
-/// - `contract` - `web3::contract::Contract` for contract instance to interact with
-/// - `fn_name` - name of the function to make a call
-/// - `params` - slice of parameter strings that required to pass to such method to make a call
-/// - `from` - address from
+/// - `ws_url` - WebSocket RPC endpoint url
+/// - `contract_address` - contract address whose emitted logs to stream
+/// - `event_topic` - signature hash of the event to filter logs by (if any)
+/// - `on_log` - called for each log
+pub async fn web3_follow_logs<F>(ws_url: &str, contract_address: &str, event_topic: Option<H256>, mut on_log: F) -> Result<(), CrunnerError>
+where
+    F: FnMut(&Log)
+{
+    let addr = get_address_from_str(contract_address)?;
+    let filter_builder = || FilterBuilder::default()
+        .address(vec![addr])
+        .topics(event_topic.map(|t| vec![t]), None, None, None);
+
+    // position i.e. block number, and log index of the last streamed log
+    let mut last_pos: Option<(u64, u64)> = None;
+    let log_pos = |log: &Log| log.block_number.map(|n| (n.as_u64(), log.log_index.map(|i| i.as_u64()).unwrap_or_default()));
+    let mut is_first = true;
+    let mut delay = Duration::from_secs(1);
+    loop {
+        if !is_first {
+            tracing::warn!(delay_secs = delay.as_secs(), "log subscription is closed; resubscribing");
+            tokio::time::sleep(delay).await;
+            delay = (delay * 2).min(Duration::from_secs(FOLLOW_MAX_BACKOFF_SECS));
+        }
+
+        let res: Result<(), String> = async {
+            let ws = web3::transports::WebSocket::new(ws_url).await.map_err(|e| format!("Error connecting to {}; err={}", ws_url, e))?;
+            let web3 = Web3::new(ws);
+            let mut stream = web3.eth_subscribe().subscribe_logs(filter_builder().build()).await
+                .map_err(|e| format!("Error subscribing to logs of {}; err={}", contract_address, e))?;
+            is_first = false;
+            delay = Duration::from_secs(1);
+
+            // catch up with logs emitted while disconnected
+            if let Some((block, _)) = last_pos {
+                let filter = filter_builder().from_block(BlockNumber::Number(block.into())).build();
+                let logs = web3.eth().logs(filter).await.map_err(|e| format!("Error querying logs for {}; err={}", contract_address, e))?;
+                for log in logs.iter() {
+                    let pos = log_pos(log);
+                    if pos.is_some() && pos <= last_pos {
+                        continue;
+                    }
+                    last_pos = pos.or(last_pos);
+                    on_log(log);
+                }
+            }
+
+            while let Some(item) = stream.next().await {
+                let log = item.map_err(|e| format!("Error receiving logs of {}; err={}", contract_address, e))?;
+                if log.removed == Some(true) {
+                    tracing::debug!(tx_hash = ?log.transaction_hash, "skipped log removed by reorg");
+                    continue;
+                }
+                // skip logs already caught up with
+                let pos = log_pos(&log);
+                if pos.is_some() && pos <= last_pos {
+                    continue;
+                }
+                last_pos = pos.or(last_pos);
+                on_log(&log);
+            }
+            Ok(())
+        }.await;
+
+        if let Err(e) = res {
+            if is_first {
+                return Err(CrunnerError::RpcError(e));
+            }
+            tracing::warn!(error = %e, "log subscription failed");
+        }
+    }
+}
+
+/// Query event logs emitted by the specified contract address within block range.
+/// The range is split into chunks of at most `chunk_size` blocks each queried
+/// separately. A chunk rejected by the RPC endpoint for being too wide, or returning
+/// too many results is retried in halves; chunk size grows back after several
+/// successive chunks.
+///
+/// # Arguments
+/// - `web3` - web3 instance
+/// - `contract_address` - contract address whose emitted logs to query
+/// - `event_topic` - signature hash of the event to filter logs by (if any)
+/// - `from_block` - block number to start from; latest block if `None`
+/// - `to_block` - block number to query up to (inclusive); latest block if `None`
+/// - `chunk_size` - maximum number of blocks to query per request
+/// - `on_progress` - called after each chunk is queried
 ///
 /// # Return
-/// On success, return `U256` indicating gas used.
-pub async fn web3_query_estimate_gas(contract: &Contract<Http>, fn_name: &str, params: &[String], from: &str) -> Result<U256, String>
+/// On success, return vector of `Log`.
+pub async fn web3_query_logs<F>(web3: &Web3<CrunnerTransport>, contract_address: &str, event_topic: Option<H256>, from_block: Option<u64>, to_block: Option<u64>, chunk_size: u64, mut on_progress: F) -> Result<Vec<Log>, CrunnerError>
+where
+    F: FnMut(&LogScanProgress)
 {
-    let parsed_params = match prepare_params(params, false) {
-        Ok(res) => res,
-        Err(e) => return Err(e),
-    };
+    let addr = get_address_from_str(contract_address)?;
 
-    let from_addr = match get_address_from_str(from) {
-        Ok(addr) => addr,
-        Err(e) => return Err(e),
+    let latest_block = match (from_block, to_block) {
+        (Some(_), Some(_)) => None,
+        _ => match web3.eth().block_number().await {
+            Ok(res) => Some(res.as_u64()),
+            Err(e) => return Err(CrunnerError::RpcError(format!("Error querying latest block number; err={}", e))),
+        },
     };
+    let to_block = to_block.or(latest_block).unwrap_or_default();
+    let from_block = from_block.or(latest_block).unwrap_or_default();
+    if from_block > to_block {
+        return Err(CrunnerError::InvalidArgument(format!("Error, from block {} is after to block {}", from_block, to_block)));
+    }
 
-    match contract.estimate_gas(fn_name, parsed_params.as_slice(), from_addr, Options::default()).await {
-        Ok(estimated_gas_used) => Ok(estimated_gas_used),
-        Err(e) => {
-            let err_msg = format!("Error calling setter method namely '{}'; err={}", fn_name, e);
-            return Err(err_msg);
-        },
+    let max_chunk_size = chunk_size.max(1);
+    let mut chunk_size = max_chunk_size;
+    let mut successes: u32 = 0;
+    let mut start = from_block;
+    let mut logs: Vec<Log> = Vec::new();
+    loop {
+        let end = start.saturating_add(chunk_size - 1).min(to_block);
+        let filter = FilterBuilder::default()
+            .address(vec![addr])
+            .topics(event_topic.map(|t| vec![t]), None, None, None)
+            .from_block(BlockNumber::Number(start.into()))
+            .to_block(BlockNumber::Number(end.into()))
+            .build();
+
+        match web3.eth().logs(filter).await {
+            Ok(res) => logs.extend(res),
+            Err(e) => {
+                let msg = e.to_string().to_lowercase();
+                if chunk_size > 1 && LOGS_RANGE_ERRORS.iter().any(|m| msg.contains(m)) {
+                    chunk_size = (chunk_size / 2).max(1);
+                    successes = 0;
+                    tracing::debug!(start, end, chunk_size, error = %e, "log query rejected; retrying with smaller chunk");
+                    continue;
+                }
+                return Err(CrunnerError::RpcError(format!("Error querying logs for {} from block {} to {}; err={}", contract_address, start, end, e)));
+            },
+        }
+
+        on_progress(&LogScanProgress {
+            from_block,
+            to_block,
+            scanned_block: end,
+            logs: logs.len(),
+        });
+        if end >= to_block {
+            break;
+        }
+        start = end + 1;
+        successes += 1;
+        if successes >= LOGS_CHUNK_GROW_AFTER && chunk_size < max_chunk_size {
+            chunk_size = chunk_size.saturating_mul(2).min(max_chunk_size);
+            successes = 0;
+        }
     }
+
+    Ok(logs)
 }
 
 /// Start measuring time. Suitable for wall-clock time measurement.
@@ -415,12 +3040,12 @@ pub fn measure_start(start: &mut std::time::Instant) {
 }
 
 /// Mark the end of the measurement of time performance.
-/// Return result in seconds, along with printing the elapsed time if `also_print`
+/// Return result in seconds, along with logging the elapsed time if `also_print`
 /// is `true`.
 pub fn measure_end(start: &std::time::Instant, also_print: bool) -> f64 {
     let elapsed = start.elapsed().as_secs_f64();
     if also_print {
-        println!("(elapsed = {:.2} secs)", elapsed);
+        tracing::info!(elapsed_secs = elapsed, "measured elapsed time");
     }
     elapsed
 }
@@ -438,9 +3063,7 @@ pub fn read_abi(filepath: &str, out: &mut Vec::<u8>) -> std::result::Result<(),
         return Err(e);
     }
     let mut reader = std::io::BufReader::new(f.unwrap());
-    if let Err(e) = reader.read_to_end(out) {
-        return Err(e);
-    }
+    reader.read_to_end(out)?;
 
     Ok(())
 }
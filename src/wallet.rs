@@ -0,0 +1,152 @@
+use crate::error::CrunnerError;
+use coins_bip32::ecdsa::SigningKey;
+use coins_bip39::{English, Mnemonic};
+use secp256k1::SecretKey;
+use std::path::Path;
+use std::str::FromStr;
+use zeroize::Zeroizing;
+
+/// Default BIP-44 derivation path of the first Ethereum account as used by MetaMask,
+/// and hardware wallets
+pub static DEFAULT_HD_PATH: &str = "m/44'/60'/0'/0/0";
+
+/// Service name under which keys are stored in OS secret store
+static KEYRING_SERVICE: &str = "crunner";
+
+/// Load secret key used to sign transactions from `CRUNNER_SETTER_SECRETKEY`
+/// environment variable.
+pub fn secret_key_from_env() -> Result<SecretKey, CrunnerError> {
+    let secret_key = match std::env::var("CRUNNER_SETTER_SECRETKEY") {
+        Ok(res) => Zeroizing::new(res),
+        Err(_) => return Err(CrunnerError::ConfigError("Error, 'CRUNNER_SETTER_SECRETKEY' environment variable is required".to_owned())),
+    };
+    match SecretKey::from_str(&secret_key) {
+        Ok(res) => Ok(res),
+        Err(e) => Err(CrunnerError::ConfigError(format!("Error parsing 'CRUNNER_SETTER_SECRETKEY'; err={}", e))),
+    }
+}
+
+/// Load secret key used to sign transactions from file containing the key in hex
+/// string. The file must not be readable by others.
+///
+/// # Arguments
+/// * `filepath` - filepath of the secret key file
+pub fn secret_key_from_file(filepath: &Path) -> Result<SecretKey, CrunnerError> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+
+        let metadata = match std::fs::metadata(filepath) {
+            Ok(res) => res,
+            Err(e) => return Err(CrunnerError::ConfigError(format!("Error reading private key file {}; err={}", filepath.display(), e))),
+        };
+        if metadata.permissions().mode() & 0o004 != 0 {
+            return Err(CrunnerError::ConfigError(format!("Error, private key file {} is world-readable; restrict it i.e. chmod 600", filepath.display())));
+        }
+    }
+
+    let content = match std::fs::read_to_string(filepath) {
+        Ok(res) => Zeroizing::new(res),
+        Err(e) => return Err(CrunnerError::ConfigError(format!("Error reading private key file {}; err={}", filepath.display(), e))),
+    };
+    let secret_key = content.trim();
+    match SecretKey::from_str(secret_key.strip_prefix("0x").unwrap_or(secret_key)) {
+        Ok(res) => Ok(res),
+        Err(e) => Err(CrunnerError::ConfigError(format!("Error parsing private key file {}; err={}", filepath.display(), e))),
+    }
+}
+
+/// Store secret key in OS secret store (keychain, libsecret, or wincred) under the
+/// named profile; overwrite the existing one if any.
+///
+/// # Arguments
+/// * `profile` - name of the profile
+/// * `secret_key` - secret key to store
+pub fn import_key_to_keyring(profile: &str, secret_key: &SecretKey) -> Result<(), CrunnerError> {
+    let entry = keyring_entry(profile)?;
+    let secret_key_hex = Zeroizing::new(hex::encode(secret_key.serialize_secret()));
+    match entry.set_password(&secret_key_hex) {
+        Ok(_) => Ok(()),
+        Err(e) => Err(CrunnerError::ConfigError(format!("Error storing key of profile '{}' in OS secret store; err={}", profile, e))),
+    }
+}
+
+/// Remove secret key of the named profile from OS secret store.
+///
+/// # Arguments
+/// * `profile` - name of the profile
+pub fn remove_key_from_keyring(profile: &str) -> Result<(), CrunnerError> {
+    let entry = keyring_entry(profile)?;
+    match entry.delete_password() {
+        Ok(_) => Ok(()),
+        Err(keyring::Error::NoEntry) => Err(CrunnerError::ConfigError(format!("Error, no key stored for profile '{}'", profile))),
+        Err(e) => Err(CrunnerError::ConfigError(format!("Error removing key of profile '{}' from OS secret store; err={}", profile, e))),
+    }
+}
+
+/// Load secret key used to sign transactions from OS secret store as stored under
+/// the named profile via `crunner key import`.
+///
+/// # Arguments
+/// * `profile` - name of the profile
+pub fn secret_key_from_keyring(profile: &str) -> Result<SecretKey, CrunnerError> {
+    let entry = keyring_entry(profile)?;
+    let secret_key = match entry.get_password() {
+        Ok(res) => Zeroizing::new(res),
+        Err(keyring::Error::NoEntry) => return Err(CrunnerError::ConfigError(format!("Error, no key stored for profile '{}'; import it with `crunner key import {}`", profile, profile))),
+        Err(e) => return Err(CrunnerError::ConfigError(format!("Error reading key of profile '{}' from OS secret store; err={}", profile, e))),
+    };
+    match SecretKey::from_str(&secret_key) {
+        Ok(res) => Ok(res),
+        Err(e) => Err(CrunnerError::ConfigError(format!("Error parsing key of profile '{}'; err={}", profile, e))),
+    }
+}
+
+fn keyring_entry(profile: &str) -> Result<keyring::Entry, CrunnerError> {
+    match keyring::Entry::new(KEYRING_SERVICE, profile) {
+        Ok(res) => Ok(res),
+        Err(e) => Err(CrunnerError::ConfigError(format!("Error accessing OS secret store for profile '{}'; err={}", profile, e))),
+    }
+}
+
+/// Decrypt secret key from keystore JSON file (web3 secret storage) i.e. `UTC--...`
+/// as created by geth, or MetaMask. Decrypted key is kept only in memory.
+///
+/// # Arguments
+/// * `filepath` - filepath of keystore JSON file
+/// * `password` - password to decrypt the keystore
+pub fn secret_key_from_keystore(filepath: &Path, password: &str) -> Result<SecretKey, CrunnerError> {
+    let key_bytes = match eth_keystore::decrypt_key(filepath, password) {
+        Ok(res) => Zeroizing::new(res),
+        Err(e) => return Err(CrunnerError::ConfigError(format!("Error decrypting keystore {}; err={}", filepath.display(), e))),
+    };
+    match SecretKey::from_slice(&key_bytes) {
+        Ok(res) => Ok(res),
+        Err(e) => Err(CrunnerError::ConfigError(format!("Error invalid secret key in keystore {}; err={}", filepath.display(), e))),
+    }
+}
+
+/// Derive secret key from BIP-39 mnemonic phrase at the specified BIP-44 derivation path.
+///
+/// # Arguments
+/// * `phrase` - mnemonic phrase in English
+/// * `hd_path` - derivation path i.e. `m/44'/60'/0'/0/0`
+/// * `passphrase` - optional BIP-39 passphrase
+pub fn secret_key_from_mnemonic(phrase: &str, hd_path: &str, passphrase: Option<&str>) -> Result<SecretKey, CrunnerError> {
+    let phrase = Zeroizing::new(phrase.split_whitespace().collect::<Vec<&str>>().join(" "));
+    let mnemonic = match Mnemonic::<English>::new_from_phrase(&phrase) {
+        Ok(res) => res,
+        Err(e) => return Err(CrunnerError::ConfigError(format!("Error parsing mnemonic phrase; err={}", e))),
+    };
+    let xpriv = match mnemonic.derive_key(hd_path, passphrase) {
+        Ok(res) => res,
+        Err(e) => return Err(CrunnerError::InvalidArgument(format!("Error deriving key at HD path '{}'; err={}", hd_path, e))),
+    };
+
+    let signing_key: &SigningKey = xpriv.as_ref();
+    let key_bytes = Zeroizing::new(signing_key.to_bytes());
+    match SecretKey::from_slice(&key_bytes) {
+        Ok(res) => Ok(res),
+        Err(e) => Err(CrunnerError::ConfigError(format!("Error invalid secret key derived at HD path '{}'; err={}", hd_path, e))),
+    }
+}